@@ -0,0 +1,89 @@
+//! 各算法原语的 native 吞吐量，输入从 16 B 到 64 MiB 。
+//! 运行：`cargo bench --bench primitives` ，只跑部分：`cargo bench --bench primitives -- sha256/`
+//!
+//! 基准 id 为 `<primitive>/<op>/<size>` ，与 benches/wasm/bench.mjs 输出的 primitive / op / size 一一对应，
+//! criterion 的结果（target/criterion/<primitive>/<op>/<size>/new/estimates.json）
+//! 可以用 `node benches/wasm/bench.mjs --compare target/criterion` 与 wasm 的结果并排比较
+
+use std::hint::black_box;
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rust_wasm::{compute_sha256_bytes, compute_sha512_bytes, registry};
+
+const SIZES: [usize; 6] = [16, 1 << 10, 64 << 10, 1 << 20, 16 << 20, 64 << 20];
+
+const KEY: &[u8] = b"1234567890123456";
+const IV: &[u8] = b"abcdef9876543210";
+
+fn input(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i * 31 + 7) as u8).collect()
+}
+
+// 大输入单次就要几十毫秒，减少采样次数，避免一次 cargo bench 跑太久
+fn configure(group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>, size: usize) {
+    group.throughput(Throughput::Bytes(size as u64));
+    if size >= 1 << 20 {
+        group.sample_size(10);
+        group.measurement_time(Duration::from_secs(10));
+    } else {
+        group.sample_size(50);
+        group.measurement_time(Duration::from_secs(3));
+    }
+}
+
+fn bench_sha(c: &mut Criterion) {
+    for (name, hash) in [
+        ("sha256", compute_sha256_bytes as fn(&[u8]) -> _),
+        ("sha512", compute_sha512_bytes),
+    ] {
+        let mut group = c.benchmark_group(name);
+        for size in SIZES {
+            let data = input(size);
+            configure(&mut group, size);
+            group.bench_with_input(BenchmarkId::new("hash", size), &data, |b, data| {
+                b.iter(|| hash(black_box(data)))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_ciphers(c: &mut Criterion) {
+    for name in ["aes_cbc", "xor"] {
+        let cipher = registry().cipher(name).unwrap();
+        let mut group = c.benchmark_group(name);
+        for size in SIZES {
+            let data = input(size);
+            let encrypted = cipher.encrypt(&data, KEY, IV).unwrap();
+            configure(&mut group, size);
+            group.bench_with_input(BenchmarkId::new("encrypt", size), &data, |b, data| {
+                b.iter(|| cipher.encrypt(black_box(data), KEY, IV).unwrap())
+            });
+            group.bench_with_input(BenchmarkId::new("decrypt", size), &encrypted, |b, data| {
+                b.iter(|| cipher.decrypt(black_box(data), KEY, IV).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_custom_base64(c: &mut Criterion) {
+    let encoder = registry().encoder("custom_base64").unwrap();
+    let mut group = c.benchmark_group("custom_base64");
+    for size in SIZES {
+        let data = input(size);
+        let encoded = encoder.encode(&data);
+        configure(&mut group, size);
+        group.bench_with_input(BenchmarkId::new("encode", size), &data, |b, data| {
+            b.iter(|| encoder.encode(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+            b.iter(|| encoder.decode(black_box(encoded)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sha, bench_ciphers, bench_custom_base64);
+criterion_main!(benches);
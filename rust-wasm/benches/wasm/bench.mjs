@@ -0,0 +1,209 @@
+// wasm 侧的吞吐量基准：在 Node 中加载 wasm-pack --target nodejs 的产物，
+// 通过 JS 导出函数调用，包含 JS 与线性内存之间的拷贝，与前端页面的实际开销一致。
+//
+// 构建：wasm-pack build --release --target nodejs --out-dir target/bench-node
+// 运行：node benches/wasm/bench.mjs [选项]
+//   --pkg <dir>          wasm-pack 产物目录，默认 target/bench-node
+//   --out <file>         结果 JSON 写入文件，默认输出到 stdout
+//   --sizes <a,b,...>    输入字节数，默认与 benches/primitives.rs 相同（16 B ~ 64 MiB）
+//   --filter <str>       只运行 id（primitive/op/size）包含该字符串的基准
+//   --min-time-ms <ms>   每个基准至少运行的时间，默认 1000
+//   --compare <dir>      读取 criterion 的结果目录（target/criterion），为每项附上 native 耗时和倍数
+//
+// 输出格式（便于长期追踪回归）：
+// { "runtime": "node-wasm", "commit": ..., "node": ..., "v8": ..., "timestamp": ..., "results": [
+//   { "id", "primitive", "op", "size", "iterations", "ns_per_iter", "bytes_per_sec",
+//     "native_ns_per_iter"?, "wasm_vs_native"? } ] }
+// 人类可读的表格输出到 stderr
+
+import { execSync } from 'node:child_process';
+import { existsSync, readFileSync, writeFileSync } from 'node:fs';
+import { createRequire } from 'node:module';
+import path from 'node:path';
+import { parseArgs } from 'node:util';
+
+const DEFAULT_SIZES = [16, 1 << 10, 64 << 10, 1 << 20, 16 << 20, 64 << 20];
+const MIN_ITERATIONS = 3;
+
+const KEY = new TextEncoder().encode('1234567890123456');
+const IV = new TextEncoder().encode('abcdef9876543210');
+
+const { values: args } = parseArgs({
+  options: {
+    pkg: { type: 'string', default: 'target/bench-node' },
+    out: { type: 'string' },
+    sizes: { type: 'string' },
+    filter: { type: 'string', default: '' },
+    'min-time-ms': { type: 'string', default: '1000' },
+    compare: { type: 'string' },
+  },
+});
+
+function loadWasm(pkgDir) {
+  const entry = path.resolve(pkgDir, 'rust_wasm.js');
+  if (!existsSync(entry)) {
+    throw new Error(
+      `${entry} not found, run: wasm-pack build --release --target nodejs --out-dir ${pkgDir}`,
+    );
+  }
+  return createRequire(import.meta.url)(entry);
+}
+
+function input(size) {
+  const data = new Uint8Array(size);
+  for (let i = 0; i < size; i++) {
+    data[i] = (i * 31 + 7) & 0xff;
+  }
+  return data;
+}
+
+// 与 benches/primitives.rs 的 <primitive>/<op> 一一对应。
+// prepare 在计时之外执行，返回被测函数的参数
+function primitives(wasm) {
+  const cipher = (name) => [
+    {
+      primitive: name,
+      op: 'encrypt',
+      prepare: (data) => data,
+      run: (data) => wasm.encrypt_by_name(name, data, KEY, IV),
+    },
+    {
+      primitive: name,
+      op: 'decrypt',
+      prepare: (data) => wasm.encrypt_by_name(name, data, KEY, IV),
+      run: (data) => wasm.decrypt_by_name(name, data, KEY, IV),
+    },
+  ];
+  return [
+    {
+      primitive: 'sha256',
+      op: 'hash',
+      prepare: (data) => data,
+      run: (data) => wasm.get_bytes_sha256_pure(data),
+    },
+    {
+      primitive: 'sha512',
+      op: 'hash',
+      prepare: (data) => data,
+      run: (data) => wasm.get_bytes_sha512_pure(data),
+    },
+    ...cipher('aes_cbc'),
+    ...cipher('xor'),
+    {
+      primitive: 'custom_base64',
+      op: 'encode',
+      prepare: (data) => data,
+      run: (data) => wasm.encode_by_name('custom_base64', data),
+    },
+    {
+      primitive: 'custom_base64',
+      op: 'decode',
+      prepare: (data) => wasm.encode_by_name('custom_base64', data),
+      run: (encoded) => wasm.decode_by_name('custom_base64', encoded),
+    },
+  ];
+}
+
+// 先预热一次，然后按批次翻倍，直到总耗时超过 minTimeNs
+function measure(run, arg, minTimeNs) {
+  run(arg);
+  let iterations = 0;
+  let elapsed = 0n;
+  let batch = 1;
+  while (elapsed < minTimeNs || iterations < MIN_ITERATIONS) {
+    const start = process.hrtime.bigint();
+    for (let i = 0; i < batch; i++) {
+      run(arg);
+    }
+    elapsed += process.hrtime.bigint() - start;
+    iterations += batch;
+    batch *= 2;
+  }
+  return { iterations, nsPerIter: Number(elapsed) / iterations };
+}
+
+// criterion 的 estimates.json ，mean.point_estimate 单位为 ns
+function nativeNsPerIter(criterionDir, { primitive, op, size }) {
+  const file = path.join(criterionDir, primitive, op, String(size), 'new', 'estimates.json');
+  if (!existsSync(file)) {
+    return undefined;
+  }
+  return JSON.parse(readFileSync(file, 'utf8')).mean.point_estimate;
+}
+
+// 不在 git 仓库中时为 null
+function gitCommit() {
+  try {
+    return execSync('git rev-parse HEAD', {
+      encoding: 'utf8',
+      stdio: ['ignore', 'pipe', 'ignore'],
+    }).trim();
+  } catch {
+    return null;
+  }
+}
+
+function formatRate(bytesPerSec) {
+  const units = ['B/s', 'KiB/s', 'MiB/s', 'GiB/s'];
+  let value = bytesPerSec;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${value.toFixed(2)} ${units[unit]}`;
+}
+
+function main() {
+  const wasm = loadWasm(args.pkg);
+  const sizes = args.sizes ? args.sizes.split(',').map(Number) : DEFAULT_SIZES;
+  const minTimeNs = BigInt(Number(args['min-time-ms']) * 1e6);
+
+  const results = [];
+  for (const size of sizes) {
+    const data = input(size);
+    for (const bench of primitives(wasm)) {
+      const id = `${bench.primitive}/${bench.op}/${size}`;
+      if (!id.includes(args.filter)) {
+        continue;
+      }
+      const { iterations, nsPerIter } = measure(bench.run, bench.prepare(data), minTimeNs);
+      const result = {
+        id,
+        primitive: bench.primitive,
+        op: bench.op,
+        size,
+        iterations,
+        ns_per_iter: nsPerIter,
+        bytes_per_sec: (size * 1e9) / nsPerIter,
+      };
+      if (args.compare) {
+        const native = nativeNsPerIter(args.compare, result);
+        if (native !== undefined) {
+          result.native_ns_per_iter = native;
+          result.wasm_vs_native = nsPerIter / native;
+        }
+      }
+      results.push(result);
+      const ratio = result.wasm_vs_native ? `  ${result.wasm_vs_native.toFixed(2)}x native` : '';
+      console.error(`${id.padEnd(32)} ${formatRate(result.bytes_per_sec).padStart(14)}${ratio}`);
+    }
+  }
+
+  const report = {
+    runtime: 'node-wasm',
+    commit: gitCommit(),
+    node: process.version,
+    v8: process.versions.v8,
+    timestamp: new Date().toISOString(),
+    results,
+  };
+  const json = `${JSON.stringify(report, null, 2)}\n`;
+  if (args.out) {
+    writeFileSync(args.out, json);
+  } else {
+    process.stdout.write(json);
+  }
+}
+
+main();
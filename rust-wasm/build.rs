@@ -0,0 +1,49 @@
+// 开启 capi feature 时用 cbindgen 生成 C 头文件，头文件随仓库提交，
+// C++ / Go 使用方不需要安装 cbindgen
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    secret_seed();
+    #[cfg(feature = "capi")]
+    generate_c_header();
+}
+
+// sealed! 宏加密常量使用的种子。默认每次运行构建脚本都随机取一个并以 warning 输出，
+// 不同构建的密文各不相同；RUST_WASM_SECRET_SEED 指定 u64 种子时用它，
+// 需要时可以用 warning 里的种子复现那次构建
+fn secret_seed() {
+    const SEED_VAR: &str = "RUST_WASM_SECRET_SEED";
+    println!("cargo:rerun-if-env-changed={SEED_VAR}");
+    let seed = match std::env::var(SEED_VAR) {
+        Ok(seed) if seed.trim() != "random" => seed.trim().parse::<u64>().unwrap_or_else(|_| {
+            panic!("{SEED_VAR} must be an unsigned 64-bit integer or \"random\", got {seed:?}")
+        }),
+        _ => {
+            use std::hash::{BuildHasher, RandomState};
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default();
+            let seed = RandomState::new().hash_one(nanos);
+            println!("cargo:warning=sealed! constants use random seed {SEED_VAR}={seed}");
+            seed
+        }
+    };
+    println!("cargo:rustc-env={SEED_VAR}={seed}");
+}
+
+#[cfg(feature = "capi")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("invalid cbindgen.toml");
+    // 只解析 capi.rs ，其余模块没有 extern "C" 函数
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/capi.rs", crate_dir))
+        .generate()
+        .expect("failed to generate C header")
+        .write_to_file(format!("{}/include/rust_wasm.h", crate_dir));
+}
@@ -0,0 +1,32 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::{aes_cbc_decrypt, decrypt_by_name, encrypt_by_name};
+
+const KEY: &[u8] = b"1234567890123456";
+const IV: &[u8] = b"abcdef9876543210";
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    b64_cipher: &'a str,
+    cipher_text: &'a [u8],
+    key: &'a [u8],
+    iv: &'a [u8],
+}
+
+fuzz_target!(|input: Input| {
+    // 任意长度的 key / iv
+    let _ = aes_cbc_decrypt(input.b64_cipher, input.key, input.iv);
+    let _ = decrypt_by_name("aes_cbc", input.cipher_text, input.key, input.iv);
+
+    // 固定 key / iv ，让 fuzzer 能走到 PKCS7 填充检查；
+    // 填充合法的密文再加密一次必须得到原密文
+    let _ = aes_cbc_decrypt(input.b64_cipher, KEY, IV);
+    if let Ok(plain) = decrypt_by_name("aes_cbc", input.cipher_text, KEY, IV) {
+        assert_eq!(
+            encrypt_by_name("aes_cbc", &plain, KEY, IV).unwrap(),
+            input.cipher_text
+        );
+    }
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::{
+    decode_base64_with_alphabet, decode_bytes_with_alphabet, encode_bytes_with_alphabet,
+};
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    alphabet: &'a str,
+    encoded: &'a str,
+}
+
+fuzz_target!(|input: Input| {
+    let _ = decode_base64_with_alphabet(input.encoded, input.alphabet);
+    // 码表合法且解码成功时，encode → decode 必须还原
+    if let Ok(decoded) = decode_bytes_with_alphabet(input.encoded, input.alphabet) {
+        let reencoded = encode_bytes_with_alphabet(&decoded, input.alphabet).unwrap();
+        assert_eq!(
+            decode_bytes_with_alphabet(&reencoded, input.alphabet).unwrap(),
+            decoded
+        );
+    }
+});
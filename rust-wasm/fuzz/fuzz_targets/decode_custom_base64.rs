@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::{decode_custom_base64, encode_custom_base64};
+
+fuzz_target!(|encoded: &str| {
+    // 解码成功的结果再编码、解码一次，应得到相同的字符串（encode 会 trim ，所以比较 trim 后的结果）
+    if let Ok(decoded) = decode_custom_base64(encoded)
+        && let Ok(reencoded) = encode_custom_base64(&decoded)
+    {
+        assert_eq!(decode_custom_base64(&reencoded).unwrap(), decoded.trim());
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::{decrypt_to_username, encrypt_username};
+
+fuzz_target!(|encrypted: &str| {
+    if let Ok(name) = decrypt_to_username(encrypted) {
+        assert_eq!(
+            decrypt_to_username(&encrypt_username(&name)).unwrap(),
+            name.trim()
+        );
+    }
+});
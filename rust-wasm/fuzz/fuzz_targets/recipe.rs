@@ -0,0 +1,29 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::{run_recipe, run_recipe_reverse, run_recipe_reverse_str, validate_recipe};
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    recipe: &'a str,
+    data: &'a [u8],
+}
+
+fuzz_target!(|input: Input| {
+    let Ok(reversible) = validate_recipe(input.recipe) else {
+        return;
+    };
+    let _ = run_recipe(input.recipe, input.data);
+    let _ = run_recipe_reverse(input.recipe, input.data);
+    if let Ok(text) = std::str::from_utf8(input.data) {
+        let _ = run_recipe_reverse_str(input.recipe, text);
+    }
+    // 可逆 recipe 的 encode → decode 必须还原输入
+    if reversible && let Ok(output) = run_recipe(input.recipe, input.data) {
+        assert_eq!(
+            run_recipe_reverse(input.recipe, &output).unwrap(),
+            input.data
+        );
+    }
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_wasm::registry;
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    encoded: &'a str,
+    cipher_text: &'a [u8],
+    key: &'a [u8],
+    iv: &'a [u8],
+}
+
+// 遍历 registry ，新注册的 encoder / cipher 自动被覆盖
+fuzz_target!(|input: Input| {
+    for encoder in registry().encoders() {
+        if let Ok(decoded) = encoder.decode(input.encoded) {
+            assert_eq!(encoder.decode(&encoder.encode(&decoded)).unwrap(), decoded);
+        }
+    }
+    for cipher in registry().ciphers() {
+        let _ = cipher.decrypt(input.cipher_text, input.key, input.iv);
+    }
+});
@@ -0,0 +1,302 @@
+/*
+ * rust-wasm 的 C ABI ，与网页使用的 WASM 导出函数是同一份实现。
+ *
+ * 约定：
+ * - 所有函数返回 RwStatus ，RW_STATUS_OK 为 0 ，出错后可用 rw_last_error_message 取得错误信息（线程局部）
+ * - 数据输入一律是 const uint8_t *ptr, size_t len ，文本输入要求是 UTF-8 ，len 为 0 时 ptr 可以为 NULL
+ * - 算法名、输出格式、recipe JSON 等短参数是以 NUL 结尾的 C 字符串
+ * - 输出写入 uint8_t *out ，size_t *out_len 传入时是缓冲区容量，返回时是实际长度；
+ *   容量不足时返回 RW_STATUS_BUFFER_TOO_SMALL 并写回所需长度，可先传 out = NULL, *out_len = 0 查询
+ * - 文本输出额外写入一个 NUL ，所需容量为 *out_len + 1 ，返回的 *out_len 不含 NUL
+ */
+
+#ifndef RUST_WASM_H
+#define RUST_WASM_H
+
+/* 本文件由 build.rs 调用 cbindgen 生成，请勿手动修改 */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// 函数返回的状态码，1 和 2 是 C ABI 自己的错误，其余与 `ErrorCode` 一一对应
+typedef enum RwStatus {
+  RW_STATUS_OK = 0,
+  RW_STATUS_NULL_POINTER = 1,
+  RW_STATUS_BUFFER_TOO_SMALL = 2,
+  RW_STATUS_EMPTY_INPUT = 10,
+  RW_STATUS_INVALID_INPUT = 11,
+  RW_STATUS_INVALID_BASE64 = 12,
+  RW_STATUS_INVALID_UTF8 = 13,
+  RW_STATUS_INVALID_ALPHABET = 14,
+  RW_STATUS_INVALID_ENCODING = 15,
+  RW_STATUS_BAD_KEY_LEN = 16,
+  RW_STATUS_BAD_IV_LEN = 17,
+  RW_STATUS_INVALID_KEY = 18,
+  RW_STATUS_ENCRYPT_FAILED = 19,
+  RW_STATUS_DECRYPT_FAILED = 20,
+  RW_STATUS_HASH_FAILED = 21,
+  RW_STATUS_UNSUPPORTED_ALGORITHM = 22,
+  RW_STATUS_UNSUPPORTED_FORMAT = 23,
+  RW_STATUS_UNKNOWN_ALGORITHM = 24,
+  RW_STATUS_INVALID_RECIPE = 25,
+  RW_STATUS_EMPTY_RECIPE = 26,
+  RW_STATUS_INVALID_PARAMS = 27,
+  RW_STATUS_NOT_REVERSIBLE = 28,
+  RW_STATUS_STEP_FAILED = 29,
+  RW_STATUS_INVALID_WASM = 30,
+} RwStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// 取得当前线程最近一次错误的描述（NUL 结尾），缓冲区约定同其他函数
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_last_error_message(uint8_t *out, size_t *out_len);
+
+// 网页上字符串 SHA 的默认盐 "hans7"，以文本写入 out
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_default_str_salt(uint8_t *out, size_t *out_len);
+
+// 网页上文件 SHA 的默认盐 "hans7_sha_bytes"，以文本写入 out
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_default_bytes_salt(uint8_t *out, size_t *out_len);
+
+// 自定义 Base64 使用的 HANS7_CUSTOM_ALPHABET ，以文本写入 out
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_hans7_custom_alphabet(uint8_t *out, size_t *out_len);
+
+// 等价于 `encrypt_username`，输出 Base64 文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_xor_encrypt(const uint8_t *name, size_t name_len, uint8_t *out, size_t *out_len);
+
+// 等价于 `decrypt_to_username`，输出 UTF-8 文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_xor_decrypt(const uint8_t *encrypted,
+                             size_t encrypted_len,
+                             uint8_t *out,
+                             size_t *out_len);
+
+// 等价于 `aes_cbc_encrypt`，输出 Base64 文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_aes_cbc_encrypt(const uint8_t *plain,
+                                 size_t plain_len,
+                                 const uint8_t *key,
+                                 size_t key_len,
+                                 const uint8_t *iv,
+                                 size_t iv_len,
+                                 uint8_t *out,
+                                 size_t *out_len);
+
+// 等价于 `aes_cbc_decrypt`，输入 Base64 密文，输出 UTF-8 文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_aes_cbc_decrypt(const uint8_t *b64_cipher,
+                                 size_t b64_cipher_len,
+                                 const uint8_t *key,
+                                 size_t key_len,
+                                 const uint8_t *iv,
+                                 size_t iv_len,
+                                 uint8_t *out,
+                                 size_t *out_len);
+
+// 用 HANS7_CUSTOM_ALPHABET 编码任意字节，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_custom_base64_encode(const uint8_t *data,
+                                      size_t data_len,
+                                      uint8_t *out,
+                                      size_t *out_len);
+
+// 用 HANS7_CUSTOM_ALPHABET 解码，输出原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_custom_base64_decode(const uint8_t *encoded,
+                                      size_t encoded_len,
+                                      uint8_t *out,
+                                      size_t *out_len);
+
+// 用任意 64 字符码表编码任意字节，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_base64_encode_with_alphabet(const uint8_t *data,
+                                             size_t data_len,
+                                             const char *alphabet,
+                                             uint8_t *out,
+                                             size_t *out_len);
+
+// 用任意 64 字符码表解码，输出原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_base64_decode_with_alphabet(const uint8_t *encoded,
+                                             size_t encoded_len,
+                                             const char *alphabet,
+                                             uint8_t *out,
+                                             size_t *out_len);
+
+// 等价于 `get_str_sha_formatted`：输入按 UTF-8 文本处理并 trim ，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_str_sha(const uint8_t *input,
+                         size_t input_len,
+                         const char *salt,
+                         const char *algorithm,
+                         const char *format,
+                         ptrdiff_t truncate,
+                         uint8_t *out,
+                         size_t *out_len);
+
+// 等价于 `get_bytes_sha_formatted`：输入按原始字节处理，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_bytes_sha(const uint8_t *data,
+                           size_t data_len,
+                           const char *salt,
+                           const char *algorithm,
+                           const char *format,
+                           ptrdiff_t truncate,
+                           uint8_t *out,
+                           size_t *out_len);
+
+// 等价于 `get_bytes_sha_raw`，输出原始摘要字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_bytes_sha_raw(const uint8_t *data,
+                               size_t data_len,
+                               const char *salt,
+                               const char *algorithm,
+                               uint8_t *out,
+                               size_t *out_len);
+
+// 校验 recipe ，成功时把是否可逆写入 `*reversible`
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_validate_recipe(const char *recipe_json, bool *reversible);
+
+// 正向执行 recipe ，输入输出都是原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_run_recipe(const char *recipe_json,
+                            const uint8_t *input,
+                            size_t input_len,
+                            uint8_t *out,
+                            size_t *out_len);
+
+// 逆向执行 recipe ，输入输出都是原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_run_recipe_reverse(const char *recipe_json,
+                                    const uint8_t *input,
+                                    size_t input_len,
+                                    uint8_t *out,
+                                    size_t *out_len);
+
+// 以 JSON 文本列出所有已注册算法
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_list_algorithms(uint8_t *out, size_t *out_len);
+
+// 按名称计算哈希，format 同 `rw_bytes_sha` ，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_hash_by_name(const char *name,
+                              const uint8_t *data,
+                              size_t data_len,
+                              const char *format,
+                              uint8_t *out,
+                              size_t *out_len);
+
+// 常量时间校验 HMAC ，tag 为原始字节，成功时把是否匹配写入 `*valid`
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_verify_hmac_by_name(const char *name,
+                                     const uint8_t *key,
+                                     size_t key_len,
+                                     const uint8_t *data,
+                                     size_t data_len,
+                                     const uint8_t *tag,
+                                     size_t tag_len,
+                                     bool *valid);
+
+// 按名称编码，输出文本
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_encode_by_name(const char *name,
+                                const uint8_t *data,
+                                size_t data_len,
+                                uint8_t *out,
+                                size_t *out_len);
+
+// 按名称解码，输出原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_decode_by_name(const char *name,
+                                const uint8_t *encoded,
+                                size_t encoded_len,
+                                uint8_t *out,
+                                size_t *out_len);
+
+// 按名称加密，输入输出都是原始字节，不需要 IV 的算法可传 `iv_len = 0`
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_encrypt_by_name(const char *name,
+                                 const uint8_t *plain,
+                                 size_t plain_len,
+                                 const uint8_t *key,
+                                 size_t key_len,
+                                 const uint8_t *iv,
+                                 size_t iv_len,
+                                 uint8_t *out,
+                                 size_t *out_len);
+
+// 按名称解密，输入输出都是原始字节
+//
+// # Safety
+// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+enum RwStatus rw_decrypt_by_name(const char *name,
+                                 const uint8_t *cipher_text,
+                                 size_t cipher_text_len,
+                                 const uint8_t *key,
+                                 size_t key_len,
+                                 const uint8_t *iv,
+                                 size_t iv_len,
+                                 uint8_t *out,
+                                 size_t *out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUST_WASM_H */
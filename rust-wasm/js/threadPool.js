@@ -0,0 +1,47 @@
+// src/thread_pool.rs 的 JS 部分，wasm-bindgen 会把它复制到 <out-dir>/snippets/<crate>/js/ 下。
+// 同一个文件既提供 startWorkers（在调用 initThreadPool 的线程中执行），也是每个线程池 worker 的入口。
+
+function waitForMessage(target, type) {
+  return new Promise((resolve) => {
+    target.addEventListener('message', function onMessage({ data }) {
+      if (data?.type === type) {
+        target.removeEventListener('message', onMessage);
+        resolve(data);
+      }
+    });
+  });
+}
+
+// worker 入口：用共享的 module / memory 初始化 wasm ，然后进入 rayon 线程循环，不再返回。
+// 普通页面或其他 worker 导入这个文件时只会多一个监听器，收不到 init 消息就什么也不做
+waitForMessage(self, 'rw_thread_pool_init').then(async ({ init, receiver }) => {
+  // snippets/<crate>/js/ -> wasm-pack 产物根目录
+  const pkg = await import('../../../rust_wasm.js');
+  await pkg.default(init);
+  postMessage({ type: 'rw_thread_pool_ready' });
+  pkg.rw_thread_pool_start_worker(receiver);
+});
+
+// 持有引用，避免 worker 被回收
+let workers = [];
+
+export async function startWorkers(module, memory, builder) {
+  const numThreads = builder.numThreads();
+  if (numThreads === 0) {
+    throw new Error('num_threads must be greater than 0');
+  }
+  const message = {
+    type: 'rw_thread_pool_init',
+    init: { module_or_path: module, memory },
+    receiver: builder.receiver(),
+  };
+  workers = await Promise.all(
+    Array.from({ length: numThreads }, async () => {
+      const worker = new Worker(new URL('./threadPool.js', import.meta.url), { type: 'module' });
+      worker.postMessage(message);
+      await waitForMessage(worker, 'rw_thread_pool_ready');
+      return worker;
+    }),
+  );
+  builder.build();
+}
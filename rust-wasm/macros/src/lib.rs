@@ -0,0 +1,65 @@
+// rust-wasm 内部使用的过程宏。
+//
+// sealed!("hans7") / sealed!(b"hctf") 在编译时用 SplitMix64 密钥流加密字面量，展开为
+// `crate::secret::Sealed::new(&[密文], 密钥)` ，运行时由 Sealed::reveal 解密，
+// 编译出的 wasm 数据段中只有密文。密钥由构建脚本给出的 RUST_WASM_SECRET_SEED
+// 和字面量本身派生，种子不同则密文不同。密钥和密文放在一起，只能挡住 strings 这类静态提取，
+// 不是真正的保密
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Error, Lit, parse_macro_input};
+
+const SEED_VAR: &str = "RUST_WASM_SECRET_SEED";
+
+// 与 secret.rs 中的解密一致
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[proc_macro]
+pub fn sealed(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as Lit);
+    let plain = match &lit {
+        Lit::Str(s) => s.value().into_bytes(),
+        Lit::ByteStr(s) => s.value(),
+        _ => {
+            return Error::new(
+                lit.span(),
+                "sealed! expects a string or byte string literal",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    let Some(seed) = std::env::var(SEED_VAR)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+    else {
+        return Error::new(
+            lit.span(),
+            format!("{SEED_VAR} is not set, it should be provided by build.rs"),
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let mut state = seed ^ fnv1a(&plain);
+    let key = splitmix64(&mut state);
+    let mut stream = key;
+    let mut data = Vec::with_capacity(plain.len());
+    for chunk in plain.chunks(8) {
+        let block = splitmix64(&mut stream).to_le_bytes();
+        data.extend(chunk.iter().zip(block).map(|(b, k)| b ^ k));
+    }
+    quote!(crate::secret::Sealed::new(&[#(#data),*], #key)).into()
+}
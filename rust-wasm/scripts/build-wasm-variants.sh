@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+# 生成前端 fileShaWorker 使用的三种 wasm 构建，worker 运行时按浏览器能力选择最快的一种：
+#   src/wasm          标量，所有浏览器可用（与 README 中的默认构建相同）
+#   src/wasm-simd     +simd128 ，BLAKE3 使用 SIMD 实现
+#   src/wasm-threads  +simd128 +atomics ，rayon 线程池运行在 Web Worker 上，需要 crossOriginIsolated 页面
+# threads 构建需要 nightly 和 rust-src（重新编译带 atomics 的 std）：
+#   rustup toolchain install nightly --component rust-src --target wasm32-unknown-unknown
+# 只构建部分变体：scripts/build-wasm-variants.sh scalar simd
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+OUT_ROOT=../wasm-re-ui/src
+VARIANTS=("$@")
+if [ ${#VARIANTS[@]} -eq 0 ]; then
+  VARIANTS=(scalar simd threads)
+fi
+
+for variant in "${VARIANTS[@]}"; do
+  case "$variant" in
+    scalar)
+      wasm-pack build --release --target web --out-dir "$OUT_ROOT/wasm"
+      ;;
+    simd)
+      RUSTFLAGS="-C target-feature=+simd128" \
+        wasm-pack build --release --target web --out-dir "$OUT_ROOT/wasm-simd" -- --features simd
+      ;;
+    threads)
+      # 共享、由 JS 传入的内存，以及 wasm-bindgen 初始化每个线程所需的 TLS 导出
+      RUSTFLAGS="-C target-feature=+atomics,+bulk-memory,+mutable-globals,+simd128 \
+-C link-arg=--shared-memory -C link-arg=--import-memory -C link-arg=--max-memory=1073741824 \
+-C link-arg=--export=__wasm_init_tls -C link-arg=--export=__tls_size \
+-C link-arg=--export=__tls_align -C link-arg=--export=__tls_base" \
+        rustup run nightly wasm-pack build --release --target web --out-dir "$OUT_ROOT/wasm-threads" \
+        -- --features simd,threads -Z build-std=panic_abort,std
+      ;;
+    *)
+      echo "unknown variant: $variant (expected scalar, simd or threads)" >&2
+      exit 1
+      ;;
+  esac
+done
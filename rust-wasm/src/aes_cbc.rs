@@ -1,61 +1,85 @@
 use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, block_padding::Pkcs7};
 use base64::prelude::*;
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
 
 type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
 type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
 
 use crate::aes_cbc_error::AesError;
+use crate::secret::{reveal, secret_into_string};
 
-#[wasm_bindgen]
-pub fn aes_cbc_encrypt(plain: &str, key: &[u8], iv: &[u8]) -> Result<String, AesError> {
+fn check_key_iv(key: &[u8], iv: &[u8]) -> Result<(), AesError> {
     if key.len() != 16 {
         return Err(AesError::BadKeyLen(key.len()));
     }
     if iv.len() != 16 {
         return Err(AesError::BadIvLen(iv.len()));
     }
+    Ok(())
+}
 
-    let inp = plain.trim();
+// 字节层面的 AES-128-CBC + PKCS7 ，不做 trim 和 base64 ，供 pipeline 等内部模块复用。
+// 加解密都在同一个 Zeroizing 缓冲区中原地进行，出错时缓冲区里的明文随之清零；
+// 开启 aes/zeroize 后 cipher 的轮密钥在 drop 时也会清零
+pub fn aes_cbc_encrypt_bytes(plain: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AesError> {
+    check_key_iv(key, iv)?;
 
     let cipher =
         Aes128CbcEnc::new_from_slices(key, iv).map_err(|_| AesError::BadKeyLen(key.len()))?;
 
-    let mut buf = vec![0u8; inp.len() + 16];
-    buf[..inp.len()].copy_from_slice(inp.as_bytes());
-    let encrypted = cipher
-        .encrypt_padded_mut::<Pkcs7>(&mut buf, inp.len())
-        .map_err(|e| AesError::AesEncrypt(e))?;
+    let mut buf = Zeroizing::new(vec![0u8; plain.len() + 16]);
+    buf[..plain.len()].copy_from_slice(plain);
+    let encrypted_len = cipher
+        .encrypt_padded_mut::<Pkcs7>(&mut buf, plain.len())
+        .map_err(AesError::AesEncrypt)?
+        .len();
 
-    Ok(BASE64_STANDARD.encode(encrypted))
+    buf.truncate(encrypted_len);
+    Ok(reveal(buf))
 }
 
-#[wasm_bindgen]
-pub fn aes_cbc_decrypt(b64_cipher: &str, key: &[u8], iv: &[u8]) -> Result<String, AesError> {
-    if key.len() != 16 {
-        return Err(AesError::BadKeyLen(key.len()));
-    }
-    if iv.len() != 16 {
-        return Err(AesError::BadIvLen(iv.len()));
-    }
-
-    let cipher_text = BASE64_STANDARD.decode(b64_cipher.trim())?;
+pub fn aes_cbc_decrypt_bytes(
+    cipher_text: &[u8],
+    key: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, AesError> {
+    check_key_iv(key, iv)?;
 
     let cipher =
         Aes128CbcDec::new_from_slices(key, iv).map_err(|_| AesError::BadKeyLen(key.len()))?;
 
-    let mut buf = cipher_text;
-    let decrypted = cipher
+    let mut buf = Zeroizing::new(cipher_text.to_vec());
+    let decrypted_len = cipher
         .decrypt_padded_mut::<Pkcs7>(&mut buf)
-        .map_err(|e| AesError::AesDecrypt(e))?;
+        .map_err(AesError::AesDecrypt)?
+        .len();
 
-    let plain = std::str::from_utf8(decrypted)?;
-    Ok(plain.to_string())
+    buf.truncate(decrypted_len);
+    Ok(reveal(buf))
+}
+
+#[wasm_bindgen]
+pub fn aes_cbc_encrypt(plain: &str, key: &[u8], iv: &[u8]) -> Result<String, AesError> {
+    let encrypted = aes_cbc_encrypt_bytes(plain.trim().as_bytes(), key, iv)?;
+    Ok(BASE64_STANDARD.encode(encrypted))
+}
+
+#[wasm_bindgen]
+pub fn aes_cbc_decrypt(b64_cipher: &str, key: &[u8], iv: &[u8]) -> Result<String, AesError> {
+    check_key_iv(key, iv)?;
+
+    let cipher_text = BASE64_STANDARD.decode(b64_cipher.trim())?;
+    let decrypted = aes_cbc_decrypt_bytes(&cipher_text, key, iv)?;
+
+    Ok(secret_into_string(decrypted)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+    use proptest::prelude::*;
 
     #[test]
     fn aes_cbc_enc_basic_test() -> anyhow::Result<()> {
@@ -91,7 +115,7 @@ mod tests {
         let key = b"1234567890123456";
         let iv = b"abcdef9876543210";
         let b64_cipher = "fSlN+cILgYX1p2Mo6i7waQ==";
-        let decrypted = aes_cbc_decrypt(&b64_cipher, key, iv)?;
+        let decrypted = aes_cbc_decrypt(b64_cipher, key, iv)?;
         assert_eq!(decrypted, plain);
 
         Ok(())
@@ -125,4 +149,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn aes_cbc_structured_error_test() {
+        let iv = b"abcdef9876543210";
+        let err = RustWasmError::from(aes_cbc_encrypt("hello", b"short", iv).unwrap_err());
+        assert_eq!(err.code, ErrorCode::BadKeyLen);
+        assert_eq!(err.module, "aes_cbc");
+        assert_eq!(err.details["expected"], 16);
+        assert_eq!(err.details["actual"], 5);
+
+        let key = b"1234567890123456";
+        let err = RustWasmError::from(
+            aes_cbc_decrypt("fSlN+cILgYX1p2Mo6i7waQ==", key, b"iv").unwrap_err(),
+        );
+        assert_eq!(err.code, ErrorCode::BadIvLen);
+        assert_eq!(err.details["actual"], 2);
+
+        let err = RustWasmError::from(aes_cbc_decrypt("not base64!", key, iv).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidBase64);
+
+        let err =
+            RustWasmError::from(aes_cbc_decrypt("AAAAAAAAAAAAAAAAAAAAAA==", key, iv).unwrap_err());
+        assert_eq!(err.code, ErrorCode::DecryptFailed);
+    }
+
+    proptest! {
+        #[test]
+        fn bytes_roundtrip_prop(plain: Vec<u8>, key: [u8; 16], iv: [u8; 16]) {
+            let encrypted = aes_cbc_encrypt_bytes(&plain, &key, &iv).unwrap();
+            prop_assert_eq!(encrypted.len(), (plain.len() / 16 + 1) * 16);
+            prop_assert_eq!(aes_cbc_decrypt_bytes(&encrypted, &key, &iv).unwrap(), plain);
+        }
+
+        #[test]
+        fn str_roundtrip_prop(plain in "\\PC*", key: [u8; 16], iv: [u8; 16]) {
+            let encrypted = aes_cbc_encrypt(&plain, &key, &iv).unwrap();
+            prop_assert_eq!(aes_cbc_decrypt(&encrypted, &key, &iv).unwrap(), plain.trim());
+        }
+
+        // key / iv 长度也是任意的，错误长度必须返回错误而不是 panic
+        #[test]
+        fn decrypt_never_panics_prop(
+            cipher_text: Vec<u8>,
+            b64 in "\\PC*",
+            key in proptest::collection::vec(any::<u8>(), 0..40),
+            iv in proptest::collection::vec(any::<u8>(), 0..40),
+        ) {
+            let _ = aes_cbc_decrypt_bytes(&cipher_text, &key, &iv);
+            let _ = aes_cbc_decrypt(&b64, &key, &iv);
+        }
+    }
 }
@@ -4,6 +4,8 @@ use wasm_bindgen::prelude::*;
 use aes::cipher::block_padding::UnpadError;
 use aes::cipher::inout::PadError;
 
+use crate::error::{ErrorCode, RustWasmError, base64_decode_error};
+
 #[derive(Debug, Error)]
 pub enum AesError {
     #[error("base64 decode failed: {0}")]
@@ -33,8 +35,35 @@ impl From<UnpadError> for AesError {
     }
 }
 
+impl From<AesError> for RustWasmError {
+    fn from(e: AesError) -> Self {
+        let message = e.to_string();
+        match e {
+            AesError::Base64(inner) => base64_decode_error(inner, "aes_cbc", message),
+            AesError::Utf8(inner) => RustWasmError::new(ErrorCode::InvalidUtf8, "aes_cbc", message)
+                .with_detail("valid_up_to", inner.valid_up_to()),
+            AesError::BadKeyLen(actual) => {
+                RustWasmError::new(ErrorCode::BadKeyLen, "aes_cbc", message)
+                    .with_detail("expected", 16)
+                    .with_detail("actual", actual)
+            }
+            AesError::BadIvLen(actual) => {
+                RustWasmError::new(ErrorCode::BadIvLen, "aes_cbc", message)
+                    .with_detail("expected", 16)
+                    .with_detail("actual", actual)
+            }
+            AesError::AesEncrypt(_) => {
+                RustWasmError::new(ErrorCode::EncryptFailed, "aes_cbc", message)
+            }
+            AesError::AesDecrypt(_) => {
+                RustWasmError::new(ErrorCode::DecryptFailed, "aes_cbc", message)
+            }
+        }
+    }
+}
+
 impl From<AesError> for JsValue {
     fn from(e: AesError) -> Self {
-        JsValue::from_str(&e.to_string())
+        RustWasmError::from(e).into()
     }
 }
@@ -0,0 +1,56 @@
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "aes")]
+use crate::aes_cbc_error::AesError;
+use crate::error::{ErrorCode, RustWasmError, base64_decode_error};
+use crate::sha_demo::sha_demo_error::ShaHashError;
+
+#[derive(Debug, Error)]
+pub enum AlgorithmError {
+    #[error("unknown {kind}: {name}")]
+    UnknownAlgorithm { kind: &'static str, name: String },
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
+    #[error("invalid encoded input: {0}")]
+    InvalidEncoding(String),
+    #[error("base64 decode failed: {0}")]
+    Base64Decode(#[from] base64::DecodeError),
+    #[cfg(feature = "aes")]
+    #[error(transparent)]
+    Aes(#[from] AesError),
+    #[error(transparent)]
+    Sha(#[from] ShaHashError),
+}
+
+impl From<AlgorithmError> for RustWasmError {
+    fn from(e: AlgorithmError) -> Self {
+        let message = e.to_string();
+        match e {
+            AlgorithmError::UnknownAlgorithm { kind, name } => {
+                RustWasmError::new(ErrorCode::UnknownAlgorithm, "algorithm", message)
+                    .with_detail("kind", kind)
+                    .with_detail("name", name)
+            }
+            AlgorithmError::InvalidKey(reason) => {
+                RustWasmError::new(ErrorCode::InvalidKey, "algorithm", message)
+                    .with_detail("reason", reason)
+            }
+            AlgorithmError::InvalidEncoding(reason) => {
+                RustWasmError::new(ErrorCode::InvalidEncoding, "algorithm", message)
+                    .with_detail("reason", reason)
+            }
+            AlgorithmError::Base64Decode(inner) => base64_decode_error(inner, "algorithm", message),
+            // 包装的底层错误保留其原始 module
+            #[cfg(feature = "aes")]
+            AlgorithmError::Aes(inner) => inner.into(),
+            AlgorithmError::Sha(inner) => inner.into(),
+        }
+    }
+}
+
+impl From<AlgorithmError> for JsValue {
+    fn from(e: AlgorithmError) -> Self {
+        RustWasmError::from(e).into()
+    }
+}
@@ -0,0 +1,93 @@
+use super::algorithm_error::AlgorithmError;
+use super::traits::{Algorithm, Cipher, CipherKind};
+#[cfg(feature = "aes")]
+use crate::aes_cbc::{aes_cbc_decrypt_bytes, aes_cbc_encrypt_bytes};
+#[cfg(feature = "xor")]
+use crate::xor_demo::{XOR_KEY, xor_with_key};
+#[cfg(feature = "xor")]
+use zeroize::Zeroizing;
+
+#[cfg(feature = "aes")]
+pub struct Aes128CbcCipher;
+
+#[cfg(feature = "aes")]
+impl Algorithm for Aes128CbcCipher {
+    fn name(&self) -> &'static str {
+        "aes_cbc"
+    }
+}
+
+#[cfg(feature = "aes")]
+impl Cipher for Aes128CbcCipher {
+    fn kind(&self) -> CipherKind {
+        CipherKind::Block
+    }
+
+    fn key_len(&self) -> Option<usize> {
+        Some(16)
+    }
+
+    fn iv_len(&self) -> Option<usize> {
+        Some(16)
+    }
+
+    fn encrypt(&self, plain: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AlgorithmError> {
+        Ok(aes_cbc_encrypt_bytes(plain, key, iv)?)
+    }
+
+    fn decrypt(
+        &self,
+        cipher_text: &[u8],
+        key: &[u8],
+        iv: &[u8],
+    ) -> Result<Vec<u8>, AlgorithmError> {
+        Ok(aes_cbc_decrypt_bytes(cipher_text, key, iv)?)
+    }
+}
+
+// encrypt_username 使用的循环异或，加密和解密是同一个操作
+#[cfg(feature = "xor")]
+pub struct XorCipher;
+
+#[cfg(feature = "xor")]
+impl Algorithm for XorCipher {
+    fn name(&self) -> &'static str {
+        "xor"
+    }
+}
+
+#[cfg(feature = "xor")]
+impl Cipher for XorCipher {
+    fn kind(&self) -> CipherKind {
+        CipherKind::Stream
+    }
+
+    fn key_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn iv_len(&self) -> Option<usize> {
+        None
+    }
+
+    // 与 encrypt_username 相同的 "hctf"
+    fn default_key(&self) -> Option<Zeroizing<Vec<u8>>> {
+        Some(XOR_KEY.reveal())
+    }
+
+    fn encrypt(&self, plain: &[u8], key: &[u8], _iv: &[u8]) -> Result<Vec<u8>, AlgorithmError> {
+        if key.is_empty() {
+            return Err(AlgorithmError::InvalidKey("xor key is empty".into()));
+        }
+        Ok(xor_with_key(plain, key))
+    }
+
+    fn decrypt(
+        &self,
+        cipher_text: &[u8],
+        key: &[u8],
+        iv: &[u8],
+    ) -> Result<Vec<u8>, AlgorithmError> {
+        self.encrypt(cipher_text, key, iv)
+    }
+}
@@ -0,0 +1,79 @@
+use base64::{Engine as _, engine::GeneralPurpose, prelude::*};
+
+use super::algorithm_error::AlgorithmError;
+use super::traits::{Algorithm, Encoder};
+#[cfg(feature = "base64")]
+use crate::custom_base64::custom_engine;
+
+// 对 base64 crate 引擎的统一封装：标准码表、URL 安全码表、HANS7 自定义码表。
+// HANS7 的码表是加密的常量，引擎在每次调用时才构造，因此这里存放构造引擎的函数
+pub struct Base64Encoder {
+    name: &'static str,
+    engine: fn() -> GeneralPurpose,
+}
+
+impl Base64Encoder {
+    pub const STANDARD: Base64Encoder = Base64Encoder {
+        name: "base64",
+        engine: || BASE64_STANDARD,
+    };
+    pub const URL_SAFE: Base64Encoder = Base64Encoder {
+        name: "base64url",
+        engine: || BASE64_URL_SAFE_NO_PAD,
+    };
+    #[cfg(feature = "base64")]
+    pub const HANS7: Base64Encoder = Base64Encoder {
+        name: "custom_base64",
+        engine: custom_engine,
+    };
+}
+
+impl Algorithm for Base64Encoder {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Encoder for Base64Encoder {
+    fn encode(&self, data: &[u8]) -> String {
+        (self.engine)().encode(data)
+    }
+
+    fn decode(&self, encoded: &str) -> Result<Vec<u8>, AlgorithmError> {
+        Ok((self.engine)().decode(encoded.trim())?)
+    }
+}
+
+pub struct HexEncoder;
+
+impl Algorithm for HexEncoder {
+    fn name(&self) -> &'static str {
+        "hex"
+    }
+}
+
+impl Encoder for HexEncoder {
+    fn encode(&self, data: &[u8]) -> String {
+        data.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn decode(&self, encoded: &str) -> Result<Vec<u8>, AlgorithmError> {
+        decode_hex(encoded.trim().as_bytes()).map_err(AlgorithmError::InvalidEncoding)
+    }
+}
+
+pub(crate) fn decode_hex(data: &[u8]) -> Result<Vec<u8>, String> {
+    if !data.len().is_multiple_of(2) {
+        return Err(format!("hex input has odd length {}", data.len()));
+    }
+    let digit = |c: u8| (c as char).to_digit(16).map(|v| v as u8);
+    data.chunks(2)
+        .map(|pair| match (digit(pair[0]), digit(pair[1])) {
+            (Some(hi), Some(lo)) => Ok((hi << 4) | lo),
+            _ => Err(format!(
+                "invalid hex digit pair {:?}",
+                String::from_utf8_lossy(pair)
+            )),
+        })
+        .collect()
+}
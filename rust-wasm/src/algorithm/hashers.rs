@@ -0,0 +1,82 @@
+use super::traits::{Algorithm, Hasher};
+#[cfg(feature = "blake3")]
+use crate::sha_demo::parallel_hash::compute_blake3_bytes;
+#[cfg(feature = "sha")]
+use crate::sha_demo::sha_bytes_demo::{compute_sha256_bytes, compute_sha512_bytes};
+use crate::sha_demo::utils::HashOutput;
+
+#[cfg(feature = "sha")]
+pub struct Sha256Hasher;
+
+#[cfg(feature = "sha")]
+impl Algorithm for Sha256Hasher {
+    fn name(&self) -> &'static str {
+        "sha256"
+    }
+}
+
+#[cfg(feature = "sha")]
+impl Hasher for Sha256Hasher {
+    fn output_len(&self) -> usize {
+        32
+    }
+
+    fn block_len(&self) -> usize {
+        64
+    }
+
+    fn hash(&self, data: &[u8]) -> HashOutput {
+        compute_sha256_bytes(data)
+    }
+}
+
+#[cfg(feature = "sha")]
+pub struct Sha512Hasher;
+
+#[cfg(feature = "sha")]
+impl Algorithm for Sha512Hasher {
+    fn name(&self) -> &'static str {
+        "sha512"
+    }
+}
+
+#[cfg(feature = "sha")]
+impl Hasher for Sha512Hasher {
+    fn output_len(&self) -> usize {
+        64
+    }
+
+    fn block_len(&self) -> usize {
+        128
+    }
+
+    fn hash(&self, data: &[u8]) -> HashOutput {
+        compute_sha512_bytes(data)
+    }
+}
+
+// threads 构建中大输入会走 rayon 并行，见 parallel_hash
+#[cfg(feature = "blake3")]
+pub struct Blake3Hasher;
+
+#[cfg(feature = "blake3")]
+impl Algorithm for Blake3Hasher {
+    fn name(&self) -> &'static str {
+        "blake3"
+    }
+}
+
+#[cfg(feature = "blake3")]
+impl Hasher for Blake3Hasher {
+    fn output_len(&self) -> usize {
+        32
+    }
+
+    fn block_len(&self) -> usize {
+        64
+    }
+
+    fn hash(&self, data: &[u8]) -> HashOutput {
+        compute_blake3_bytes(data)
+    }
+}
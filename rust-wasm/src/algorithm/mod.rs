@@ -0,0 +1,8 @@
+pub mod algorithm_error;
+#[cfg(any(feature = "aes", feature = "xor"))]
+pub mod ciphers;
+pub mod encoders;
+#[cfg(any(feature = "sha", feature = "blake3"))]
+pub mod hashers;
+pub mod registry;
+pub mod traits;
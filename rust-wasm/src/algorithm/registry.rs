@@ -0,0 +1,419 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+use super::algorithm_error::AlgorithmError;
+#[cfg(feature = "aes")]
+use super::ciphers::Aes128CbcCipher;
+#[cfg(feature = "xor")]
+use super::ciphers::XorCipher;
+use super::encoders::{Base64Encoder, HexEncoder};
+#[cfg(feature = "blake3")]
+use super::hashers::Blake3Hasher;
+#[cfg(feature = "sha")]
+use super::hashers::{Sha256Hasher, Sha512Hasher};
+use super::traits::{Cipher, Encoder, Hasher};
+use crate::secret::ct_eq;
+use crate::sha_demo::utils::HashFormat;
+
+// 运行时算法注册表。新增算法只需实现对应 trait 并在 with_builtins 里注册，
+// 即可通过下面的 *_by_name 导出函数被前端和测试使用。
+// 只注册当前 feature 开启的算法，未开启的算法按名称查找时报 UnknownAlgorithm
+#[derive(Default)]
+pub struct Registry {
+    hashers: Vec<Box<dyn Hasher>>,
+    encoders: Vec<Box<dyn Encoder>>,
+    ciphers: Vec<Box<dyn Cipher>>,
+}
+
+// 名称大小写不敏感，'-' 与 '_' 等价，例如 "AES-CBC" 可查到 "aes_cbc"
+fn normalize_name(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace('-', "_")
+}
+
+impl Registry {
+    pub fn with_builtins() -> Self {
+        let mut registry = Registry::default();
+        #[cfg(feature = "sha")]
+        {
+            registry.register_hasher(Box::new(Sha256Hasher));
+            registry.register_hasher(Box::new(Sha512Hasher));
+        }
+        #[cfg(feature = "blake3")]
+        registry.register_hasher(Box::new(Blake3Hasher));
+        registry.register_encoder(Box::new(Base64Encoder::STANDARD));
+        registry.register_encoder(Box::new(Base64Encoder::URL_SAFE));
+        #[cfg(feature = "base64")]
+        registry.register_encoder(Box::new(Base64Encoder::HANS7));
+        registry.register_encoder(Box::new(HexEncoder));
+        #[cfg(feature = "aes")]
+        registry.register_cipher(Box::new(Aes128CbcCipher));
+        #[cfg(feature = "xor")]
+        registry.register_cipher(Box::new(XorCipher));
+        registry
+    }
+
+    pub fn register_hasher(&mut self, hasher: Box<dyn Hasher>) {
+        self.hashers.push(hasher);
+    }
+
+    pub fn register_encoder(&mut self, encoder: Box<dyn Encoder>) {
+        self.encoders.push(encoder);
+    }
+
+    pub fn register_cipher(&mut self, cipher: Box<dyn Cipher>) {
+        self.ciphers.push(cipher);
+    }
+
+    pub fn hasher(&self, name: &str) -> Result<&dyn Hasher, AlgorithmError> {
+        let key = normalize_name(name);
+        self.hashers
+            .iter()
+            .find(|h| h.name() == key)
+            .map(|h| h.as_ref())
+            .ok_or_else(|| AlgorithmError::UnknownAlgorithm {
+                kind: "hasher",
+                name: name.to_string(),
+            })
+    }
+
+    pub fn encoder(&self, name: &str) -> Result<&dyn Encoder, AlgorithmError> {
+        let key = normalize_name(name);
+        self.encoders
+            .iter()
+            .find(|e| e.name() == key)
+            .map(|e| e.as_ref())
+            .ok_or_else(|| AlgorithmError::UnknownAlgorithm {
+                kind: "encoder",
+                name: name.to_string(),
+            })
+    }
+
+    pub fn cipher(&self, name: &str) -> Result<&dyn Cipher, AlgorithmError> {
+        let key = normalize_name(name);
+        self.ciphers
+            .iter()
+            .find(|c| c.name() == key)
+            .map(|c| c.as_ref())
+            .ok_or_else(|| AlgorithmError::UnknownAlgorithm {
+                kind: "cipher",
+                name: name.to_string(),
+            })
+    }
+
+    pub fn hashers(&self) -> impl Iterator<Item = &dyn Hasher> {
+        self.hashers.iter().map(|h| h.as_ref())
+    }
+
+    pub fn encoders(&self) -> impl Iterator<Item = &dyn Encoder> {
+        self.encoders.iter().map(|e| e.as_ref())
+    }
+
+    pub fn ciphers(&self) -> impl Iterator<Item = &dyn Cipher> {
+        self.ciphers.iter().map(|c| c.as_ref())
+    }
+}
+
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::with_builtins)
+}
+
+#[derive(Serialize)]
+struct HasherInfo {
+    name: &'static str,
+    output_len: usize,
+}
+
+#[derive(Serialize)]
+struct CipherInfo {
+    name: &'static str,
+    kind: &'static str,
+    key_len: Option<usize>,
+    iv_len: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct AlgorithmList {
+    hashers: Vec<HasherInfo>,
+    encoders: Vec<&'static str>,
+    ciphers: Vec<CipherInfo>,
+}
+
+/// 以 JSON 形式列出所有已注册算法，前端可据此动态生成选项
+#[wasm_bindgen]
+pub fn list_algorithms() -> String {
+    let registry = registry();
+    let list = AlgorithmList {
+        hashers: registry
+            .hashers()
+            .map(|h| HasherInfo {
+                name: h.name(),
+                output_len: h.output_len(),
+            })
+            .collect(),
+        encoders: registry.encoders().map(|e| e.name()).collect(),
+        ciphers: registry
+            .ciphers()
+            .map(|c| CipherInfo {
+                name: c.name(),
+                kind: c.kind().as_str(),
+                key_len: c.key_len(),
+                iv_len: c.iv_len(),
+            })
+            .collect(),
+    };
+    serde_json::to_string(&list).unwrap_or_default()
+}
+
+/// 按名称计算哈希，format 同 `get_bytes_sha_formatted`
+#[wasm_bindgen]
+pub fn hash_by_name(name: &str, data: &[u8], format: &str) -> Result<String, AlgorithmError> {
+    let format: HashFormat = format.parse()?;
+    let hasher = registry().hasher(name)?;
+    Ok(hasher.hash(data).format(format))
+}
+
+/// 校验 HMAC ，tag 为原始字节（JS 侧为 `Uint8Array`）。用常量时间比较，
+/// 不会通过耗时泄露 tag 前几个字节是否正确；不接受截断的 tag
+#[wasm_bindgen]
+pub fn verify_hmac_by_name(
+    name: &str,
+    key: &[u8],
+    data: &[u8],
+    tag: &[u8],
+) -> Result<bool, AlgorithmError> {
+    let hasher = registry().hasher(name)?;
+    let expected = Zeroizing::new(hasher.hmac(key, data).into_bytes());
+    Ok(ct_eq(&expected, tag))
+}
+
+#[wasm_bindgen]
+pub fn encode_by_name(name: &str, data: &[u8]) -> Result<String, AlgorithmError> {
+    Ok(registry().encoder(name)?.encode(data))
+}
+
+#[wasm_bindgen]
+pub fn decode_by_name(name: &str, encoded: &str) -> Result<Vec<u8>, AlgorithmError> {
+    registry().encoder(name)?.decode(encoded)
+}
+
+#[wasm_bindgen]
+pub fn encrypt_by_name(
+    name: &str,
+    plain: &[u8],
+    key: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, AlgorithmError> {
+    registry().cipher(name)?.encrypt(plain, key, iv)
+}
+
+#[wasm_bindgen]
+pub fn decrypt_by_name(
+    name: &str,
+    cipher_text: &[u8],
+    key: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, AlgorithmError> {
+    registry().cipher(name)?.decrypt(cipher_text, key, iv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLES: [&[u8]; 5] = [
+        b"",
+        b"a",
+        b"hello world",
+        "爱拼才会赢💪".as_bytes(),
+        &[0x00, 0xFF, 0xFE, 0x80, 0x7F],
+    ];
+
+    // 以下三个测试遍历整个 registry ，新注册的算法无需额外代码即可得到覆盖
+
+    #[test]
+    fn every_hasher_output_len_test() {
+        for hasher in registry().hashers() {
+            for sample in SAMPLES {
+                let out = hasher.hash(sample);
+                assert_eq!(
+                    out.as_bytes().len(),
+                    hasher.output_len(),
+                    "{}",
+                    hasher.name()
+                );
+                assert_eq!(
+                    out,
+                    hasher.hash(sample),
+                    "{} not deterministic",
+                    hasher.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_encoder_roundtrip_test() -> anyhow::Result<()> {
+        for encoder in registry().encoders() {
+            for sample in SAMPLES {
+                let encoded = encoder.encode(sample);
+                assert_eq!(encoder.decode(&encoded)?, sample, "{}", encoder.name());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn every_cipher_roundtrip_test() -> anyhow::Result<()> {
+        for cipher in registry().ciphers() {
+            let key = vec![0x42; cipher.key_len().unwrap_or(5)];
+            let iv = vec![0x24; cipher.iv_len().unwrap_or(0)];
+            for sample in SAMPLES {
+                let encrypted = cipher.encrypt(sample, &key, &iv)?;
+                let decrypted = cipher.decrypt(&encrypted, &key, &iv)?;
+                assert_eq!(decrypted, sample, "{}", cipher.name());
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "aes", feature = "base64", feature = "sha", feature = "xor"))]
+    #[test]
+    fn registry_matches_existing_exports_test() -> anyhow::Result<()> {
+        use crate::aes_cbc::aes_cbc_encrypt;
+        use crate::custom_base64::encode_custom_base64;
+        use crate::sha_demo::sha_bytes_demo::{get_bytes_sha256_pure, get_bytes_sha512_pure};
+        use crate::xor_demo::encrypt_username;
+        use base64::prelude::*;
+
+        assert_eq!(
+            hash_by_name("sha256", b"hello world", "hex")?,
+            get_bytes_sha256_pure(b"hello world")
+        );
+        assert_eq!(
+            hash_by_name("SHA512", b"hello world", "hex")?,
+            get_bytes_sha512_pure(b"hello world")
+        );
+
+        let xor = encrypt_by_name("xor", b"hans7", b"hctf", &[])?;
+        assert_eq!(BASE64_STANDARD.encode(xor), encrypt_username("hans7"));
+
+        let key = b"1234567890123456";
+        let iv = b"abcdef9876543210";
+        let aes = encrypt_by_name("AES-CBC", b"hello world", key, iv)?;
+        assert_eq!(
+            encode_by_name("base64", &aes)?,
+            aes_cbc_encrypt("hello world", key, iv)?
+        );
+
+        assert_eq!(
+            encode_by_name("custom_base64", "爱拼才会赢💪".as_bytes())?,
+            encode_custom_base64("爱拼才会赢💪")?
+        );
+        assert_eq!(decode_by_name("hex", "DEADbeef")?, [0xde, 0xad, 0xbe, 0xef]);
+
+        Ok(())
+    }
+
+    // RFC 4231 test case 2 ，完整的向量见 tests/vectors
+    #[cfg(feature = "sha")]
+    #[test]
+    fn verify_hmac_by_name_test() -> anyhow::Result<()> {
+        let tag = decode_by_name(
+            "hex",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        )?;
+        let data = b"what do ya want for nothing?";
+        assert!(verify_hmac_by_name("sha256", b"Jefe", data, &tag)?);
+        assert!(!verify_hmac_by_name("sha256", b"jefe", data, &tag)?);
+        assert!(!verify_hmac_by_name("sha256", b"Jefe", data, &tag[..16])?);
+        assert!(verify_hmac_by_name("md5", b"k", b"x", &tag).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_by_name_test() -> anyhow::Result<()> {
+        assert_eq!(
+            hash_by_name("BLAKE3", b"abc", "hex")?,
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "aes", feature = "xor"))]
+    #[test]
+    fn registry_errors_test() {
+        assert!(registry().hasher("md5").is_err());
+        assert!(hash_by_name("sha256", b"x", "base32").is_err());
+        assert!(encode_by_name("base32", b"x").is_err());
+        assert!(decode_by_name("hex", "abc").is_err());
+        assert!(decode_by_name("base64", "!!!").is_err());
+        assert!(encrypt_by_name("des", b"x", b"k", b"").is_err());
+        assert!(encrypt_by_name("xor", b"x", b"", b"").is_err());
+        assert!(encrypt_by_name("aes_cbc", b"x", b"short", &[0; 16]).is_err());
+
+        let err = registry().cipher("rc4").err().unwrap();
+        assert_eq!(err.to_string(), "unknown cipher: rc4");
+    }
+
+    #[cfg(all(feature = "aes", feature = "base64", feature = "sha", feature = "xor"))]
+    #[test]
+    fn list_algorithms_test() -> anyhow::Result<()> {
+        let list: serde_json::Value = serde_json::from_str(&list_algorithms())?;
+        assert_eq!(list["hashers"][0]["name"], "sha256");
+        assert_eq!(list["hashers"][1]["output_len"], 64);
+        assert!(
+            list["encoders"]
+                .as_array()
+                .unwrap()
+                .contains(&"custom_base64".into())
+        );
+        assert_eq!(list["ciphers"][0]["kind"], "block");
+        assert_eq!(list["ciphers"][1]["key_len"], serde_json::Value::Null);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn every_encoder_roundtrip_prop(data: Vec<u8>) {
+            for encoder in registry().encoders() {
+                let encoded = encoder.encode(&data);
+                prop_assert_eq!(encoder.decode(&encoded).unwrap(), data.clone(), "{}", encoder.name());
+            }
+        }
+
+        #[test]
+        fn every_cipher_roundtrip_prop(data: Vec<u8>, key: [u8; 16], iv: [u8; 16]) {
+            for cipher in registry().ciphers() {
+                let key = &key[..cipher.key_len().unwrap_or(16)];
+                let iv = &iv[..cipher.iv_len().unwrap_or(0)];
+                let encrypted = cipher.encrypt(&data, key, iv).unwrap();
+                prop_assert_eq!(cipher.decrypt(&encrypted, key, iv).unwrap(), data.clone(), "{}", cipher.name());
+            }
+        }
+
+        // *_by_name 的输入都来自前端，任何输入都只能返回错误
+        #[test]
+        fn by_name_never_panics_prop(
+            name in "\\PC{0,16}",
+            encoded in "\\PC*",
+            data: Vec<u8>,
+            key in proptest::collection::vec(any::<u8>(), 0..40),
+            iv in proptest::collection::vec(any::<u8>(), 0..40),
+        ) {
+            let _ = decode_by_name(&name, &encoded);
+            let _ = decrypt_by_name(&name, &data, &key, &iv);
+            for encoder in registry().encoders() {
+                let _ = encoder.decode(&encoded);
+            }
+            for cipher in registry().ciphers() {
+                let _ = cipher.decrypt(&data, &key, &iv);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use zeroize::Zeroizing;
+
+use super::algorithm_error::AlgorithmError;
+use crate::sha_demo::utils::HashOutput;
+
+// 所有算法共有的元信息，registry 按 name 查找
+pub trait Algorithm: Send + Sync {
+    fn name(&self) -> &'static str;
+}
+
+pub trait Hasher: Algorithm {
+    // 摘要长度（字节）
+    fn output_len(&self) -> usize;
+    // 压缩函数的分组长度（字节），HMAC 需要
+    fn block_len(&self) -> usize;
+    fn hash(&self, data: &[u8]) -> HashOutput;
+
+    // RFC 2104 HMAC ，对任意 Hasher 通用，新注册的哈希算法自动支持
+    // 补齐后的密钥和两次哈希的输入都含密钥材料，用 Zeroizing 保存，并一次申请好容量，避免扩容时留下副本
+    fn hmac(&self, key: &[u8], data: &[u8]) -> HashOutput {
+        let block_len = self.block_len();
+        let mut block_key = Zeroizing::new(vec![0u8; block_len]);
+        if key.len() > block_len {
+            let hashed = Zeroizing::new(self.hash(key).into_bytes());
+            block_key[..hashed.len()].copy_from_slice(&hashed);
+        } else {
+            block_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Zeroizing::new(Vec::with_capacity(block_len + data.len()));
+        inner.extend(block_key.iter().map(|k| k ^ 0x36));
+        inner.extend_from_slice(data);
+        let inner_hash = Zeroizing::new(self.hash(&inner).into_bytes());
+
+        let mut outer = Zeroizing::new(Vec::with_capacity(block_len + inner_hash.len()));
+        outer.extend(block_key.iter().map(|k| k ^ 0x5c));
+        outer.extend_from_slice(&inner_hash);
+        self.hash(&outer)
+    }
+}
+
+pub trait Encoder: Algorithm {
+    fn encode(&self, data: &[u8]) -> String;
+    fn decode(&self, encoded: &str) -> Result<Vec<u8>, AlgorithmError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherKind {
+    Block,
+    Stream,
+}
+
+impl CipherKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CipherKind::Block => "block",
+            CipherKind::Stream => "stream",
+        }
+    }
+}
+
+// 分组密码和流密码共用一个接口；不需要 iv 的算法 iv_len 返回 None 并忽略 iv 参数
+pub trait Cipher: Algorithm {
+    fn kind(&self) -> CipherKind;
+    // None 表示接受任意非空长度的 key
+    fn key_len(&self) -> Option<usize>;
+    fn iv_len(&self) -> Option<usize>;
+    // recipe 中省略 key 时使用的密钥，None 表示必须提供
+    fn default_key(&self) -> Option<Zeroizing<Vec<u8>>> {
+        None
+    }
+    fn encrypt(&self, plain: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AlgorithmError>;
+    fn decrypt(&self, cipher_text: &[u8], key: &[u8], iv: &[u8])
+    -> Result<Vec<u8>, AlgorithmError>;
+}
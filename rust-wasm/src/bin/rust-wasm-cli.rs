@@ -0,0 +1,395 @@
+//! rust-wasm 的命令行版本，算法实现与 WASM 导出函数完全相同，供脚本和 CI 使用
+//!
+//! 默认按“文本”处理输入：要求是 UTF-8 并先 trim ，与网页上的字符串导出函数一致；
+//! 加 `--binary` 则按原始字节处理，与 `get_bytes_*` 系列一致。
+//!
+//! clap 是可选依赖，需要开启 cli feature ：`cargo run --features cli --bin rust-wasm-cli -- --help`
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rust_wasm::{
+    DEFAULT_BYTES_SALT, DEFAULT_STR_SALT, HashFormat, HashOutput, XOR_KEY, decode_by_name,
+    decode_bytes_with_alphabet, decrypt_by_name, encode_by_name, encode_bytes_with_alphabet,
+    encrypt_by_name, get_bytes_sha_raw, get_str_sha_raw, list_algorithms, registry, run_recipe,
+    run_recipe_reverse,
+};
+use zeroize::Zeroizing;
+
+#[derive(Parser)]
+#[command(
+    name = "rust-wasm-cli",
+    version,
+    about = "用命令行调用 rust-wasm 的各种加密算法"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 循环异或 + Base64 ，等价于 encrypt_username / decrypt_to_username
+    Xor {
+        #[arg(value_enum)]
+        direction: Direction,
+        /// 不指定时使用内置的 XOR_KEY ，与网页一致
+        #[arg(long)]
+        key: Option<String>,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// AES-128-CBC + PKCS7 ，等价于 aes_cbc_encrypt / aes_cbc_decrypt
+    Aes {
+        #[arg(value_enum)]
+        direction: Direction,
+        #[arg(long)]
+        key: String,
+        #[arg(long)]
+        iv: String,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// SHA 哈希，文本模式等价于 get_str_sha*，--binary 等价于 get_bytes_sha*
+    Sha {
+        #[arg(long, default_value = "sha256")]
+        algorithm: String,
+        #[arg(long, conflicts_with = "default_salt")]
+        salt: Option<String>,
+        /// 使用网页上的默认盐（文本 "hans7"，二进制 "hans7_sha_bytes"）
+        #[arg(long)]
+        default_salt: bool,
+        /// 只输出前 N 个字符，raw 输出时为前 N 个字节
+        #[arg(long)]
+        truncate: Option<usize>,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// Base64 编解码，--hans7 使用 HANS7_CUSTOM_ALPHABET ，--alphabet 使用任意码表
+    Base64 {
+        #[arg(value_enum)]
+        direction: Codec,
+        #[arg(long, conflicts_with = "hans7")]
+        alphabet: Option<String>,
+        #[arg(long)]
+        hans7: bool,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// 按名称调用 registry 中的哈希算法
+    Hash {
+        name: String,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// 按名称调用 registry 中的编码器
+    Codec {
+        name: String,
+        #[arg(value_enum)]
+        direction: Codec,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// 按名称调用 registry 中的密码算法
+    Cipher {
+        name: String,
+        #[arg(value_enum)]
+        direction: Direction,
+        #[arg(long)]
+        key: String,
+        #[arg(long, default_value = "")]
+        iv: String,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// 执行 JSON recipe ，参数可以是 JSON 字符串或 JSON 文件路径
+    Recipe {
+        #[arg(value_enum)]
+        direction: Direction,
+        recipe: String,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// 以 JSON 列出所有已注册算法
+    List,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Codec {
+    Encode,
+    Decode,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Raw,
+    Hex,
+    HexUpper,
+    Base64,
+    Base64url,
+}
+
+impl Format {
+    fn hash_format(self) -> Option<HashFormat> {
+        match self {
+            Format::Raw => None,
+            Format::Hex => Some(HashFormat::Hex),
+            Format::HexUpper => Some(HashFormat::HexUpper),
+            Format::Base64 => Some(HashFormat::Base64),
+            Format::Base64url => Some(HashFormat::Base64Url),
+        }
+    }
+}
+
+#[derive(Args)]
+struct IoArgs {
+    /// 直接从命令行传入输入
+    #[arg(long, conflicts_with = "input")]
+    text: Option<String>,
+    /// 输入文件，缺省或 "-" 表示 stdin
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// 输出文件，缺省表示 stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// 按原始字节处理输入，不做 UTF-8 校验和 trim
+    #[arg(long)]
+    binary: bool,
+    /// 加密 / 哈希结果的输出格式（默认与对应的 WASM 导出函数一致）
+    #[arg(long, value_enum)]
+    output_format: Option<Format>,
+    /// 解密时密文的输入格式，默认 base64
+    #[arg(long, value_enum, default_value = "base64")]
+    input_format: Format,
+}
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+impl IoArgs {
+    fn read_raw(&self) -> CliResult<Vec<u8>> {
+        if let Some(text) = &self.text {
+            return Ok(text.as_bytes().to_vec());
+        }
+        match &self.input {
+            Some(path) if path.as_os_str() != "-" => Ok(fs::read(path)?),
+            _ => {
+                let mut buf = Vec::new();
+                io::stdin().read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+
+    // 文本模式下与 WASM 字符串导出函数一样先 trim
+    fn read_payload(&self) -> CliResult<Vec<u8>> {
+        let raw = self.read_raw()?;
+        if self.binary {
+            return Ok(raw);
+        }
+        let text = String::from_utf8(raw).map_err(|_| "input is not valid UTF-8")?;
+        Ok(text.trim().as_bytes().to_vec())
+    }
+
+    // 读取编码过的密文，按 input_format 还原成字节（解码器自带 trim）
+    fn read_encoded(&self) -> CliResult<Vec<u8>> {
+        let raw = self.read_raw()?;
+        let encoder = match self.input_format {
+            Format::Raw => return Ok(raw),
+            Format::Hex | Format::HexUpper => "hex",
+            Format::Base64 => "base64",
+            Format::Base64url => "base64url",
+        };
+        let text = String::from_utf8(raw).map_err(|_| "input is not valid UTF-8")?;
+        Ok(decode_by_name(encoder, &text)?)
+    }
+
+    fn write_bytes(&self, bytes: Vec<u8>, default_format: Format) -> CliResult<()> {
+        let format = self.output_format.unwrap_or(default_format);
+        match format.hash_format() {
+            Some(f) => self.write_text(&HashOutput { bytes }.format(f)),
+            None => self.write_raw(&bytes),
+        }
+    }
+
+    // 哈希结果默认输出 hex ，truncate 对文本格式按字符截断，对 raw 按字节截断
+    fn write_digest(&self, output: &HashOutput, truncate: Option<usize>) -> CliResult<()> {
+        match self.output_format.unwrap_or(Format::Hex).hash_format() {
+            Some(format) => self.write_text(&output.format_truncated(format, truncate)),
+            None => self.write_raw(output.truncated(truncate.unwrap_or(usize::MAX)).as_bytes()),
+        }
+    }
+
+    fn write_text(&self, text: &str) -> CliResult<()> {
+        let mut line = text.as_bytes().to_vec();
+        // 写到终端时补一个换行，写文件时保持原样，便于和网页结果逐字节对比
+        if self.output.is_none() {
+            line.push(b'\n');
+        }
+        self.write_raw(&line)
+    }
+
+    fn write_raw(&self, bytes: &[u8]) -> CliResult<()> {
+        match &self.output {
+            Some(path) => fs::write(path, bytes)?,
+            None => io::stdout().write_all(bytes)?,
+        }
+        Ok(())
+    }
+}
+
+fn load_recipe(recipe: &str) -> CliResult<String> {
+    if recipe.trim_start().starts_with('{') {
+        return Ok(recipe.to_string());
+    }
+    Ok(fs::read_to_string(recipe)?)
+}
+
+fn run_cipher(
+    name: &str,
+    direction: Direction,
+    key: &[u8],
+    iv: &[u8],
+    io: &IoArgs,
+) -> CliResult<()> {
+    match direction {
+        Direction::Encrypt => {
+            let encrypted = encrypt_by_name(name, &io.read_payload()?, key, iv)?;
+            io.write_bytes(encrypted, Format::Base64)
+        }
+        Direction::Decrypt => {
+            let decrypted = decrypt_by_name(name, &io.read_encoded()?, key, iv)?;
+            io.write_bytes(decrypted, Format::Raw)
+        }
+    }
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    match cli.command {
+        Command::Xor { direction, key, io } => {
+            // XOR_KEY 在编译时加密，不能写成 clap 的 default_value ，否则明文会出现在二进制中
+            let key = match key {
+                Some(key) => Zeroizing::new(key.into_bytes()),
+                None => XOR_KEY.reveal(),
+            };
+            run_cipher("xor", direction, &key, b"", &io)
+        }
+        Command::Aes {
+            direction,
+            key,
+            iv,
+            io,
+        } => run_cipher("aes_cbc", direction, key.as_bytes(), iv.as_bytes(), &io),
+        Command::Cipher {
+            name,
+            direction,
+            key,
+            iv,
+            io,
+        } => run_cipher(&name, direction, key.as_bytes(), iv.as_bytes(), &io),
+        Command::Sha {
+            algorithm,
+            salt,
+            default_salt,
+            truncate,
+            io,
+        } => {
+            let digest = if io.binary {
+                let salt = if default_salt {
+                    DEFAULT_BYTES_SALT.reveal_str().to_string()
+                } else {
+                    salt.unwrap_or_default()
+                };
+                get_bytes_sha_raw(&io.read_raw()?, &salt, &algorithm)?
+            } else {
+                let salt = if default_salt {
+                    DEFAULT_STR_SALT.reveal_str().to_string()
+                } else {
+                    salt.unwrap_or_default()
+                };
+                let input =
+                    String::from_utf8(io.read_raw()?).map_err(|_| "input is not valid UTF-8")?;
+                get_str_sha_raw(&input, &salt, &algorithm)?
+            };
+            io.write_digest(&HashOutput { bytes: digest }, truncate)
+        }
+        Command::Base64 {
+            direction,
+            alphabet,
+            hans7,
+            io,
+        } => {
+            let name = if hans7 { "custom_base64" } else { "base64" };
+            match direction {
+                Codec::Encode => {
+                    let payload = io.read_payload()?;
+                    let encoded = match &alphabet {
+                        Some(alphabet) => encode_bytes_with_alphabet(&payload, alphabet)?,
+                        None => encode_by_name(name, &payload)?,
+                    };
+                    io.write_text(&encoded)
+                }
+                Codec::Decode => {
+                    let text = String::from_utf8(io.read_raw()?)
+                        .map_err(|_| "input is not valid UTF-8")?;
+                    let decoded = match &alphabet {
+                        Some(alphabet) => decode_bytes_with_alphabet(&text, alphabet)?,
+                        None => decode_by_name(name, &text)?,
+                    };
+                    io.write_raw(&decoded)
+                }
+            }
+        }
+        Command::Hash { name, io } => {
+            let output = registry().hasher(&name)?.hash(&io.read_payload()?);
+            io.write_digest(&output, None)
+        }
+        Command::Codec {
+            name,
+            direction,
+            io,
+        } => match direction {
+            Codec::Encode => io.write_text(&encode_by_name(&name, &io.read_payload()?)?),
+            Codec::Decode => {
+                let text =
+                    String::from_utf8(io.read_raw()?).map_err(|_| "input is not valid UTF-8")?;
+                io.write_raw(&decode_by_name(&name, &text)?)
+            }
+        },
+        Command::Recipe {
+            direction,
+            recipe,
+            io,
+        } => {
+            let recipe = load_recipe(&recipe)?;
+            let payload = io.read_payload()?;
+            let output = match direction {
+                Direction::Encrypt => run_recipe(&recipe, &payload)?,
+                Direction::Decrypt => run_recipe_reverse(&recipe, &payload)?,
+            };
+            io.write_bytes(output, Format::Raw)
+        }
+        Command::List => {
+            println!("{}", list_algorithms());
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
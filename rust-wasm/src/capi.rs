@@ -0,0 +1,912 @@
+//! C ABI 导出，供 C++ 题目二进制和 Go (cgo) 工具链接与网页相同的实现。
+//! 头文件由 build.rs 调用 cbindgen 生成到 `include/rust_wasm.h`。
+//!
+//! 约定：
+//! - 所有函数返回 `RwStatus`，`RW_STATUS_OK` 为 0 ，其余为错误码，
+//!   出错后可用 `rw_last_error_message` 取得当前线程最近一次的错误信息
+//! - 数据输入一律是 `const uint8_t *ptr, size_t len`，文本输入要求是 UTF-8 ，len 为 0 时 ptr 可以为 NULL
+//! - 算法名、输出格式、recipe JSON 等短参数是以 NUL 结尾的 C 字符串
+//! - 输出写入调用方提供的 `uint8_t *out` ，`size_t *out_len` 传入时是缓冲区容量，返回时是实际长度。
+//!   容量不足时返回 `RW_STATUS_BUFFER_TOO_SMALL` 并把所需长度写回 `*out_len` ，
+//!   因此可以先传 `out = NULL, *out_len = 0` 查询长度
+//! - 文本输出额外写入一个 NUL ，所需容量为 `*out_len + 1` ，返回的 `*out_len` 不含 NUL
+
+// 没有开启任何算法 feature 时只剩 rw_last_error_message ，下面的辅助函数都用不到；
+// C 字符串参数和字节输出只有 base64 、sha 和 registry 的导出用到，单独按 feature 开启
+#![cfg_attr(
+    not(any(
+        feature = "xor",
+        feature = "aes",
+        feature = "sha",
+        feature = "base64",
+        feature = "registry"
+    )),
+    allow(dead_code)
+)]
+
+use std::cell::RefCell;
+#[cfg(any(feature = "base64", feature = "sha", feature = "registry"))]
+use std::ffi::{CStr, c_char};
+use std::ptr;
+use std::slice;
+
+use zeroize::Zeroizing;
+
+use crate::error::{ErrorCode, RustWasmError};
+#[cfg(any(feature = "sha", feature = "base64"))]
+use crate::secret::Sealed;
+
+/// 函数返回的状态码，1 和 2 是 C ABI 自己的错误，其余与 `ErrorCode` 一一对应
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RwStatus {
+    Ok = 0,
+    NullPointer = 1,
+    BufferTooSmall = 2,
+    EmptyInput = 10,
+    InvalidInput = 11,
+    InvalidBase64 = 12,
+    InvalidUtf8 = 13,
+    InvalidAlphabet = 14,
+    InvalidEncoding = 15,
+    BadKeyLen = 16,
+    BadIvLen = 17,
+    InvalidKey = 18,
+    EncryptFailed = 19,
+    DecryptFailed = 20,
+    HashFailed = 21,
+    UnsupportedAlgorithm = 22,
+    UnsupportedFormat = 23,
+    UnknownAlgorithm = 24,
+    InvalidRecipe = 25,
+    EmptyRecipe = 26,
+    InvalidParams = 27,
+    NotReversible = 28,
+    StepFailed = 29,
+    InvalidWasm = 30,
+}
+
+impl From<ErrorCode> for RwStatus {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::EmptyInput => RwStatus::EmptyInput,
+            ErrorCode::InvalidInput => RwStatus::InvalidInput,
+            ErrorCode::InvalidBase64 => RwStatus::InvalidBase64,
+            ErrorCode::InvalidUtf8 => RwStatus::InvalidUtf8,
+            ErrorCode::InvalidAlphabet => RwStatus::InvalidAlphabet,
+            ErrorCode::InvalidEncoding => RwStatus::InvalidEncoding,
+            ErrorCode::BadKeyLen => RwStatus::BadKeyLen,
+            ErrorCode::BadIvLen => RwStatus::BadIvLen,
+            ErrorCode::InvalidKey => RwStatus::InvalidKey,
+            ErrorCode::EncryptFailed => RwStatus::EncryptFailed,
+            ErrorCode::DecryptFailed => RwStatus::DecryptFailed,
+            ErrorCode::HashFailed => RwStatus::HashFailed,
+            ErrorCode::UnsupportedAlgorithm => RwStatus::UnsupportedAlgorithm,
+            ErrorCode::UnsupportedFormat => RwStatus::UnsupportedFormat,
+            ErrorCode::UnknownAlgorithm => RwStatus::UnknownAlgorithm,
+            ErrorCode::InvalidRecipe => RwStatus::InvalidRecipe,
+            ErrorCode::EmptyRecipe => RwStatus::EmptyRecipe,
+            ErrorCode::InvalidParams => RwStatus::InvalidParams,
+            ErrorCode::NotReversible => RwStatus::NotReversible,
+            ErrorCode::StepFailed => RwStatus::StepFailed,
+            ErrorCode::InvalidWasm => RwStatus::InvalidWasm,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+// 内部统一用 Result<_, RwStatus> 传递，失败时顺便记录错误信息
+fn fail<E: Into<RustWasmError>>(e: E) -> RwStatus {
+    let err: RustWasmError = e.into();
+    set_last_error(format!("{}: {}", err.module, err.message));
+    err.code.into()
+}
+
+fn arg_error(status: RwStatus, message: &str) -> RwStatus {
+    set_last_error(message.to_string());
+    status
+}
+
+unsafe fn input_bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], RwStatus> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(arg_error(RwStatus::NullPointer, "input pointer is null"));
+    }
+    Ok(unsafe { slice::from_raw_parts(data, len) })
+}
+
+unsafe fn input_str<'a>(data: *const u8, len: usize) -> Result<&'a str, RwStatus> {
+    let bytes = unsafe { input_bytes(data, len) }?;
+    std::str::from_utf8(bytes).map_err(|_| arg_error(RwStatus::InvalidUtf8, "input is not UTF-8"))
+}
+
+#[cfg(any(feature = "base64", feature = "sha", feature = "registry"))]
+unsafe fn c_str_arg<'a>(s: *const c_char) -> Result<&'a str, RwStatus> {
+    if s.is_null() {
+        return Err(arg_error(RwStatus::NullPointer, "string argument is null"));
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| arg_error(RwStatus::InvalidUtf8, "string argument is not UTF-8"))
+}
+
+// 不记录错误信息，否则查询 rw_last_error_message 的长度时会把原来的错误覆盖掉
+unsafe fn write_output(bytes: &[u8], nul: bool, out: *mut u8, out_len: *mut usize) -> RwStatus {
+    if out_len.is_null() {
+        return RwStatus::NullPointer;
+    }
+    let capacity = unsafe { *out_len };
+    unsafe { *out_len = bytes.len() };
+    let required = bytes.len() + usize::from(nul);
+    if capacity < required || (required > 0 && out.is_null()) {
+        return RwStatus::BufferTooSmall;
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+        if nul {
+            *out.add(bytes.len()) = 0;
+        }
+    }
+    RwStatus::Ok
+}
+
+#[cfg(any(feature = "base64", feature = "sha", feature = "registry"))]
+unsafe fn finish_bytes(
+    result: Result<Vec<u8>, RwStatus>,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    match result {
+        // 结果可能是解密出的明文，复制给调用方后清零
+        Ok(bytes) => unsafe { write_output(&Zeroizing::new(bytes), false, out, out_len) },
+        Err(status) => status,
+    }
+}
+
+unsafe fn finish_text(
+    result: Result<String, RwStatus>,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    match result {
+        Ok(text) => unsafe { write_output(Zeroizing::new(text).as_bytes(), true, out, out_len) },
+        Err(status) => status,
+    }
+}
+
+/// 取得当前线程最近一次错误的描述（NUL 结尾），缓冲区约定同其他函数
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_last_error_message(out: *mut u8, out_len: *mut usize) -> RwStatus {
+    let message = LAST_ERROR.with(|last| last.borrow().clone());
+    unsafe { write_output(message.as_bytes(), true, out, out_len) }
+}
+
+// 加密的常量每次按需解密，写入调用方的缓冲区后清零，进程中不保留明文副本
+#[cfg(any(feature = "sha", feature = "base64"))]
+unsafe fn write_sealed(sealed: &Sealed, out: *mut u8, out_len: *mut usize) -> RwStatus {
+    unsafe { write_output(sealed.reveal_str().as_bytes(), true, out, out_len) }
+}
+
+/// 网页上字符串 SHA 的默认盐 "hans7"，以文本写入 out
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "sha")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_default_str_salt(out: *mut u8, out_len: *mut usize) -> RwStatus {
+    unsafe { write_sealed(&crate::sha_demo::sha_demo::DEFAULT_STR_SALT, out, out_len) }
+}
+
+/// 网页上文件 SHA 的默认盐 "hans7_sha_bytes"，以文本写入 out
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "sha")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_default_bytes_salt(out: *mut u8, out_len: *mut usize) -> RwStatus {
+    unsafe {
+        write_sealed(
+            &crate::sha_demo::sha_bytes_demo::DEFAULT_BYTES_SALT,
+            out,
+            out_len,
+        )
+    }
+}
+
+/// 自定义 Base64 使用的 HANS7_CUSTOM_ALPHABET ，以文本写入 out
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "base64")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_hans7_custom_alphabet(out: *mut u8, out_len: *mut usize) -> RwStatus {
+    unsafe {
+        write_sealed(
+            &crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR,
+            out,
+            out_len,
+        )
+    }
+}
+
+/// 等价于 `encrypt_username`，输出 Base64 文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "xor")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_xor_encrypt(
+    name: *const u8,
+    name_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = unsafe { input_str(name, name_len) }.map(crate::xor_demo::encrypt_username);
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 等价于 `decrypt_to_username`，输出 UTF-8 文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "xor")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_xor_decrypt(
+    encrypted: *const u8,
+    encrypted_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = unsafe { input_str(encrypted, encrypted_len) }
+        .and_then(|s| crate::xor_demo::decrypt_to_username(s).map_err(fail));
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 等价于 `aes_cbc_encrypt`，输出 Base64 文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "aes")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_aes_cbc_encrypt(
+    plain: *const u8,
+    plain_len: usize,
+    key: *const u8,
+    key_len: usize,
+    iv: *const u8,
+    iv_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let plain = unsafe { input_str(plain, plain_len) }?;
+        let key = unsafe { input_bytes(key, key_len) }?;
+        let iv = unsafe { input_bytes(iv, iv_len) }?;
+        crate::aes_cbc::aes_cbc_encrypt(plain, key, iv).map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 等价于 `aes_cbc_decrypt`，输入 Base64 密文，输出 UTF-8 文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "aes")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_aes_cbc_decrypt(
+    b64_cipher: *const u8,
+    b64_cipher_len: usize,
+    key: *const u8,
+    key_len: usize,
+    iv: *const u8,
+    iv_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let cipher = unsafe { input_str(b64_cipher, b64_cipher_len) }?;
+        let key = unsafe { input_bytes(key, key_len) }?;
+        let iv = unsafe { input_bytes(iv, iv_len) }?;
+        crate::aes_cbc::aes_cbc_decrypt(cipher, key, iv).map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 用 HANS7_CUSTOM_ALPHABET 编码任意字节，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "base64")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_custom_base64_encode(
+    data: *const u8,
+    data_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = unsafe { input_bytes(data, data_len) }.and_then(|data| {
+        crate::custom_base64::encode_bytes_with_alphabet(
+            data,
+            &crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR.reveal_str(),
+        )
+        .map_err(fail)
+    });
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 用 HANS7_CUSTOM_ALPHABET 解码，输出原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "base64")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_custom_base64_decode(
+    encoded: *const u8,
+    encoded_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = unsafe { input_str(encoded, encoded_len) }.and_then(|encoded| {
+        crate::custom_base64::decode_bytes_with_alphabet(
+            encoded,
+            &crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR.reveal_str(),
+        )
+        .map_err(fail)
+    });
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 用任意 64 字符码表编码任意字节，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "base64")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_base64_encode_with_alphabet(
+    data: *const u8,
+    data_len: usize,
+    alphabet: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let data = unsafe { input_bytes(data, data_len) }?;
+        let alphabet = unsafe { c_str_arg(alphabet) }?;
+        crate::custom_base64::encode_bytes_with_alphabet(data, alphabet).map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 用任意 64 字符码表解码，输出原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "base64")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_base64_decode_with_alphabet(
+    encoded: *const u8,
+    encoded_len: usize,
+    alphabet: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let encoded = unsafe { input_str(encoded, encoded_len) }?;
+        let alphabet = unsafe { c_str_arg(alphabet) }?;
+        crate::custom_base64::decode_bytes_with_alphabet(encoded, alphabet).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+// truncate < 0 表示不截断
+#[cfg(feature = "sha")]
+fn truncate_arg(truncate: isize) -> Option<usize> {
+    usize::try_from(truncate).ok()
+}
+
+/// 等价于 `get_str_sha_formatted`：输入按 UTF-8 文本处理并 trim ，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "sha")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_str_sha(
+    input: *const u8,
+    input_len: usize,
+    salt: *const c_char,
+    algorithm: *const c_char,
+    format: *const c_char,
+    truncate: isize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let input = unsafe { input_str(input, input_len) }?;
+        let salt = unsafe { c_str_arg(salt) }?;
+        let algorithm = unsafe { c_str_arg(algorithm) }?;
+        let format = unsafe { c_str_arg(format) }?;
+        crate::sha_demo::sha_demo::get_str_sha_formatted(
+            input,
+            salt,
+            algorithm,
+            format,
+            truncate_arg(truncate),
+        )
+        .map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 等价于 `get_bytes_sha_formatted`：输入按原始字节处理，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "sha")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_bytes_sha(
+    data: *const u8,
+    data_len: usize,
+    salt: *const c_char,
+    algorithm: *const c_char,
+    format: *const c_char,
+    truncate: isize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let data = unsafe { input_bytes(data, data_len) }?;
+        let salt = unsafe { c_str_arg(salt) }?;
+        let algorithm = unsafe { c_str_arg(algorithm) }?;
+        let format = unsafe { c_str_arg(format) }?;
+        crate::sha_demo::sha_bytes_demo::get_bytes_sha_formatted(
+            data,
+            salt,
+            algorithm,
+            format,
+            truncate_arg(truncate),
+        )
+        .map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 等价于 `get_bytes_sha_raw`，输出原始摘要字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "sha")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_bytes_sha_raw(
+    data: *const u8,
+    data_len: usize,
+    salt: *const c_char,
+    algorithm: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let data = unsafe { input_bytes(data, data_len) }?;
+        let salt = unsafe { c_str_arg(salt) }?;
+        let algorithm = unsafe { c_str_arg(algorithm) }?;
+        crate::sha_demo::sha_bytes_demo::get_bytes_sha_raw(data, salt, algorithm).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 校验 recipe ，成功时把是否可逆写入 `*reversible`
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "recipe")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_validate_recipe(
+    recipe_json: *const c_char,
+    reversible: *mut bool,
+) -> RwStatus {
+    let result = unsafe { c_str_arg(recipe_json) }
+        .and_then(|recipe| crate::pipeline::recipe::validate_recipe(recipe).map_err(fail));
+    match result {
+        Ok(_) if reversible.is_null() => arg_error(RwStatus::NullPointer, "reversible is null"),
+        Ok(value) => {
+            unsafe { *reversible = value };
+            RwStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// 正向执行 recipe ，输入输出都是原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "recipe")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_run_recipe(
+    recipe_json: *const c_char,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let recipe = unsafe { c_str_arg(recipe_json) }?;
+        let input = unsafe { input_bytes(input, input_len) }?;
+        crate::pipeline::recipe::run_recipe(recipe, input).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 逆向执行 recipe ，输入输出都是原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "recipe")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_run_recipe_reverse(
+    recipe_json: *const c_char,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let recipe = unsafe { c_str_arg(recipe_json) }?;
+        let input = unsafe { input_bytes(input, input_len) }?;
+        crate::pipeline::recipe::run_recipe_reverse(recipe, input).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 以 JSON 文本列出所有已注册算法
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_list_algorithms(out: *mut u8, out_len: *mut usize) -> RwStatus {
+    let list = crate::algorithm::registry::list_algorithms();
+    unsafe { write_output(list.as_bytes(), true, out, out_len) }
+}
+
+/// 按名称计算哈希，format 同 `rw_bytes_sha` ，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_hash_by_name(
+    name: *const c_char,
+    data: *const u8,
+    data_len: usize,
+    format: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let data = unsafe { input_bytes(data, data_len) }?;
+        let format = unsafe { c_str_arg(format) }?;
+        crate::algorithm::registry::hash_by_name(name, data, format).map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 常量时间校验 HMAC ，tag 为原始字节，成功时把是否匹配写入 `*valid`
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_verify_hmac_by_name(
+    name: *const c_char,
+    key: *const u8,
+    key_len: usize,
+    data: *const u8,
+    data_len: usize,
+    tag: *const u8,
+    tag_len: usize,
+    valid: *mut bool,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let key = unsafe { input_bytes(key, key_len) }?;
+        let data = unsafe { input_bytes(data, data_len) }?;
+        let tag = unsafe { input_bytes(tag, tag_len) }?;
+        crate::algorithm::registry::verify_hmac_by_name(name, key, data, tag).map_err(fail)
+    })();
+    match result {
+        Ok(_) if valid.is_null() => arg_error(RwStatus::NullPointer, "valid is null"),
+        Ok(value) => {
+            unsafe { *valid = value };
+            RwStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// 按名称编码，输出文本
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_encode_by_name(
+    name: *const c_char,
+    data: *const u8,
+    data_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let data = unsafe { input_bytes(data, data_len) }?;
+        crate::algorithm::registry::encode_by_name(name, data).map_err(fail)
+    })();
+    unsafe { finish_text(result, out, out_len) }
+}
+
+/// 按名称解码，输出原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_decode_by_name(
+    name: *const c_char,
+    encoded: *const u8,
+    encoded_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let encoded = unsafe { input_str(encoded, encoded_len) }?;
+        crate::algorithm::registry::decode_by_name(name, encoded).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 按名称加密，输入输出都是原始字节，不需要 IV 的算法可传 `iv_len = 0`
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_encrypt_by_name(
+    name: *const c_char,
+    plain: *const u8,
+    plain_len: usize,
+    key: *const u8,
+    key_len: usize,
+    iv: *const u8,
+    iv_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let plain = unsafe { input_bytes(plain, plain_len) }?;
+        let key = unsafe { input_bytes(key, key_len) }?;
+        let iv = unsafe { input_bytes(iv, iv_len) }?;
+        crate::algorithm::registry::encrypt_by_name(name, plain, key, iv).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+/// 按名称解密，输入输出都是原始字节
+///
+/// # Safety
+/// 指针与缓冲区须满足模块文档（即 rust_wasm.h 开头）的约定
+#[cfg(feature = "registry")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_decrypt_by_name(
+    name: *const c_char,
+    cipher_text: *const u8,
+    cipher_text_len: usize,
+    key: *const u8,
+    key_len: usize,
+    iv: *const u8,
+    iv_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> RwStatus {
+    let result = (|| {
+        let name = unsafe { c_str_arg(name) }?;
+        let cipher_text = unsafe { input_bytes(cipher_text, cipher_text_len) }?;
+        let key = unsafe { input_bytes(key, key_len) }?;
+        let iv = unsafe { input_bytes(iv, iv_len) }?;
+        crate::algorithm::registry::decrypt_by_name(name, cipher_text, key, iv).map_err(fail)
+    })();
+    unsafe { finish_bytes(result, out, out_len) }
+}
+
+#[cfg(all(
+    test,
+    feature = "aes",
+    feature = "base64",
+    feature = "sha",
+    feature = "xor",
+    feature = "recipe"
+))]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+
+    // 模拟 C 调用方：先查询长度，再分配缓冲区
+    fn call_text(f: impl Fn(*mut u8, *mut usize) -> RwStatus) -> Result<String, RwStatus> {
+        let mut len = 0usize;
+        let status = f(ptr::null_mut(), &mut len);
+        if status != RwStatus::BufferTooSmall && status != RwStatus::Ok {
+            return Err(status);
+        }
+        let mut buf = vec![0u8; len + 1];
+        let mut cap = buf.len();
+        match f(buf.as_mut_ptr(), &mut cap) {
+            RwStatus::Ok => {
+                assert_eq!(cap, len);
+                assert_eq!(buf[len], 0);
+                buf.truncate(len);
+                Ok(String::from_utf8(buf).unwrap())
+            }
+            status => Err(status),
+        }
+    }
+
+    fn last_error() -> String {
+        call_text(|out, out_len| unsafe { rw_last_error_message(out, out_len) }).unwrap()
+    }
+
+    #[test]
+    fn capi_matches_wasm_exports_test() -> anyhow::Result<()> {
+        let name = "hans7";
+        let encrypted = call_text(|out, out_len| unsafe {
+            rw_xor_encrypt(name.as_ptr(), name.len(), out, out_len)
+        })
+        .unwrap();
+        assert_eq!(encrypted, crate::xor_demo::encrypt_username(name));
+
+        let (plain, key, iv) = ("有空 格", b"1234567890123456", b"abcdef9876543210");
+        let aes = call_text(|out, out_len| unsafe {
+            rw_aes_cbc_encrypt(
+                plain.as_ptr(),
+                plain.len(),
+                key.as_ptr(),
+                16,
+                iv.as_ptr(),
+                16,
+                out,
+                out_len,
+            )
+        })
+        .unwrap();
+        assert_eq!(aes, crate::aes_cbc::aes_cbc_encrypt(plain, key, iv)?);
+
+        let input = "hello world";
+        let salt = CString::new(
+            call_text(|out, out_len| unsafe { rw_default_str_salt(out, out_len) }).unwrap(),
+        )?;
+        let sha = call_text(|out, out_len| unsafe {
+            rw_str_sha(
+                input.as_ptr(),
+                input.len(),
+                salt.as_ptr(),
+                c"sha512".as_ptr(),
+                c"hex".as_ptr(),
+                -1,
+                out,
+                out_len,
+            )
+        })
+        .unwrap();
+        assert_eq!(sha, crate::sha_demo::sha_demo::get_str_sha512(input)?);
+
+        // RFC 4231 test case 2
+        let (key, data) = (b"Jefe", b"what do ya want for nothing?");
+        let tag = crate::algorithm::registry::decode_by_name(
+            "hex",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        )?;
+        let mut valid = false;
+        let status = unsafe {
+            rw_verify_hmac_by_name(
+                c"sha256".as_ptr(),
+                key.as_ptr(),
+                key.len(),
+                data.as_ptr(),
+                data.len(),
+                tag.as_ptr(),
+                tag.len(),
+                &mut valid,
+            )
+        };
+        assert_eq!(status, RwStatus::Ok);
+        assert!(valid);
+        Ok(())
+    }
+
+    #[test]
+    fn capi_constants_match_rust_test() {
+        let constant = |f: unsafe extern "C" fn(*mut u8, *mut usize) -> RwStatus| {
+            call_text(|out, out_len| unsafe { f(out, out_len) }).unwrap()
+        };
+        assert_eq!(
+            constant(rw_default_str_salt),
+            crate::sha_demo::sha_demo::DEFAULT_STR_SALT
+                .reveal_str()
+                .as_str()
+        );
+        assert_eq!(
+            constant(rw_default_bytes_salt),
+            crate::sha_demo::sha_bytes_demo::DEFAULT_BYTES_SALT
+                .reveal_str()
+                .as_str()
+        );
+        assert_eq!(
+            constant(rw_hans7_custom_alphabet),
+            crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR
+                .reveal_str()
+                .as_str()
+        );
+    }
+
+    #[test]
+    fn capi_buffer_and_error_conventions_test() {
+        let data = [0x00u8, 0xFF, 0x80];
+        let mut buf = [0u8; 2];
+        let mut len = buf.len();
+        let status = unsafe {
+            rw_encrypt_by_name(
+                c"xor".as_ptr(),
+                data.as_ptr(),
+                data.len(),
+                b"k".as_ptr(),
+                1,
+                ptr::null(),
+                0,
+                buf.as_mut_ptr(),
+                &mut len,
+            )
+        };
+        assert_eq!(status, RwStatus::BufferTooSmall);
+        assert_eq!(len, 3);
+
+        let key = b"short";
+        let mut len = 0;
+        let status = unsafe {
+            rw_aes_cbc_encrypt(
+                b"hi".as_ptr(),
+                2,
+                key.as_ptr(),
+                key.len(),
+                key.as_ptr(),
+                key.len(),
+                ptr::null_mut(),
+                &mut len,
+            )
+        };
+        assert_eq!(status, RwStatus::BadKeyLen);
+        assert!(last_error().starts_with("aes_cbc: invalid key length"));
+
+        let status = unsafe { rw_xor_encrypt(ptr::null(), 3, ptr::null_mut(), &mut len) };
+        assert_eq!(status, RwStatus::NullPointer);
+
+        let mut reversible = false;
+        let recipe = cr#"{"steps": [{"op": "sha256", "salt": ""}]}"#;
+        let status = unsafe { rw_validate_recipe(recipe.as_ptr(), &mut reversible) };
+        assert_eq!(status, RwStatus::Ok);
+        assert!(!reversible);
+    }
+}
@@ -3,18 +3,23 @@ use base64::{
     alphabet::*,
     engine::{GeneralPurpose, GeneralPurposeConfig},
 };
+
+use rust_wasm_macros::sealed;
 use thiserror::Error;
 use wasm_bindgen::prelude::*;
 
-static HANS7_CUSTOM_ALPHABET: Alphabet = {
-    match Alphabet::new("ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/") {
-        Ok(x) => x,
-        Err(_) => panic!("creation of alphabet failed"),
-    }
-};
+use crate::error::{ErrorCode, RustWasmError, base64_decode_error};
+use crate::secret::Sealed;
 
-const CUSTOM_ENGINE: GeneralPurpose =
-    GeneralPurpose::new(&HANS7_CUSTOM_ALPHABET, GeneralPurposeConfig::new());
+pub static HANS7_CUSTOM_ALPHABET_STR: Sealed =
+    sealed!("ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/");
+
+// 引擎的编码表就是码表本身，每次调用时才解密码表并建表，用完随调用一起释放，
+// 不在静态变量里常驻明文码表
+pub(crate) fn custom_engine() -> GeneralPurpose {
+    build_engine_from_alphabet(&HANS7_CUSTOM_ALPHABET_STR.reveal_str())
+        .expect("creation of alphabet failed")
+}
 
 #[derive(Debug, Error)]
 pub enum CustomBase64Error {
@@ -28,20 +33,50 @@ pub enum CustomBase64Error {
     InvalidAlphabet(String),
 }
 
+impl From<CustomBase64Error> for RustWasmError {
+    fn from(e: CustomBase64Error) -> Self {
+        let message = e.to_string();
+        match e {
+            CustomBase64Error::EmptyInput => {
+                RustWasmError::new(ErrorCode::EmptyInput, "custom_base64", message)
+            }
+            CustomBase64Error::InvalidUtf8 => {
+                RustWasmError::new(ErrorCode::InvalidUtf8, "custom_base64", message)
+            }
+            CustomBase64Error::Base64Decode(inner) => {
+                base64_decode_error(inner, "custom_base64", message)
+            }
+            CustomBase64Error::InvalidAlphabet(reason) => {
+                RustWasmError::new(ErrorCode::InvalidAlphabet, "custom_base64", message)
+                    .with_detail("reason", reason)
+            }
+        }
+    }
+}
+
 impl From<CustomBase64Error> for JsValue {
     fn from(e: CustomBase64Error) -> Self {
-        JsValue::from_str(&e.to_string())
+        RustWasmError::from(e).into()
     }
 }
 
-fn build_engine_from_alphabet(alphabet_str: &str) -> Result<GeneralPurpose, CustomBase64Error> {
+pub(crate) fn build_engine_from_alphabet(
+    alphabet_str: &str,
+) -> Result<GeneralPurpose, CustomBase64Error> {
+    // 解码前会 trim 输入，码表里的空格编码到开头或结尾时会被吃掉，无法还原
+    if alphabet_str.contains(char::is_whitespace) {
+        return Err(CustomBase64Error::InvalidAlphabet(
+            "alphabet must not contain whitespace".into(),
+        ));
+    }
     let alphabet = Alphabet::new(alphabet_str)
         .map_err(|e| CustomBase64Error::InvalidAlphabet(e.to_string()))?;
     Ok(GeneralPurpose::new(&alphabet, GeneralPurposeConfig::new()))
 }
 
+// 输入会先 trim ，只有空白字符的输入同样视为空，保证编码和解码两侧的行为一致
 fn validate_input(input: &str) -> Result<(), CustomBase64Error> {
-    if input.is_empty() {
+    if input.trim().is_empty() {
         return Err(CustomBase64Error::EmptyInput);
     }
 
@@ -52,18 +87,16 @@ fn validate_input(input: &str) -> Result<(), CustomBase64Error> {
 #[wasm_bindgen]
 pub fn encode_custom_base64(input: &str) -> Result<String, CustomBase64Error> {
     validate_input(input)?;
-    let encoded = CUSTOM_ENGINE.encode(input.trim().as_bytes());
+    let encoded = custom_engine().encode(input.trim().as_bytes());
     Ok(encoded)
 }
 
 /// 使用自定义码表对 Base64 字符串进行解码，并还原为原始字符串
 #[wasm_bindgen]
 pub fn decode_custom_base64(encoded: &str) -> Result<String, CustomBase64Error> {
-    if encoded.is_empty() {
-        return Err(CustomBase64Error::EmptyInput.into());
-    }
+    validate_input(encoded)?;
 
-    let decoded_bytes = CUSTOM_ENGINE
+    let decoded_bytes = custom_engine()
         .decode(encoded.trim())
         .map_err(CustomBase64Error::Base64Decode)?;
 
@@ -73,22 +106,39 @@ pub fn decode_custom_base64(encoded: &str) -> Result<String, CustomBase64Error>
     Ok(decoded_str)
 }
 
-/// 使用运行时提供的 Base64 码表对字符串进行编码
-#[wasm_bindgen]
-pub fn encode_base64_with_alphabet(
-    input: &str,
+// 字节层面的自定义码表编解码，不做 trim ，供 CLI 等处理二进制数据的场景使用
+pub fn encode_bytes_with_alphabet(
+    data: &[u8],
     alphabet: &str,
 ) -> Result<String, CustomBase64Error> {
-    if input.is_empty() {
-        return Err(CustomBase64Error::EmptyInput.into());
-    }
     if alphabet.is_empty() {
-        return Err(CustomBase64Error::InvalidAlphabet("empty alphabet".into()).into());
+        return Err(CustomBase64Error::InvalidAlphabet("empty alphabet".into()));
     }
+    let engine = build_engine_from_alphabet(alphabet.trim())?;
+    Ok(engine.encode(data))
+}
 
+pub fn decode_bytes_with_alphabet(
+    encoded: &str,
+    alphabet: &str,
+) -> Result<Vec<u8>, CustomBase64Error> {
+    if alphabet.is_empty() {
+        return Err(CustomBase64Error::InvalidAlphabet("empty alphabet".into()));
+    }
     let engine = build_engine_from_alphabet(alphabet.trim())?;
-    let encoded = engine.encode(input.trim().as_bytes());
-    Ok(encoded)
+    engine
+        .decode(encoded.trim())
+        .map_err(CustomBase64Error::Base64Decode)
+}
+
+/// 使用运行时提供的 Base64 码表对字符串进行编码
+#[wasm_bindgen]
+pub fn encode_base64_with_alphabet(
+    input: &str,
+    alphabet: &str,
+) -> Result<String, CustomBase64Error> {
+    validate_input(input)?;
+    encode_bytes_with_alphabet(input.trim().as_bytes(), alphabet)
 }
 
 /// 使用运行时提供的 Base64 码表对字符串进行解码
@@ -97,17 +147,8 @@ pub fn decode_base64_with_alphabet(
     encoded: &str,
     alphabet: &str,
 ) -> Result<String, CustomBase64Error> {
-    if encoded.is_empty() {
-        return Err(CustomBase64Error::EmptyInput.into());
-    }
-    if alphabet.is_empty() {
-        return Err(CustomBase64Error::InvalidAlphabet("empty alphabet".into()).into());
-    }
-
-    let engine = build_engine_from_alphabet(alphabet.trim())?;
-    let decoded_bytes = engine
-        .decode(encoded.trim())
-        .map_err(CustomBase64Error::Base64Decode)?;
+    validate_input(encoded)?;
+    let decoded_bytes = decode_bytes_with_alphabet(encoded, alphabet)?;
     let decoded_str =
         String::from_utf8(decoded_bytes).map_err(|_| CustomBase64Error::InvalidUtf8)?;
     Ok(decoded_str)
@@ -116,6 +157,7 @@ pub fn decode_base64_with_alphabet(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn encode_decode_roundtrip_basic() -> anyhow::Result<()> {
@@ -156,6 +198,14 @@ mod tests {
     fn empty_input_rejected() {
         assert!(encode_custom_base64("").is_err());
         assert!(decode_custom_base64("").is_err());
+        // fuzz 发现的用例：只有空白的输入以前会编码成空串，而空串又无法解码
+        let alphabet = HANS7_CUSTOM_ALPHABET_STR.reveal_str();
+        for blank in [" ", " \n\t"] {
+            assert!(encode_custom_base64(blank).is_err());
+            assert!(decode_custom_base64(blank).is_err());
+            assert!(encode_base64_with_alphabet(blank, &alphabet).is_err());
+            assert!(decode_base64_with_alphabet(blank, &alphabet).is_err());
+        }
     }
 
     #[test]
@@ -198,7 +248,7 @@ mod tests {
         // 构造一个合法 Base64 编码，但解码后不是 UTF-8
         // 例如：字节 [0xFF, 0xFE] 不是合法 UTF-8
         let bad_bytes = [0xFF, 0xFE];
-        let fake_encoded = CUSTOM_ENGINE.encode(&bad_bytes);
+        let fake_encoded = custom_engine().encode(bad_bytes);
         // 这个 fake_encoded 是合法 Base64（按你的码表），但解码后无法转为 String
         let result = decode_custom_base64(&fake_encoded);
         assert!(result.is_err());
@@ -240,11 +290,12 @@ mod tests {
             "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/-", // 65 chars
             "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789++",  // 重复 '+'
             "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789哈",  // 非 ASCII
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz012345678 +/",  // 含空格
         ];
 
         for alpha in &invalid_alphabets {
-            let enc_result = encode_base64_with_alphabet(input, &alpha);
-            let dec_result = decode_base64_with_alphabet("any", &alpha);
+            let enc_result = encode_base64_with_alphabet(input, alpha);
+            let dec_result = decode_base64_with_alphabet("any", alpha);
             assert!(enc_result.is_err(), "Should reject alphabet: {:?}", alpha);
             assert!(dec_result.is_err(), "Should reject alphabet: {:?}", alpha);
         }
@@ -274,4 +325,36 @@ mod tests {
                 .contains("base64 decode failed")
         );
     }
+
+    // 合法码表：64 个互不相同的可打印 ASCII 字符，不含空格和 padding 用的 '='
+    fn valid_alphabet() -> impl Strategy<Value = String> {
+        let chars: Vec<char> = ('!'..='~').filter(|&c| c != '=').collect();
+        proptest::sample::subsequence(chars, 64)
+            .prop_shuffle()
+            .prop_map(|chars| chars.into_iter().collect())
+    }
+
+    proptest! {
+        #[test]
+        fn bytes_roundtrip_with_any_alphabet_prop(data: Vec<u8>, alphabet in valid_alphabet()) {
+            let encoded = encode_bytes_with_alphabet(&data, &alphabet).unwrap();
+            prop_assert_eq!(decode_bytes_with_alphabet(&encoded, &alphabet).unwrap(), data);
+        }
+
+        // 字符串版本会先 trim 输入
+        #[test]
+        fn str_roundtrip_prop(input in "\\PC*") {
+            prop_assume!(!input.trim().is_empty());
+            let encoded = encode_custom_base64(&input).unwrap();
+            prop_assert_eq!(decode_custom_base64(&encoded).unwrap(), input.trim());
+        }
+
+        #[test]
+        fn decoders_never_panic_prop(encoded in "\\PC*", alphabet in "\\PC{0,80}") {
+            let _ = decode_custom_base64(&encoded);
+            let _ = decode_base64_with_alphabet(&encoded, &alphabet);
+            let _ = decode_base64_with_alphabet(&encoded, &HANS7_CUSTOM_ALPHABET_STR.reveal_str());
+            let _ = encode_base64_with_alphabet(&encoded, &alphabet);
+        }
+    }
 }
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use wasm_bindgen::prelude::*;
+
+// 机器可读的错误码，前端按 code 分支，不再匹配错误信息字符串
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    EmptyInput,
+    InvalidInput,
+    InvalidBase64,
+    InvalidUtf8,
+    InvalidAlphabet,
+    InvalidEncoding,
+    BadKeyLen,
+    BadIvLen,
+    InvalidKey,
+    EncryptFailed,
+    DecryptFailed,
+    HashFailed,
+    UnsupportedAlgorithm,
+    UnsupportedFormat,
+    UnknownAlgorithm,
+    InvalidRecipe,
+    EmptyRecipe,
+    InvalidParams,
+    NotReversible,
+    StepFailed,
+    InvalidWasm,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::EmptyInput => "EMPTY_INPUT",
+            ErrorCode::InvalidInput => "INVALID_INPUT",
+            ErrorCode::InvalidBase64 => "INVALID_BASE64",
+            ErrorCode::InvalidUtf8 => "INVALID_UTF8",
+            ErrorCode::InvalidAlphabet => "INVALID_ALPHABET",
+            ErrorCode::InvalidEncoding => "INVALID_ENCODING",
+            ErrorCode::BadKeyLen => "BAD_KEY_LEN",
+            ErrorCode::BadIvLen => "BAD_IV_LEN",
+            ErrorCode::InvalidKey => "INVALID_KEY",
+            ErrorCode::EncryptFailed => "ENCRYPT_FAILED",
+            ErrorCode::DecryptFailed => "DECRYPT_FAILED",
+            ErrorCode::HashFailed => "HASH_FAILED",
+            ErrorCode::UnsupportedAlgorithm => "UNSUPPORTED_ALGORITHM",
+            ErrorCode::UnsupportedFormat => "UNSUPPORTED_FORMAT",
+            ErrorCode::UnknownAlgorithm => "UNKNOWN_ALGORITHM",
+            ErrorCode::InvalidRecipe => "INVALID_RECIPE",
+            ErrorCode::EmptyRecipe => "EMPTY_RECIPE",
+            ErrorCode::InvalidParams => "INVALID_PARAMS",
+            ErrorCode::NotReversible => "NOT_REVERSIBLE",
+            ErrorCode::StepFailed => "STEP_FAILED",
+            ErrorCode::InvalidWasm => "INVALID_WASM",
+        }
+    }
+}
+
+// 全 crate 统一的错误类型。各模块仍保留自己的错误枚举供 Rust 侧匹配，
+// 但传给 JS 时一律先转换成它，JS 侧拿到的是带 code / module / details 的 Error 对象
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RustWasmError {
+    pub code: ErrorCode,
+    pub module: &'static str,
+    pub message: String,
+    pub details: Map<String, Value>,
+}
+
+impl RustWasmError {
+    pub fn new(code: ErrorCode, module: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            module,
+            message: message.into(),
+            details: Map::new(),
+        }
+    }
+
+    pub fn with_detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!(self)
+    }
+}
+
+impl fmt::Display for RustWasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RustWasmError {}
+
+// 多个模块都会遇到 base64 解码失败，统一在这里展开出错位置等细节
+#[cfg(any(
+    feature = "xor",
+    feature = "aes",
+    feature = "base64",
+    feature = "registry"
+))]
+pub(crate) fn base64_decode_error(
+    e: base64::DecodeError,
+    module: &'static str,
+    message: String,
+) -> RustWasmError {
+    let err = RustWasmError::new(ErrorCode::InvalidBase64, module, message);
+    match e {
+        base64::DecodeError::InvalidByte(offset, byte) => err
+            .with_detail("reason", "invalid_byte")
+            .with_detail("offset", offset)
+            .with_detail("byte", byte),
+        base64::DecodeError::InvalidLength(len) => err
+            .with_detail("reason", "invalid_length")
+            .with_detail("length", len),
+        base64::DecodeError::InvalidLastSymbol(offset, byte) => err
+            .with_detail("reason", "invalid_last_symbol")
+            .with_detail("offset", offset)
+            .with_detail("byte", byte),
+        base64::DecodeError::InvalidPadding => err.with_detail("reason", "invalid_padding"),
+    }
+}
+
+impl From<RustWasmError> for JsValue {
+    fn from(e: RustWasmError) -> Self {
+        let err = js_sys::Error::new(&e.message);
+        err.set_name("RustWasmError");
+        let obj: &JsValue = err.as_ref();
+        let details = serde_json::to_string(&e.details)
+            .ok()
+            .and_then(|s| js_sys::JSON::parse(&s).ok())
+            .unwrap_or_else(|| js_sys::Object::new().into());
+        // Reflect::set 只会在目标不是对象时失败，这里可以安全忽略返回值
+        let _ = js_sys::Reflect::set(obj, &"code".into(), &e.code.as_str().into());
+        let _ = js_sys::Reflect::set(obj, &"module".into(), &e.module.into());
+        let _ = js_sys::Reflect::set(obj, &"details".into(), &details);
+        err.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_serializes_with_code_and_details_test() {
+        let err = RustWasmError::new(ErrorCode::BadKeyLen, "aes_cbc", "bad key")
+            .with_detail("expected", 16)
+            .with_detail("actual", 5);
+        assert_eq!(err.to_string(), "bad key");
+        assert_eq!(
+            err.to_json(),
+            json!({
+                "code": "BAD_KEY_LEN",
+                "module": "aes_cbc",
+                "message": "bad key",
+                "details": {"expected": 16, "actual": 5},
+            })
+        );
+    }
+
+    #[cfg(feature = "xor")]
+    #[test]
+    fn base64_decode_error_details_test() {
+        let err = base64_decode_error(
+            base64::DecodeError::InvalidByte(3, b'!'),
+            "xor_demo",
+            "Invalid Base64".into(),
+        );
+        assert_eq!(err.code, ErrorCode::InvalidBase64);
+        assert_eq!(err.details["reason"], "invalid_byte");
+        assert_eq!(err.details["offset"], 3);
+        assert_eq!(err.details["byte"], 33);
+    }
+
+    #[test]
+    fn error_code_as_str_matches_serde_test() {
+        let codes = [
+            ErrorCode::EmptyInput,
+            ErrorCode::InvalidUtf8,
+            ErrorCode::BadIvLen,
+            ErrorCode::UnsupportedAlgorithm,
+            ErrorCode::NotReversible,
+        ];
+        for code in codes {
+            assert_eq!(json!(code), Value::from(code.as_str()));
+        }
+    }
+}
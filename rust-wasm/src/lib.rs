@@ -1,17 +1,97 @@
+#[cfg(feature = "aes")]
 mod aes_cbc;
+#[cfg(feature = "aes")]
 mod aes_cbc_error;
+#[cfg(feature = "registry")]
+mod algorithm;
+#[cfg(any(feature = "capi", feature = "wasi"))]
+pub mod capi;
+#[cfg(feature = "base64")]
 mod custom_base64;
+mod error;
+#[cfg(feature = "recipe")]
+mod pipeline;
+#[cfg(feature = "python")]
+mod python;
+mod secret;
 mod sha_demo;
+#[cfg(all(feature = "threads", target_arch = "wasm32"))]
+mod thread_pool;
+#[cfg(feature = "wasi")]
+pub mod wasi;
+#[cfg(feature = "inspect")]
+mod wasm_inspect;
+#[cfg(feature = "xor")]
 mod xor_demo;
+#[cfg(feature = "xor")]
+mod xor_demo_error;
 
+#[cfg(feature = "aes")]
 pub use crate::aes_cbc::{aes_cbc_decrypt, aes_cbc_encrypt};
-pub use crate::custom_base64::{decode_custom_base64, encode_custom_base64};
+#[cfg(feature = "registry")]
+pub use crate::algorithm::registry::{
+    Registry, decode_by_name, decrypt_by_name, encode_by_name, encrypt_by_name, hash_by_name,
+    list_algorithms, registry, verify_hmac_by_name,
+};
+#[cfg(feature = "registry")]
+pub use crate::algorithm::traits::{Algorithm, Cipher, CipherKind, Encoder, Hasher};
+#[cfg(feature = "base64")]
+pub use crate::custom_base64::{
+    HANS7_CUSTOM_ALPHABET_STR, decode_base64_with_alphabet, decode_bytes_with_alphabet,
+    decode_custom_base64, encode_base64_with_alphabet, encode_bytes_with_alphabet,
+    encode_custom_base64,
+};
+pub use crate::error::{ErrorCode, RustWasmError};
+#[cfg(feature = "recipe")]
+pub use crate::pipeline::recipe::{
+    run_recipe, run_recipe_reverse, run_recipe_reverse_str, run_recipe_str, validate_recipe,
+};
+pub use crate::secret::{Sealed, SecretBytes, ZeroizingAlloc, ct_eq};
+pub use crate::sha_demo::parallel_hash::hash_capabilities;
+#[cfg(feature = "blake3")]
+pub use crate::sha_demo::parallel_hash::{compute_blake3_bytes, get_bytes_blake3};
+#[cfg(feature = "sha")]
+pub use crate::sha_demo::parallel_hash::{compute_sha_chunked_bytes, get_bytes_sha_chunked};
+#[cfg(feature = "sha")]
 pub use crate::sha_demo::sha_bytes_demo::{
-    get_bytes_sha256, get_bytes_sha256_pure, get_bytes_sha256_with_salt, get_bytes_sha512,
-    get_bytes_sha512_pure, get_bytes_sha512_with_salt,
+    DEFAULT_BYTES_SALT, compute_sha256_bytes, compute_sha512_bytes, get_bytes_sha_formatted,
+    get_bytes_sha_raw, get_bytes_sha256, get_bytes_sha256_pure, get_bytes_sha256_with_salt,
+    get_bytes_sha512, get_bytes_sha512_pure, get_bytes_sha512_with_salt,
 };
+#[cfg(feature = "sha")]
 pub use crate::sha_demo::sha_demo::{
-    get_str_sha256, get_str_sha256_pure, get_str_sha256_with_salt, get_str_sha512,
-    get_str_sha512_pure, get_str_sha512_with_salt,
+    DEFAULT_STR_SALT, get_str_sha_formatted, get_str_sha_raw, get_str_sha256, get_str_sha256_pure,
+    get_str_sha256_with_salt, get_str_sha512, get_str_sha512_pure, get_str_sha512_with_salt,
+};
+pub use crate::sha_demo::utils::{HashFormat, HashOutput, ShaAlgorithm};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::crypto_scan::{CryptoHit, scan_crypto, scan_crypto_constants};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::decompiler::{decompile_wasm, decompile_wasm_function};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::disassembler::{disassemble_wasm, disassemble_wasm_function};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::inspect_error::InspectError;
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::interpreter::{
+    Execution, TraceEvent, TraceOptions, Trap, Value as WasmValue, WasmInterpreter,
+};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::module_info::{ModuleInfo, inspect_wasm, parse_module};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::obfuscator::{
+    ObfuscateOptions, ObfuscatedWasm, obfuscate_module, obfuscate_wasm,
+};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::patcher::{Patch, patch_module, patch_wasm};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::signatures::{
+    FunctionSignature, SignatureMatch, apply_signatures, apply_wasm_signatures,
+    generate_signatures, generate_wasm_signatures, match_signatures, match_wasm_signatures,
+};
+#[cfg(feature = "inspect")]
+pub use crate::wasm_inspect::strings::{
+    StringXref, WasmString, extract_strings, extract_wasm_strings,
 };
-pub use crate::xor_demo::{decrypt_to_username, encrypt_username};
+#[cfg(feature = "xor")]
+pub use crate::xor_demo::{XOR_KEY, decrypt_to_username, encrypt_username};
@@ -0,0 +1,3 @@
+pub mod pipeline_error;
+pub mod recipe;
+pub mod step;
@@ -0,0 +1,73 @@
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ErrorCode, RustWasmError};
+
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("invalid recipe json: {0}")]
+    InvalidRecipe(String),
+    #[error("recipe has no steps")]
+    EmptyRecipe,
+    #[error("step {index} ({op}) has invalid params: {message}")]
+    InvalidParams {
+        index: usize,
+        op: &'static str,
+        message: String,
+    },
+    #[error("step {index} ({op}) is not reversible")]
+    NotReversible { index: usize, op: &'static str },
+    #[error("step {index} ({op}) failed: {message}")]
+    StepFailed {
+        index: usize,
+        op: &'static str,
+        message: String,
+    },
+    #[error("pipeline output is not valid UTF-8")]
+    InvalidUtf8,
+}
+
+impl From<PipelineError> for RustWasmError {
+    fn from(e: PipelineError) -> Self {
+        let message = e.to_string();
+        match e {
+            PipelineError::InvalidRecipe(reason) => {
+                RustWasmError::new(ErrorCode::InvalidRecipe, "pipeline", message)
+                    .with_detail("reason", reason)
+            }
+            PipelineError::EmptyRecipe => {
+                RustWasmError::new(ErrorCode::EmptyRecipe, "pipeline", message)
+            }
+            PipelineError::InvalidParams {
+                index,
+                op,
+                message: reason,
+            } => RustWasmError::new(ErrorCode::InvalidParams, "pipeline", message)
+                .with_detail("index", index)
+                .with_detail("op", op)
+                .with_detail("reason", reason),
+            PipelineError::NotReversible { index, op } => {
+                RustWasmError::new(ErrorCode::NotReversible, "pipeline", message)
+                    .with_detail("index", index)
+                    .with_detail("op", op)
+            }
+            PipelineError::StepFailed {
+                index,
+                op,
+                message: reason,
+            } => RustWasmError::new(ErrorCode::StepFailed, "pipeline", message)
+                .with_detail("index", index)
+                .with_detail("op", op)
+                .with_detail("reason", reason),
+            PipelineError::InvalidUtf8 => {
+                RustWasmError::new(ErrorCode::InvalidUtf8, "pipeline", message)
+            }
+        }
+    }
+}
+
+impl From<PipelineError> for JsValue {
+    fn from(e: PipelineError) -> Self {
+        RustWasmError::from(e).into()
+    }
+}
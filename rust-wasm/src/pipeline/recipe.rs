@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+use super::pipeline_error::PipelineError;
+use super::step::Step;
+use crate::secret::{reveal, secret_into_string};
+
+// 一个 recipe 就是有序的步骤列表，正向按顺序执行，逆向按倒序执行每一步的逆操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Recipe {
+    pub steps: Vec<Step>,
+}
+
+impl Recipe {
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let recipe: Recipe =
+            serde_json::from_str(json).map_err(|e| PipelineError::InvalidRecipe(e.to_string()))?;
+        recipe.validate()?;
+        Ok(recipe)
+    }
+
+    pub fn validate(&self) -> Result<(), PipelineError> {
+        if self.steps.is_empty() {
+            return Err(PipelineError::EmptyRecipe);
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            step.validate()
+                .map_err(|message| PipelineError::InvalidParams {
+                    index,
+                    op: step.op_name(),
+                    message,
+                })?;
+        }
+        Ok(())
+    }
+
+    pub fn is_reversible(&self) -> bool {
+        self.steps.iter().all(Step::is_reversible)
+    }
+
+    // 中间结果可能是明文，每一步的输出都用 Zeroizing 接收，被下一步替换时清零
+    pub fn run(&self, input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+        let mut data = Zeroizing::new(input.to_vec());
+        for (index, step) in self.steps.iter().enumerate() {
+            data = Zeroizing::new(step.forward(&data).map_err(|message| {
+                PipelineError::StepFailed {
+                    index,
+                    op: step.op_name(),
+                    message,
+                }
+            })?);
+        }
+        Ok(reveal(data))
+    }
+
+    pub fn run_reverse(&self, input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+        // 先整体检查，避免执行到一半才发现某一步不可逆
+        if let Some((index, step)) = self
+            .steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| !step.is_reversible())
+        {
+            return Err(PipelineError::NotReversible {
+                index,
+                op: step.op_name(),
+            });
+        }
+
+        let mut data = Zeroizing::new(input.to_vec());
+        for (index, step) in self.steps.iter().enumerate().rev() {
+            data = Zeroizing::new(step.reverse(&data).map_err(|message| {
+                PipelineError::StepFailed {
+                    index,
+                    op: step.op_name(),
+                    message,
+                }
+            })?);
+        }
+        Ok(reveal(data))
+    }
+}
+
+/// 校验 recipe ，返回该 recipe 是否可以逆向执行
+#[wasm_bindgen]
+pub fn validate_recipe(recipe_json: &str) -> Result<bool, PipelineError> {
+    let recipe = Recipe::from_json(recipe_json)?;
+    Ok(recipe.is_reversible())
+}
+
+/// 正向执行 recipe ，输入输出均为字节（JS 侧为 `Uint8Array`）
+#[wasm_bindgen]
+pub fn run_recipe(recipe_json: &str, input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    Recipe::from_json(recipe_json)?.run(input)
+}
+
+/// 逆向执行 recipe ，遇到哈希等不可逆步骤时报错
+#[wasm_bindgen]
+pub fn run_recipe_reverse(recipe_json: &str, input: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    Recipe::from_json(recipe_json)?.run_reverse(input)
+}
+
+/// 字符串版本：输入先 trim ，与其他导出函数保持一致；输出必须是合法 UTF-8
+#[wasm_bindgen]
+pub fn run_recipe_str(recipe_json: &str, input: &str) -> Result<String, PipelineError> {
+    let output = run_recipe(recipe_json, input.trim().as_bytes())?;
+    secret_into_string(output).map_err(|_| PipelineError::InvalidUtf8)
+}
+
+#[wasm_bindgen]
+pub fn run_recipe_reverse_str(recipe_json: &str, input: &str) -> Result<String, PipelineError> {
+    let output = run_recipe_reverse(recipe_json, input.trim().as_bytes())?;
+    secret_into_string(output).map_err(|_| PipelineError::InvalidUtf8)
+}
+
+// 测试用例组合了所有算法步骤，只在全部算法 feature 开启时运行
+#[cfg(all(
+    test,
+    feature = "aes",
+    feature = "base64",
+    feature = "sha",
+    feature = "xor"
+))]
+mod tests {
+    use super::*;
+    use crate::aes_cbc::aes_cbc_encrypt;
+    use crate::custom_base64::encode_custom_base64;
+    use crate::sha_demo::sha_bytes_demo::get_bytes_sha256;
+    use crate::xor_demo::encrypt_username;
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn recipe_matches_standalone_exports_test() -> anyhow::Result<()> {
+        let xor_b64 = r#"{"steps": [{"op": "xor"}, {"op": "base64"}]}"#;
+        assert_eq!(run_recipe_str(xor_b64, "hans7")?, encrypt_username("hans7"));
+        assert_eq!(
+            run_recipe_str(xor_b64, "爱拼才会赢")?,
+            "j+vFgOPfku/lh8j8gNbW"
+        );
+
+        let aes = r#"{"steps": [
+            {"op": "aes_cbc", "key": "1234567890123456", "iv": "abcdef9876543210"},
+            {"op": "base64"}
+        ]}"#;
+        let key = b"1234567890123456";
+        let iv = b"abcdef9876543210";
+        assert_eq!(
+            run_recipe_str(aes, "hello world")?,
+            aes_cbc_encrypt("hello world", key, iv)?
+        );
+
+        let custom = r#"{"steps": [{"op": "custom_base64"}]}"#;
+        assert_eq!(
+            run_recipe_str(custom, "爱拼才会赢💪")?,
+            encode_custom_base64("爱拼才会赢💪")?
+        );
+
+        let sha = r#"{"steps": [{"op": "sha256", "salt": "hans7_sha_bytes"}, {"op": "hex"}]}"#;
+        assert_eq!(
+            run_recipe_str(sha, "hello world")?,
+            get_bytes_sha256(b"hello world")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn recipe_roundtrip_test() -> anyhow::Result<()> {
+        let recipe = r#"{"steps": [
+            {"op": "xor", "key": "hans7"},
+            {"op": "aes_cbc", "key": "1234567890123456", "iv": "abcdef9876543210"},
+            {"op": "base64", "alphabet": "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/"},
+            {"op": "hex"},
+            {"op": "custom_base64"}
+        ]}"#;
+        assert!(validate_recipe(recipe)?);
+        for input in ["hello world", "有空 格", "🚀🚀🚀"] {
+            let encrypted = run_recipe_str(recipe, input)?;
+            assert_eq!(run_recipe_reverse_str(recipe, &encrypted)?, input);
+        }
+
+        let binary = [0x00, 0xFF, 0x10, 0x80];
+        let encrypted = run_recipe(recipe, &binary)?;
+        assert_eq!(run_recipe_reverse(recipe, &encrypted)?, binary);
+
+        Ok(())
+    }
+
+    #[test]
+    fn recipe_order_matters_test() -> anyhow::Result<()> {
+        let a = r#"{"steps": [{"op": "xor"}, {"op": "base64"}]}"#;
+        let b = r#"{"steps": [{"op": "base64"}, {"op": "xor"}]}"#;
+        assert_ne!(run_recipe(a, b"hans7")?, run_recipe(b, b"hans7")?);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_recipe_rejected_test() {
+        let cases = [
+            "not json",
+            r#"{"steps": []}"#,
+            r#"{"steps": [{"op": "rot13"}]}"#,
+            r#"{"steps": [{"op": "xor", "unknown": 1}]}"#,
+            r#"{"steps": [{"op": "aes_cbc", "key": "short", "iv": "abcdef9876543210"}]}"#,
+            r#"{"steps": [{"op": "aes_cbc", "key": "1234567890123456"}]}"#,
+            r#"{"steps": [{"op": "base64", "alphabet": "ABC"}]}"#,
+            r#"{"steps": [{"op": "xor", "key": ""}]}"#,
+            r#"{"steps": [{"op": "xor", "iv": "abcdef9876543210"}]}"#,
+            r#"{"steps": [{"op": "hex", "alphabet": "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/"}]}"#,
+            r#"{"steps": [{"op": "sha256", "key": "k"}]}"#,
+        ];
+        for case in cases {
+            assert!(validate_recipe(case).is_err(), "Should reject: {}", case);
+        }
+
+        let err = validate_recipe(r#"{"steps": [{"op": "xor"}, {"op": "aes_cbc", "key": "k", "iv": "abcdef9876543210"}]}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("step 1 (aes_cbc)"));
+    }
+
+    // op 按名字在 registry 中查找，注册过的算法都能直接用在 recipe 中
+    #[test]
+    fn registry_ops_test() -> anyhow::Result<()> {
+        use crate::algorithm::registry::{encode_by_name, encrypt_by_name};
+
+        let recipe = r#"{"steps": [{"op": "AES-CBC", "key": "1234567890123456", "iv": "abcdef9876543210"}, {"op": "base64url"}]}"#;
+        let aes = encrypt_by_name(
+            "aes_cbc",
+            b"hello",
+            b"1234567890123456",
+            b"abcdef9876543210",
+        )?;
+        assert_eq!(
+            run_recipe_str(recipe, "hello")?,
+            encode_by_name("base64url", &aes)?
+        );
+        assert_eq!(
+            run_recipe_reverse_str(recipe, &run_recipe_str(recipe, "hello")?)?,
+            "hello"
+        );
+
+        let err = run_recipe_reverse(recipe, b"!!").unwrap_err();
+        assert!(matches!(
+            err,
+            PipelineError::StepFailed {
+                index: 1,
+                op: "base64url",
+                ..
+            }
+        ));
+
+        #[cfg(feature = "blake3")]
+        {
+            use crate::algorithm::registry::hash_by_name;
+
+            let blake3 = r#"{"steps": [{"op": "blake3", "salt": "s"}, {"op": "hex"}]}"#;
+            assert!(!validate_recipe(blake3)?);
+            assert_eq!(
+                run_recipe_str(blake3, "abc")?,
+                hash_by_name("blake3", b"abc_s", "hex")?
+            );
+        }
+
+        let err = validate_recipe(r#"{"steps": [{"op": "rot13"}]}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown algorithm: rot13"));
+        Ok(())
+    }
+
+    #[test]
+    fn irreversible_recipe_test() -> anyhow::Result<()> {
+        let recipe = r#"{"steps": [{"op": "base64"}, {"op": "sha512"}]}"#;
+        assert!(!validate_recipe(recipe)?);
+        assert_eq!(run_recipe(recipe, b"hello")?.len(), 64);
+        let err = run_recipe_reverse(recipe, b"anything").unwrap_err();
+        assert!(matches!(
+            err,
+            PipelineError::NotReversible {
+                index: 1,
+                op: "sha512"
+            }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn step_failure_reports_index_test() {
+        let recipe = r#"{"steps": [{"op": "xor"}, {"op": "base64"}]}"#;
+        let err = run_recipe_reverse_str(recipe, "not base64!!").unwrap_err();
+        assert!(matches!(err, PipelineError::StepFailed { index: 1, .. }));
+
+        let hex = r#"{"steps": [{"op": "hex"}]}"#;
+        assert!(run_recipe_reverse(hex, b"abc").is_err());
+        assert!(run_recipe_reverse(hex, b"zz").is_err());
+        assert!(run_recipe_reverse(hex, b"+f").is_err());
+
+        let bad_utf8 = r#"{"steps": [{"op": "sha256"}]}"#;
+        assert!(matches!(
+            run_recipe_str(bad_utf8, "hello"),
+            Err(PipelineError::InvalidUtf8)
+        ));
+    }
+
+    fn step(params: serde_json::Value) -> Step {
+        serde_json::from_value(params).unwrap()
+    }
+
+    fn reversible_step() -> impl Strategy<Value = Step> {
+        let alphabet = prop_oneof![
+            Just(None),
+            Just(Some(
+                crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR
+                    .reveal_str()
+                    .to_string()
+            )),
+        ];
+        prop_oneof![
+            "\\PC+".prop_map(|key| step(json!({"op": "xor", "key": key}))),
+            ("[ -~]{16}", "[ -~]{16}")
+                .prop_map(|(key, iv)| step(json!({"op": "aes_cbc", "key": key, "iv": iv}))),
+            alphabet.prop_map(|alphabet| step(json!({"op": "base64", "alphabet": alphabet}))),
+            Just(step(json!({"op": "custom_base64"}))),
+            Just(step(json!({"op": "base64url"}))),
+            Just(step(json!({"op": "hex"}))),
+        ]
+    }
+
+    proptest! {
+        // 任意可逆步骤的组合，逆向执行都能还原任意字节
+        #[test]
+        fn reversible_recipe_roundtrip_prop(
+            steps in proptest::collection::vec(reversible_step(), 1..6),
+            input: Vec<u8>,
+        ) {
+            let recipe = serde_json::to_string(&Recipe { steps }).unwrap();
+            prop_assert!(validate_recipe(&recipe).unwrap());
+            let output = run_recipe(&recipe, &input).unwrap();
+            prop_assert_eq!(run_recipe_reverse(&recipe, &output).unwrap(), input);
+        }
+
+        #[test]
+        fn recipe_never_panics_prop(
+            steps in proptest::collection::vec(reversible_step(), 1..4),
+            recipe_json in "\\PC*",
+            input: Vec<u8>,
+        ) {
+            let _ = validate_recipe(&recipe_json);
+            let _ = run_recipe_reverse(&recipe_json, &input);
+            // 合法 recipe 配上任意输入
+            let recipe = serde_json::to_string(&Recipe { steps }).unwrap();
+            let _ = run_recipe_reverse(&recipe, &input);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use base64::{Engine as _, engine::GeneralPurpose};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::algorithm::algorithm_error::AlgorithmError;
+use crate::algorithm::registry::registry;
+use crate::algorithm::traits::{Cipher, Encoder, Hasher};
+#[cfg(feature = "base64")]
+use crate::custom_base64::build_engine_from_alphabet;
+use crate::sha_demo::utils::get_salted_bytes;
+
+// 可以指定 alphabet 的编码算法
+const ALPHABET_OP: &str = "base64";
+
+// 自定义码表的校验复用 custom_base64 模块，未开启 base64 feature 时只支持标准码表
+#[cfg(feature = "base64")]
+fn alphabet_engine(alphabet: &str) -> Result<GeneralPurpose, String> {
+    build_engine_from_alphabet(alphabet).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "base64"))]
+fn alphabet_engine(_alphabet: &str) -> Result<GeneralPurpose, String> {
+    Err("custom alphabet requires the `base64` feature".into())
+}
+
+// recipe JSON 中一步的原样内容。op 是 registry 中的算法名，大小写和 '-' / '_' 的规则与 *_by_name 相同。
+// 字符串参数一律按 UTF-8 取字节
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepParams {
+    pub op: String,
+    // 加密算法的 key / iv 。省略 key 时使用算法的默认密钥（xor 为 "hctf"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iv: Option<String>,
+    // 只用于 base64 ，缺省时使用标准码表
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alphabet: Option<String>,
+    // 只用于哈希，规则与 get_bytes_sha*_with_salt 相同
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+}
+
+// key / iv 是密钥材料，recipe 释放时清零
+impl Drop for StepParams {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.iv.zeroize();
+    }
+}
+
+// op 解析到的 registry 算法。加密算法可逆，哈希不可逆，编码算法的逆操作是解码
+#[derive(Clone, Copy)]
+enum Op {
+    Cipher(&'static dyn Cipher),
+    Hasher(&'static dyn Hasher),
+    Encoder(&'static dyn Encoder),
+}
+
+impl Op {
+    // 同名时依次查找加密算法、哈希、编码
+    fn resolve(name: &str) -> Result<Self, AlgorithmError> {
+        let registry = registry();
+        if let Ok(cipher) = registry.cipher(name) {
+            return Ok(Op::Cipher(cipher));
+        }
+        if let Ok(hasher) = registry.hasher(name) {
+            return Ok(Op::Hasher(hasher));
+        }
+        registry
+            .encoder(name)
+            .map(Op::Encoder)
+            .map_err(|_| AlgorithmError::UnknownAlgorithm {
+                kind: "algorithm",
+                name: name.to_string(),
+            })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Op::Cipher(cipher) => cipher.name(),
+            Op::Hasher(hasher) => hasher.name(),
+            Op::Encoder(encoder) => encoder.name(),
+        }
+    }
+}
+
+// pipeline 中的一步，解析 recipe 时即按 op 在 registry 中查找算法，未注册（或未开启 feature）的 op 直接报错。
+// 新算法注册到 registry 后无需修改这里即可在 recipe 中使用
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "StepParams", into = "StepParams")]
+pub struct Step {
+    op: Op,
+    params: StepParams,
+}
+
+impl TryFrom<StepParams> for Step {
+    type Error = AlgorithmError;
+
+    fn try_from(params: StepParams) -> Result<Self, Self::Error> {
+        Ok(Step {
+            op: Op::resolve(&params.op)?,
+            params,
+        })
+    }
+}
+
+impl From<Step> for StepParams {
+    fn from(step: Step) -> Self {
+        step.params.clone()
+    }
+}
+
+impl PartialEq for Step {
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params
+    }
+}
+
+impl Eq for Step {}
+
+// 不输出 key / iv
+impl fmt::Debug for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Step")
+            .field("op", &self.op_name())
+            .finish_non_exhaustive()
+    }
+}
+
+// 参数只对某一类算法有意义，其他算法带上时报错，避免写错 op 后参数被静默忽略
+fn reject(present: bool, param: &str, op: &str) -> Result<(), String> {
+    if present {
+        return Err(format!("{} does not take `{}`", op, param));
+    }
+    Ok(())
+}
+
+fn check_len(value: Option<&String>, param: &str, len: usize) -> Result<(), String> {
+    match value {
+        None => Err(format!("{} is required", param)),
+        Some(value) if value.len() != len => Err(format!(
+            "{} must be {} bytes, got {}",
+            param,
+            len,
+            value.len()
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+impl Step {
+    // registry 中的规范名，例如 op 写成 "AES-CBC" 时为 "aes_cbc"
+    pub fn op_name(&self) -> &'static str {
+        self.op.name()
+    }
+
+    pub fn is_reversible(&self) -> bool {
+        !matches!(self.op, Op::Hasher(_))
+    }
+
+    // 只检查参数本身，不依赖输入数据
+    pub fn validate(&self) -> Result<(), String> {
+        let params = &self.params;
+        let op = self.op_name();
+        match self.op {
+            Op::Cipher(cipher) => {
+                match (cipher.key_len(), &params.key) {
+                    (Some(len), _) => check_len(params.key.as_ref(), "key", len)?,
+                    (None, Some(key)) if key.is_empty() => {
+                        return Err("key must not be empty".into());
+                    }
+                    (None, None) if cipher.default_key().is_none() => {
+                        return Err("key is required".into());
+                    }
+                    _ => {}
+                }
+                match cipher.iv_len() {
+                    Some(len) => check_len(params.iv.as_ref(), "iv", len)?,
+                    None => reject(params.iv.is_some(), "iv", op)?,
+                }
+                reject(params.alphabet.is_some(), "alphabet", op)?;
+                reject(params.salt.is_some(), "salt", op)
+            }
+            Op::Hasher(_) => {
+                reject(params.key.is_some(), "key", op)?;
+                reject(params.iv.is_some(), "iv", op)?;
+                reject(params.alphabet.is_some(), "alphabet", op)
+            }
+            Op::Encoder(_) => {
+                reject(params.key.is_some(), "key", op)?;
+                reject(params.iv.is_some(), "iv", op)?;
+                reject(params.salt.is_some(), "salt", op)?;
+                match &params.alphabet {
+                    Some(alphabet) if op == ALPHABET_OP => alphabet_engine(alphabet).map(|_| ()),
+                    Some(_) => reject(true, "alphabet", op),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    fn key(&self, cipher: &dyn Cipher) -> Zeroizing<Vec<u8>> {
+        match &self.params.key {
+            Some(key) => Zeroizing::new(key.as_bytes().to_vec()),
+            None => cipher.default_key().unwrap_or_default(),
+        }
+    }
+
+    fn iv(&self) -> &[u8] {
+        self.params.iv.as_deref().unwrap_or_default().as_bytes()
+    }
+
+    pub fn forward(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        match self.op {
+            Op::Cipher(cipher) => cipher
+                .encrypt(data, &self.key(cipher), self.iv())
+                .map_err(|e| e.to_string()),
+            Op::Hasher(hasher) => {
+                let salt = self.params.salt.as_deref().unwrap_or_default();
+                Ok(hasher.hash(&get_salted_bytes(data, salt)).into_bytes())
+            }
+            Op::Encoder(encoder) => match &self.params.alphabet {
+                Some(alphabet) => Ok(alphabet_engine(alphabet)?.encode(data).into_bytes()),
+                None => Ok(encoder.encode(data).into_bytes()),
+            },
+        }
+    }
+
+    pub fn reverse(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        match self.op {
+            Op::Cipher(cipher) => cipher
+                .decrypt(data, &self.key(cipher), self.iv())
+                .map_err(|e| e.to_string()),
+            Op::Hasher(_) => Err(format!("{} is a one-way step", self.op_name())),
+            Op::Encoder(encoder) => match &self.params.alphabet {
+                Some(alphabet) => alphabet_engine(alphabet)?
+                    .decode(data)
+                    .map_err(|e| e.to_string()),
+                None => {
+                    let encoded = std::str::from_utf8(data)
+                        .map_err(|_| format!("{} input is not valid UTF-8", self.op_name()))?;
+                    encoder.decode(encoded).map_err(|e| e.to_string())
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,500 @@
+// Python 扩展模块：导出与 WASM 完全相同的函数，方便 Python 解题脚本直接调用 Rust 实现。
+// 构建：maturin develop --features python ，之后 `import rust_wasm` 。测试：pytest tests/python
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[cfg(feature = "aes")]
+use crate::aes_cbc_error::AesError;
+#[cfg(feature = "registry")]
+use crate::algorithm::algorithm_error::AlgorithmError;
+#[cfg(feature = "base64")]
+use crate::custom_base64::CustomBase64Error;
+use crate::error::RustWasmError;
+#[cfg(feature = "recipe")]
+use crate::pipeline::pipeline_error::PipelineError;
+use crate::sha_demo::sha_demo_error::ShaHashError;
+#[cfg(feature = "xor")]
+use crate::xor_demo_error::XorError;
+
+// 异常层级：所有异常都继承 RustWasmError(ValueError)，每个 Rust 错误枚举对应一个子类，
+// 实例上带有 code / module / details 属性，与 JS 侧的 Error 对象字段一致。
+// 异常类按 module 选择：registry 包装的底层错误（例如 encrypt_by_name 的密钥长度错误）
+// 仍抛出底层模块的异常类，与 code / module 属性一致
+mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyValueError;
+
+    create_exception!(rust_wasm, RustWasmError, PyValueError);
+    create_exception!(rust_wasm, AesError, RustWasmError);
+    create_exception!(rust_wasm, ShaHashError, RustWasmError);
+    create_exception!(rust_wasm, CustomBase64Error, RustWasmError);
+    create_exception!(rust_wasm, XorError, RustWasmError);
+    create_exception!(rust_wasm, PipelineError, RustWasmError);
+    create_exception!(rust_wasm, AlgorithmError, RustWasmError);
+}
+
+fn to_py_err(err: RustWasmError) -> PyErr {
+    Python::attach(|py| {
+        let message = err.message.clone();
+        let py_err = match err.module {
+            "aes_cbc" => exceptions::AesError::new_err(message),
+            "sha_demo" => exceptions::ShaHashError::new_err(message),
+            "custom_base64" => exceptions::CustomBase64Error::new_err(message),
+            "xor_demo" => exceptions::XorError::new_err(message),
+            "pipeline" => exceptions::PipelineError::new_err(message),
+            "algorithm" => exceptions::AlgorithmError::new_err(message),
+            _ => exceptions::RustWasmError::new_err(message),
+        };
+        let value = py_err.value(py);
+        // details 借道 JSON 转成 Python dict ，失败时退化为空 dict
+        let details = serde_json::to_string(&err.details)
+            .ok()
+            .and_then(|text| {
+                py.import("json")
+                    .and_then(|json| json.call_method1("loads", (text,)))
+                    .ok()
+            })
+            .unwrap_or_else(|| PyDict::new(py).into_any());
+        // setattr 只会在异常对象不可写时失败，这里可以安全忽略返回值
+        let _ = value.setattr("code", err.code.as_str());
+        let _ = value.setattr("module", err.module);
+        let _ = value.setattr("details", details);
+        py_err
+    })
+}
+
+macro_rules! impl_into_py_err {
+    ($($(#[$meta:meta])* $error:ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            impl From<$error> for PyErr {
+                fn from(e: $error) -> Self {
+                    to_py_err(RustWasmError::from(e))
+                }
+            }
+        )*
+    };
+}
+
+// 异常类始终注册，未开启的 feature 只是不会抛出对应异常
+impl_into_py_err!(
+    #[cfg(feature = "aes")]
+    AesError,
+    ShaHashError,
+    #[cfg(feature = "base64")]
+    CustomBase64Error,
+    #[cfg(feature = "xor")]
+    XorError,
+    #[cfg(feature = "recipe")]
+    PipelineError,
+    #[cfg(feature = "registry")]
+    AlgorithmError,
+);
+
+#[cfg(feature = "xor")]
+#[pyfunction]
+fn encrypt_username(name: &str) -> String {
+    crate::xor_demo::encrypt_username(name)
+}
+
+#[cfg(feature = "xor")]
+#[pyfunction]
+fn decrypt_to_username(encrypted: &str) -> PyResult<String> {
+    Ok(crate::xor_demo::decrypt_to_username(encrypted)?)
+}
+
+#[cfg(feature = "aes")]
+#[pyfunction]
+fn aes_cbc_encrypt(plain: &str, key: &[u8], iv: &[u8]) -> PyResult<String> {
+    Ok(crate::aes_cbc::aes_cbc_encrypt(plain, key, iv)?)
+}
+
+#[cfg(feature = "aes")]
+#[pyfunction]
+fn aes_cbc_decrypt(b64_cipher: &str, key: &[u8], iv: &[u8]) -> PyResult<String> {
+    Ok(crate::aes_cbc::aes_cbc_decrypt(b64_cipher, key, iv)?)
+}
+
+#[cfg(feature = "base64")]
+#[pyfunction]
+fn encode_custom_base64(input: &str) -> PyResult<String> {
+    Ok(crate::custom_base64::encode_custom_base64(input)?)
+}
+
+#[cfg(feature = "base64")]
+#[pyfunction]
+fn decode_custom_base64(encoded: &str) -> PyResult<String> {
+    Ok(crate::custom_base64::decode_custom_base64(encoded)?)
+}
+
+#[cfg(feature = "base64")]
+#[pyfunction]
+fn encode_base64_with_alphabet(input: &str, alphabet: &str) -> PyResult<String> {
+    Ok(crate::custom_base64::encode_base64_with_alphabet(
+        input, alphabet,
+    )?)
+}
+
+#[cfg(feature = "base64")]
+#[pyfunction]
+fn decode_base64_with_alphabet(encoded: &str, alphabet: &str) -> PyResult<String> {
+    Ok(crate::custom_base64::decode_base64_with_alphabet(
+        encoded, alphabet,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha256_pure(input: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha256_pure(input)?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha512_pure(input: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha512_pure(input)?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha256_with_salt(input: &str, salt: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha256_with_salt(
+        input, salt,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha512_with_salt(input: &str, salt: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha512_with_salt(
+        input, salt,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha256(input: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha256(input)?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_str_sha512(input: &str) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha512(input)?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+#[pyo3(signature = (input, salt = "", algorithm = "sha256", format = "hex", truncate = None))]
+fn get_str_sha_formatted(
+    input: &str,
+    salt: &str,
+    algorithm: &str,
+    format: &str,
+    truncate: Option<usize>,
+) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha_formatted(
+        input, salt, algorithm, format, truncate,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+#[pyo3(signature = (input, salt = "", algorithm = "sha256"))]
+fn get_str_sha_raw(input: &str, salt: &str, algorithm: &str) -> PyResult<Vec<u8>> {
+    Ok(crate::sha_demo::sha_demo::get_str_sha_raw(
+        input, salt, algorithm,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha256_pure(data: &[u8]) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha256_pure(data)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha512_pure(data: &[u8]) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha512_pure(data)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha256_with_salt(data: &[u8], salt: &str) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha256_with_salt(data, salt)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha512_with_salt(data: &[u8], salt: &str) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha512_with_salt(data, salt)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha256(data: &[u8]) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha256(data)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha512(data: &[u8]) -> String {
+    crate::sha_demo::sha_bytes_demo::get_bytes_sha512(data)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+#[pyo3(signature = (data, salt = "", algorithm = "sha256", format = "hex", truncate = None))]
+fn get_bytes_sha_formatted(
+    data: &[u8],
+    salt: &str,
+    algorithm: &str,
+    format: &str,
+    truncate: Option<usize>,
+) -> PyResult<String> {
+    Ok(crate::sha_demo::sha_bytes_demo::get_bytes_sha_formatted(
+        data, salt, algorithm, format, truncate,
+    )?)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+#[pyo3(signature = (data, salt = "", algorithm = "sha256"))]
+fn get_bytes_sha_raw(data: &[u8], salt: &str, algorithm: &str) -> PyResult<Vec<u8>> {
+    Ok(crate::sha_demo::sha_bytes_demo::get_bytes_sha_raw(
+        data, salt, algorithm,
+    )?)
+}
+
+#[cfg(feature = "blake3")]
+#[pyfunction]
+fn get_bytes_blake3(data: &[u8]) -> String {
+    crate::sha_demo::parallel_hash::get_bytes_blake3(data)
+}
+
+#[cfg(feature = "sha")]
+#[pyfunction]
+fn get_bytes_sha_chunked(data: &[u8], algorithm: &str, chunk_size: usize) -> PyResult<String> {
+    Ok(crate::sha_demo::parallel_hash::get_bytes_sha_chunked(
+        data, algorithm, chunk_size,
+    )?)
+}
+
+#[pyfunction]
+fn hash_capabilities() -> String {
+    crate::sha_demo::parallel_hash::hash_capabilities()
+}
+
+#[cfg(feature = "recipe")]
+#[pyfunction]
+fn validate_recipe(recipe_json: &str) -> PyResult<bool> {
+    Ok(crate::pipeline::recipe::validate_recipe(recipe_json)?)
+}
+
+#[cfg(feature = "recipe")]
+#[pyfunction]
+fn run_recipe(recipe_json: &str, input: &[u8]) -> PyResult<Vec<u8>> {
+    Ok(crate::pipeline::recipe::run_recipe(recipe_json, input)?)
+}
+
+#[cfg(feature = "recipe")]
+#[pyfunction]
+fn run_recipe_reverse(recipe_json: &str, input: &[u8]) -> PyResult<Vec<u8>> {
+    Ok(crate::pipeline::recipe::run_recipe_reverse(
+        recipe_json,
+        input,
+    )?)
+}
+
+#[cfg(feature = "recipe")]
+#[pyfunction]
+fn run_recipe_str(recipe_json: &str, input: &str) -> PyResult<String> {
+    Ok(crate::pipeline::recipe::run_recipe_str(recipe_json, input)?)
+}
+
+#[cfg(feature = "recipe")]
+#[pyfunction]
+fn run_recipe_reverse_str(recipe_json: &str, input: &str) -> PyResult<String> {
+    Ok(crate::pipeline::recipe::run_recipe_reverse_str(
+        recipe_json,
+        input,
+    )?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+fn list_algorithms() -> String {
+    crate::algorithm::registry::list_algorithms()
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+#[pyo3(signature = (name, data, format = "hex"))]
+fn hash_by_name(name: &str, data: &[u8], format: &str) -> PyResult<String> {
+    Ok(crate::algorithm::registry::hash_by_name(
+        name, data, format,
+    )?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+fn verify_hmac_by_name(name: &str, key: &[u8], data: &[u8], tag: &[u8]) -> PyResult<bool> {
+    Ok(crate::algorithm::registry::verify_hmac_by_name(
+        name, key, data, tag,
+    )?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+fn encode_by_name(name: &str, data: &[u8]) -> PyResult<String> {
+    Ok(crate::algorithm::registry::encode_by_name(name, data)?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+fn decode_by_name(name: &str, encoded: &str) -> PyResult<Vec<u8>> {
+    Ok(crate::algorithm::registry::decode_by_name(name, encoded)?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+#[pyo3(signature = (name, plain, key, iv = b"".as_slice()))]
+fn encrypt_by_name(name: &str, plain: &[u8], key: &[u8], iv: &[u8]) -> PyResult<Vec<u8>> {
+    Ok(crate::algorithm::registry::encrypt_by_name(
+        name, plain, key, iv,
+    )?)
+}
+
+#[cfg(feature = "registry")]
+#[pyfunction]
+#[pyo3(signature = (name, cipher_text, key, iv = b"".as_slice()))]
+fn decrypt_by_name(name: &str, cipher_text: &[u8], key: &[u8], iv: &[u8]) -> PyResult<Vec<u8>> {
+    Ok(crate::algorithm::registry::decrypt_by_name(
+        name,
+        cipher_text,
+        key,
+        iv,
+    )?)
+}
+
+#[pymodule]
+fn rust_wasm(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("RustWasmError", py.get_type::<exceptions::RustWasmError>())?;
+    m.add("AesError", py.get_type::<exceptions::AesError>())?;
+    m.add("ShaHashError", py.get_type::<exceptions::ShaHashError>())?;
+    m.add(
+        "CustomBase64Error",
+        py.get_type::<exceptions::CustomBase64Error>(),
+    )?;
+    m.add("XorError", py.get_type::<exceptions::XorError>())?;
+    m.add("PipelineError", py.get_type::<exceptions::PipelineError>())?;
+    m.add(
+        "AlgorithmError",
+        py.get_type::<exceptions::AlgorithmError>(),
+    )?;
+
+    #[cfg(feature = "base64")]
+    m.add(
+        "HANS7_CUSTOM_ALPHABET",
+        crate::custom_base64::HANS7_CUSTOM_ALPHABET_STR
+            .reveal_str()
+            .as_str(),
+    )?;
+    #[cfg(feature = "sha")]
+    m.add(
+        "DEFAULT_STR_SALT",
+        crate::sha_demo::sha_demo::DEFAULT_STR_SALT
+            .reveal_str()
+            .as_str(),
+    )?;
+    #[cfg(feature = "sha")]
+    m.add(
+        "DEFAULT_BYTES_SALT",
+        crate::sha_demo::sha_bytes_demo::DEFAULT_BYTES_SALT
+            .reveal_str()
+            .as_str(),
+    )?;
+    #[cfg(feature = "xor")]
+    m.add("XOR_KEY", crate::xor_demo::XOR_KEY.reveal().as_slice())?;
+
+    #[cfg(feature = "xor")]
+    m.add_function(wrap_pyfunction!(encrypt_username, m)?)?;
+    #[cfg(feature = "xor")]
+    m.add_function(wrap_pyfunction!(decrypt_to_username, m)?)?;
+    #[cfg(feature = "aes")]
+    m.add_function(wrap_pyfunction!(aes_cbc_encrypt, m)?)?;
+    #[cfg(feature = "aes")]
+    m.add_function(wrap_pyfunction!(aes_cbc_decrypt, m)?)?;
+    #[cfg(feature = "base64")]
+    m.add_function(wrap_pyfunction!(encode_custom_base64, m)?)?;
+    #[cfg(feature = "base64")]
+    m.add_function(wrap_pyfunction!(decode_custom_base64, m)?)?;
+    #[cfg(feature = "base64")]
+    m.add_function(wrap_pyfunction!(encode_base64_with_alphabet, m)?)?;
+    #[cfg(feature = "base64")]
+    m.add_function(wrap_pyfunction!(decode_base64_with_alphabet, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha256_pure, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha512_pure, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha256_with_salt, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha512_with_salt, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha256, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha512, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha_formatted, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_str_sha_raw, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha256_pure, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha512_pure, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha256_with_salt, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha512_with_salt, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha256, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha512, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha_formatted, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha_raw, m)?)?;
+    #[cfg(feature = "blake3")]
+    m.add_function(wrap_pyfunction!(get_bytes_blake3, m)?)?;
+    #[cfg(feature = "sha")]
+    m.add_function(wrap_pyfunction!(get_bytes_sha_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(hash_capabilities, m)?)?;
+    #[cfg(feature = "recipe")]
+    m.add_function(wrap_pyfunction!(validate_recipe, m)?)?;
+    #[cfg(feature = "recipe")]
+    m.add_function(wrap_pyfunction!(run_recipe, m)?)?;
+    #[cfg(feature = "recipe")]
+    m.add_function(wrap_pyfunction!(run_recipe_reverse, m)?)?;
+    #[cfg(feature = "recipe")]
+    m.add_function(wrap_pyfunction!(run_recipe_str, m)?)?;
+    #[cfg(feature = "recipe")]
+    m.add_function(wrap_pyfunction!(run_recipe_reverse_str, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(list_algorithms, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(hash_by_name, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(verify_hmac_by_name, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(encode_by_name, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(decode_by_name, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(encrypt_by_name, m)?)?;
+    #[cfg(feature = "registry")]
+    m.add_function(wrap_pyfunction!(decrypt_by_name, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,284 @@
+// 密钥、IV 和明文的内存卫生。WASM 的线性内存可以被页面直接 dump ，释放后不清零的缓冲区
+// 会把明文和密钥留在内存里，因此：
+// - crate 内部存放明文 / 密钥的缓冲区一律用 Zeroizing 包装，drop 时清零；
+//   注意 Vec 扩容会直接释放旧缓冲区，这类缓冲区要一次性申请好容量
+// - wasm32 上（zeroize-alloc feature）使用释放前清零的全局分配器，覆盖 wasm-bindgen 胶水代码
+//   为 `&[u8]` / `&str` 参数申请的副本，以及返回给 JS 后释放的结果
+// - 校验类函数用 ct_eq 比较，耗时与第一个不同字节的位置无关
+// - 盐、XOR key 、自定义码表等常量用 sealed! 宏在编译时加密，wasm 数据段中没有明文，
+//   用到时才解密到 Zeroizing 缓冲区
+use std::alloc::{GlobalAlloc, Layout};
+
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+pub type SecretBytes = Zeroizing<Vec<u8>>;
+
+/// 常量时间比较。长度不同时直接返回 false ，长度本身不视为秘密
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+// 取出 Zeroizing 中的 Vec 作为返回值，包装里只剩下空 Vec
+#[cfg(any(feature = "aes", feature = "recipe"))]
+pub(crate) fn reveal(mut secret: SecretBytes) -> Vec<u8> {
+    std::mem::take(&mut *secret)
+}
+
+// 解密结果转 String 。与 String::from_utf8 相同不复制，但失败时先清零再丢弃原缓冲区
+#[cfg(any(feature = "aes", feature = "xor", feature = "recipe"))]
+pub(crate) fn secret_into_string(bytes: Vec<u8>) -> Result<String, std::str::Utf8Error> {
+    String::from_utf8(bytes).map_err(|e| {
+        let error = e.utf8_error();
+        e.into_bytes().zeroize();
+        error
+    })
+}
+
+/// sealed! 宏生成的加密常量，密钥流为 SplitMix64 ，与 macros/src/lib.rs 一致
+pub struct Sealed {
+    data: &'static [u8],
+    key: u64,
+}
+
+impl Sealed {
+    #[doc(hidden)]
+    pub const fn new(data: &'static [u8], key: u64) -> Self {
+        Sealed { data, key }
+    }
+
+    /// 解密。密文和密钥经过 black_box ，避免优化器在编译期算出明文写进代码里
+    pub fn reveal(&self) -> SecretBytes {
+        let data = std::hint::black_box(self.data);
+        let mut state = std::hint::black_box(self.key);
+        let mut plain = Zeroizing::new(Vec::with_capacity(data.len()));
+        for chunk in data.chunks(8) {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            let block = (z ^ (z >> 31)).to_le_bytes();
+            plain.extend(chunk.iter().zip(block).map(|(b, k)| b ^ k));
+        }
+        plain
+    }
+
+    /// 解密字符串字面量
+    pub fn reveal_str(&self) -> Zeroizing<String> {
+        let mut bytes = self.reveal();
+        let plain = String::from_utf8(std::mem::take(&mut *bytes))
+            .expect("sealed! string literals are valid UTF-8");
+        Zeroizing::new(plain)
+    }
+}
+
+impl std::fmt::Debug for Sealed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sealed({} bytes)", self.data.len())
+    }
+}
+
+/// 释放前把内存清零的分配器包装。`realloc` 使用默认实现（申请新块、复制、释放旧块），
+/// 旧块同样经过 `dealloc` 清零
+pub struct ZeroizingAlloc<A>(pub A);
+
+// SAFETY: 申请全部转发给内部分配器，dealloc 只在转发前写入调用方交回的这块内存
+unsafe impl<A: GlobalAlloc> GlobalAlloc for ZeroizingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { self.0.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        unsafe { self.0.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            std::slice::from_raw_parts_mut(ptr, layout.size()).zeroize();
+            self.0.dealloc(ptr, layout);
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "zeroize-alloc", not(test)))]
+#[global_allocator]
+static GLOBAL: ZeroizingAlloc<std::alloc::System> = ZeroizingAlloc(std::alloc::System);
+
+// 单元测试使用的全局分配器：释放内存时检查其中是否残留 KEY_CANARY / PLAIN_CANARY（共同前缀 CANARY_PREFIX），
+// 有残留说明对应的缓冲区没有清零。测试的输入只用字面量（不在堆上），输出用 Zeroizing 接收
+#[cfg(all(
+    test,
+    feature = "aes",
+    feature = "xor",
+    feature = "sha",
+    feature = "registry",
+    feature = "recipe"
+))]
+mod leak_check {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use zeroize::Zeroizing;
+
+    use crate::aes_cbc::{aes_cbc_decrypt, aes_cbc_encrypt};
+    use crate::algorithm::registry::{
+        decrypt_by_name, encrypt_by_name, registry, verify_hmac_by_name,
+    };
+    use crate::pipeline::recipe::run_recipe_reverse_str;
+    use crate::sha_demo::sha_bytes_demo::get_bytes_sha256_with_salt;
+    use crate::xor_demo::{decrypt_to_username, encrypt_username};
+
+    const CANARY_PREFIX: &[u8] = b"zeroize-canary-";
+    const KEY_CANARY: &[u8; 16] = b"zeroize-canary-K";
+    const PLAIN_CANARY: &str = "zeroize-canary-plaintext";
+
+    static LEAKS: AtomicUsize = AtomicUsize::new(0);
+
+    struct LeakCheckAlloc;
+
+    unsafe impl GlobalAlloc for LeakCheckAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let block = unsafe { std::slice::from_raw_parts(ptr, layout.size()) };
+            // 每次释放都要扫描，先按首字节过滤，避免拖慢其他测试
+            let leaked = (0..block.len())
+                .any(|i| block[i] == CANARY_PREFIX[0] && block[i..].starts_with(CANARY_PREFIX));
+            if leaked {
+                LEAKS.fetch_add(1, Ordering::Relaxed);
+            }
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: LeakCheckAlloc = LeakCheckAlloc;
+
+    fn leaks() -> usize {
+        LEAKS.load(Ordering::Relaxed)
+    }
+
+    // 走一遍处理明文和密钥的函数，释放的内存中不能残留明文或密钥
+    #[test]
+    fn secret_buffers_are_cleared_test() -> anyhow::Result<()> {
+        let iv = b"abcdef9876543210";
+        let before = leaks();
+
+        let cipher = aes_cbc_encrypt(PLAIN_CANARY, KEY_CANARY, iv)?;
+        let plain = Zeroizing::new(aes_cbc_decrypt(&cipher, KEY_CANARY, iv)?);
+        assert_eq!(plain.as_str(), PLAIN_CANARY);
+
+        let raw_cipher = encrypt_by_name("aes_cbc", PLAIN_CANARY.as_bytes(), KEY_CANARY, iv)?;
+        let plain = Zeroizing::new(decrypt_by_name("aes_cbc", &raw_cipher, KEY_CANARY, iv)?);
+        assert_eq!(plain.as_slice(), PLAIN_CANARY.as_bytes());
+        // 密钥错误时 PKCS7 校验失败，已解密的部分同样要清零
+        assert!(aes_cbc_decrypt(&cipher, b"1234567890123456", iv).is_err());
+
+        let xored = encrypt_username(PLAIN_CANARY);
+        let plain = Zeroizing::new(decrypt_to_username(&xored)?);
+        assert_eq!(plain.as_str(), PLAIN_CANARY);
+
+        let tag = registry()
+            .hasher("sha256")?
+            .hmac(KEY_CANARY, PLAIN_CANARY.as_bytes())
+            .into_bytes();
+        assert!(verify_hmac_by_name(
+            "sha256",
+            KEY_CANARY,
+            PLAIN_CANARY.as_bytes(),
+            &tag
+        )?);
+        get_bytes_sha256_with_salt(PLAIN_CANARY.as_bytes(), "salt");
+
+        let recipe = r#"{"steps": [{"op": "aes_cbc", "key": "zeroize-canary-K", "iv": "abcdef9876543210"}, {"op": "base64"}]}"#;
+        let plain = Zeroizing::new(run_recipe_reverse_str(recipe, &cipher)?);
+        assert_eq!(plain.as_str(), PLAIN_CANARY);
+
+        assert_eq!(leaks(), before);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::System;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn ct_eq_test() {
+        assert!(ct_eq(b"tag", b"tag"));
+        assert!(!ct_eq(b"tag", b"tah"));
+        assert!(!ct_eq(b"tag", b"tag!"));
+        assert!(ct_eq(b"", b""));
+    }
+
+    #[test]
+    fn sealed_test() {
+        static SALT: Sealed = rust_wasm_macros::sealed!("hans7_sha_bytes");
+        static KEY: Sealed = rust_wasm_macros::sealed!(b"hctf");
+        static EMPTY: Sealed = rust_wasm_macros::sealed!("");
+        assert_eq!(SALT.reveal_str().as_str(), "hans7_sha_bytes");
+        assert_eq!(KEY.reveal().as_slice(), b"hctf");
+        assert!(EMPTY.reveal().is_empty());
+        assert_eq!(format!("{KEY:?}"), "Sealed(4 bytes)");
+        // 密文中看不出明文，相同前缀的字面量密钥不同
+        assert_ne!(SALT.data, b"hans7_sha_bytes");
+        assert!(!SALT.data.windows(5).any(|w| w == b"hans7"));
+        static PREFIX: Sealed = rust_wasm_macros::sealed!("hans7");
+        assert_ne!(PREFIX.key, SALT.key);
+        assert_ne!(PREFIX.data, &SALT.data[..5]);
+    }
+
+    #[cfg(any(feature = "aes", feature = "xor", feature = "recipe"))]
+    #[test]
+    fn secret_into_string_test() {
+        assert_eq!(secret_into_string(b"ok".to_vec()).unwrap(), "ok");
+        assert_eq!(
+            secret_into_string(vec![b'a', 0xff])
+                .unwrap_err()
+                .valid_up_to(),
+            1
+        );
+    }
+
+    // 内部分配器在 dealloc 时检查收到的内存是否已经全部清零
+    struct AssertZeroed(AtomicBool);
+
+    unsafe impl GlobalAlloc for AssertZeroed {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let block = unsafe { std::slice::from_raw_parts(ptr, layout.size()) };
+            self.0
+                .store(block.iter().all(|&b| b == 0), Ordering::Relaxed);
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn zeroizing_alloc_clears_on_dealloc_test() {
+        let alloc = ZeroizingAlloc(AssertZeroed(AtomicBool::new(false)));
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            ptr.write_bytes(0xAA, layout.size());
+            alloc.dealloc(ptr, layout);
+        }
+        assert!(alloc.0.0.load(Ordering::Relaxed));
+
+        // realloc 搬迁后旧块也要清零
+        alloc.0.0.store(false, Ordering::Relaxed);
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            ptr.write_bytes(0xAA, layout.size());
+            let ptr = alloc.realloc(ptr, layout, 4096);
+            assert!(alloc.0.0.load(Ordering::Relaxed));
+            alloc.dealloc(ptr, Layout::from_size_align(4096, 8).unwrap());
+        }
+    }
+}
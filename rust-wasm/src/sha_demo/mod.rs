@@ -1,4 +1,10 @@
+// utils 和 sha_demo_error 不依赖 sha2 ，HashOutput / HashFormat 也被 registry 使用，因此始终编译
+#[cfg(feature = "sha")]
 pub mod sha_bytes_demo;
+#[cfg(feature = "sha")]
+#[allow(clippy::module_inception)]
 pub mod sha_demo;
+// BLAKE3 / 分块哈希以及 hash_capabilities ，内部按 feature 裁剪
+pub mod parallel_hash;
 pub mod sha_demo_error;
 pub mod utils;
@@ -0,0 +1,213 @@
+// 大文件哈希的加速路径：BLAKE3（树结构，可并行）和按固定大小分块的 SHA 。三种构建共用这份代码：
+// - 标量（默认）：全部顺序计算
+// - simd：BLAKE3 使用 wasm SIMD 实现（simd feature + target-feature=+simd128）
+// - threads：线程池就绪后用 rayon 并行，wasm 上由 initThreadPool 启动 Web Worker
+// 前端 worker 通过 hash_capabilities 判断加载的是哪种构建，从而选择最快的路径
+#[cfg(feature = "threads")]
+use rayon::prelude::*;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "sha")]
+use super::sha_bytes_demo::{compute_sha256_bytes, compute_sha512_bytes};
+#[cfg(feature = "sha")]
+use super::sha_demo_error::ShaHashError;
+#[cfg(any(feature = "sha", feature = "blake3"))]
+use super::utils::HashOutput;
+#[cfg(feature = "sha")]
+use super::utils::ShaAlgorithm;
+
+// BLAKE3 官方建议：输入小于 128 KiB 时并行没有收益
+#[cfg(all(feature = "blake3", feature = "threads"))]
+const BLAKE3_PARALLEL_THRESHOLD: usize = 128 * 1024;
+
+// native 上 rayon 会自动创建全局线程池；wasm 上要等 initThreadPool 完成，之前一律走顺序计算
+#[cfg(feature = "threads")]
+pub(crate) fn thread_pool_ready() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::thread_pool::is_ready()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        true
+    }
+}
+
+#[cfg(feature = "blake3")]
+pub fn compute_blake3_bytes(data: &[u8]) -> HashOutput {
+    #[cfg(feature = "threads")]
+    if data.len() >= BLAKE3_PARALLEL_THRESHOLD && thread_pool_ready() {
+        let hash = blake3::Hasher::new().update_rayon(data).finalize();
+        return HashOutput {
+            bytes: hash.as_bytes().to_vec(),
+        };
+    }
+    HashOutput {
+        bytes: blake3::hash(data).as_bytes().to_vec(),
+    }
+}
+
+#[cfg(feature = "blake3")]
+#[wasm_bindgen]
+pub fn get_bytes_blake3(data: &[u8]) -> String {
+    compute_blake3_bytes(data).to_hex()
+}
+
+#[cfg(feature = "sha")]
+fn hash_chunks(data: &[u8], chunk_size: usize, hash: fn(&[u8]) -> HashOutput) -> Vec<HashOutput> {
+    #[cfg(feature = "threads")]
+    if thread_pool_ready() {
+        return data.par_chunks(chunk_size).map(hash).collect();
+    }
+    data.chunks(chunk_size).map(hash).collect()
+}
+
+/// 分块哈希：`H(le64(chunk_size) || H(chunk_0) || H(chunk_1) || ...)`
+///
+/// 各块互不依赖，threads 构建中并行计算，结果只取决于 chunk_size 。
+/// 它与直接对整个输入求 SHA 的结果不同，校验方必须用同样的 chunk_size 计算
+#[cfg(feature = "sha")]
+pub fn compute_sha_chunked_bytes(
+    algorithm: ShaAlgorithm,
+    data: &[u8],
+    chunk_size: usize,
+) -> Result<HashOutput, ShaHashError> {
+    if chunk_size == 0 {
+        return Err(ShaHashError::InvalidInput(
+            "chunk_size must be greater than 0".into(),
+        ));
+    }
+    let hash: fn(&[u8]) -> HashOutput = match algorithm {
+        ShaAlgorithm::Sha256 => compute_sha256_bytes,
+        ShaAlgorithm::Sha512 => compute_sha512_bytes,
+    };
+    let mut combined = (chunk_size as u64).to_le_bytes().to_vec();
+    for digest in hash_chunks(data, chunk_size, hash) {
+        combined.extend_from_slice(digest.as_bytes());
+    }
+    Ok(hash(&combined))
+}
+
+#[cfg(feature = "sha")]
+#[wasm_bindgen]
+pub fn get_bytes_sha_chunked(
+    data: &[u8],
+    algorithm: &str,
+    chunk_size: usize,
+) -> Result<String, ShaHashError> {
+    let algorithm: ShaAlgorithm = algorithm.parse()?;
+    Ok(compute_sha_chunked_bytes(algorithm, data, chunk_size)?.to_hex())
+}
+
+#[derive(Serialize)]
+struct HashCapabilities {
+    // 编译时是否开启了 simd128 。开启了也只有 BLAKE3 有 SIMD 实现，SHA 系列仍是标量代码
+    simd128: bool,
+    // 实际使用 SIMD 实现的哈希算法，前端据此说明 simd 构建加速了什么
+    simd_accelerated: Vec<&'static str>,
+    // 是否为支持多线程的构建（native 上开启 threads 即可，wasm 上还需要 atomics）
+    threads: bool,
+    // 当前可用的线程数，线程池未就绪时为 1
+    thread_pool_size: usize,
+    blake3: bool,
+    sha_chunked: bool,
+}
+
+/// 以 JSON 形式返回当前构建的加速能力，前端 worker 据此选择哈希路径
+#[wasm_bindgen]
+pub fn hash_capabilities() -> String {
+    let threads = cfg!(feature = "threads")
+        && (cfg!(not(target_arch = "wasm32")) || cfg!(target_feature = "atomics"));
+    #[cfg(feature = "threads")]
+    let thread_pool_size = if thread_pool_ready() {
+        rayon::current_num_threads()
+    } else {
+        1
+    };
+    #[cfg(not(feature = "threads"))]
+    let thread_pool_size = 1;
+    let mut simd_accelerated = Vec::new();
+    if cfg!(all(
+        feature = "blake3",
+        feature = "simd",
+        target_arch = "wasm32",
+        target_feature = "simd128"
+    )) {
+        simd_accelerated.push("blake3");
+    }
+    let capabilities = HashCapabilities {
+        simd128: cfg!(target_feature = "simd128"),
+        simd_accelerated,
+        threads,
+        thread_pool_size,
+        blake3: cfg!(feature = "blake3"),
+        sha_chunked: cfg!(feature = "sha"),
+    };
+    serde_json::to_string(&capabilities).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_known_answer_test() {
+        assert_eq!(
+            get_bytes_blake3(b""),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(
+            get_bytes_blake3(b"abc"),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        // 超过并行阈值的输入，threads 构建走 update_rayon ，结果必须与顺序计算一致
+        let large: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+        assert_eq!(
+            compute_blake3_bytes(&large).as_bytes(),
+            blake3::hash(&large).as_bytes()
+        );
+    }
+
+    #[cfg(feature = "sha")]
+    #[test]
+    fn sha_chunked_matches_definition_test() -> anyhow::Result<()> {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i * 7) as u8).collect();
+        let chunk_size = 4096;
+
+        let mut expected = (chunk_size as u64).to_le_bytes().to_vec();
+        for chunk in data.chunks(chunk_size) {
+            expected.extend_from_slice(compute_sha256_bytes(chunk).as_bytes());
+        }
+        assert_eq!(
+            get_bytes_sha_chunked(&data, "sha256", chunk_size)?,
+            compute_sha256_bytes(&expected).to_hex()
+        );
+
+        // chunk_size 参与计算，不同分块大小结果不同；空输入只有长度前缀
+        assert_ne!(
+            get_bytes_sha_chunked(&data, "sha256", chunk_size)?,
+            get_bytes_sha_chunked(&data, "sha256", 1024)?
+        );
+        assert_eq!(
+            get_bytes_sha_chunked(b"", "sha512", 64)?,
+            compute_sha512_bytes(&64u64.to_le_bytes()).to_hex()
+        );
+
+        assert!(get_bytes_sha_chunked(&data, "sha256", 0).is_err());
+        assert!(get_bytes_sha_chunked(&data, "md5", 1024).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn hash_capabilities_test() -> anyhow::Result<()> {
+        let capabilities: serde_json::Value = serde_json::from_str(&hash_capabilities())?;
+        assert_eq!(capabilities["simd128"], false);
+        assert_eq!(capabilities["simd_accelerated"], serde_json::json!([]));
+        assert_eq!(capabilities["threads"], cfg!(feature = "threads"));
+        assert_eq!(capabilities["blake3"], cfg!(feature = "blake3"));
+        assert!(capabilities["thread_pool_size"].as_u64().unwrap() >= 1);
+        Ok(())
+    }
+}
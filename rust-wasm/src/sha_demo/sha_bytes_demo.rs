@@ -1,7 +1,13 @@
+use super::sha_demo_error::ShaHashError;
 use super::utils::*;
+use crate::secret::Sealed;
+use rust_wasm_macros::sealed;
 use sha2::{Digest, Sha256, Sha512};
 use wasm_bindgen::prelude::*;
 
+// get_bytes_sha256 / get_bytes_sha512 使用的默认盐
+pub static DEFAULT_BYTES_SALT: Sealed = sealed!("hans7_sha_bytes");
+
 pub fn compute_sha256_bytes(data: &[u8]) -> HashOutput {
     let mut hasher = Sha256::new();
     hasher.update(data);
@@ -30,17 +36,6 @@ pub fn get_bytes_sha512_pure(data: &[u8]) -> String {
     compute_sha512_bytes(data).to_hex()
 }
 
-fn get_salted_bytes(data: &[u8], salt: &str) -> Vec<u8> {
-    let salt_bytes = salt.as_bytes();
-    let mut combined = Vec::with_capacity(data.len() + salt_bytes.len() + 1); // +1 for separator
-    combined.extend_from_slice(data);
-    if !salt.is_empty() {
-        combined.push(b'_'); // separator
-        combined.extend_from_slice(salt_bytes);
-    }
-    combined
-}
-
 #[wasm_bindgen]
 pub fn get_bytes_sha256_with_salt(data: &[u8], salt: &str) -> String {
     let combined = get_salted_bytes(data, salt);
@@ -55,12 +50,46 @@ pub fn get_bytes_sha512_with_salt(data: &[u8], salt: &str) -> String {
 
 #[wasm_bindgen]
 pub fn get_bytes_sha256(data: &[u8]) -> String {
-    get_bytes_sha256_with_salt(data, "hans7_sha_bytes")
+    get_bytes_sha256_with_salt(data, &DEFAULT_BYTES_SALT.reveal_str())
 }
 
 #[wasm_bindgen]
 pub fn get_bytes_sha512(data: &[u8]) -> String {
-    get_bytes_sha512_with_salt(data, "hans7_sha_bytes")
+    get_bytes_sha512_with_salt(data, &DEFAULT_BYTES_SALT.reveal_str())
+}
+
+fn compute_bytes_sha(data: &[u8], salt: &str, algorithm: &str) -> Result<HashOutput, ShaHashError> {
+    let algorithm: ShaAlgorithm = algorithm.parse()?;
+    let combined = get_salted_bytes(data, salt);
+    Ok(match algorithm {
+        ShaAlgorithm::Sha256 => compute_sha256_bytes(&combined),
+        ShaAlgorithm::Sha512 => compute_sha512_bytes(&combined),
+    })
+}
+
+/// 按指定算法和输出格式计算字节哈希，参数含义同 `get_str_sha_formatted`
+#[wasm_bindgen]
+pub fn get_bytes_sha_formatted(
+    data: &[u8],
+    salt: &str,
+    algorithm: &str,
+    format: &str,
+    truncate: Option<usize>,
+) -> Result<String, ShaHashError> {
+    let format: HashFormat = format.parse()?;
+    let output = compute_bytes_sha(data, salt, algorithm)?;
+    Ok(output.format_truncated(format, truncate))
+}
+
+/// 返回原始摘要字节，JS 侧得到 `Uint8Array`
+#[wasm_bindgen]
+pub fn get_bytes_sha_raw(
+    data: &[u8],
+    salt: &str,
+    algorithm: &str,
+) -> Result<Vec<u8>, ShaHashError> {
+    let output = compute_bytes_sha(data, salt, algorithm)?;
+    Ok(output.into_bytes())
 }
 
 #[cfg(test)]
@@ -223,4 +252,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn bytes_sha_formatted_test() -> anyhow::Result<()> {
+        let data = b"hello world";
+        let upper = get_bytes_sha_formatted(data, "", "sha256", "hex_upper", None)?;
+        assert_eq!(upper, get_bytes_sha256_pure(data).to_uppercase());
+        let b64 = get_bytes_sha_formatted(data, "", "sha256", "base64", None)?;
+        assert_eq!(b64, "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=");
+        let short = get_bytes_sha_formatted(data, "hans7_sha_bytes", "sha512", "hex", Some(16))?;
+        assert_eq!(short, &get_bytes_sha512(data)[..16]);
+
+        let raw = get_bytes_sha_raw(data, "hans666", "sha512")?;
+        assert_eq!(
+            HashOutput { bytes: raw }.to_hex(),
+            get_bytes_sha512_with_salt(data, "hans666")
+        );
+        // 与字符串版本不同，空输入是合法的
+        assert_eq!(get_bytes_sha_raw(&[], "", "sha256")?.len(), 32);
+
+        assert!(get_bytes_sha_formatted(data, "", "sha1", "hex", None).is_err());
+        assert!(get_bytes_sha_formatted(data, "", "sha256", "oct", None).is_err());
+
+        Ok(())
+    }
 }
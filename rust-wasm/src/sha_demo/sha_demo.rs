@@ -1,11 +1,17 @@
 use super::sha_demo_error::ShaHashError;
 use super::utils::*;
+use crate::secret::Sealed;
+use rust_wasm_macros::sealed;
 use sha2::{Digest, Sha256, Sha512};
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+// get_str_sha256 / get_str_sha512 使用的默认盐
+pub static DEFAULT_STR_SALT: Sealed = sealed!("hans7");
 
 pub fn compute_sha256(input: InputStr) -> Result<HashOutput, ShaHashError> {
     let mut hasher = Sha256::new();
-    hasher.update(input.s.as_bytes());
+    hasher.update(input.as_str().as_bytes());
 
     let result = hasher.finalize();
     let hash_bytes = result.to_vec();
@@ -15,7 +21,7 @@ pub fn compute_sha256(input: InputStr) -> Result<HashOutput, ShaHashError> {
 
 pub fn compute_sha512(input: InputStr) -> Result<HashOutput, ShaHashError> {
     let mut hasher = Sha512::new();
-    hasher.update(input.s.as_bytes());
+    hasher.update(input.as_str().as_bytes());
 
     let result = hasher.finalize();
     let hash_bytes = result.to_vec();
@@ -37,11 +43,16 @@ pub fn get_str_sha512_pure(input: &str) -> Result<String, ShaHashError> {
     Ok(output.to_hex())
 }
 
-fn get_salted_str(input: &str, salt: &str) -> String {
-    if salt.trim().is_empty() {
-        return input.trim().to_string();
+// 与 get_salted_bytes 相同，一次申请好容量，drop 时清零
+fn get_salted_str(input: &str, salt: &str) -> Zeroizing<String> {
+    let (input, salt) = (input.trim(), salt.trim());
+    let mut combined = Zeroizing::new(String::with_capacity(input.len() + salt.len() + 1));
+    combined.push_str(input);
+    if !salt.is_empty() {
+        combined.push('_');
+        combined.push_str(salt);
     }
-    format!("{}_{}", input.trim(), salt.trim())
+    combined
 }
 
 #[wasm_bindgen]
@@ -62,12 +73,44 @@ pub fn get_str_sha512_with_salt(input: &str, salt: &str) -> Result<String, ShaHa
 
 #[wasm_bindgen]
 pub fn get_str_sha256(input: &str) -> Result<String, ShaHashError> {
-    get_str_sha256_with_salt(input, "hans7")
+    get_str_sha256_with_salt(input, &DEFAULT_STR_SALT.reveal_str())
 }
 
 #[wasm_bindgen]
 pub fn get_str_sha512(input: &str) -> Result<String, ShaHashError> {
-    get_str_sha512_with_salt(input, "hans7")
+    get_str_sha512_with_salt(input, &DEFAULT_STR_SALT.reveal_str())
+}
+
+fn compute_str_sha(input: &str, salt: &str, algorithm: &str) -> Result<HashOutput, ShaHashError> {
+    let algorithm: ShaAlgorithm = algorithm.parse()?;
+    let combined = get_salted_str(input, salt);
+    let inp_combined = InputStr::new(&combined)?;
+    match algorithm {
+        ShaAlgorithm::Sha256 => compute_sha256(inp_combined),
+        ShaAlgorithm::Sha512 => compute_sha512(inp_combined),
+    }
+}
+
+/// 按指定算法和输出格式计算字符串哈希。salt 为空串时等价于 `*_pure` ，
+/// format 支持 hex / hex_upper / base64 / base64url ，truncate 为保留的字符数
+#[wasm_bindgen]
+pub fn get_str_sha_formatted(
+    input: &str,
+    salt: &str,
+    algorithm: &str,
+    format: &str,
+    truncate: Option<usize>,
+) -> Result<String, ShaHashError> {
+    let format: HashFormat = format.parse()?;
+    let output = compute_str_sha(input, salt, algorithm)?;
+    Ok(output.format_truncated(format, truncate))
+}
+
+/// 返回原始摘要字节，JS 侧得到 `Uint8Array`
+#[wasm_bindgen]
+pub fn get_str_sha_raw(input: &str, salt: &str, algorithm: &str) -> Result<Vec<u8>, ShaHashError> {
+    let output = compute_str_sha(input, salt, algorithm)?;
+    Ok(output.into_bytes())
 }
 
 #[cfg(test)]
@@ -277,6 +320,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn str_sha_formatted_test() -> anyhow::Result<()> {
+        let hex = get_str_sha_formatted("hello world", "", "sha256", "hex", None)?;
+        assert_eq!(hex, get_str_sha256_pure("hello world")?);
+        let upper = get_str_sha_formatted("hello world", "", "sha256", "hex_upper", None)?;
+        assert_eq!(
+            upper,
+            "B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9"
+        );
+        let b64 = get_str_sha_formatted("hello world", "", "sha256", "base64", None)?;
+        assert_eq!(b64, "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=");
+        let b64url = get_str_sha_formatted("hello world", "", "sha256", "base64url", None)?;
+        assert_eq!(b64url, "uU0nuZNNPgilLlLX2n2r-sSE7-N6U4DukIj3rOLvzek");
+        let short = get_str_sha_formatted("hello world", "", "sha256", "hex", Some(16))?;
+        assert_eq!(short, "b94d27b9934d3e08");
+
+        let salted = get_str_sha_formatted("hello world", "hans7", "sha512", "hex", None)?;
+        assert_eq!(salted, get_str_sha512("hello world")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn str_sha_raw_test() -> anyhow::Result<()> {
+        let raw = get_str_sha_raw("hello world", "hans666", "sha256")?;
+        assert_eq!(raw.len(), 32);
+        assert_eq!(
+            HashOutput { bytes: raw }.to_hex(),
+            get_str_sha256_with_salt("hello world", "hans666")?
+        );
+        assert_eq!(get_str_sha_raw("hello world", "", "sha512")?.len(), 64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn str_sha_formatted_invalid_args_test() {
+        assert!(get_str_sha_formatted("hello", "", "md5", "hex", None).is_err());
+        assert!(get_str_sha_formatted("hello", "", "sha256", "base32", None).is_err());
+        assert!(get_str_sha_formatted("  ", "", "sha256", "hex", None).is_err());
+        assert!(get_str_sha_raw("", "", "sha256").is_err());
+    }
+
     #[test]
     fn empty_input_test() {
         // 1. 完全空串
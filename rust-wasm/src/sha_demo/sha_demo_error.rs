@@ -1,16 +1,45 @@
 use thiserror::Error;
 use wasm_bindgen::prelude::*;
 
+use crate::error::{ErrorCode, RustWasmError};
+
 #[derive(Error, Debug)]
 pub enum ShaHashError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
     #[error("Sha Hash computation failed")]
     HashFailed,
+    #[error("Unsupported hash algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("Unsupported output format: {0}")]
+    UnsupportedFormat(String),
+}
+
+impl From<ShaHashError> for RustWasmError {
+    fn from(e: ShaHashError) -> Self {
+        let message = e.to_string();
+        match e {
+            ShaHashError::InvalidInput(reason) => {
+                RustWasmError::new(ErrorCode::InvalidInput, "sha_demo", message)
+                    .with_detail("reason", reason)
+            }
+            ShaHashError::HashFailed => {
+                RustWasmError::new(ErrorCode::HashFailed, "sha_demo", message)
+            }
+            ShaHashError::UnsupportedAlgorithm(name) => {
+                RustWasmError::new(ErrorCode::UnsupportedAlgorithm, "sha_demo", message)
+                    .with_detail("algorithm", name)
+            }
+            ShaHashError::UnsupportedFormat(name) => {
+                RustWasmError::new(ErrorCode::UnsupportedFormat, "sha_demo", message)
+                    .with_detail("format", name)
+            }
+        }
+    }
 }
 
 impl From<ShaHashError> for JsValue {
     fn from(e: ShaHashError) -> Self {
-        JsValue::from_str(&e.to_string())
+        RustWasmError::from(e).into()
     }
 }
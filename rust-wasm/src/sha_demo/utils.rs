@@ -1,12 +1,18 @@
 use super::sha_demo_error::ShaHashError;
+use base64::prelude::*;
 use std::fmt;
+use std::str::FromStr;
+#[cfg(any(feature = "sha", feature = "recipe"))]
+use zeroize::Zeroizing;
 
 // 封装输入字符串，可添加各种守卫，避免泛型生命周期泛滥
+#[cfg(feature = "sha")]
 #[derive(Debug)]
 pub struct InputStr<'a> {
     pub s: &'a str,
 }
 
+#[cfg(feature = "sha")]
 impl<'a> InputStr<'a> {
     pub fn new(inp: &'a str) -> Result<Self, ShaHashError> {
         let s = inp.trim();
@@ -21,6 +27,62 @@ impl<'a> InputStr<'a> {
     }
 }
 
+// 拼接结果含有输入的明文，drop 时清零。sha_bytes_demo 和 recipe 的哈希步骤共用
+#[cfg(any(feature = "sha", feature = "recipe"))]
+pub(crate) fn get_salted_bytes(data: &[u8], salt: &str) -> Zeroizing<Vec<u8>> {
+    let salt_bytes = salt.as_bytes();
+    let mut combined = Vec::with_capacity(data.len() + salt_bytes.len() + 1); // +1 for separator
+    combined.extend_from_slice(data);
+    if !salt.is_empty() {
+        combined.push(b'_'); // separator
+        combined.extend_from_slice(salt_bytes);
+    }
+    Zeroizing::new(combined)
+}
+
+// 支持的 SHA 算法，供“选择算法”的导出函数使用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl FromStr for ShaAlgorithm {
+    type Err = ShaHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(Self::Sha256),
+            "sha512" | "sha-512" => Ok(Self::Sha512),
+            other => Err(ShaHashError::UnsupportedAlgorithm(other.to_string())),
+        }
+    }
+}
+
+// 哈希结果的文本格式。逆向目标常把哈希做 base64 、大写或截断后再发送
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashFormat {
+    Hex,
+    HexUpper,
+    Base64,
+    // URL 安全码表，不带 padding（与 JWT 等场景一致）
+    Base64Url,
+}
+
+impl FromStr for HashFormat {
+    type Err = ShaHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "hex" => Ok(Self::Hex),
+            "hex_upper" | "hexupper" | "upper" => Ok(Self::HexUpper),
+            "base64" | "b64" => Ok(Self::Base64),
+            "base64url" | "base64_url" | "b64url" => Ok(Self::Base64Url),
+            other => Err(ShaHashError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
 // 封装哈希结果，便于统一处理和格式化
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HashOutput {
@@ -32,9 +94,52 @@ impl HashOutput {
         self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
     }
 
+    pub fn to_hex_upper(&self) -> String {
+        self.bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    pub fn to_base64(&self) -> String {
+        BASE64_STANDARD.encode(&self.bytes)
+    }
+
+    pub fn to_base64url(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(&self.bytes)
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    // 保留摘要的前 len 个字节，例如 HMAC 截断后的 tag ；超过摘要长度时原样返回。
+    // 按字符截断格式化后的文本用 format_truncated
+    pub fn truncated(&self, len: usize) -> HashOutput {
+        let len = len.min(self.bytes.len());
+        HashOutput {
+            bytes: self.bytes[..len].to_vec(),
+        }
+    }
+
+    pub fn format(&self, format: HashFormat) -> String {
+        match format {
+            HashFormat::Hex => self.to_hex(),
+            HashFormat::HexUpper => self.to_hex_upper(),
+            HashFormat::Base64 => self.to_base64(),
+            HashFormat::Base64Url => self.to_base64url(),
+        }
+    }
+
+    // 按字符截断格式化后的文本，例如“取 hex 的前 16 位”。None 表示不截断
+    pub fn format_truncated(&self, format: HashFormat, max_chars: Option<usize>) -> String {
+        let text = self.format(format);
+        match max_chars {
+            Some(n) => text.chars().take(n).collect(),
+            None => text,
+        }
+    }
 }
 
 impl fmt::Display for HashOutput {
@@ -42,3 +147,73 @@ impl fmt::Display for HashOutput {
         write!(f, "{}", self.to_hex())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> HashOutput {
+        HashOutput {
+            bytes: vec![0xde, 0xad, 0xbe, 0xef, 0xfb, 0xff],
+        }
+    }
+
+    #[test]
+    fn hash_output_formats_test() {
+        let out = sample();
+        assert_eq!(out.to_hex(), "deadbeeffbff");
+        assert_eq!(out.to_hex_upper(), "DEADBEEFFBFF");
+        assert_eq!(out.to_base64(), "3q2+7/v/");
+        assert_eq!(out.to_base64url(), "3q2-7_v_");
+        assert_eq!(out.format(HashFormat::HexUpper), out.to_hex_upper());
+        assert_eq!(out.as_bytes(), &[0xde, 0xad, 0xbe, 0xef, 0xfb, 0xff]);
+    }
+
+    #[test]
+    fn hash_output_truncation_test() {
+        let out = sample();
+        assert_eq!(out.truncated(2).to_hex(), "dead");
+        assert_eq!(out.truncated(5).to_hex(), "deadbeeffb");
+        assert_eq!(out.truncated(0).as_bytes(), &[] as &[u8]);
+        assert_eq!(out.truncated(100), out);
+        // truncated 按字节，format_truncated 按格式化后的字符
+        for n in 0..=out.as_bytes().len() {
+            assert_eq!(
+                out.truncated(n).to_hex(),
+                out.format_truncated(HashFormat::Hex, Some(n * 2))
+            );
+        }
+        assert_eq!(out.format_truncated(HashFormat::Hex, Some(4)), "dead");
+        assert_eq!(out.format_truncated(HashFormat::Hex, Some(5)), "deadb");
+        assert_eq!(out.format_truncated(HashFormat::Base64, Some(5)), "3q2+7");
+        assert_eq!(
+            out.format_truncated(HashFormat::Hex, Some(100)),
+            "deadbeeffbff"
+        );
+        assert_eq!(out.format_truncated(HashFormat::Hex, None), "deadbeeffbff");
+        assert_eq!(out.format_truncated(HashFormat::Hex, Some(0)), "");
+    }
+
+    #[test]
+    fn parse_format_and_algorithm_test() {
+        assert_eq!("HEX".parse::<HashFormat>().ok(), Some(HashFormat::Hex));
+        assert_eq!(
+            "hex_upper".parse::<HashFormat>().ok(),
+            Some(HashFormat::HexUpper)
+        );
+        assert_eq!(
+            " base64url ".parse::<HashFormat>().ok(),
+            Some(HashFormat::Base64Url)
+        );
+        assert!("base32".parse::<HashFormat>().is_err());
+        assert_eq!(
+            "SHA-256".parse::<ShaAlgorithm>().ok(),
+            Some(ShaAlgorithm::Sha256)
+        );
+        assert_eq!(
+            "sha512".parse::<ShaAlgorithm>().ok(),
+            Some(ShaAlgorithm::Sha512)
+        );
+        assert!("md5".parse::<ShaAlgorithm>().is_err());
+    }
+}
@@ -0,0 +1,92 @@
+// wasm 上的 rayon 线程池：每个 rayon 线程是一个 Web Worker ，共享同一块线性内存（SharedArrayBuffer）。
+// 做法与 wasm-bindgen-rayon 相同：initThreadPool 启动 worker ，每个 worker 用同一个 module 和 memory
+// 初始化 wasm ，然后在 rw_thread_pool_start_worker 中领取 rayon 的 ThreadBuilder 并一直运行。
+// 注意：
+// - 只有 atomics 构建（scripts/build-wasm-variants.sh 的 threads 变体）才会编译这个模块
+// - 页面需要 COOP / COEP 响应头（crossOriginIsolated）才能使用 SharedArrayBuffer
+// - 主线程不允许 Atomics.wait ，并行函数只能在 Worker 中调用
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+
+use rayon::{ThreadBuilder, ThreadPoolBuilder};
+use wasm_bindgen::prelude::*;
+
+static READY: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn is_ready() -> bool {
+    READY.load(Ordering::Acquire)
+}
+
+// 交给 js/threadPool.js 持有：worker 全部就绪后调用 build ，把 ThreadBuilder 逐个发给等待中的 worker
+#[wasm_bindgen]
+pub struct WorkerPoolBuilder {
+    num_threads: usize,
+    sender: SyncSender<ThreadBuilder>,
+    receiver: &'static Mutex<Receiver<ThreadBuilder>>,
+}
+
+#[wasm_bindgen]
+impl WorkerPoolBuilder {
+    #[wasm_bindgen(js_name = numThreads)]
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    // 线性内存中的地址，worker 共享同一块内存，因此可以直接传指针
+    pub fn receiver(&self) -> usize {
+        self.receiver as *const _ as usize
+    }
+
+    pub fn build(&mut self) -> Result<(), JsError> {
+        let sender = self.sender.clone();
+        ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .spawn_handler(move |thread| {
+                sender
+                    .send(thread)
+                    .map_err(|_| std::io::Error::other("thread pool receiver dropped"))
+            })
+            .build_global()
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        READY.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(module = "/js/threadPool.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = startWorkers)]
+    fn start_workers(
+        module: JsValue,
+        memory: JsValue,
+        builder: WorkerPoolBuilder,
+    ) -> js_sys::Promise;
+}
+
+/// 启动 num_threads 个 Web Worker 作为 rayon 的全局线程池，
+/// 返回的 Promise 完成后 BLAKE3 和分块哈希才会走并行路径，之前一律顺序计算
+#[wasm_bindgen(js_name = initThreadPool)]
+pub fn init_thread_pool(num_threads: usize) -> js_sys::Promise {
+    let (sender, receiver) = sync_channel(num_threads);
+    // 所有 worker 都要通过指针访问，只初始化一次，直接泄漏
+    let receiver = Box::leak(Box::new(Mutex::new(receiver)));
+    start_workers(
+        wasm_bindgen::module(),
+        wasm_bindgen::memory(),
+        WorkerPoolBuilder {
+            num_threads,
+            sender,
+            receiver,
+        },
+    )
+}
+
+/// 由 js/threadPool.js 在 worker 中调用，领取一个 ThreadBuilder 后不再返回
+#[wasm_bindgen]
+pub fn rw_thread_pool_start_worker(receiver: usize) {
+    // SAFETY: receiver 来自 WorkerPoolBuilder::receiver ，指向泄漏的 Mutex ，永不释放
+    let receiver = unsafe { &*(receiver as *const Mutex<Receiver<ThreadBuilder>>) };
+    let thread = receiver.lock().unwrap_throw().recv().unwrap_throw();
+    thread.run();
+}
@@ -0,0 +1,74 @@
+//! 独立 WASM 模块（wasm32-wasip1）的内存管理导出，不依赖 wasm-bindgen 的 JS 胶水代码，
+//! wasmtime / wasmer / wazero 等宿主可以直接加载。构建：`cargo build-wasi`（见 .cargo/config.toml）
+//!
+//! 算法函数复用 `capi` 模块的 `rw_*` 导出，指针都指向线性内存。宿主的典型调用流程：
+//! 1. `rw_alloc(len)` 申请输入缓冲区并写入数据，C 字符串参数要带结尾的 NUL
+//! 2. `rw_alloc(4)` 申请 out_len（wasm32 上 size_t 是 u32） ，写入 0 后调用 `rw_xxx(..., NULL, out_len)` 查询输出长度
+//! 3. 按返回的长度（文本输出再加 1）申请输出缓冲区，写入容量后再调用一次
+//! 4. 读出结果，用 `rw_free(ptr, len)` 逐个释放
+//!
+//! tests/wasi/driver.wat 在 wasmtime 中按这个流程调用一次 `rw_xor_encrypt` ，CI 中每次提交都会运行
+
+use std::alloc::{Layout, alloc, dealloc};
+use std::ptr;
+
+// 按 8 字节对齐，申请到的内存也可以直接当作 size_t* 等参数使用
+const ALIGN: usize = 8;
+
+/// 在线性内存中申请 len 字节，len 为 0 时返回 NULL
+#[unsafe(no_mangle)]
+pub extern "C" fn rw_alloc(len: usize) -> *mut u8 {
+    match Layout::from_size_align(len, ALIGN) {
+        Ok(layout) if len > 0 => unsafe { alloc(layout) },
+        _ => ptr::null_mut(),
+    }
+}
+
+/// 释放 `rw_alloc` 申请的内存，len 必须与申请时一致
+///
+/// # Safety
+/// ptr 必须来自 `rw_alloc(len)` 且只能释放一次
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rw_free(ptr: *mut u8, len: usize) {
+    if ptr.is_null() || len == 0 {
+        return;
+    }
+    if let Ok(layout) = Layout::from_size_align(len, ALIGN) {
+        unsafe { dealloc(ptr, layout) };
+    }
+}
+
+#[cfg(all(test, feature = "xor"))]
+mod tests {
+    use super::*;
+    use crate::capi::{RwStatus, rw_xor_decrypt};
+
+    // 按宿主的方式走一遍：所有缓冲区都通过 rw_alloc 申请
+    #[test]
+    fn alloc_call_free_roundtrip_test() {
+        let input = b"AAIaFV8=";
+        let input_ptr = rw_alloc(input.len());
+        unsafe { ptr::copy_nonoverlapping(input.as_ptr(), input_ptr, input.len()) };
+
+        let out_len = rw_alloc(size_of::<usize>()) as *mut usize;
+        unsafe { *out_len = 0 };
+        let status = unsafe { rw_xor_decrypt(input_ptr, input.len(), ptr::null_mut(), out_len) };
+        assert_eq!(status, RwStatus::BufferTooSmall);
+
+        let len = unsafe { *out_len };
+        let out = rw_alloc(len + 1);
+        unsafe { *out_len = len + 1 };
+        let status = unsafe { rw_xor_decrypt(input_ptr, input.len(), out, out_len) };
+        assert_eq!(status, RwStatus::Ok);
+        let decrypted = unsafe { std::slice::from_raw_parts(out, len) };
+        assert_eq!(decrypted, b"hans7");
+
+        unsafe {
+            rw_free(out, len + 1);
+            rw_free(out_len as *mut u8, size_of::<usize>());
+            rw_free(input_ptr, input.len());
+        }
+        assert!(rw_alloc(0).is_null());
+        unsafe { rw_free(ptr::null_mut(), 0) };
+    }
+}
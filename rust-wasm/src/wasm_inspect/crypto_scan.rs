@@ -0,0 +1,680 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasmparser::{Operator, Parser, Payload};
+
+use super::module_info::ModuleInfo;
+
+// 与 FindCrypt 相同的思路：常见密码算法的常量表在二进制中原样存在，找到常量表就找到了算法。
+// - 常量表（S 盒、轮常量、CRC 表等）按字节序列搜索，多字节整数表同时搜索小端和大端两种布局。
+//   只取表的前若干项，残缺的表也能命中
+// - WASM 代码中的常量以 LEB128 编码在 i32.const / i64.const 里，字节搜索找不到，单独遍历指令
+// - Base64 码表：标准 / URL 安全码表按字节搜索，另外报告由 64 个不同 Base64 字符组成的置换码表
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+const STANDARD_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+const SHA224_IV: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+const SHA256_K: [u32; 16] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+];
+const SHA512_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+const SHA384_IV: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+const SHA512_K: [u64; 8] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+];
+// floor(|sin(i + 1)| * 2^32) 的前 16 项
+const MD5_T: [u32; 16] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+];
+const TEA_DELTA: u32 = 0x9e3779b9;
+// TEA 解密时 sum 的初值 delta * 32
+const TEA_SUM: u32 = 0xc6ef3720;
+const CRC32_POLY: u32 = 0xedb88320;
+const CRC32C_POLY: u32 = 0x82f63b78;
+const CRC16_ARC_POLY: u16 = 0xa001;
+const CRC16_CCITT_POLY: u16 = 0x1021;
+
+// 整数表只搜索前 16 项
+const TABLE_PREFIX: usize = 16;
+
+/// 一处命中
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CryptoHit {
+    pub algorithm: &'static str,
+    pub signature: &'static str,
+    // 文件偏移和命中的字节数。代码中的常量是第一条匹配指令的位置，size 为 0
+    pub offset: usize,
+    pub size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endian: Option<&'static str>,
+    // raw（非 wasm 文件）、code 或命中所在 section 的种类（data 、custom ……）
+    pub location: &'static str,
+    // 位于主动数据段时，加载到线性内存后的地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_name: Option<String>,
+    // 代码中匹配到的不同常量个数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched: Option<usize>,
+    // 码表内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl CryptoHit {
+    fn new(algorithm: &'static str, signature: &'static str, offset: usize, size: usize) -> Self {
+        CryptoHit {
+            algorithm,
+            signature,
+            offset,
+            size,
+            endian: None,
+            location: "raw",
+            address: None,
+            function: None,
+            function_name: None,
+            matched: None,
+            text: None,
+        }
+    }
+}
+
+struct BytePattern {
+    algorithm: &'static str,
+    signature: &'static str,
+    endian: Option<&'static str>,
+    bytes: Vec<u8>,
+}
+
+// 代码常量的一组签名，同一函数中出现 min_matches 个不同的值才报告
+struct ImmediateGroup {
+    algorithm: &'static str,
+    signature: &'static str,
+    values: Vec<u64>,
+    min_matches: usize,
+}
+
+/// 扫描 wasm 模块或任意二进制文件（如 native 可执行文件）中的密码学常量，按偏移排序。
+/// wasm 模块额外扫描代码中的常量，并给出命中位置所在的 section 、线性内存地址和函数
+pub fn scan_crypto(bytes: &[u8]) -> Vec<CryptoHit> {
+    let mut hits = scan_bytes(bytes);
+    if bytes.starts_with(WASM_MAGIC)
+        && let Ok(info) = ModuleInfo::parse(bytes)
+    {
+        for hit in &mut hits {
+            locate(hit, &info);
+        }
+        // 代码解析失败时只保留字节搜索的结果
+        if let Ok(code_hits) = scan_code(bytes, &info) {
+            hits.extend(code_hits);
+        }
+    }
+    hits.sort_by_key(|hit| hit.offset);
+    hits
+}
+
+/// 扫描二进制中的密码学常量，以 JSON 数组返回命中的位置和算法
+#[wasm_bindgen]
+pub fn scan_crypto_constants(bytes: &[u8]) -> String {
+    serde_json::to_string(&scan_crypto(bytes)).unwrap_or_default()
+}
+
+fn scan_bytes(bytes: &[u8]) -> Vec<CryptoHit> {
+    // native 可执行文件可能有几十 MB ，只遍历一遍，按首字节挑出需要比较的签名
+    let patterns = byte_patterns();
+    let mut by_first_byte: Vec<Vec<&BytePattern>> = vec![Vec::new(); 256];
+    for pattern in patterns {
+        by_first_byte[pattern.bytes[0] as usize].push(pattern);
+    }
+    let mut hits = Vec::new();
+    for (offset, &b) in bytes.iter().enumerate() {
+        for pattern in &by_first_byte[b as usize] {
+            if bytes[offset..].starts_with(&pattern.bytes) {
+                let mut hit = CryptoHit::new(
+                    pattern.algorithm,
+                    pattern.signature,
+                    offset,
+                    pattern.bytes.len(),
+                );
+                hit.endian = pattern.endian;
+                hits.push(hit);
+            }
+        }
+    }
+    for (offset, alphabet) in find_permuted_alphabets(bytes) {
+        let mut hit = CryptoHit::new("Base64", "permuted alphabet", offset, alphabet.len());
+        hit.text = Some(String::from_utf8_lossy(alphabet).into_owned());
+        hits.push(hit);
+    }
+    hits
+}
+
+fn is_base64_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_')
+}
+
+// 连续 64 个互不相同的 Base64 字符（含 URL 安全的 - 和 _），且不是标准 / URL 安全码表
+fn find_permuted_alphabets(bytes: &[u8]) -> Vec<(usize, &[u8])> {
+    let mut found = Vec::new();
+    let mut last_seen = [usize::MAX; 256];
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !is_base64_char(b) {
+            start = i + 1;
+        } else if last_seen[b as usize] != usize::MAX && last_seen[b as usize] >= start {
+            start = last_seen[b as usize] + 1;
+        }
+        last_seen[b as usize] = i;
+        if i + 1 - start == 64 {
+            let alphabet = &bytes[start..=i];
+            if alphabet != STANDARD_ALPHABET && alphabet != URL_SAFE_ALPHABET {
+                found.push((start, alphabet));
+            }
+            // 同一段字符只报告一次
+            start = i + 1;
+        }
+        i += 1;
+    }
+    found
+}
+
+fn byte_patterns() -> &'static [BytePattern] {
+    static PATTERNS: OnceLock<Vec<BytePattern>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let mut patterns = Vec::new();
+        let (sbox, inv_sbox) = aes_sboxes();
+        patterns.push(BytePattern {
+            algorithm: "AES",
+            signature: "S-box",
+            endian: None,
+            bytes: sbox[..32].to_vec(),
+        });
+        patterns.push(BytePattern {
+            algorithm: "AES",
+            signature: "inverse S-box",
+            endian: None,
+            bytes: inv_sbox[..32].to_vec(),
+        });
+        let te0: Vec<u32> = sbox[..TABLE_PREFIX]
+            .iter()
+            .map(|&s| u32::from_be_bytes([gmul(s, 2), s, s, gmul(s, 3)]))
+            .collect();
+        let td0: Vec<u32> = inv_sbox[..TABLE_PREFIX]
+            .iter()
+            .map(|&s| u32::from_be_bytes([gmul(s, 14), gmul(s, 9), gmul(s, 13), gmul(s, 11)]))
+            .collect();
+        for (signatures, table) in [
+            (["Te0", "Te1", "Te2", "Te3"], &te0),
+            (["Td0", "Td1", "Td2", "Td3"], &td0),
+        ] {
+            for (rotation, signature) in signatures.into_iter().enumerate() {
+                let rotated: Vec<u32> = table
+                    .iter()
+                    .map(|w| w.rotate_right(8 * rotation as u32))
+                    .collect();
+                push_u32_table(&mut patterns, "AES", signature, &rotated);
+            }
+        }
+
+        push_u32_table(&mut patterns, "SHA-256", "IV", &SHA256_IV);
+        push_u32_table(&mut patterns, "SHA-224", "IV", &SHA224_IV);
+        push_u32_table(&mut patterns, "SHA-256", "K", &SHA256_K);
+        push_u64_table(&mut patterns, "SHA-512", "IV", &SHA512_IV);
+        push_u64_table(&mut patterns, "SHA-384", "IV", &SHA384_IV);
+        push_u64_table(&mut patterns, "SHA-512", "K", &SHA512_K);
+        push_u32_table(&mut patterns, "MD5", "T (sine table)", &MD5_T);
+        push_u32_table(&mut patterns, "CRC-32", "table", &crc32_table(CRC32_POLY));
+        push_u32_table(&mut patterns, "CRC-32C", "table", &crc32_table(CRC32C_POLY));
+        push_u16_table(
+            &mut patterns,
+            "CRC-16/ARC",
+            "table",
+            &crc16_reflected_table(CRC16_ARC_POLY),
+        );
+        push_u16_table(
+            &mut patterns,
+            "CRC-16/CCITT",
+            "table",
+            &crc16_table(CRC16_CCITT_POLY),
+        );
+        push_u32_table(&mut patterns, "TEA", "delta", &[TEA_DELTA]);
+        push_u32_table(&mut patterns, "TEA", "delta * 32", &[TEA_SUM]);
+
+        patterns.push(BytePattern {
+            algorithm: "Base64",
+            signature: "standard alphabet",
+            endian: None,
+            bytes: STANDARD_ALPHABET.to_vec(),
+        });
+        patterns.push(BytePattern {
+            algorithm: "Base64",
+            signature: "URL-safe alphabet",
+            endian: None,
+            bytes: URL_SAFE_ALPHABET.to_vec(),
+        });
+        patterns
+    })
+}
+
+fn push_u32_table(
+    patterns: &mut Vec<BytePattern>,
+    algorithm: &'static str,
+    signature: &'static str,
+    table: &[u32],
+) {
+    let table = &table[..table.len().min(TABLE_PREFIX)];
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("little"),
+        bytes: table.iter().flat_map(|w| w.to_le_bytes()).collect(),
+    });
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("big"),
+        bytes: table.iter().flat_map(|w| w.to_be_bytes()).collect(),
+    });
+}
+
+fn push_u64_table(
+    patterns: &mut Vec<BytePattern>,
+    algorithm: &'static str,
+    signature: &'static str,
+    table: &[u64],
+) {
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("little"),
+        bytes: table.iter().flat_map(|w| w.to_le_bytes()).collect(),
+    });
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("big"),
+        bytes: table.iter().flat_map(|w| w.to_be_bytes()).collect(),
+    });
+}
+
+fn push_u16_table(
+    patterns: &mut Vec<BytePattern>,
+    algorithm: &'static str,
+    signature: &'static str,
+    table: &[u16],
+) {
+    let table = &table[..TABLE_PREFIX];
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("little"),
+        bytes: table.iter().flat_map(|w| w.to_le_bytes()).collect(),
+    });
+    patterns.push(BytePattern {
+        algorithm,
+        signature,
+        endian: Some("big"),
+        bytes: table.iter().flat_map(|w| w.to_be_bytes()).collect(),
+    });
+}
+
+// GF(2^8) 乘法，模 x^8 + x^4 + x^3 + x + 1
+fn gmul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+// S 盒 = 仿射变换(乘法逆元)，不硬编码 256 字节的表
+fn aes_sboxes() -> ([u8; 256], [u8; 256]) {
+    let mut sbox = [0u8; 256];
+    let mut inv_sbox = [0u8; 256];
+    for x in 0..=255u8 {
+        let inverse = if x == 0 {
+            0
+        } else {
+            (1..=255u8).find(|&y| gmul(x, y) == 1).unwrap_or(0)
+        };
+        let s = inverse
+            ^ inverse.rotate_left(1)
+            ^ inverse.rotate_left(2)
+            ^ inverse.rotate_left(3)
+            ^ inverse.rotate_left(4)
+            ^ 0x63;
+        sbox[x as usize] = s;
+        inv_sbox[s as usize] = x;
+    }
+    (sbox, inv_sbox)
+}
+
+fn crc32_table(poly: u32) -> Vec<u32> {
+    (0..TABLE_PREFIX as u32)
+        .map(|i| (0..8).fold(i, |c, _| if c & 1 != 0 { (c >> 1) ^ poly } else { c >> 1 }))
+        .collect()
+}
+
+fn crc16_reflected_table(poly: u16) -> Vec<u16> {
+    (0..TABLE_PREFIX as u16)
+        .map(|i| (0..8).fold(i, |c, _| if c & 1 != 0 { (c >> 1) ^ poly } else { c >> 1 }))
+        .collect()
+}
+
+fn crc16_table(poly: u16) -> Vec<u16> {
+    (0..TABLE_PREFIX as u16)
+        .map(|i| {
+            (0..8).fold(i << 8, |c, _| {
+                if c & 0x8000 != 0 {
+                    (c << 1) ^ poly
+                } else {
+                    c << 1
+                }
+            })
+        })
+        .collect()
+}
+
+fn immediate_groups() -> &'static [ImmediateGroup] {
+    static GROUPS: OnceLock<Vec<ImmediateGroup>> = OnceLock::new();
+    GROUPS.get_or_init(|| {
+        let u32s = |values: &[u32]| values.iter().map(|&v| v as u64).collect::<Vec<_>>();
+        let group = |algorithm, signature, values: Vec<u64>| ImmediateGroup {
+            algorithm,
+            signature,
+            min_matches: values.len().min(2),
+            values,
+        };
+        vec![
+            group("SHA-256", "IV", u32s(&SHA256_IV)),
+            group("SHA-224", "IV", u32s(&SHA224_IV)),
+            group("SHA-256", "K", u32s(&SHA256_K)),
+            group("SHA-512", "IV", SHA512_IV.to_vec()),
+            group("SHA-384", "IV", SHA384_IV.to_vec()),
+            group("SHA-512", "K", SHA512_K.to_vec()),
+            group("MD5", "T (sine table)", u32s(&MD5_T)),
+            group("TEA", "delta", vec![TEA_DELTA as u64]),
+            group("TEA", "delta * 32", vec![TEA_SUM as u64]),
+            group("CRC-32", "polynomial", vec![CRC32_POLY as u64]),
+            group("CRC-32C", "polynomial", vec![CRC32C_POLY as u64]),
+        ]
+    })
+}
+
+// 遍历每个函数的 i32.const / i64.const 。i64.const 可能是两个 32 位常量拼在一起（例如一次写入
+// 两个 SHA-256 IV），高低两半也分别比较
+fn scan_code(
+    bytes: &[u8],
+    info: &ModuleInfo,
+) -> Result<Vec<CryptoHit>, wasmparser::BinaryReaderError> {
+    let mut hits = Vec::new();
+    let mut function = info.imported_function_count() as u32;
+    for payload in Parser::new(0).parse_all(bytes) {
+        let Payload::CodeSectionEntry(body) = payload? else {
+            continue;
+        };
+        // 常量值 -> 第一次出现的偏移
+        let mut constants: BTreeMap<u64, usize> = BTreeMap::new();
+        let mut reader = body.get_operators_reader()?;
+        while !reader.eof() {
+            let offset = reader.original_position();
+            let values = match reader.read()? {
+                Operator::I32Const { value } => [Some(value as u32 as u64), None, None],
+                Operator::I64Const { value } => {
+                    let value = value as u64;
+                    [Some(value), Some(value & 0xffff_ffff), Some(value >> 32)]
+                }
+                _ => continue,
+            };
+            for value in values.into_iter().flatten() {
+                constants.entry(value).or_insert(offset);
+            }
+        }
+        for group in immediate_groups() {
+            let offsets: Vec<usize> = group
+                .values
+                .iter()
+                .filter_map(|value| constants.get(value).copied())
+                .collect();
+            if offsets.len() >= group.min_matches {
+                let mut hit = CryptoHit::new(
+                    group.algorithm,
+                    group.signature,
+                    offsets.iter().copied().min().unwrap_or(0),
+                    0,
+                );
+                hit.location = "code";
+                hit.function = Some(function);
+                hit.function_name = info.function_name(function).map(str::to_string);
+                hit.matched = Some(offsets.len());
+                hits.push(hit);
+            }
+        }
+        function += 1;
+    }
+    Ok(hits)
+}
+
+// 按文件偏移找到命中所在的 section ，位于主动数据段时换算成线性内存地址
+fn locate(hit: &mut CryptoHit, info: &ModuleInfo) {
+    if let Some(section) = info
+        .sections
+        .iter()
+        .find(|s| (s.offset..s.offset + s.size).contains(&hit.offset))
+    {
+        hit.location = section.kind;
+    }
+    if let Some(segment) = info
+        .data
+        .iter()
+        .find(|d| (d.file_offset..d.file_offset + d.size).contains(&hit.offset))
+    {
+        hit.address = segment
+            .offset
+            .map(|base| base + (hit.offset - segment.file_offset) as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // custom_base64 的 HANS7_CUSTOM_ALPHABET_STR ，只开 inspect feature 时那个模块不参与编译
+    const HANS7_ALPHABET: &str = "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/";
+
+    fn signatures(hits: &[CryptoHit]) -> Vec<(&str, &str)> {
+        hits.iter().map(|h| (h.algorithm, h.signature)).collect()
+    }
+
+    #[test]
+    fn generated_tables_test() {
+        let (sbox, inv_sbox) = aes_sboxes();
+        assert_eq!(sbox[..4], [0x63, 0x7c, 0x77, 0x7b]);
+        assert_eq!(sbox[0xff], 0x16);
+        assert_eq!(inv_sbox[..4], [0x52, 0x09, 0x6a, 0xd5]);
+        assert_eq!(crc32_table(CRC32_POLY)[1], 0x77073096);
+        assert_eq!(crc32_table(CRC32C_POLY)[1], 0xf26b8303);
+        assert_eq!(crc16_reflected_table(CRC16_ARC_POLY)[1], 0xc0c1);
+        assert_eq!(crc16_table(CRC16_CCITT_POLY)[1], 0x1021);
+
+        // OpenSSL 的 Te0[0] 、Td0[0]
+        let te0 = byte_patterns()
+            .iter()
+            .find(|p| p.signature == "Te0" && p.endian == Some("big"))
+            .unwrap();
+        assert_eq!(te0.bytes[..4], [0xc6, 0x63, 0x63, 0xa5]);
+        let td0 = byte_patterns()
+            .iter()
+            .find(|p| p.signature == "Td0" && p.endian == Some("big"))
+            .unwrap();
+        assert_eq!(td0.bytes[..4], [0x51, 0xf4, 0xa7, 0x50]);
+    }
+
+    // 硬编码的常量与定义一致：MD5 为 floor(|sin(i + 1)| * 2^32) ，SHA-256 为素数平方根 / 立方根的小数部分
+    #[test]
+    fn hardcoded_constants_test() {
+        for (i, &t) in MD5_T.iter().enumerate() {
+            assert_eq!(((i as f64 + 1.0).sin().abs() * 4294967296.0) as u32, t);
+        }
+        let primes = [
+            2.0f64, 3.0, 5.0, 7.0, 11.0, 13.0, 17.0, 19.0, 23.0, 29.0, 31.0, 37.0, 41.0, 43.0,
+            47.0, 53.0,
+        ];
+        let frac = |x: f64| ((x - x.floor()) * 4294967296.0) as u32;
+        for (i, p) in primes.iter().enumerate() {
+            assert_eq!(frac(p.cbrt()), SHA256_K[i]);
+            if i < 8 {
+                assert_eq!(frac(p.sqrt()), SHA256_IV[i]);
+                assert_eq!((SHA512_IV[i] >> 32) as u32, SHA256_IV[i]);
+                assert_eq!((SHA512_K[i] >> 32) as u32, SHA256_K[i]);
+                assert_eq!(SHA384_IV[i] as u32, SHA224_IV[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_raw_bytes_test() {
+        let mut bytes = vec![0xcc; 17];
+        bytes.extend(SHA256_K.iter().flat_map(|k| k.to_le_bytes()));
+        bytes.extend(b"..");
+        bytes.extend(HANS7_ALPHABET.as_bytes());
+        bytes.extend(b"=\0");
+        bytes.extend(STANDARD_ALPHABET);
+        bytes.extend(TEA_DELTA.to_be_bytes());
+
+        let hits = scan_crypto(&bytes);
+        assert_eq!(
+            signatures(&hits),
+            [
+                ("SHA-256", "K"),
+                ("Base64", "permuted alphabet"),
+                ("Base64", "standard alphabet"),
+                ("TEA", "delta"),
+            ]
+        );
+        assert_eq!(hits[0].offset, 17);
+        assert_eq!(hits[0].size, 64);
+        assert_eq!(hits[0].endian, Some("little"));
+        assert_eq!(hits[0].location, "raw");
+        assert_eq!(hits[1].offset, 17 + 64 + 2);
+        assert_eq!(hits[1].text.as_deref(), Some(HANS7_ALPHABET));
+        assert_eq!(hits[3].endian, Some("big"));
+    }
+
+    #[test]
+    fn scan_permuted_alphabet_test() {
+        // 字符有重复或不足 64 个都不算码表
+        assert!(
+            find_permuted_alphabets(
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+"
+            )
+            .is_empty()
+        );
+        assert!(find_permuted_alphabets(&[b'A'; 200]).is_empty());
+        let mut bytes = b"AAAA".to_vec();
+        bytes.extend(STANDARD_ALPHABET.iter().rev());
+        let found = find_permuted_alphabets(&bytes);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 3);
+    }
+
+    #[test]
+    fn scan_wasm_module_test() {
+        let (sbox, _) = aes_sboxes();
+        let escaped: String = sbox.iter().map(|b| format!("\\{b:02x}")).collect();
+        let wat = format!(
+            r#"(module
+                (memory 1)
+                (func $tea_encrypt (result i32) (i32.const 0x9e3779b9))
+                (func (export "sha256_init")
+                    (i64.store (i32.const 0) (i64.const 0xbb67ae856a09e667))
+                    (i64.store (i32.const 8) (i64.const 0xa54ff53a3c6ef372)))
+                (data (i32.const 1024) "padding{escaped}"))"#
+        );
+        let bytes = wat::parse_str(wat).unwrap();
+        let hits = scan_crypto(&bytes);
+        assert_eq!(
+            signatures(&hits),
+            [("TEA", "delta"), ("SHA-256", "IV"), ("AES", "S-box")]
+        );
+
+        assert_eq!(hits[0].location, "code");
+        assert_eq!(hits[0].function, Some(0));
+        assert_eq!(hits[0].function_name.as_deref(), Some("tea_encrypt"));
+        assert_eq!(hits[1].function_name.as_deref(), Some("sha256_init"));
+        assert_eq!(hits[1].matched, Some(4));
+
+        assert_eq!(hits[2].location, "data");
+        assert_eq!(hits[2].address, Some(1024 + 7));
+        assert_eq!(
+            &bytes[hits[2].offset..hits[2].offset + 4],
+            [0x63, 0x7c, 0x77, 0x7b]
+        );
+    }
+
+    // 测试程序本身链接了 sha2 ，能在可执行文件中找到它的轮常量
+    #[cfg(feature = "sha")]
+    #[test]
+    fn scan_native_binary_test() {
+        let exe = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+        let json = scan_crypto_constants(&exe);
+        let hits: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let found = |algorithm: &str| {
+            hits.iter()
+                .any(|h| h["algorithm"] == algorithm && h["signature"] == "K")
+        };
+        assert!(found("SHA-256"));
+        assert!(found("SHA-512"));
+    }
+}
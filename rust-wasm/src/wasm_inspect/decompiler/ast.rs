@@ -0,0 +1,352 @@
+// 反编译的中间表示：表达式树和结构化语句
+
+pub(super) type Label = u32;
+
+/// 变量：wasm 的局部变量（含参数），或反编译时引入的临时变量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Var {
+    Local(u32),
+    Temp(u32),
+}
+
+/// 二元运算符。cast 是无符号运算时给操作数加的类型转换，如 `(u32)a < (u32)b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BinOp {
+    pub symbol: &'static str,
+    pub prec: u8,
+    pub cast: Option<&'static str>,
+    // 逻辑右移只转换左操作数
+    pub cast_right: bool,
+    // 整数比较，取反时可以直接换成相反的比较（浮点数因为 NaN 不行）
+    pub int_compare: bool,
+}
+
+// C 的运算符优先级，数值越大结合越紧
+pub(super) const PREC_TERNARY: u8 = 3;
+pub(super) const PREC_BIT_OR: u8 = 6;
+pub(super) const PREC_BIT_XOR: u8 = 7;
+pub(super) const PREC_BIT_AND: u8 = 8;
+pub(super) const PREC_EQUALITY: u8 = 9;
+pub(super) const PREC_RELATION: u8 = 10;
+pub(super) const PREC_SHIFT: u8 = 11;
+pub(super) const PREC_ADD: u8 = 12;
+pub(super) const PREC_MUL: u8 = 13;
+pub(super) const PREC_UNARY: u8 = 14;
+pub(super) const PREC_PRIMARY: u8 = 16;
+
+impl BinOp {
+    pub const fn new(symbol: &'static str, prec: u8) -> Self {
+        BinOp {
+            symbol,
+            prec,
+            cast: None,
+            cast_right: true,
+            int_compare: false,
+        }
+    }
+
+    pub const fn unsigned(self, cast: &'static str) -> Self {
+        BinOp {
+            cast: Some(cast),
+            ..self
+        }
+    }
+
+    pub const fn left_cast_only(self) -> Self {
+        BinOp {
+            cast_right: false,
+            ..self
+        }
+    }
+
+    pub const fn int_compare(self) -> Self {
+        BinOp {
+            int_compare: true,
+            ..self
+        }
+    }
+
+    fn negated(self) -> Option<Self> {
+        if !self.int_compare {
+            return None;
+        }
+        let symbol = match self.symbol {
+            "==" => "!=",
+            "!=" => "==",
+            "<" => ">=",
+            ">=" => "<",
+            ">" => "<=",
+            "<=" => ">",
+            _ => return None,
+        };
+        let prec = if matches!(symbol, "==" | "!=") {
+            PREC_EQUALITY
+        } else {
+            PREC_RELATION
+        };
+        Some(BinOp {
+            symbol,
+            prec,
+            ..self
+        })
+    }
+}
+
+// 读取机器状态的内置函数，与 load 一样不能越过写操作
+const VOLATILE_BUILTINS: &[&str] = &["memory_size", "memory_grow"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Expr {
+    I32(i32),
+    I64(i64),
+    // 浮点数保存位模式
+    F32(u32),
+    F64(u64),
+    RefNull,
+    RefFunc(u32),
+    Var(Var),
+    Global(u32),
+    // 前缀运算符 "!" 、"-" 、"~"
+    Unary(&'static str, Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    // 类型转换 `(u32)x`
+    Cast(&'static str, Box<Expr>),
+    Load {
+        ty: &'static str,
+        addr: Box<Expr>,
+        offset: u64,
+    },
+    Call(u32, Vec<Expr>),
+    CallIndirect {
+        table: u32,
+        index: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    // 没有对应 C 运算符的指令，如 rotl 、sqrt 、memory_grow
+    Builtin(&'static str, Vec<Expr>),
+    // select 和只有一个值的 if / else ：`cond ? a : b`
+    Select(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(op: BinOp, a: Expr, b: Expr) -> Expr {
+        Expr::Binary(op, Box::new(a), Box::new(b))
+    }
+
+    pub fn cast(ty: &'static str, e: Expr) -> Expr {
+        Expr::Cast(ty, Box::new(e))
+    }
+
+    pub fn is_const(&self) -> bool {
+        matches!(
+            self,
+            Expr::I32(_)
+                | Expr::I64(_)
+                | Expr::F32(_)
+                | Expr::F64(_)
+                | Expr::RefNull
+                | Expr::RefFunc(_)
+        )
+    }
+
+    fn any(&self, pred: &impl Fn(&Expr) -> bool) -> bool {
+        if pred(self) {
+            return true;
+        }
+        match self {
+            Expr::Unary(_, e) | Expr::Cast(_, e) => e.any(pred),
+            Expr::Load { addr, .. } => addr.any(pred),
+            Expr::Binary(_, a, b) => a.any(pred) || b.any(pred),
+            Expr::Call(_, args) | Expr::Builtin(_, args) => args.iter().any(|e| e.any(pred)),
+            Expr::CallIndirect { index, args, .. } => {
+                index.any(pred) || args.iter().any(|e| e.any(pred))
+            }
+            Expr::Select(c, a, b) => c.any(pred) || a.any(pred) || b.any(pred),
+            _ => false,
+        }
+    }
+
+    pub fn uses(&self, var: Var) -> bool {
+        self.any(&|e| *e == Expr::Var(var))
+    }
+
+    /// 含有函数调用，丢弃时也要保留
+    pub fn has_call(&self) -> bool {
+        self.any(&|e| matches!(e, Expr::Call(..) | Expr::CallIndirect { .. }))
+    }
+
+    /// 结果依赖内存、全局变量或有副作用，不能移到写内存、调用等操作之后求值
+    pub fn is_volatile(&self) -> bool {
+        self.any(&|e| match e {
+            Expr::Load { .. } | Expr::Global(_) | Expr::Call(..) | Expr::CallIndirect { .. } => {
+                true
+            }
+            Expr::Builtin(name, _) => VOLATILE_BUILTINS.contains(name),
+            _ => false,
+        })
+    }
+
+    /// 逻辑取反，整数比较换成相反的比较，`!!x` 化简为 x
+    pub fn negate(self) -> Expr {
+        match self {
+            Expr::Unary("!", e) => *e,
+            Expr::Binary(op, a, b) => match op.negated() {
+                Some(op) => Expr::Binary(op, a, b),
+                None => Expr::Unary("!", Box::new(Expr::Binary(op, a, b))),
+            },
+            e => Expr::Unary("!", Box::new(e)),
+        }
+    }
+}
+
+/// 跳转的目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Target {
+    // 跳出块（或结构化后跳出循环）
+    Break(Label),
+    // 回到循环开头
+    Continue(Label),
+    Return,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Stmt {
+    // 有副作用的表达式，如没有返回值的调用
+    Expr(Expr),
+    Set(Var, Expr),
+    // 返回多个值的调用
+    SetMulti(Vec<Var>, Expr),
+    GlobalSet(u32, Expr),
+    Store {
+        ty: &'static str,
+        addr: Expr,
+        offset: u64,
+        value: Expr,
+    },
+    Block {
+        label: Label,
+        body: Vec<Stmt>,
+    },
+    Loop {
+        label: Label,
+        body: Vec<Stmt>,
+    },
+    If {
+        label: Option<Label>,
+        cond: Expr,
+        then: Vec<Stmt>,
+        els: Vec<Stmt>,
+    },
+    // cond 为 None 时是 while (true)
+    While {
+        label: Label,
+        cond: Option<Expr>,
+        body: Vec<Stmt>,
+    },
+    DoWhile {
+        label: Label,
+        body: Vec<Stmt>,
+        cond: Expr,
+    },
+    Jump {
+        target: Target,
+        cond: Option<Expr>,
+    },
+    Switch {
+        index: Expr,
+        targets: Vec<Target>,
+        default: Target,
+    },
+    Return(Vec<Expr>),
+    Unreachable,
+}
+
+impl Stmt {
+    /// 执行后不会落到下一条语句
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Stmt::Jump { cond: None, .. }
+                | Stmt::Switch { .. }
+                | Stmt::Return(_)
+                | Stmt::Unreachable
+        )
+    }
+
+    fn targets(&self) -> Vec<Target> {
+        match self {
+            Stmt::Jump { target, .. } => vec![*target],
+            Stmt::Switch {
+                targets, default, ..
+            } => targets.iter().chain([default]).copied().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn children(&self) -> Vec<&Vec<Stmt>> {
+        match self {
+            Stmt::Block { body, .. }
+            | Stmt::Loop { body, .. }
+            | Stmt::While { body, .. }
+            | Stmt::DoWhile { body, .. } => vec![body],
+            Stmt::If { then, els, .. } => vec![then, els],
+            _ => Vec::new(),
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Vec<Stmt>> {
+        match self {
+            Stmt::Block { body, .. }
+            | Stmt::Loop { body, .. }
+            | Stmt::While { body, .. }
+            | Stmt::DoWhile { body, .. } => vec![body],
+            Stmt::If { then, els, .. } => vec![then, els],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 语句列表中（含嵌套）跳到 label 的次数
+pub(super) fn count_jumps(stmts: &[Stmt], label: Label) -> usize {
+    stmts
+        .iter()
+        .map(|stmt| {
+            let direct = stmt
+                .targets()
+                .iter()
+                .filter(|t| matches!(t, Target::Break(l) | Target::Continue(l) if *l == label))
+                .count();
+            direct
+                + stmt
+                    .children()
+                    .into_iter()
+                    .map(|body| count_jumps(body, label))
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+/// 把跳出 from 的 break 改为跳出 to
+pub(super) fn retarget_breaks(stmts: &mut [Stmt], from: Label, to: Label) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Jump { target, .. } => retarget(target, from, to),
+            Stmt::Switch {
+                targets, default, ..
+            } => {
+                targets.iter_mut().for_each(|t| retarget(t, from, to));
+                retarget(default, from, to);
+            }
+            _ => {}
+        }
+        for body in stmt.children_mut() {
+            retarget_breaks(body, from, to);
+        }
+    }
+}
+
+fn retarget(target: &mut Target, from: Label, to: Label) {
+    if *target == Target::Break(from) {
+        *target = Target::Break(to);
+    }
+}
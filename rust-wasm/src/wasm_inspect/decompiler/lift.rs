@@ -0,0 +1,1132 @@
+use wasmparser::{BlockType, MemArg, Operator, ValType};
+
+use super::Context;
+use super::ast::*;
+use crate::wasm_inspect::inspect_error::InspectError;
+
+// 把栈式指令提升为表达式树：操作数栈上保存的是尚未求值的表达式，指令产生副作用时才输出语句。
+// 为了不改变求值顺序：
+// - 写局部变量前，栈上读取该变量的表达式先存入临时变量
+// - 写内存、调用函数前，栈上读取内存 / 全局变量或含有调用的表达式先存入临时变量
+// - 进入 block / loop / if 前，栈上的非常量表达式全部存入临时变量
+// 带参数的块（multi-value 的 block 参数）不支持
+
+/// 提升后的函数
+pub(super) struct Lifted {
+    pub params: Vec<ValType>,
+    pub results: Vec<ValType>,
+    // 参数之后声明的局部变量
+    pub locals: Vec<ValType>,
+    pub temps: Vec<ValType>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Function,
+    Block,
+    Loop,
+    If,
+}
+
+struct Frame {
+    kind: Kind,
+    label: Label,
+    results: Vec<ValType>,
+    // 被带值跳转时，结果存放在这些临时变量中
+    result_vars: Option<Vec<Var>>,
+    // 进入块时操作数栈的高度
+    height: usize,
+    body: Vec<Stmt>,
+    // if 的条件，以及遇到 else 时保存的 then 分支和它落到末尾时的结果
+    cond: Option<Expr>,
+    then: Option<(Vec<Stmt>, Option<Vec<Expr>>)>,
+    unreachable: bool,
+    jumped: bool,
+}
+
+struct Lifter<'c, 'a> {
+    ctx: &'c Context<'a>,
+    // 参数和局部变量的类型
+    local_types: Vec<ValType>,
+    temps: Vec<ValType>,
+    stack: Vec<(Expr, ValType)>,
+    frames: Vec<Frame>,
+    next_label: Label,
+    // 不可达代码中嵌套的块数
+    dead: usize,
+    offset: usize,
+}
+
+const ADD: BinOp = BinOp::new("+", PREC_ADD);
+const SUB: BinOp = BinOp::new("-", PREC_ADD);
+const MUL: BinOp = BinOp::new("*", PREC_MUL);
+const DIV: BinOp = BinOp::new("/", PREC_MUL);
+const REM: BinOp = BinOp::new("%", PREC_MUL);
+const AND: BinOp = BinOp::new("&", PREC_BIT_AND);
+const OR: BinOp = BinOp::new("|", PREC_BIT_OR);
+const XOR: BinOp = BinOp::new("^", PREC_BIT_XOR);
+const SHL: BinOp = BinOp::new("<<", PREC_SHIFT);
+const SHR: BinOp = BinOp::new(">>", PREC_SHIFT);
+const EQ: BinOp = BinOp::new("==", PREC_EQUALITY);
+const NE: BinOp = BinOp::new("!=", PREC_EQUALITY);
+const LT: BinOp = BinOp::new("<", PREC_RELATION);
+const GT: BinOp = BinOp::new(">", PREC_RELATION);
+const LE: BinOp = BinOp::new("<=", PREC_RELATION);
+const GE: BinOp = BinOp::new(">=", PREC_RELATION);
+
+pub(super) fn lift(ctx: &Context, function: u32) -> Result<Lifted, InspectError> {
+    let ty = ctx.func_type(function)?;
+    let body = ctx.body(function)?;
+    let mut locals = Vec::new();
+    for pair in body.get_locals_reader()? {
+        let (count, ty) = pair?;
+        locals.extend(std::iter::repeat_n(ty, count as usize));
+    }
+    let mut lifter = Lifter {
+        ctx,
+        local_types: ty.params.iter().chain(&locals).copied().collect(),
+        temps: Vec::new(),
+        stack: Vec::new(),
+        frames: Vec::new(),
+        next_label: 0,
+        dead: 0,
+        offset: 0,
+    };
+    lifter.push_frame(Kind::Function, ty.results.clone());
+
+    let mut reader = body.get_operators_reader()?;
+    let mut lifted = None;
+    while !reader.eof() {
+        lifter.offset = reader.original_position();
+        let op = reader.read()?;
+        if lifter.frames.last().is_some_and(|f| f.unreachable) && !lifter.skip_dead(&op) {
+            continue;
+        }
+        if let Some(body) = lifter.operator(op)? {
+            lifted = Some(body);
+        }
+    }
+    let body = lifted.ok_or_else(|| lifter.invalid("missing end of function"))?;
+    Ok(Lifted {
+        params: ty.params.clone(),
+        results: ty.results.clone(),
+        locals,
+        temps: lifter.temps,
+        body,
+    })
+}
+
+impl Lifter<'_, '_> {
+    fn invalid(&self, message: &str) -> InspectError {
+        InspectError::InvalidWasm {
+            offset: self.offset,
+            message: message.to_string(),
+        }
+    }
+
+    // 不可达代码只跟踪块的嵌套，返回 true 表示这条指令仍需处理（结束当前块的 else / end）
+    fn skip_dead(&mut self, op: &Operator) -> bool {
+        match op {
+            Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } => {
+                self.dead += 1;
+                false
+            }
+            Operator::End if self.dead > 0 => {
+                self.dead -= 1;
+                false
+            }
+            Operator::End => true,
+            Operator::Else => self.dead == 0,
+            _ => false,
+        }
+    }
+
+    fn push_frame(&mut self, kind: Kind, results: Vec<ValType>) {
+        let label = self.next_label;
+        self.next_label += 1;
+        self.frames.push(Frame {
+            kind,
+            label,
+            results,
+            result_vars: None,
+            height: self.stack.len(),
+            body: Vec::new(),
+            cond: None,
+            then: None,
+            unreachable: false,
+            jumped: false,
+        });
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("函数体外没有指令")
+    }
+
+    fn emit(&mut self, stmt: Stmt) {
+        self.frame().body.push(stmt);
+    }
+
+    fn push(&mut self, e: Expr, ty: ValType) {
+        self.stack.push((e, ty));
+    }
+
+    fn pop(&mut self) -> Result<Expr, InspectError> {
+        let height = self.frames.last().map_or(0, |f| f.height);
+        if self.stack.len() <= height {
+            return Err(self.invalid("operand stack underflow"));
+        }
+        Ok(self.stack.pop().map(|(e, _)| e).unwrap_or(Expr::I32(0)))
+    }
+
+    fn pop_n(&mut self, n: usize) -> Result<Vec<Expr>, InspectError> {
+        let mut values = (0..n).map(|_| self.pop()).collect::<Result<Vec<_>, _>>()?;
+        values.reverse();
+        Ok(values)
+    }
+
+    fn temp(&mut self, ty: ValType) -> Var {
+        self.temps.push(ty);
+        Var::Temp(self.temps.len() as u32 - 1)
+    }
+
+    // 栈上满足 pred 的表达式按从底到顶的顺序存入临时变量
+    fn spill(&mut self, pred: impl Fn(&Expr) -> bool) {
+        for i in 0..self.stack.len() {
+            let (e, ty) = &self.stack[i];
+            if e.is_const() || matches!(e, Expr::Var(Var::Temp(_))) || !pred(e) {
+                continue;
+            }
+            let ty = *ty;
+            let var = self.temp(ty);
+            let e = std::mem::replace(&mut self.stack[i].0, Expr::Var(var));
+            self.emit(Stmt::Set(var, e));
+        }
+    }
+
+    fn spill_volatile(&mut self) {
+        self.spill(Expr::is_volatile);
+    }
+
+    fn block_results(&self, block_type: BlockType) -> Result<Vec<ValType>, InspectError> {
+        match block_type {
+            BlockType::Empty => Ok(Vec::new()),
+            BlockType::Type(ty) => Ok(vec![ty]),
+            BlockType::FuncType(index) => {
+                let ty = self.ctx.type_at(index)?;
+                if !ty.params.is_empty() {
+                    return Err(InspectError::Unsupported("block parameters".into()));
+                }
+                Ok(ty.results.clone())
+            }
+        }
+    }
+
+    fn result_vars(&mut self, index: usize) -> Vec<Var> {
+        if let Some(vars) = &self.frames[index].result_vars {
+            return vars.clone();
+        }
+        let results = self.frames[index].results.clone();
+        let vars: Vec<_> = results.into_iter().map(|ty| self.temp(ty)).collect();
+        self.frames[index].result_vars = Some(vars.clone());
+        vars
+    }
+
+    // br / br_if 。br_if 带值时值仍留在栈上，先存入临时变量以免重复求值
+    fn jump(&mut self, depth: u32, cond: Option<Expr>) -> Result<(), InspectError> {
+        let index = (self.frames.len() - 1)
+            .checked_sub(depth as usize)
+            .ok_or_else(|| self.invalid("branch depth out of range"))?;
+        let (kind, label, arity) = {
+            let frame = &mut self.frames[index];
+            frame.jumped = true;
+            let arity = match frame.kind {
+                Kind::Loop => 0,
+                _ => frame.results.len(),
+            };
+            (frame.kind, frame.label, arity)
+        };
+        let values = match cond {
+            None => self.pop_n(arity)?,
+            Some(_) => {
+                let top = self.stack.len().saturating_sub(arity);
+                for i in top..self.stack.len() {
+                    let (e, ty) = self.stack[i].clone();
+                    if !e.is_const() && !matches!(e, Expr::Var(Var::Temp(_))) {
+                        let var = self.temp(ty);
+                        self.emit(Stmt::Set(var, e));
+                        self.stack[i].0 = Expr::Var(var);
+                    }
+                }
+                self.stack[top..].iter().map(|(e, _)| e.clone()).collect()
+            }
+        };
+
+        let mut stmts = Vec::new();
+        match kind {
+            Kind::Function => stmts.push(Stmt::Return(values)),
+            Kind::Loop => stmts.push(Stmt::Jump {
+                target: Target::Continue(label),
+                cond: None,
+            }),
+            Kind::Block | Kind::If => {
+                let vars = if arity > 0 {
+                    self.result_vars(index)
+                } else {
+                    Vec::new()
+                };
+                stmts.extend(vars.into_iter().zip(values).map(|(v, e)| Stmt::Set(v, e)));
+                stmts.push(Stmt::Jump {
+                    target: Target::Break(label),
+                    cond: None,
+                });
+            }
+        }
+        match cond {
+            None => stmts.into_iter().for_each(|s| self.emit(s)),
+            // 只有跳转本身时写成带条件的跳转，否则是 if (cond) { ...; break; }
+            Some(cond) => match &mut stmts[..] {
+                [Stmt::Jump { cond: c, .. }] => {
+                    *c = Some(cond);
+                    self.emit(stmts.remove(0));
+                }
+                _ => self.emit(Stmt::If {
+                    label: None,
+                    cond,
+                    then: stmts,
+                    els: Vec::new(),
+                }),
+            },
+        }
+        Ok(())
+    }
+
+    fn target(&mut self, depth: u32) -> Result<Target, InspectError> {
+        let index = (self.frames.len() - 1)
+            .checked_sub(depth as usize)
+            .ok_or_else(|| self.invalid("branch depth out of range"))?;
+        let frame = &mut self.frames[index];
+        frame.jumped = true;
+        let arity = match frame.kind {
+            Kind::Loop => 0,
+            _ => frame.results.len(),
+        };
+        if arity > 0 {
+            return Err(InspectError::Unsupported("br_table with values".into()));
+        }
+        Ok(match frame.kind {
+            Kind::Function => Target::Return,
+            Kind::Loop => Target::Continue(frame.label),
+            Kind::Block | Kind::If => Target::Break(frame.label),
+        })
+    }
+
+    // 当前分支落到末尾时的结果值；不可达时丢弃栈上剩余的值
+    fn fallthrough_values(&mut self) -> Result<Option<Vec<Expr>>, InspectError> {
+        let frame = self.frames.last().expect("函数体外没有指令");
+        let (height, unreachable, arity) = (frame.height, frame.unreachable, frame.results.len());
+        if unreachable {
+            self.stack.truncate(height);
+            return Ok(None);
+        }
+        let values = self.pop_n(arity)?;
+        if self.stack.len() != height {
+            return Err(self.invalid("values remaining on stack at end of block"));
+        }
+        Ok(Some(values))
+    }
+
+    fn enter(&mut self, kind: Kind, block_type: BlockType) -> Result<(), InspectError> {
+        let results = self.block_results(block_type)?;
+        let cond = if kind == Kind::If {
+            Some(self.pop()?)
+        } else {
+            None
+        };
+        self.spill(|_| true);
+        self.push_frame(kind, results);
+        self.frame().cond = cond;
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<Option<Vec<Stmt>>, InspectError> {
+        let values = self.fallthrough_values()?;
+        let mut frame = self.frames.pop().expect("函数体外没有指令");
+        let results = frame.results.clone();
+        let types = results.clone();
+        let push_values = |lifter: &mut Self, values: Vec<Expr>| {
+            for (e, ty) in values.into_iter().zip(&types) {
+                lifter.push(e, *ty);
+            }
+        };
+
+        let reachable_after = match frame.kind {
+            Kind::Function => {
+                if let Some(values) = values
+                    && !values.is_empty()
+                {
+                    frame.body.push(Stmt::Return(values));
+                }
+                return Ok(Some(frame.body));
+            }
+            Kind::Loop => {
+                let reachable = values.is_some();
+                if frame.jumped {
+                    self.emit(Stmt::Loop {
+                        label: frame.label,
+                        body: frame.body,
+                    });
+                } else {
+                    // 没有回到开头的跳转，只执行一次
+                    self.frame().body.extend(frame.body);
+                }
+                if let Some(values) = values {
+                    push_values(self, values);
+                }
+                reachable
+            }
+            Kind::Block => {
+                let reachable = values.is_some() || frame.jumped;
+                if !frame.jumped {
+                    self.frame().body.extend(frame.body);
+                    if let Some(values) = values {
+                        push_values(self, values);
+                    }
+                } else {
+                    let vars = frame.result_vars.clone().unwrap_or_default();
+                    if let Some(values) = values {
+                        frame
+                            .body
+                            .extend(vars.iter().zip(values).map(|(v, e)| Stmt::Set(*v, e)));
+                    }
+                    self.emit(Stmt::Block {
+                        label: frame.label,
+                        body: frame.body,
+                    });
+                    let vars = vars.into_iter().map(Expr::Var).collect();
+                    push_values(self, vars);
+                }
+                reachable
+            }
+            Kind::If => {
+                let cond = frame.cond.take().unwrap_or(Expr::I32(0));
+                let ((then, then_values), (els, els_values)) = match frame.then.take() {
+                    Some(then) => (then, (frame.body, values)),
+                    None => ((frame.body, values), (Vec::new(), Some(Vec::new()))),
+                };
+                let reachable = then_values.is_some() || els_values.is_some() || frame.jumped;
+                if let (1, true, true, false, Some(a), Some(b)) = (
+                    results.len(),
+                    then.is_empty(),
+                    els.is_empty(),
+                    frame.jumped,
+                    &then_values,
+                    &els_values,
+                ) {
+                    let e = Expr::Select(
+                        Box::new(cond),
+                        Box::new(a[0].clone()),
+                        Box::new(b[0].clone()),
+                    );
+                    self.push(e, results[0]);
+                    return Ok(None);
+                }
+                let vars = if results.is_empty() {
+                    Vec::new()
+                } else {
+                    frame
+                        .result_vars
+                        .clone()
+                        .unwrap_or_else(|| results.iter().map(|ty| self.temp(*ty)).collect())
+                };
+                let assign = |mut body: Vec<Stmt>, values: Option<Vec<Expr>>| {
+                    if let Some(values) = values {
+                        body.extend(vars.iter().zip(values).map(|(v, e)| Stmt::Set(*v, e)));
+                    }
+                    body
+                };
+                let then = assign(then, then_values);
+                let els = assign(els, els_values);
+                self.emit(Stmt::If {
+                    label: Some(frame.label),
+                    cond,
+                    then,
+                    els,
+                });
+                let vars = vars.iter().map(|v| Expr::Var(*v)).collect();
+                push_values(self, vars);
+                reachable
+            }
+        };
+        if !reachable_after {
+            self.frame().unreachable = true;
+        }
+        Ok(None)
+    }
+
+    fn unary(&mut self, ty: ValType, f: impl FnOnce(Expr) -> Expr) -> Result<(), InspectError> {
+        let e = self.pop()?;
+        self.push(f(e), ty);
+        Ok(())
+    }
+
+    fn binary(&mut self, op: BinOp, ty: ValType) -> Result<(), InspectError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let e = match (op.symbol, &b) {
+            // 加负数写成减法，常见于栈指针的调整
+            ("+", Expr::I32(c)) if *c < 0 && *c != i32::MIN => Expr::binary(SUB, a, Expr::I32(-c)),
+            ("+", Expr::I64(c)) if *c < 0 && *c != i64::MIN => Expr::binary(SUB, a, Expr::I64(-c)),
+            ("^", Expr::I32(-1) | Expr::I64(-1)) => Expr::Unary("~", Box::new(a)),
+            _ => Expr::binary(op, a, b),
+        };
+        self.push(e, ty);
+        Ok(())
+    }
+
+    fn builtin(
+        &mut self,
+        name: &'static str,
+        arity: usize,
+        ty: ValType,
+    ) -> Result<(), InspectError> {
+        let args = self.pop_n(arity)?;
+        self.push(Expr::Builtin(name, args), ty);
+        Ok(())
+    }
+
+    fn cast(&mut self, casts: &[&'static str], ty: ValType) -> Result<(), InspectError> {
+        self.unary(ty, |e| casts.iter().rev().fold(e, |e, c| Expr::cast(c, e)))
+    }
+
+    fn mem_arg(&self, memarg: &MemArg) -> Result<u64, InspectError> {
+        if memarg.memory != 0 {
+            return Err(InspectError::Unsupported("multiple memories".into()));
+        }
+        Ok(memarg.offset)
+    }
+
+    fn load(
+        &mut self,
+        ty: &'static str,
+        memarg: MemArg,
+        result: ValType,
+    ) -> Result<(), InspectError> {
+        let offset = self.mem_arg(&memarg)?;
+        let addr = Box::new(self.pop()?);
+        self.push(Expr::Load { ty, addr, offset }, result);
+        Ok(())
+    }
+
+    fn store(&mut self, ty: &'static str, memarg: MemArg) -> Result<(), InspectError> {
+        let offset = self.mem_arg(&memarg)?;
+        let value = self.pop()?;
+        let addr = self.pop()?;
+        self.spill_volatile();
+        self.emit(Stmt::Store {
+            ty,
+            addr,
+            offset,
+            value,
+        });
+        Ok(())
+    }
+
+    fn set_local(&mut self, index: u32) -> Result<Var, InspectError> {
+        let value = self.pop()?;
+        let var = Var::Local(index);
+        self.spill(|e| e.uses(var));
+        self.emit(Stmt::Set(var, value));
+        Ok(var)
+    }
+
+    fn local_type(&self, index: u32) -> ValType {
+        self.local_types
+            .get(index as usize)
+            .copied()
+            .unwrap_or(ValType::I32)
+    }
+
+    // 调用：没有返回值时输出语句，一个返回值时压栈，多个返回值存入临时变量
+    fn call(&mut self, call: Expr, results: &[ValType]) {
+        self.spill_volatile();
+        match results {
+            [] => self.emit(Stmt::Expr(call)),
+            [ty] => self.push(call, *ty),
+            _ => {
+                let vars: Vec<_> = results.iter().map(|ty| self.temp(*ty)).collect();
+                self.emit(Stmt::SetMulti(vars.clone(), call));
+                for (var, ty) in vars.into_iter().zip(results) {
+                    self.push(Expr::Var(var), *ty);
+                }
+            }
+        }
+    }
+
+    fn operator(&mut self, op: Operator) -> Result<Option<Vec<Stmt>>, InspectError> {
+        use ValType::{F32, F64, I32, I64};
+        let ctx = self.ctx;
+        match op {
+            Operator::Unreachable => {
+                self.emit(Stmt::Unreachable);
+                self.frame().unreachable = true;
+            }
+            Operator::Nop => {}
+            Operator::Block { blockty } => self.enter(Kind::Block, blockty)?,
+            Operator::Loop { blockty } => self.enter(Kind::Loop, blockty)?,
+            Operator::If { blockty } => self.enter(Kind::If, blockty)?,
+            Operator::Else => {
+                let values = self.fallthrough_values()?;
+                let frame = self.frame();
+                let then = std::mem::take(&mut frame.body);
+                frame.then = Some((then, values));
+                frame.unreachable = false;
+            }
+            Operator::End => return self.end(),
+            Operator::Br { relative_depth } => {
+                self.jump(relative_depth, None)?;
+                self.frame().unreachable = true;
+            }
+            Operator::BrIf { relative_depth } => {
+                let cond = self.pop()?;
+                self.jump(relative_depth, Some(cond))?;
+            }
+            Operator::BrTable { targets } => {
+                let index = self.pop()?;
+                let default = self.target(targets.default())?;
+                let targets = targets
+                    .targets()
+                    .map(|depth| self.target(depth?))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.emit(Stmt::Switch {
+                    index,
+                    targets,
+                    default,
+                });
+                self.frame().unreachable = true;
+            }
+            Operator::Return => {
+                let arity = self.frames[0].results.len();
+                let values = self.pop_n(arity)?;
+                self.emit(Stmt::Return(values));
+                self.frame().unreachable = true;
+            }
+            Operator::Call { function_index } => {
+                let ty = ctx.func_type(function_index)?;
+                let args = self.pop_n(ty.params.len())?;
+                self.call(Expr::Call(function_index, args), &ty.results);
+            }
+            Operator::CallIndirect {
+                type_index,
+                table_index,
+            } => {
+                let ty = ctx.type_at(type_index)?;
+                let index = Box::new(self.pop()?);
+                let args = self.pop_n(ty.params.len())?;
+                let call = Expr::CallIndirect {
+                    table: table_index,
+                    index,
+                    args,
+                };
+                self.call(call, &ty.results);
+            }
+            Operator::Drop => {
+                let e = self.pop()?;
+                if e.has_call() {
+                    self.spill_volatile();
+                    self.emit(Stmt::Expr(e));
+                }
+            }
+            Operator::Select | Operator::TypedSelect { .. } => {
+                let cond = self.pop()?;
+                let b = self.pop()?;
+                let (a, ty) = match self.stack.pop() {
+                    Some(entry) => entry,
+                    None => return Err(self.invalid("operand stack underflow")),
+                };
+                // 两个值都会求值，含有调用时不能写成只求一个的 ?:
+                let (a, b) = if a.has_call() || b.has_call() {
+                    self.push(a, ty);
+                    self.push(b, ty);
+                    self.spill(Expr::has_call);
+                    let b = self.pop()?;
+                    (self.pop()?, b)
+                } else {
+                    (a, b)
+                };
+                self.push(Expr::Select(Box::new(cond), Box::new(a), Box::new(b)), ty);
+            }
+            Operator::LocalGet { local_index } => {
+                let ty = self.local_type(local_index);
+                self.push(Expr::Var(Var::Local(local_index)), ty);
+            }
+            Operator::LocalSet { local_index } => {
+                self.set_local(local_index)?;
+            }
+            Operator::LocalTee { local_index } => {
+                let var = self.set_local(local_index)?;
+                let ty = self.local_type(local_index);
+                self.push(Expr::Var(var), ty);
+            }
+            Operator::GlobalGet { global_index } => {
+                let ty = ctx
+                    .globals
+                    .get(global_index as usize)
+                    .copied()
+                    .unwrap_or(I32);
+                self.push(Expr::Global(global_index), ty);
+            }
+            Operator::GlobalSet { global_index } => {
+                let value = self.pop()?;
+                self.spill_volatile();
+                self.emit(Stmt::GlobalSet(global_index, value));
+            }
+
+            Operator::I32Load { memarg } => self.load("i32", memarg, I32)?,
+            Operator::I64Load { memarg } => self.load("i64", memarg, I64)?,
+            Operator::F32Load { memarg } => self.load("f32", memarg, F32)?,
+            Operator::F64Load { memarg } => self.load("f64", memarg, F64)?,
+            Operator::I32Load8S { memarg } => self.load("i8", memarg, I32)?,
+            Operator::I32Load8U { memarg } => self.load("u8", memarg, I32)?,
+            Operator::I32Load16S { memarg } => self.load("i16", memarg, I32)?,
+            Operator::I32Load16U { memarg } => self.load("u16", memarg, I32)?,
+            Operator::I64Load8S { memarg } => self.load("i8", memarg, I64)?,
+            Operator::I64Load8U { memarg } => self.load("u8", memarg, I64)?,
+            Operator::I64Load16S { memarg } => self.load("i16", memarg, I64)?,
+            Operator::I64Load16U { memarg } => self.load("u16", memarg, I64)?,
+            Operator::I64Load32S { memarg } => self.load("i32", memarg, I64)?,
+            Operator::I64Load32U { memarg } => self.load("u32", memarg, I64)?,
+            Operator::I32Store { memarg } => self.store("i32", memarg)?,
+            Operator::I64Store { memarg } => self.store("i64", memarg)?,
+            Operator::F32Store { memarg } => self.store("f32", memarg)?,
+            Operator::F64Store { memarg } => self.store("f64", memarg)?,
+            Operator::I32Store8 { memarg } | Operator::I64Store8 { memarg } => {
+                self.store("u8", memarg)?
+            }
+            Operator::I32Store16 { memarg } | Operator::I64Store16 { memarg } => {
+                self.store("u16", memarg)?
+            }
+            Operator::I64Store32 { memarg } => self.store("u32", memarg)?,
+            Operator::MemorySize { .. } => self.push(Expr::Builtin("memory_size", Vec::new()), I32),
+            Operator::MemoryGrow { .. } => {
+                let pages = self.pop()?;
+                self.spill_volatile();
+                let var = self.temp(I32);
+                self.emit(Stmt::Set(var, Expr::Builtin("memory_grow", vec![pages])));
+                self.push(Expr::Var(var), I32);
+            }
+            // memory.copy 允许重叠，对应 memmove
+            Operator::MemoryCopy { .. } | Operator::MemoryFill { .. } => {
+                let args = self.pop_n(3)?;
+                self.spill_volatile();
+                let name = match op {
+                    Operator::MemoryCopy { .. } => "memmove",
+                    _ => "memset",
+                };
+                self.emit(Stmt::Expr(Expr::Builtin(name, args)));
+            }
+            Operator::MemoryInit { data_index, .. } => {
+                let mut args = vec![Expr::I32(data_index as i32)];
+                args.extend(self.pop_n(3)?);
+                self.spill_volatile();
+                self.emit(Stmt::Expr(Expr::Builtin("memory_init", args)));
+            }
+            Operator::DataDrop { data_index } => {
+                let args = vec![Expr::I32(data_index as i32)];
+                self.emit(Stmt::Expr(Expr::Builtin("data_drop", args)));
+            }
+
+            Operator::I32Const { value } => self.push(Expr::I32(value), I32),
+            Operator::I64Const { value } => self.push(Expr::I64(value), I64),
+            Operator::F32Const { value } => self.push(Expr::F32(value.bits()), F32),
+            Operator::F64Const { value } => self.push(Expr::F64(value.bits()), F64),
+            Operator::RefNull { hty } => {
+                let ty = ValType::Ref(
+                    wasmparser::RefType::new(true, hty).unwrap_or(wasmparser::RefType::FUNCREF),
+                );
+                self.push(Expr::RefNull, ty);
+            }
+            Operator::RefIsNull => self.builtin("is_null", 1, I32)?,
+            Operator::RefFunc { function_index } => {
+                self.push(Expr::RefFunc(function_index), ValType::FUNCREF)
+            }
+
+            Operator::I32Eqz | Operator::I64Eqz => self.unary(I32, Expr::negate)?,
+            Operator::I32Eq | Operator::I64Eq => self.binary(EQ.int_compare(), I32)?,
+            Operator::I32Ne | Operator::I64Ne => self.binary(NE.int_compare(), I32)?,
+            Operator::I32LtS | Operator::I64LtS => self.binary(LT.int_compare(), I32)?,
+            Operator::I32GtS | Operator::I64GtS => self.binary(GT.int_compare(), I32)?,
+            Operator::I32LeS | Operator::I64LeS => self.binary(LE.int_compare(), I32)?,
+            Operator::I32GeS | Operator::I64GeS => self.binary(GE.int_compare(), I32)?,
+            Operator::I32LtU => self.binary(LT.int_compare().unsigned("u32"), I32)?,
+            Operator::I32GtU => self.binary(GT.int_compare().unsigned("u32"), I32)?,
+            Operator::I32LeU => self.binary(LE.int_compare().unsigned("u32"), I32)?,
+            Operator::I32GeU => self.binary(GE.int_compare().unsigned("u32"), I32)?,
+            Operator::I64LtU => self.binary(LT.int_compare().unsigned("u64"), I32)?,
+            Operator::I64GtU => self.binary(GT.int_compare().unsigned("u64"), I32)?,
+            Operator::I64LeU => self.binary(LE.int_compare().unsigned("u64"), I32)?,
+            Operator::I64GeU => self.binary(GE.int_compare().unsigned("u64"), I32)?,
+            Operator::F32Eq | Operator::F64Eq => self.binary(EQ, I32)?,
+            Operator::F32Ne | Operator::F64Ne => self.binary(NE, I32)?,
+            Operator::F32Lt | Operator::F64Lt => self.binary(LT, I32)?,
+            Operator::F32Gt | Operator::F64Gt => self.binary(GT, I32)?,
+            Operator::F32Le | Operator::F64Le => self.binary(LE, I32)?,
+            Operator::F32Ge | Operator::F64Ge => self.binary(GE, I32)?,
+
+            Operator::I32Clz => self.builtin("clz", 1, I32)?,
+            Operator::I32Ctz => self.builtin("ctz", 1, I32)?,
+            Operator::I32Popcnt => self.builtin("popcnt", 1, I32)?,
+            Operator::I64Clz => self.builtin("clz", 1, I64)?,
+            Operator::I64Ctz => self.builtin("ctz", 1, I64)?,
+            Operator::I64Popcnt => self.builtin("popcnt", 1, I64)?,
+            Operator::I32Add => self.binary(ADD, I32)?,
+            Operator::I32Sub => self.binary(SUB, I32)?,
+            Operator::I32Mul => self.binary(MUL, I32)?,
+            Operator::I32DivS => self.binary(DIV, I32)?,
+            Operator::I32DivU => self.binary(DIV.unsigned("u32"), I32)?,
+            Operator::I32RemS => self.binary(REM, I32)?,
+            Operator::I32RemU => self.binary(REM.unsigned("u32"), I32)?,
+            Operator::I32And => self.binary(AND, I32)?,
+            Operator::I32Or => self.binary(OR, I32)?,
+            Operator::I32Xor => self.binary(XOR, I32)?,
+            Operator::I32Shl => self.binary(SHL, I32)?,
+            Operator::I32ShrS => self.binary(SHR, I32)?,
+            Operator::I32ShrU => self.binary(SHR.unsigned("u32").left_cast_only(), I32)?,
+            Operator::I32Rotl => self.builtin("rotl", 2, I32)?,
+            Operator::I32Rotr => self.builtin("rotr", 2, I32)?,
+            Operator::I64Add => self.binary(ADD, I64)?,
+            Operator::I64Sub => self.binary(SUB, I64)?,
+            Operator::I64Mul => self.binary(MUL, I64)?,
+            Operator::I64DivS => self.binary(DIV, I64)?,
+            Operator::I64DivU => self.binary(DIV.unsigned("u64"), I64)?,
+            Operator::I64RemS => self.binary(REM, I64)?,
+            Operator::I64RemU => self.binary(REM.unsigned("u64"), I64)?,
+            Operator::I64And => self.binary(AND, I64)?,
+            Operator::I64Or => self.binary(OR, I64)?,
+            Operator::I64Xor => self.binary(XOR, I64)?,
+            Operator::I64Shl => self.binary(SHL, I64)?,
+            Operator::I64ShrS => self.binary(SHR, I64)?,
+            Operator::I64ShrU => self.binary(SHR.unsigned("u64").left_cast_only(), I64)?,
+            Operator::I64Rotl => self.builtin("rotl", 2, I64)?,
+            Operator::I64Rotr => self.builtin("rotr", 2, I64)?,
+
+            Operator::F32Abs => self.builtin("fabs", 1, F32)?,
+            Operator::F32Neg => self.unary(F32, |e| Expr::Unary("-", Box::new(e)))?,
+            Operator::F32Ceil => self.builtin("ceil", 1, F32)?,
+            Operator::F32Floor => self.builtin("floor", 1, F32)?,
+            Operator::F32Trunc => self.builtin("trunc", 1, F32)?,
+            Operator::F32Nearest => self.builtin("nearest", 1, F32)?,
+            Operator::F32Sqrt => self.builtin("sqrt", 1, F32)?,
+            Operator::F32Add => self.binary(ADD, F32)?,
+            Operator::F32Sub => self.binary(SUB, F32)?,
+            Operator::F32Mul => self.binary(MUL, F32)?,
+            Operator::F32Div => self.binary(DIV, F32)?,
+            Operator::F32Min => self.builtin("fmin", 2, F32)?,
+            Operator::F32Max => self.builtin("fmax", 2, F32)?,
+            Operator::F32Copysign => self.builtin("copysign", 2, F32)?,
+            Operator::F64Abs => self.builtin("fabs", 1, F64)?,
+            Operator::F64Neg => self.unary(F64, |e| Expr::Unary("-", Box::new(e)))?,
+            Operator::F64Ceil => self.builtin("ceil", 1, F64)?,
+            Operator::F64Floor => self.builtin("floor", 1, F64)?,
+            Operator::F64Trunc => self.builtin("trunc", 1, F64)?,
+            Operator::F64Nearest => self.builtin("nearest", 1, F64)?,
+            Operator::F64Sqrt => self.builtin("sqrt", 1, F64)?,
+            Operator::F64Add => self.binary(ADD, F64)?,
+            Operator::F64Sub => self.binary(SUB, F64)?,
+            Operator::F64Mul => self.binary(MUL, F64)?,
+            Operator::F64Div => self.binary(DIV, F64)?,
+            Operator::F64Min => self.builtin("fmin", 2, F64)?,
+            Operator::F64Max => self.builtin("fmax", 2, F64)?,
+            Operator::F64Copysign => self.builtin("copysign", 2, F64)?,
+
+            Operator::I32WrapI64 => self.cast(&["i32"], I32)?,
+            Operator::I32TruncF32S | Operator::I32TruncF64S => self.cast(&["i32"], I32)?,
+            Operator::I32TruncF32U | Operator::I32TruncF64U => self.cast(&["u32"], I32)?,
+            Operator::I64TruncF32S | Operator::I64TruncF64S => self.cast(&["i64"], I64)?,
+            Operator::I64TruncF32U | Operator::I64TruncF64U => self.cast(&["u64"], I64)?,
+            Operator::I64ExtendI32S => self.cast(&["i64"], I64)?,
+            Operator::I64ExtendI32U => self.cast(&["u64", "u32"], I64)?,
+            Operator::F32ConvertI32S | Operator::F32ConvertI64S => self.cast(&["f32"], F32)?,
+            Operator::F32ConvertI32U => self.cast(&["f32", "u32"], F32)?,
+            Operator::F32ConvertI64U => self.cast(&["f32", "u64"], F32)?,
+            Operator::F64ConvertI32S | Operator::F64ConvertI64S => self.cast(&["f64"], F64)?,
+            Operator::F64ConvertI32U => self.cast(&["f64", "u32"], F64)?,
+            Operator::F64ConvertI64U => self.cast(&["f64", "u64"], F64)?,
+            Operator::F32DemoteF64 => self.cast(&["f32"], F32)?,
+            Operator::F64PromoteF32 => self.cast(&["f64"], F64)?,
+            Operator::I32ReinterpretF32 => self.builtin("bits", 1, I32)?,
+            Operator::I64ReinterpretF64 => self.builtin("bits", 1, I64)?,
+            Operator::F32ReinterpretI32 => self.builtin("from_bits_f32", 1, F32)?,
+            Operator::F64ReinterpretI64 => self.builtin("from_bits_f64", 1, F64)?,
+            Operator::I32Extend8S => self.cast(&["i32", "i8"], I32)?,
+            Operator::I32Extend16S => self.cast(&["i32", "i16"], I32)?,
+            Operator::I64Extend8S => self.cast(&["i64", "i8"], I64)?,
+            Operator::I64Extend16S => self.cast(&["i64", "i16"], I64)?,
+            Operator::I64Extend32S => self.cast(&["i64", "i32"], I64)?,
+            Operator::I32TruncSatF32S | Operator::I32TruncSatF64S => {
+                self.builtin("sat_i32", 1, I32)?
+            }
+            Operator::I32TruncSatF32U | Operator::I32TruncSatF64U => {
+                self.builtin("sat_u32", 1, I32)?
+            }
+            Operator::I64TruncSatF32S | Operator::I64TruncSatF64S => {
+                self.builtin("sat_i64", 1, I64)?
+            }
+            Operator::I64TruncSatF32U | Operator::I64TruncSatF64U => {
+                self.builtin("sat_u64", 1, I64)?
+            }
+            op => {
+                let name = format!("{op:?}");
+                let name = name.split([' ', '{']).next().unwrap_or_default();
+                return Err(InspectError::Unsupported(format!(
+                    "{name} at offset {}",
+                    self.offset
+                )));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lift_wat(wat: &str, function: u32) -> Lifted {
+        let bytes = wat::parse_str(wat).unwrap();
+        let ctx = Context::parse(&bytes).unwrap();
+        lift(&ctx, function).unwrap()
+    }
+
+    fn local(index: u32) -> Expr {
+        Expr::Var(Var::Local(index))
+    }
+
+    fn temp(index: u32) -> Expr {
+        Expr::Var(Var::Temp(index))
+    }
+
+    fn br(label: Label, cond: Option<Expr>) -> Stmt {
+        Stmt::Jump {
+            target: Target::Break(label),
+            cond,
+        }
+    }
+
+    #[test]
+    fn lift_expressions_test() {
+        let lifted = lift_wat(
+            r#"(module (func (param i32 i32) (result i32)
+                (i32.add (i32.mul (local.get 0) (i32.const 3)) (i32.xor (local.get 1) (i32.const -1)))))"#,
+            0,
+        );
+        assert_eq!(
+            lifted.body,
+            [Stmt::Return(vec![Expr::binary(
+                ADD,
+                Expr::binary(MUL, local(0), Expr::I32(3)),
+                Expr::Unary("~", Box::new(local(1)))
+            )])]
+        );
+        assert!(lifted.temps.is_empty());
+
+        // 加负数写成减法，eqz 是逻辑取反，无符号比较给操作数加类型转换
+        let lifted = lift_wat(
+            r#"(module (func (param i32) (result i32)
+                (i32.eqz (i32.lt_u (i32.add (local.get 0) (i32.const -16)) (i32.const 8)))))"#,
+            0,
+        );
+        let sub = Expr::binary(SUB, local(0), Expr::I32(16));
+        assert_eq!(
+            lifted.body,
+            [Stmt::Return(vec![Expr::binary(
+                GE.int_compare().unsigned("u32"),
+                sub,
+                Expr::I32(8)
+            )])]
+        );
+    }
+
+    #[test]
+    fn lift_evaluation_order_test() {
+        // 写 local 0 之前读到的旧值、写内存之前读到的内存都要先存入临时变量
+        let lifted = lift_wat(
+            r#"(module (memory 1) (func (param i32) (result i32)
+                (local.get 0)
+                (local.set 0 (i32.const 1))
+                (i32.load (i32.const 8))
+                (i32.store (i32.const 8) (local.get 0))
+                (i32.sub)
+                (i32.add (local.get 0))))"#,
+            0,
+        );
+        let load = Expr::Load {
+            ty: "i32",
+            addr: Box::new(Expr::I32(8)),
+            offset: 0,
+        };
+        assert_eq!(
+            lifted.body,
+            [
+                Stmt::Set(Var::Temp(0), local(0)),
+                Stmt::Set(Var::Local(0), Expr::I32(1)),
+                Stmt::Set(Var::Temp(1), load),
+                Stmt::Store {
+                    ty: "i32",
+                    addr: Expr::I32(8),
+                    offset: 0,
+                    value: local(0),
+                },
+                Stmt::Return(vec![Expr::binary(
+                    ADD,
+                    Expr::binary(SUB, temp(0), temp(1)),
+                    local(0)
+                )]),
+            ]
+        );
+        assert_eq!(lifted.temps, [ValType::I32, ValType::I32]);
+
+        // 丢弃的值中有调用时保留调用
+        let lifted = lift_wat(
+            r#"(module
+                (func $f (result i32) (i32.const 1))
+                (func (drop (i32.add (call $f) (i32.const 2))) (drop (i32.const 3))))"#,
+            1,
+        );
+        assert_eq!(
+            lifted.body,
+            [Stmt::Expr(Expr::binary(
+                ADD,
+                Expr::Call(0, Vec::new()),
+                Expr::I32(2)
+            ))]
+        );
+    }
+
+    #[test]
+    fn lift_multi_value_test() {
+        // 多个返回值的调用存入临时变量，多个返回值的函数直接返回
+        let wat = r#"(module
+            (func $pair (result i32 i64) (i32.const 1) (i64.const 2))
+            (func (result i64 i32) (local i64)
+                (call $pair)
+                (local.set 0)
+                (i64.extend_i32_u)
+                (local.get 0)
+                (i32.wrap_i64))
+            (func (param i32) (result i32 i32)
+                (block (result i32 i32)
+                    (i32.const 1)
+                    (i32.const 2)
+                    (br_if 0 (local.get 0))
+                    (drop)
+                    (drop)
+                    (i32.const 3)
+                    (i32.const 4))))"#;
+        let lifted = lift_wat(wat, 0);
+        assert_eq!(lifted.results, [ValType::I32, ValType::I64]);
+        assert_eq!(
+            lifted.body,
+            [Stmt::Return(vec![Expr::I32(1), Expr::I64(2)])]
+        );
+
+        let lifted = lift_wat(wat, 1);
+        assert_eq!(
+            lifted.body,
+            [
+                Stmt::SetMulti(vec![Var::Temp(0), Var::Temp(1)], Expr::Call(0, Vec::new())),
+                Stmt::Set(Var::Local(0), temp(1)),
+                Stmt::Return(vec![
+                    Expr::cast("u64", Expr::cast("u32", temp(0))),
+                    Expr::cast("i32", local(0)),
+                ]),
+            ]
+        );
+        assert_eq!(lifted.temps, [ValType::I32, ValType::I64]);
+
+        // 带值跳出 block 时，两个出口都把结果写入同一组临时变量
+        let lifted = lift_wat(wat, 2);
+        assert_eq!(
+            lifted.body,
+            [
+                Stmt::Block {
+                    label: 1,
+                    body: vec![
+                        Stmt::If {
+                            label: None,
+                            cond: local(0),
+                            then: vec![
+                                Stmt::Set(Var::Temp(0), Expr::I32(1)),
+                                Stmt::Set(Var::Temp(1), Expr::I32(2)),
+                                br(1, None),
+                            ],
+                            els: Vec::new(),
+                        },
+                        Stmt::Set(Var::Temp(0), Expr::I32(3)),
+                        Stmt::Set(Var::Temp(1), Expr::I32(4)),
+                    ],
+                },
+                Stmt::Return(vec![temp(0), temp(1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn lift_unreachable_test() {
+        // return / unreachable 之后的指令（包括嵌套的块）都不输出，函数末尾也不再补 return
+        let lifted = lift_wat(
+            r#"(module (func (param i32) (result i32)
+                (block
+                    (br_if 0 (local.get 0))
+                    (return (i32.const 1))
+                    (i32.const 99)
+                    (block (drop (i32.const 2)))
+                    (drop))
+                (unreachable)
+                (i32.const 5)))"#,
+            0,
+        );
+        assert_eq!(
+            lifted.body,
+            [
+                Stmt::Block {
+                    label: 1,
+                    body: vec![br(1, Some(local(0))), Stmt::Return(vec![Expr::I32(1)])],
+                },
+                Stmt::Unreachable,
+            ]
+        );
+
+        // 一个分支不可达时，结果只来自另一个分支
+        let lifted = lift_wat(
+            r#"(module (func (param i32) (result i32)
+                (if (result i32) (local.get 0) (then (unreachable)) (else (i32.const 7)))))"#,
+            0,
+        );
+        assert_eq!(
+            lifted.body,
+            [
+                Stmt::If {
+                    label: Some(1),
+                    cond: local(0),
+                    then: vec![Stmt::Unreachable],
+                    els: vec![Stmt::Set(Var::Temp(0), Expr::I32(7))],
+                },
+                Stmt::Return(vec![temp(0)]),
+            ]
+        );
+
+        // 没有出口的循环之后不可达，有返回值的函数也不需要 return
+        let lifted = lift_wat("(module (func (result i32) (loop (br 0))))", 0);
+        assert_eq!(
+            lifted.body,
+            [Stmt::Loop {
+                label: 1,
+                body: vec![Stmt::Jump {
+                    target: Target::Continue(1),
+                    cond: None,
+                }],
+            }]
+        );
+    }
+}
@@ -0,0 +1,348 @@
+mod ast;
+mod lift;
+mod print;
+mod structure;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use wasm_bindgen::prelude::*;
+use wasmparser::{CompositeInnerType, FunctionBody, Parser, Payload, TypeRef, ValType};
+
+use super::inspect_error::InspectError;
+use super::module_info::ModuleInfo;
+use super::strings::extract_strings;
+
+// 把函数反编译为类 C 的伪代码，面向 rustc 生成的简单函数（如逐字节异或的循环）：
+// 栈式指令提升为表达式树（lift），block / loop / br_if 还原为 if / while / do-while（structure），
+// 最后按 C 的运算符优先级打印（print）。异常处理、GC 、SIMD 等指令不支持
+
+// 函数类型，GC 提案的结构体 / 数组类型为 None
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FuncType {
+    params: Vec<ValType>,
+    results: Vec<ValType>,
+}
+
+// 反编译需要的模块信息，函数体借用调用方的字节
+struct Context<'a> {
+    info: ModuleInfo,
+    types: Vec<Option<FuncType>>,
+    globals: Vec<ValType>,
+    // 模块内定义的函数体，下标为函数索引减去导入函数的个数
+    bodies: Vec<FunctionBody<'a>>,
+    // 数据段中字符串的地址 -> 内容，用来注释常量
+    strings: BTreeMap<u64, String>,
+}
+
+impl<'a> Context<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<Self, InspectError> {
+        let info = ModuleInfo::parse(bytes)?;
+        let mut types = Vec::new();
+        let mut globals = Vec::new();
+        let mut bodies = Vec::new();
+        for payload in Parser::new(0).parse_all(bytes) {
+            match payload? {
+                Payload::TypeSection(reader) => {
+                    for rec_group in reader {
+                        for sub_type in rec_group?.into_types() {
+                            types.push(match &sub_type.composite_type.inner {
+                                CompositeInnerType::Func(ty) => Some(FuncType {
+                                    params: ty.params().to_vec(),
+                                    results: ty.results().to_vec(),
+                                }),
+                                _ => None,
+                            });
+                        }
+                    }
+                }
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        if let TypeRef::Global(ty) = import?.ty {
+                            globals.push(ty.content_type);
+                        }
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        globals.push(global?.ty.content_type);
+                    }
+                }
+                Payload::CodeSectionEntry(body) => bodies.push(body),
+                _ => {}
+            }
+        }
+        let strings = extract_strings(bytes, 4)?
+            .into_iter()
+            .filter_map(|s| Some((s.address?, s.text)))
+            .collect();
+        Ok(Context {
+            info,
+            types,
+            globals,
+            bodies,
+            strings,
+        })
+    }
+
+    fn type_at(&self, index: u32) -> Result<&FuncType, InspectError> {
+        self.types
+            .get(index as usize)
+            .and_then(Option::as_ref)
+            .ok_or_else(|| InspectError::Unsupported(format!("non-function type {index}")))
+    }
+
+    fn func_type(&self, function: u32) -> Result<&FuncType, InspectError> {
+        let info = self
+            .info
+            .functions
+            .get(function as usize)
+            .ok_or_else(|| InspectError::FunctionNotFound(function.to_string()))?;
+        self.type_at(info.type_index)
+    }
+
+    fn body(&self, function: u32) -> Result<FunctionBody<'a>, InspectError> {
+        let local = (function as usize)
+            .checked_sub(self.info.imported_function_count())
+            .and_then(|index| self.bodies.get(index));
+        local
+            .cloned()
+            .ok_or_else(|| InspectError::ImportedFunction(function.to_string()))
+    }
+
+    fn decompile(&self, function: u32) -> Result<String, InspectError> {
+        let lifted = lift::lift(self, function)?;
+        let body = structure::structure(lifted.body.clone());
+        Ok(print::print_function(self, function, &lifted, &body))
+    }
+}
+
+/// 把一个函数反编译为类 C 伪代码。`function` 可以是函数索引、name section 中的名字或导出名
+#[wasm_bindgen]
+pub fn decompile_wasm_function(bytes: &[u8], function: &str) -> Result<String, InspectError> {
+    let ctx = Context::parse(bytes)?;
+    let target = ctx
+        .info
+        .find_function(function)
+        .ok_or_else(|| InspectError::FunctionNotFound(function.to_string()))?;
+    if target.body_offset.is_none() {
+        return Err(InspectError::ImportedFunction(function.to_string()));
+    }
+    ctx.decompile(target.index)
+}
+
+/// 反编译模块中定义的所有函数，含有不支持的指令的函数输出为一行注释
+#[wasm_bindgen]
+pub fn decompile_wasm(bytes: &[u8]) -> Result<String, InspectError> {
+    let ctx = Context::parse(bytes)?;
+    let mut out = String::new();
+    for function in ctx
+        .info
+        .functions
+        .iter()
+        .filter(|f| f.body_offset.is_some())
+    {
+        match ctx.decompile(function.index) {
+            Ok(text) => out.push_str(&text),
+            Err(e) => {
+                let name = ctx.info.function_name(function.index).unwrap_or_default();
+                let _ = writeln!(out, "// func {} {name}: {e}", function.index);
+            }
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decompile(wat: &str, function: &str) -> String {
+        let bytes = wat::parse_str(wat).unwrap();
+        decompile_wasm_function(&bytes, function).unwrap()
+    }
+
+    // rustc 生成的 string_xor ：密钥 "hctf" 在数据段，循环写成 do-while
+    const STRING_XOR_WAT: &str = r#"
+        (module
+            (memory 17)
+            (global $__stack_pointer (mut i32) (i32.const 1048576))
+            (data (i32.const 1048576) "hctf")
+            (func $string_xor (param i32 i32 i32) (local i32 i32)
+                (block
+                    (br_if 0 (i32.eqz (local.get 2)))
+                    (local.set 3 (i32.const 0))
+                    (loop
+                        (i32.store8 (i32.add (local.get 0) (local.get 3))
+                            (i32.xor
+                                (i32.load8_u (i32.add (local.get 1) (local.get 3)))
+                                (i32.load8_u offset=1048576 (i32.and (local.get 3) (i32.const 3)))))
+                        (br_if 0 (i32.ne
+                            (local.tee 3 (i32.add (local.get 3) (i32.const 1)))
+                            (local.get 2))))))
+            (func $while (export "count") (param $n i32) (result i32) (local $i i32) (local $sum i32)
+                (block $done
+                    (loop $next
+                        (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                        (local.set $sum (i32.add (local.get $sum) (local.get $i)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br $next)))
+                (local.get $sum)))
+    "#;
+
+    const MISC_WAT: &str = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (type $pair (func (result i32 i32)))
+            (memory 1)
+            (global $__stack_pointer (mut i32) (i32.const 65536))
+            (func $pick (param $a i32) (param $b i32) (result i32)
+                (if (result i32) (i32.lt_s (local.get $a) (local.get $b))
+                    (then (local.get $a))
+                    (else (local.get $b))))
+            (func $classify (param $x i32) (result i32) (local $r i32)
+                (if (i32.eqz (local.get $x))
+                    (then (local.set $r (i32.const 10)))
+                    (else
+                        (call $log (local.get $x))
+                        (local.set $r (i32.shr_u (local.get $x) (i32.const 1)))))
+                (block (result i32)
+                    (drop (br_if 0 (i32.const 7) (i32.gt_u (local.get $r) (i32.const 100))))
+                    (i32.mul (local.get $r) (i32.const 3))))
+            (func $dispatch (param $op i32) (result i32) (local $sp i32)
+                (global.set $__stack_pointer
+                    (local.tee $sp (i32.sub (global.get $__stack_pointer) (i32.const 16))))
+                (block $c
+                    (block $b
+                        (block $a
+                            (br_table $a $b $c (local.get $op)))
+                        (i32.store offset=12 (local.get $sp) (i32.const 1))
+                        (br $c))
+                    (call $log (i32.load offset=12 (local.get $sp))))
+                (global.set $__stack_pointer (i32.add (local.get $sp) (i32.const 16)))
+                (i32.load offset=12 (local.get $sp)))
+            (func $nested (param $n i32) (local $i i32) (local $j i32)
+                (block $outer
+                    (loop $rows
+                        (local.set $j (i32.const 0))
+                        (loop $cols
+                            (br_if $outer (i32.eq (local.get $j) (i32.const 99)))
+                            (local.set $j (i32.add (local.get $j) (i32.const 1)))
+                            (br_if $cols (i32.lt_u (local.get $j) (local.get $n))))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br_if $rows (i32.lt_u (local.get $i) (local.get $n))))))
+            (func $multi (type $pair) (i32.const 1) (i32.const 2))
+            (func $use_multi (result i32) (local $t i32)
+                (call $multi)
+                (local.set $t)
+                (i32.add (local.get $t)))
+        )
+    "#;
+
+    #[test]
+    fn decompile_string_xor_test() {
+        assert_eq!(
+            decompile(STRING_XOR_WAT, "string_xor"),
+            "// func 0
+void string_xor(i32 p0, i32 p1, i32 p2) {
+    i32 l3, l4;
+
+    if (p2) {
+        l3 = 0;
+        do {
+            *(u8 *)(p0 + l3) = *(u8 *)(p1 + l3) ^ ((u8 *)0x100000 /* \"hctf\" */)[l3 & 3];
+            l3 += 1;
+        } while (l3 != p2);
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn decompile_loops_test() {
+        assert_eq!(
+            decompile(STRING_XOR_WAT, "count"),
+            "// func 1
+i32 while(i32 n) {
+    i32 i, sum;
+
+    while ((u32)i < (u32)n) {
+        sum += i;
+        i += 1;
+    }
+    return sum;
+}
+"
+        );
+        // 跳出外层循环需要标签
+        let nested = decompile(MISC_WAT, "nested");
+        assert!(nested.contains("    L2: do {\n        j = 0;\n        do {\n"));
+        assert!(nested.contains("            if (j == 99) break L2;\n"));
+        assert!(nested.contains("        } while ((u32)j < (u32)n);\n"));
+    }
+
+    #[test]
+    fn decompile_values_test() {
+        assert!(decompile(MISC_WAT, "pick").contains("    return a < b ? a : b;\n"));
+        // if / else 的两个分支都有语句，带值跳出 block 的结果存入临时变量
+        let classify = decompile(MISC_WAT, "classify");
+        assert!(classify.contains(
+            "    if (!x) {\n        r = 10;\n    } else {\n        log(x);\n        r = (u32)x >> 1;\n    }\n"
+        ));
+        assert!(classify.contains(
+            "    if ((u32)r > 100) {\n        t0 = 7;\n    } else {\n        t0 = r * 3;\n    }\n    return t0;\n"
+        ));
+        // 多个返回值
+        assert!(decompile(MISC_WAT, "multi").starts_with("// func 5\n(i32, i32) multi() {\n"));
+        let use_multi = decompile(MISC_WAT, "use_multi");
+        assert!(use_multi.contains("    (t0, t1) = multi();\n    t = t1;\n    return t0 + t;\n"));
+    }
+
+    #[test]
+    fn decompile_switch_test() {
+        let dispatch = decompile(MISC_WAT, "dispatch");
+        assert!(dispatch.contains("    sp = __stack_pointer - 16;\n    __stack_pointer = sp;\n"));
+        assert!(dispatch.contains(
+            "switch (op) {\n                    case 0: break L3;\n                    case 1: break L2;\n                    default: break L1;\n"
+        ));
+        assert!(dispatch.contains("            *(i32 *)(sp + 12) = 1;\n            break L1;\n"));
+        assert!(dispatch.contains("        log(*(i32 *)(sp + 12));\n"));
+        assert!(dispatch.ends_with("    return *(i32 *)(sp + 12);\n}\n"));
+    }
+
+    #[test]
+    fn decompile_errors_test() {
+        let wat = r#"
+            (module
+                (import "env" "log" (func $log (param i32)))
+                (func $simd (drop (v128.const i64x2 0 0)))
+                (func $params (result i32)
+                    (i32.const 1)
+                    (block (param i32) (result i32) (i32.add (i32.const 2)))))
+        "#;
+        let bytes = wat::parse_str(wat).unwrap();
+        assert!(matches!(
+            decompile_wasm_function(&bytes, "missing"),
+            Err(InspectError::FunctionNotFound(_))
+        ));
+        assert!(matches!(
+            decompile_wasm_function(&bytes, "env.log"),
+            Err(InspectError::ImportedFunction(_))
+        ));
+        assert!(matches!(
+            decompile_wasm_function(&bytes, "params"),
+            Err(InspectError::Unsupported(feature)) if feature == "block parameters"
+        ));
+
+        // 整个模块反编译时，不支持的函数输出为注释
+        let all = decompile_wasm(&bytes).unwrap();
+        assert!(all.starts_with("// func 1 simd: unsupported wasm feature: V128Const at offset "));
+        assert!(all.contains("// func 2 params: unsupported wasm feature: block parameters\n"));
+
+        let all = decompile_wasm(&wat::parse_str(MISC_WAT).unwrap()).unwrap();
+        assert_eq!(all.matches("// func ").count(), 6);
+        assert!(!all.contains("unsupported"));
+    }
+}
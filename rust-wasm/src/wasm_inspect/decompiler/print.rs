@@ -0,0 +1,514 @@
+use std::collections::{BTreeMap, HashSet};
+
+use wasmparser::ValType;
+
+use super::Context;
+use super::ast::*;
+use super::lift::Lifted;
+
+// 按 C 的语法和运算符优先级打印。与 C 不同的地方：
+// - 类型名沿用 wasm 的 i32 / i64 / f32 / f64 ，内存访问写成 `*(u8 *)(addr)` 这样的指针转换
+// - 跳出外层循环或 block 时写成 Java 式的带标签 break / continue ，标签只在用到时输出
+// - 返回多个值写成 `return (a, b);`
+
+const INDENT: &str = "    ";
+// 注释中字符串的最大字符数
+const MAX_STRING: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Loop(Label),
+    Block(Label),
+    Switch,
+}
+
+struct Printer<'c, 'a> {
+    ctx: &'c Context<'a>,
+    function: u32,
+    params: usize,
+    lines: Vec<String>,
+    indent: usize,
+    scopes: Vec<Scope>,
+    // 需要输出标签的 block 和循环
+    labels: HashSet<Label>,
+}
+
+pub(super) fn print_function(
+    ctx: &Context,
+    function: u32,
+    lifted: &Lifted,
+    body: &[Stmt],
+) -> String {
+    let mut p = Printer {
+        ctx,
+        function,
+        params: lifted.params.len(),
+        lines: Vec::new(),
+        indent: 1,
+        scopes: Vec::new(),
+        labels: HashSet::new(),
+    };
+
+    // 局部变量和临时变量按类型分组声明
+    let mut decls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (i, ty) in lifted.locals.iter().enumerate() {
+        let var = Var::Local((p.params + i) as u32);
+        decls.entry(ty.to_string()).or_default().push(p.var(var));
+    }
+    for (i, ty) in lifted.temps.iter().enumerate() {
+        decls
+            .entry(ty.to_string())
+            .or_default()
+            .push(p.var(Var::Temp(i as u32)));
+    }
+    for (ty, names) in &decls {
+        p.line(format!("{ty} {};", names.join(", ")));
+    }
+    if !decls.is_empty() && !body.is_empty() {
+        p.line(String::new());
+    }
+    p.stmts(body);
+
+    let result = match &lifted.results[..] {
+        [] => "void".to_string(),
+        [ty] => ty.to_string(),
+        results => format!("({})", type_list(results)),
+    };
+    let params: Vec<_> = lifted
+        .params
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| format!("{ty} {}", p.var(Var::Local(i as u32))))
+        .collect();
+    let mut out = format!("// func {function}\n");
+    out.push_str(&format!(
+        "{result} {}({}) {{\n",
+        p.function_name(function),
+        params.join(", ")
+    ));
+    for line in &p.lines {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn type_list(types: &[ValType]) -> String {
+    types
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Printer<'_, '_> {
+    fn line(&mut self, text: String) {
+        self.lines
+            .push(format!("{}{text}", INDENT.repeat(self.indent)));
+    }
+
+    fn function_name(&self, index: u32) -> String {
+        self.ctx
+            .info
+            .function_name(index)
+            .map_or_else(|| format!("func_{index}"), str::to_string)
+    }
+
+    fn var(&self, var: Var) -> String {
+        match var {
+            Var::Local(index) => {
+                let named = self
+                    .ctx
+                    .info
+                    .names
+                    .locals
+                    .get(&self.function)
+                    .and_then(|locals| locals.get(&index));
+                match named {
+                    Some(name) => name.clone(),
+                    None if (index as usize) < self.params => format!("p{index}"),
+                    None => format!("l{index}"),
+                }
+            }
+            Var::Temp(index) => format!("t{index}"),
+        }
+    }
+
+    fn global(&self, index: u32) -> String {
+        self.ctx
+            .info
+            .names
+            .globals
+            .get(&index)
+            .cloned()
+            .unwrap_or_else(|| format!("g{index}"))
+    }
+
+    // 大于 0xffff 的值按十六进制输出，正好是数据段中字符串的地址时附上字符串
+    fn constant(&self, value: i64, bits: u32) -> String {
+        let unsigned = if bits == 32 {
+            value as u32 as u64
+        } else {
+            value as u64
+        };
+        if (-0xffff..=0xffff).contains(&value) {
+            return value.to_string();
+        }
+        match self.ctx.strings.get(&unsigned) {
+            Some(text) => {
+                let text: String = text.chars().take(MAX_STRING).collect();
+                format!("{unsigned:#x} /* {text:?} */")
+            }
+            None => format!("{unsigned:#x}"),
+        }
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    fn nested(&mut self, scope: Option<Scope>, body: &[Stmt]) {
+        self.indent += 1;
+        self.scopes.extend(scope);
+        self.stmts(body);
+        if scope.is_some() {
+            self.scopes.pop();
+        }
+        self.indent -= 1;
+    }
+
+    // 先打印循环体，再按需要给开头一行加上标签
+    fn labeled(&mut self, label: Label, scope: Scope, head: String, body: &[Stmt], tail: String) {
+        let start = self.lines.len();
+        self.line(head);
+        self.nested(Some(scope), body);
+        self.line(tail);
+        // break 到 block 总是带标签的
+        if self.labels.contains(&label) || scope == Scope::Block(label) {
+            let head = self.lines[start].trim_start().to_string();
+            self.lines[start] = format!("{}L{label}: {head}", INDENT.repeat(self.indent));
+        }
+    }
+
+    fn jump(&mut self, target: Target) -> String {
+        match target {
+            Target::Return => "return;".to_string(),
+            Target::Break(label) => {
+                // C 的 break 跳出最内层的循环或 switch ，block 只能用带标签的 break
+                let innermost = self
+                    .scopes
+                    .iter()
+                    .rev()
+                    .find(|s| !matches!(s, Scope::Block(_)));
+                if innermost == Some(&Scope::Loop(label)) {
+                    "break;".to_string()
+                } else {
+                    self.labels.insert(label);
+                    format!("break L{label};")
+                }
+            }
+            Target::Continue(label) => {
+                let innermost = self.scopes.iter().rev().find_map(|s| match s {
+                    Scope::Loop(l) => Some(*l),
+                    _ => None,
+                });
+                if innermost == Some(label) {
+                    "continue;".to_string()
+                } else {
+                    self.labels.insert(label);
+                    format!("continue L{label};")
+                }
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(e) => {
+                let text = self.expr(e, 0);
+                self.line(format!("{text};"));
+            }
+            Stmt::Set(var, e) => {
+                let name = self.var(*var);
+                let text = self.assign(&name, Expr::Var(*var), e);
+                self.line(text);
+            }
+            Stmt::SetMulti(vars, e) => {
+                let names: Vec<_> = vars.iter().map(|v| self.var(*v)).collect();
+                let text = self.expr(e, 0);
+                self.line(format!("({}) = {text};", names.join(", ")));
+            }
+            Stmt::GlobalSet(index, e) => {
+                let name = self.global(*index);
+                let text = self.assign(&name, Expr::Global(*index), e);
+                self.line(text);
+            }
+            Stmt::Store {
+                ty,
+                addr,
+                offset,
+                value,
+            } => {
+                let target = self.memory(ty, addr, *offset);
+                let load = Expr::Load {
+                    ty,
+                    addr: Box::new(addr.clone()),
+                    offset: *offset,
+                };
+                let text = self.assign(&target, load, value);
+                self.line(text);
+            }
+            Stmt::Block { label, body } => {
+                self.labeled(
+                    *label,
+                    Scope::Block(*label),
+                    "{".to_string(),
+                    body,
+                    "}".to_string(),
+                );
+            }
+            Stmt::Loop { label, body } => {
+                // 结构化之后不会出现，按 while (true) 打印
+                self.labeled(
+                    *label,
+                    Scope::Loop(*label),
+                    "while (true) {".to_string(),
+                    body,
+                    "}".to_string(),
+                );
+            }
+            Stmt::While { label, cond, body } => {
+                let cond = cond
+                    .as_ref()
+                    .map_or_else(|| "true".to_string(), |c| self.expr(c, 0));
+                self.labeled(
+                    *label,
+                    Scope::Loop(*label),
+                    format!("while ({cond}) {{"),
+                    body,
+                    "}".to_string(),
+                );
+            }
+            Stmt::DoWhile { label, body, cond } => {
+                let cond = self.expr(cond, 0);
+                self.labeled(
+                    *label,
+                    Scope::Loop(*label),
+                    "do {".to_string(),
+                    body,
+                    format!("}} while ({cond});"),
+                );
+            }
+            Stmt::If {
+                cond, then, els, ..
+            } => {
+                let cond = self.expr(cond, 0);
+                self.line(format!("if ({cond}) {{"));
+                self.else_chain(then, els);
+            }
+            Stmt::Jump { target, cond } => {
+                let jump = self.jump(*target);
+                match cond {
+                    Some(cond) => {
+                        let cond = self.expr(cond, 0);
+                        self.line(format!("if ({cond}) {jump}"));
+                    }
+                    None => self.line(jump),
+                }
+            }
+            Stmt::Switch {
+                index,
+                targets,
+                default,
+            } => {
+                let index = self.expr(index, 0);
+                self.line(format!("switch ({index}) {{"));
+                self.scopes.push(Scope::Switch);
+                // 跳到同一目标的 case 合并为一行
+                let mut cases: Vec<(Target, Vec<usize>)> = Vec::new();
+                for (i, target) in targets.iter().enumerate() {
+                    match cases.iter_mut().find(|(t, _)| t == target) {
+                        Some((_, values)) if target != default => values.push(i),
+                        None if target != default => cases.push((*target, vec![i])),
+                        _ => {}
+                    }
+                }
+                for (target, values) in cases {
+                    let jump = self.jump(target);
+                    let labels: Vec<_> = values.iter().map(|v| format!("case {v}:")).collect();
+                    self.line(format!("{}{} {jump}", INDENT, labels.join(" ")));
+                }
+                let jump = self.jump(*default);
+                self.line(format!("{INDENT}default: {jump}"));
+                self.scopes.pop();
+                self.line("}".to_string());
+            }
+            Stmt::Return(values) => {
+                let values: Vec<_> = values.iter().map(|v| self.expr(v, 0)).collect();
+                match &values[..] {
+                    [] => self.line("return;".to_string()),
+                    [value] => self.line(format!("return {value};")),
+                    values => self.line(format!("return ({});", values.join(", "))),
+                }
+            }
+            Stmt::Unreachable => self.line("unreachable();".to_string()),
+        }
+    }
+
+    // if 的分支，else 中只有一个 if 时写成 else if
+    fn else_chain(&mut self, then: &[Stmt], els: &[Stmt]) {
+        self.nested(None, then);
+        match els {
+            [] => self.line("}".to_string()),
+            [
+                Stmt::If {
+                    label: None,
+                    cond,
+                    then,
+                    els,
+                },
+            ] => {
+                let cond = self.expr(cond, 0);
+                self.line(format!("}} else if ({cond}) {{"));
+                self.else_chain(then, els);
+            }
+            els => {
+                self.line("} else {".to_string());
+                self.nested(None, els);
+                self.line("}".to_string());
+            }
+        }
+    }
+
+    // `x = x + y` 写成 `x += y`
+    fn assign(&self, target: &str, current: Expr, value: &Expr) -> String {
+        if let Expr::Binary(op, a, b) = value
+            && **a == current
+            && op.cast.is_none()
+            && matches!(
+                op.symbol,
+                "+" | "-" | "*" | "/" | "%" | "&" | "|" | "^" | "<<" | ">>"
+            )
+        {
+            return format!("{target} {}= {};", op.symbol, self.expr(b, 1));
+        }
+        format!("{target} = {};", self.expr(value, 1))
+    }
+
+    // 内存访问。偏移量很大的字节访问通常是以数据段中的数组为基址、以地址为下标，写成数组形式
+    fn memory(&self, ty: &str, addr: &Expr, offset: u64) -> String {
+        if matches!(ty, "u8" | "i8") && offset > 0xffff {
+            let base = self.constant(offset as i64, 64);
+            return format!("(({ty} *){base})[{}]", self.expr(addr, 0));
+        }
+        if offset == 0 {
+            return format!("*({ty} *){}", self.expr(addr, PREC_UNARY));
+        }
+        let offset = self.constant(offset as i64, 64);
+        format!("*({ty} *)({} + {offset})", self.expr(addr, PREC_ADD))
+    }
+
+    fn args(&self, args: &[Expr]) -> String {
+        args.iter()
+            .map(|a| self.expr(a, 1))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // 打印表达式，优先级低于 min 时加括号
+    fn expr(&self, e: &Expr, min: u8) -> String {
+        let (text, prec) = match e {
+            Expr::I32(v) => (self.constant(*v as i64, 32), PREC_PRIMARY),
+            Expr::I64(v) => (self.constant(*v, 64), PREC_PRIMARY),
+            Expr::F32(bits) => (float(f32::from_bits(*bits) as f64, "f"), PREC_PRIMARY),
+            Expr::F64(bits) => (float(f64::from_bits(*bits), ""), PREC_PRIMARY),
+            Expr::RefNull => ("null".to_string(), PREC_PRIMARY),
+            Expr::RefFunc(index) => (format!("&{}", self.function_name(*index)), PREC_UNARY),
+            Expr::Var(var) => (self.var(*var), PREC_PRIMARY),
+            Expr::Global(index) => (self.global(*index), PREC_PRIMARY),
+            Expr::Unary(op, a) => {
+                let a = self.expr(a, PREC_UNARY);
+                // 避免 `- -1` 写成 `--1`
+                let a = if a.starts_with(['-', '!', '~']) && *op == "-" {
+                    format!("({a})")
+                } else {
+                    a
+                };
+                (format!("{op}{a}"), PREC_UNARY)
+            }
+            Expr::Cast(ty, a) => (format!("({ty}){}", self.expr(a, PREC_UNARY)), PREC_UNARY),
+            Expr::Binary(op, a, b) => {
+                // 非负常量不需要转换成无符号数
+                let operand = |e: &Expr, min: u8, cast: bool| match op.cast {
+                    Some(_) if matches!(e, Expr::I32(0..) | Expr::I64(0..)) => self.expr(e, min),
+                    Some(ty) if cast => format!("({ty}){}", self.expr(e, PREC_UNARY)),
+                    _ if mixes_bitwise(op, e) => format!("({})", self.expr(e, 0)),
+                    _ => self.expr(e, min),
+                };
+                let left = operand(a, op.prec, true);
+                let right = operand(b, op.prec + 1, op.cast_right);
+                (format!("{left} {} {right}", op.symbol), op.prec)
+            }
+            Expr::Load { ty, addr, offset } => {
+                let text = self.memory(ty, addr, *offset);
+                let prec = if text.starts_with('*') {
+                    PREC_UNARY
+                } else {
+                    PREC_PRIMARY
+                };
+                (text, prec)
+            }
+            Expr::Call(index, args) => (
+                format!("{}({})", self.function_name(*index), self.args(args)),
+                PREC_PRIMARY,
+            ),
+            Expr::CallIndirect { table, index, args } => (
+                format!("table{table}[{}]({})", self.expr(index, 0), self.args(args)),
+                PREC_PRIMARY,
+            ),
+            Expr::Builtin(name, args) => (format!("{name}({})", self.args(args)), PREC_PRIMARY),
+            Expr::Select(c, a, b) => {
+                let c = self.expr(c, PREC_TERNARY + 1);
+                let a = self.expr(a, PREC_TERNARY + 1);
+                let b = self.expr(b, PREC_TERNARY);
+                (format!("{c} ? {a} : {b}"), PREC_TERNARY)
+            }
+        };
+        if prec < min {
+            format!("({text})")
+        } else {
+            text
+        }
+    }
+}
+
+// 位运算、移位和其他运算混在一起时即使优先级正确也加上括号，如 `a & (b << 2)`
+fn mixes_bitwise(op: &BinOp, operand: &Expr) -> bool {
+    let bitwise = |prec| matches!(prec, PREC_BIT_OR | PREC_BIT_XOR | PREC_BIT_AND | PREC_SHIFT);
+    match operand {
+        Expr::Binary(inner, ..) => {
+            inner.prec != op.prec
+                && inner.prec > PREC_TERNARY
+                && (bitwise(op.prec) || bitwise(inner.prec))
+        }
+        _ => false,
+    }
+}
+
+fn float(value: f64, suffix: &str) -> String {
+    if value.is_nan() {
+        return "NAN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "INFINITY" } else { "-INFINITY" }.to_string();
+    }
+    let text = value.to_string();
+    if text.contains(['.', 'e']) {
+        format!("{text}{suffix}")
+    } else {
+        format!("{text}.0{suffix}")
+    }
+}
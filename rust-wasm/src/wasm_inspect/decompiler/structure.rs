@@ -0,0 +1,480 @@
+use super::ast::*;
+
+// 把 block / loop / br_if 还原成 if 、while 和 do-while ，自底向上逐层化简：
+// - loop 末尾无条件跳回开头是 while (true) ，有条件跳回开头（且中间没有 continue）是 do-while
+// - while (true) 开头的条件 break 提到循环条件中
+// - block 的最后一条语句是循环时，从循环内跳出 block 等于跳出循环
+// - block 中的条件 break 把后面的语句包进 if (!cond) { ... } ，if 分支末尾的 break 把后面的语句放进 else
+// - 没有被跳转到的 block 和 if 的标签去掉
+
+pub(super) fn structure(body: Vec<Stmt>) -> Vec<Stmt> {
+    let mut out = Vec::new();
+    for stmt in body {
+        simplify(stmt, &mut out);
+    }
+    out
+}
+
+fn simplify(stmt: Stmt, out: &mut Vec<Stmt>) {
+    match stmt {
+        Stmt::Block { label, body } => block(label, structure(body), out),
+        Stmt::Loop { label, body } => out.push(tighten(make_loop(label, structure(body)))),
+        Stmt::If {
+            label,
+            cond,
+            then,
+            els,
+        } => {
+            let mut then = structure(then);
+            let mut els = structure(els);
+            let label = label.filter(|&l| {
+                strip_break(&mut then, l);
+                strip_break(&mut els, l);
+                count_jumps(&then, l) + count_jumps(&els, l) > 0
+            });
+            let stmt = make_if(cond, then, els);
+            match (label, stmt) {
+                // 分支中跳出 if 的 break ，C 的 if 不能 break ，包一层 block
+                (Some(label), Some(stmt)) => block(label, vec![stmt], out),
+                (_, stmt) => out.extend(stmt),
+            }
+        }
+        stmt => out.push(stmt),
+    }
+}
+
+// 两个分支都为空时只保留条件中的调用，then 为空时把条件取反后交换分支
+fn make_if(cond: Expr, then: Vec<Stmt>, els: Vec<Stmt>) -> Option<Stmt> {
+    match (then.is_empty(), els.is_empty()) {
+        (true, true) => cond.has_call().then_some(Stmt::Expr(cond)),
+        (true, false) => Some(Stmt::If {
+            label: None,
+            cond: cond.negate(),
+            then: els,
+            els: Vec::new(),
+        }),
+        _ => Some(Stmt::If {
+            label: None,
+            cond,
+            then,
+            els,
+        }),
+    }
+}
+
+// 去掉语句列表末尾跳出 label 的 break
+fn strip_break(body: &mut Vec<Stmt>, label: Label) {
+    if let Some(Stmt::Jump {
+        target: Target::Break(l),
+        cond: None,
+    }) = body.last()
+        && *l == label
+    {
+        body.pop();
+    }
+}
+
+fn block(label: Label, mut body: Vec<Stmt>, out: &mut Vec<Stmt>) {
+    strip_break(&mut body, label);
+    if let Some(Stmt::While { label: inner, .. } | Stmt::DoWhile { label: inner, .. }) =
+        body.last_mut()
+    {
+        let inner = *inner;
+        if let Some(stmt) = body.last_mut() {
+            for children in stmt_bodies(stmt) {
+                retarget_breaks(children, label, inner);
+            }
+        }
+        if let Some(stmt) = body.pop() {
+            body.push(tighten(stmt));
+        }
+    }
+    let body = nest_breaks(body, label);
+    if count_jumps(&body, label) == 0 {
+        out.extend(body);
+    } else {
+        out.push(Stmt::Block { label, body });
+    }
+}
+
+fn stmt_bodies(stmt: &mut Stmt) -> Vec<&mut Vec<Stmt>> {
+    match stmt {
+        Stmt::While { body, .. } | Stmt::DoWhile { body, .. } => vec![body],
+        _ => Vec::new(),
+    }
+}
+
+// block 顶层的 `if (c) break;` 之后的语句只在 !c 时执行
+fn nest_breaks(body: Vec<Stmt>, label: Label) -> Vec<Stmt> {
+    let mut out = Vec::new();
+    let mut rest = body.into_iter();
+    while let Some(stmt) = rest.next() {
+        match stmt {
+            Stmt::Jump {
+                target: Target::Break(l),
+                cond: Some(cond),
+            } if l == label => {
+                let then = nest_breaks(rest.collect(), label);
+                out.extend(make_if(cond.negate(), then, Vec::new()));
+                break;
+            }
+            Stmt::If {
+                label: None,
+                cond,
+                mut then,
+                els,
+            } if els.is_empty()
+                && matches!(then.last(), Some(Stmt::Jump { target: Target::Break(l), cond: None }) if *l == label) =>
+            {
+                then.pop();
+                let els = nest_breaks(rest.collect(), label);
+                out.extend(make_if(cond, then, els));
+                break;
+            }
+            stmt => out.push(stmt),
+        }
+    }
+    out
+}
+
+fn make_loop(label: Label, mut body: Vec<Stmt>) -> Stmt {
+    match body.pop() {
+        Some(Stmt::Jump {
+            target: Target::Continue(l),
+            cond: None,
+        }) if l == label => Stmt::While {
+            label,
+            cond: None,
+            body,
+        },
+        Some(Stmt::Jump {
+            target: Target::Continue(l),
+            cond: Some(cond),
+        }) if l == label => {
+            // do-while 中的 continue 会先判断条件，中间还有跳回开头的指令时只能写成 while (true)
+            if count_jumps(&body, label) == 0 {
+                Stmt::DoWhile { label, body, cond }
+            } else {
+                body.push(Stmt::Jump {
+                    target: Target::Break(label),
+                    cond: Some(cond.negate()),
+                });
+                Stmt::While {
+                    label,
+                    cond: None,
+                    body,
+                }
+            }
+        }
+        last => {
+            let terminal = last.as_ref().is_some_and(Stmt::is_terminal);
+            body.extend(last);
+            if !terminal {
+                body.push(Stmt::Jump {
+                    target: Target::Break(label),
+                    cond: None,
+                });
+            }
+            Stmt::While {
+                label,
+                cond: None,
+                body,
+            }
+        }
+    }
+}
+
+// while (true) { if (c) break; ... } => while (!c) { ... }
+// while (true) { ...; if (c) break; } => do { ... } while (!c)
+fn tighten(stmt: Stmt) -> Stmt {
+    let Stmt::While {
+        label,
+        cond: None,
+        mut body,
+    } = stmt
+    else {
+        return stmt;
+    };
+    if let Some(Stmt::Jump {
+        target: Target::Break(l),
+        cond: Some(_),
+    }) = body.first()
+        && *l == label
+        && let Stmt::Jump {
+            cond: Some(cond), ..
+        } = body.remove(0)
+    {
+        return Stmt::While {
+            label,
+            cond: Some(cond.negate()),
+            body,
+        };
+    }
+    if let Some(Stmt::Jump {
+        target: Target::Break(l),
+        cond: Some(_),
+    }) = body.last()
+        && *l == label
+        && count_jumps(&body, label) == 1
+        && let Some(Stmt::Jump {
+            cond: Some(cond), ..
+        }) = body.pop()
+    {
+        return Stmt::DoWhile {
+            label,
+            body,
+            cond: cond.negate(),
+        };
+    }
+    Stmt::While {
+        label,
+        cond: None,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADD: BinOp = BinOp::new("+", PREC_ADD);
+    const EQ: BinOp = BinOp::new("==", PREC_EQUALITY).int_compare();
+    const LT: BinOp = BinOp::new("<", PREC_RELATION).int_compare();
+    const GE: BinOp = BinOp::new(">=", PREC_RELATION).int_compare();
+
+    fn local(index: u32) -> Expr {
+        Expr::Var(Var::Local(index))
+    }
+
+    fn increment(index: u32) -> Stmt {
+        Stmt::Set(
+            Var::Local(index),
+            Expr::binary(ADD, local(index), Expr::I32(1)),
+        )
+    }
+
+    fn jump(target: Target, cond: Option<Expr>) -> Stmt {
+        Stmt::Jump { target, cond }
+    }
+
+    #[test]
+    fn structure_block_test() {
+        // block 开头的条件 break 变成 if ，没有其他跳转时去掉 block
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![jump(Target::Break(1), Some(local(0))), increment(1)],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::If {
+                label: None,
+                cond: Expr::Unary("!", Box::new(local(0))),
+                then: vec![increment(1)],
+                els: Vec::new(),
+            }]
+        );
+
+        // if 分支末尾跳出 block ，后面的语句放进 else
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![
+                Stmt::If {
+                    label: None,
+                    cond: local(0),
+                    then: vec![increment(1), jump(Target::Break(1), None)],
+                    els: Vec::new(),
+                },
+                increment(2),
+            ],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::If {
+                label: None,
+                cond: local(0),
+                then: vec![increment(1)],
+                els: vec![increment(2)],
+            }]
+        );
+    }
+
+    #[test]
+    fn structure_while_test() {
+        // block { loop { if (i >= n) break; ...; continue; } } => while (i < n) { ... }
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![Stmt::Loop {
+                label: 2,
+                body: vec![
+                    jump(Target::Break(1), Some(Expr::binary(GE, local(1), local(0)))),
+                    increment(1),
+                    jump(Target::Continue(2), None),
+                ],
+            }],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::While {
+                label: 2,
+                cond: Some(Expr::binary(LT, local(1), local(0))),
+                body: vec![increment(1)],
+            }]
+        );
+    }
+
+    #[test]
+    fn structure_br_table_test() {
+        // 每个目标都被跳转到，block 全部保留
+        let switch = Stmt::Switch {
+            index: local(0),
+            targets: vec![Target::Break(3), Target::Break(2)],
+            default: Target::Break(1),
+        };
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![
+                Stmt::Block {
+                    label: 2,
+                    body: vec![
+                        Stmt::Block {
+                            label: 3,
+                            body: vec![switch.clone()],
+                        },
+                        increment(1),
+                        jump(Target::Break(1), None),
+                    ],
+                },
+                increment(2),
+            ],
+        }];
+        assert_eq!(structure(body.clone()), body);
+
+        // 循环外 block 的目标改为跳出循环，block 随之去掉；switch 结尾的循环不补 break
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![Stmt::Loop {
+                label: 2,
+                body: vec![
+                    increment(0),
+                    Stmt::Switch {
+                        index: local(0),
+                        targets: vec![Target::Continue(2), Target::Break(1)],
+                        default: Target::Continue(2),
+                    },
+                ],
+            }],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::While {
+                label: 2,
+                cond: None,
+                body: vec![
+                    increment(0),
+                    Stmt::Switch {
+                        index: local(0),
+                        targets: vec![Target::Continue(2), Target::Break(2)],
+                        default: Target::Continue(2),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn structure_nested_loops_test() {
+        // 内层循环中跳出最外层 block 的 break 改为跳出外层循环
+        let inner_exit = Expr::binary(EQ, local(1), Expr::I32(99));
+        let inner_cond = Expr::binary(LT, local(1), local(0));
+        let outer_cond = Expr::binary(LT, local(2), local(0));
+        let body = vec![Stmt::Block {
+            label: 1,
+            body: vec![Stmt::Loop {
+                label: 2,
+                body: vec![
+                    Stmt::Set(Var::Local(1), Expr::I32(0)),
+                    Stmt::Loop {
+                        label: 3,
+                        body: vec![
+                            jump(Target::Break(1), Some(inner_exit.clone())),
+                            increment(1),
+                            jump(Target::Continue(3), Some(inner_cond.clone())),
+                        ],
+                    },
+                    increment(2),
+                    jump(Target::Continue(2), Some(outer_cond.clone())),
+                ],
+            }],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::DoWhile {
+                label: 2,
+                body: vec![
+                    Stmt::Set(Var::Local(1), Expr::I32(0)),
+                    Stmt::DoWhile {
+                        label: 3,
+                        body: vec![jump(Target::Break(2), Some(inner_exit)), increment(1)],
+                        cond: inner_cond,
+                    },
+                    increment(2),
+                ],
+                cond: outer_cond,
+            }]
+        );
+
+        // 内层循环 continue 外层循环时保留标签，外层循环落到末尾时补上 break
+        let body = vec![Stmt::Loop {
+            label: 1,
+            body: vec![
+                increment(0),
+                Stmt::Loop {
+                    label: 2,
+                    body: vec![
+                        jump(Target::Continue(1), Some(local(1))),
+                        increment(2),
+                        jump(Target::Continue(2), None),
+                    ],
+                },
+            ],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::While {
+                label: 1,
+                cond: None,
+                body: vec![
+                    increment(0),
+                    Stmt::While {
+                        label: 2,
+                        cond: None,
+                        body: vec![jump(Target::Continue(1), Some(local(1))), increment(2)],
+                    },
+                    jump(Target::Break(1), None),
+                ],
+            }]
+        );
+
+        // do-while 中间还有 continue 时只能写成 while (true) ，末尾的条件取反后 break
+        let body = vec![Stmt::Loop {
+            label: 1,
+            body: vec![
+                jump(Target::Continue(1), Some(local(1))),
+                increment(0),
+                jump(Target::Continue(1), Some(local(2))),
+            ],
+        }];
+        assert_eq!(
+            structure(body),
+            [Stmt::While {
+                label: 1,
+                cond: None,
+                body: vec![
+                    jump(Target::Continue(1), Some(local(1))),
+                    increment(0),
+                    jump(Target::Break(1), Some(Expr::Unary("!", Box::new(local(2))))),
+                ],
+            }]
+        );
+    }
+}
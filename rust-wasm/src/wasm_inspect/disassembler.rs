@@ -0,0 +1,277 @@
+use std::borrow::Cow;
+
+use wasm_bindgen::prelude::*;
+use wasmparser::{BinaryReader, Parser, Payload};
+
+use super::inspect_error::InspectError;
+use super::module_info::ModuleInfo;
+
+const NAME_SECTION: &str = "name";
+const FUNCTION_NAMES: u8 = 1;
+
+/// 把整个模块反汇编成 WAT 。函数名取自 name section 、wasm-bindgen 的导出名和导入名，
+/// `offsets` 为 true 时每行前加上 `(;@偏移;)`，偏移是原文件中的位置
+#[wasm_bindgen]
+pub fn disassemble_wasm(bytes: &[u8], offsets: bool) -> Result<String, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    let named = with_function_names(bytes, &info)?;
+    let mut wat = String::new();
+    wasmprinter::Config::new()
+        .print_offsets(offsets)
+        .print(&named, &mut wasmprinter::PrintFmtWrite(&mut wat))
+        .map_err(print_error)?;
+    Ok(wat)
+}
+
+/// 只反汇编一个函数。`function` 可以是函数索引、name section 中的名字、导出名或导入的 "module.name"
+#[wasm_bindgen]
+pub fn disassemble_wasm_function(
+    bytes: &[u8],
+    function: &str,
+    offsets: bool,
+) -> Result<String, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    let target = info
+        .find_function(function)
+        .ok_or_else(|| InspectError::FunctionNotFound(function.to_string()))?;
+    let (Some(start), Some(size)) = (target.body_offset, target.body_size) else {
+        return Err(InspectError::ImportedFunction(function.to_string()));
+    };
+    let named = with_function_names(bytes, &info)?;
+
+    // wasmprinter 只能打印整个模块，按每行对应的文件偏移截出函数体所在的行：
+    // 函数头的偏移就是函数体的起始位置，没有偏移的行属于上一行的延续
+    let mut storage = String::new();
+    let lines = wasmprinter::Config::new()
+        .print_offsets(offsets)
+        .offsets_and_lines(&named, &mut storage)
+        .map_err(print_error)?;
+    let body = start..start + size;
+    let mut wat = String::new();
+    let mut inside = false;
+    for (offset, line) in lines {
+        match offset {
+            Some(offset) if offset == start && !inside => inside = true,
+            Some(offset) if inside && !body.contains(&offset) => break,
+            _ => {}
+        }
+        if inside {
+            wat.push_str(&dedent(line.trim_end(), offsets));
+            wat.push('\n');
+        }
+    }
+    Ok(wat)
+}
+
+// 去掉模块内的一层缩进。带偏移时缩进在 `(;@偏移;)` 之后，偏移注释固定 11 个字符
+fn dedent(line: &str, offsets: bool) -> Cow<'_, str> {
+    let prefix = if offsets { 11.min(line.len()) } else { 0 };
+    match line[prefix..].strip_prefix("  ") {
+        Some(rest) => Cow::Owned(format!("{}{rest}", &line[..prefix])),
+        None => Cow::Borrowed(line),
+    }
+}
+
+fn print_error(e: anyhow::Error) -> InspectError {
+    match e.downcast_ref::<wasmparser::BinaryReaderError>() {
+        Some(e) => InspectError::from(e.clone()),
+        None => InspectError::InvalidWasm {
+            offset: 0,
+            message: e.to_string(),
+        },
+    }
+}
+
+// 重写 name section 的函数名子段，给没有名字的函数补上导出名和导入名，这样 wasmprinter
+// 打印定义和 call 指令时都会带上 $名字。新的 name section 放在原来的位置（没有则追加到末尾），
+// 它前面的 section 偏移不变；name section 按惯例在所有 section 之后，函数体的偏移因此与原文件一致
+pub(super) fn with_function_names<'a>(
+    bytes: &'a [u8],
+    info: &ModuleInfo,
+) -> Result<Cow<'a, [u8]>, InspectError> {
+    let unnamed = info
+        .functions
+        .iter()
+        .any(|f| f.name.is_none() && info.function_name(f.index).is_some());
+    if !unnamed {
+        return Ok(Cow::Borrowed(bytes));
+    }
+    let named: Vec<_> = (0..info.functions.len() as u32)
+        .filter_map(|i| info.function_name(i).map(|name| (i, name)))
+        .collect();
+    Ok(Cow::Owned(replace_function_names(bytes, &named)?))
+}
+
+/// 用 `named`（按函数索引升序）替换 name section 的函数名子段，其他子段和 section 原样保留
+pub(super) fn replace_function_names(
+    bytes: &[u8],
+    named: &[(u32, &str)],
+) -> Result<Vec<u8>, InspectError> {
+    let mut function_names = Vec::new();
+    write_u32(&mut function_names, named.len() as u32);
+    for &(index, name) in named {
+        write_u32(&mut function_names, index);
+        write_name(&mut function_names, name);
+    }
+
+    // 找到原 name section 的完整范围（含 section id 和长度），保留函数名以外的子段
+    let mut section_start = 8;
+    let mut original = None;
+    for payload in Parser::new(0).parse_all(bytes) {
+        let payload = payload?;
+        let Some((_, range)) = payload.as_section() else {
+            continue;
+        };
+        if let Payload::CustomSection(reader) = &payload
+            && reader.name() == NAME_SECTION
+            && original.is_none()
+        {
+            let subsections =
+                name_subsections(reader.data(), reader.data_offset()).unwrap_or_default();
+            original = Some((section_start..range.end, subsections));
+        }
+        section_start = range.end;
+    }
+
+    let (range, subsections) = original.unwrap_or((bytes.len()..bytes.len(), Vec::new()));
+    let mut content = Vec::new();
+    write_name(&mut content, NAME_SECTION);
+    let mut function_names_written = false;
+    for (id, data) in subsections {
+        if id > FUNCTION_NAMES && !function_names_written {
+            write_subsection(&mut content, FUNCTION_NAMES, &function_names);
+            function_names_written = true;
+        }
+        if id != FUNCTION_NAMES {
+            write_subsection(&mut content, id, data);
+        }
+    }
+    if !function_names_written {
+        write_subsection(&mut content, FUNCTION_NAMES, &function_names);
+    }
+
+    let mut out = Vec::with_capacity(bytes.len() + content.len() + 6);
+    out.extend_from_slice(&bytes[..range.start]);
+    out.push(0);
+    write_u32(&mut out, content.len() as u32);
+    out.extend_from_slice(&content);
+    out.extend_from_slice(&bytes[range.end..]);
+    Ok(out)
+}
+
+// name section 的子段：(id, 内容)。格式错误时返回 Err ，调用方丢弃原有子段
+fn name_subsections(data: &[u8], offset: usize) -> Result<Vec<(u8, &[u8])>, InspectError> {
+    let mut reader = BinaryReader::new(data, offset);
+    let mut subsections = Vec::new();
+    while !reader.eof() {
+        let id = reader.read_u8()?;
+        let size = reader.read_var_u32()? as usize;
+        subsections.push((id, reader.read_bytes(size)?));
+    }
+    Ok(subsections)
+}
+
+fn write_subsection(out: &mut Vec<u8>, id: u8, data: &[u8]) {
+    out.push(id);
+    write_u32(out, data.len() as u32);
+    out.extend_from_slice(data);
+}
+
+fn write_name(out: &mut Vec<u8>, name: &str) {
+    write_u32(out, name.len() as u32);
+    out.extend_from_slice(name.as_bytes());
+}
+
+// 无符号 LEB128
+fn write_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+
+    const SAMPLE_WAT: &str = r#"
+        (module
+            (import "env" "log" (func (param i32)))
+            (func $helper (param $x i32) (result i32) (i32.mul (local.get $x) (i32.const 3)))
+            (func (export "aes_cbc_encrypt") (param i32) (result i32)
+                (call 0 (i32.const 1))
+                (call $helper (local.get 0)))
+            (func (result i32) (call 2 (i32.const 7))))
+    "#;
+
+    fn sample() -> Vec<u8> {
+        wat::parse_str(SAMPLE_WAT).unwrap()
+    }
+
+    #[test]
+    fn disassemble_wasm_names_test() {
+        let wat = disassemble_wasm(&sample(), false).unwrap();
+        // 没有 name section 名字的函数用导出名和导入名，原有的函数名和参数名保留
+        assert!(wat.contains("(import \"env\" \"log\" (func $env.log (;0;) (type 0)))"));
+        assert!(wat.contains("(func $helper (;1;) (type 1) (param $x i32) (result i32)"));
+        assert!(wat.contains("(func $aes_cbc_encrypt (;2;) (type 1)"));
+        assert!(wat.contains("call $env.log"));
+        assert!(wat.contains("call $aes_cbc_encrypt"));
+        assert!(wat.contains("(func (;3;) (type 2) (result i32)"));
+        // 反汇编结果可以重新汇编
+        assert!(wat::parse_str(&wat).is_ok());
+    }
+
+    #[test]
+    fn disassemble_wasm_offsets_test() {
+        let bytes = sample();
+        let info = ModuleInfo::parse(&bytes).unwrap();
+        let wat = disassemble_wasm(&bytes, true).unwrap();
+        let header = format!(
+            "(;@{:<6x};)  (func $aes_cbc_encrypt",
+            info.functions[2].body_offset.unwrap()
+        );
+        assert!(wat.lines().any(|line| line.starts_with(&header)));
+        assert!(!disassemble_wasm(&bytes, false).unwrap().contains("(;@"));
+    }
+
+    #[test]
+    fn disassemble_wasm_function_test() {
+        let bytes = sample();
+        let wat = disassemble_wasm_function(&bytes, "aes_cbc_encrypt", false).unwrap();
+        assert_eq!(
+            wat,
+            "(func $aes_cbc_encrypt (;2;) (type 1) (param i32) (result i32)\n  \
+             i32.const 1\n  call $env.log\n  local.get 0\n  call $helper\n)\n"
+        );
+        // 按索引和 name section 中的名字查找
+        assert_eq!(disassemble_wasm_function(&bytes, "2", false).unwrap(), wat);
+        let helper = disassemble_wasm_function(&bytes, "helper", true).unwrap();
+        assert!(helper.starts_with("(;@"));
+        assert!(helper.contains(";)(func $helper (;1;)"));
+        assert_eq!(helper.lines().count(), 5);
+    }
+
+    #[test]
+    fn disassemble_wasm_function_errors_test() {
+        let bytes = sample();
+        let err =
+            RustWasmError::from(disassemble_wasm_function(&bytes, "missing", false).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert_eq!(err.details["function"], "missing");
+        assert!(matches!(
+            disassemble_wasm_function(&bytes, "env.log", false),
+            Err(InspectError::ImportedFunction(_))
+        ));
+        assert!(matches!(
+            disassemble_wasm(b"\0asm\x01\0\0\0\x0a", false),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+use super::interpreter::Trap;
+use crate::error::{ErrorCode, RustWasmError};
+
+#[derive(Debug, Error)]
+pub enum InspectError {
+    #[error("invalid wasm at offset {offset}: {message}")]
+    InvalidWasm { offset: usize, message: String },
+    #[error("wasm components are not supported, only core modules")]
+    Component,
+    #[error("function not found: {0}")]
+    FunctionNotFound(String),
+    #[error("function {0} is imported and has no body")]
+    ImportedFunction(String),
+    #[error("unsupported wasm feature: {0}")]
+    Unsupported(String),
+    #[error("invalid arguments: {0}")]
+    InvalidArguments(String),
+    #[error("instantiation failed: {0}")]
+    Instantiation(Trap),
+    #[error("memory access out of bounds: {size} bytes at {address}")]
+    MemoryOutOfBounds { address: u64, size: usize },
+    #[error("invalid patch: {0}")]
+    InvalidPatch(String),
+    #[error("patched module does not validate at offset {offset}: {message}")]
+    PatchValidation { offset: usize, message: String },
+}
+
+impl From<wasmparser::BinaryReaderError> for InspectError {
+    fn from(e: wasmparser::BinaryReaderError) -> Self {
+        InspectError::InvalidWasm {
+            offset: e.offset(),
+            message: e.message().to_string(),
+        }
+    }
+}
+
+impl From<InspectError> for RustWasmError {
+    fn from(e: InspectError) -> Self {
+        let message = e.to_string();
+        match e {
+            InspectError::InvalidWasm {
+                offset,
+                message: reason,
+            } => RustWasmError::new(ErrorCode::InvalidWasm, "wasm_inspect", message)
+                .with_detail("offset", offset)
+                .with_detail("reason", reason),
+            InspectError::Component => {
+                RustWasmError::new(ErrorCode::UnsupportedFormat, "wasm_inspect", message)
+            }
+            InspectError::FunctionNotFound(function) | InspectError::ImportedFunction(function) => {
+                RustWasmError::new(ErrorCode::InvalidInput, "wasm_inspect", message)
+                    .with_detail("function", function)
+            }
+            InspectError::Unsupported(feature) => {
+                RustWasmError::new(ErrorCode::UnsupportedFormat, "wasm_inspect", message)
+                    .with_detail("feature", feature)
+            }
+            InspectError::InvalidArguments(reason) => {
+                RustWasmError::new(ErrorCode::InvalidInput, "wasm_inspect", message)
+                    .with_detail("arguments", reason)
+            }
+            // 主动段越界或 start 函数 trap ，模块无法实例化
+            InspectError::Instantiation(trap) => {
+                RustWasmError::new(ErrorCode::InvalidWasm, "wasm_inspect", message)
+                    .with_detail("trap", trap.to_string())
+            }
+            InspectError::MemoryOutOfBounds { address, size } => {
+                RustWasmError::new(ErrorCode::InvalidInput, "wasm_inspect", message)
+                    .with_detail("address", address)
+                    .with_detail("size", size)
+            }
+            InspectError::InvalidPatch(reason) => {
+                RustWasmError::new(ErrorCode::InvalidInput, "wasm_inspect", message)
+                    .with_detail("patch", reason)
+            }
+            // 修改后的模块不合法，例如 nop 掉指令后操作数栈不平衡
+            InspectError::PatchValidation {
+                offset,
+                message: reason,
+            } => RustWasmError::new(ErrorCode::InvalidWasm, "wasm_inspect", message)
+                .with_detail("offset", offset)
+                .with_detail("reason", reason),
+        }
+    }
+}
+
+impl From<InspectError> for JsValue {
+    fn from(e: InspectError) -> Self {
+        RustWasmError::from(e).into()
+    }
+}
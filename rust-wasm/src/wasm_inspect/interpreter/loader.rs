@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use wasmparser::{
+    BinaryReaderError, BlockType, CompositeInnerType, DataKind, ElementItems, ElementKind,
+    Operator, OperatorsReader, Parser, Payload, TypeRef, ValType,
+};
+
+use super::value::{Trap, Value, null_ref};
+use crate::wasm_inspect::disassembler::with_function_names;
+use crate::wasm_inspect::inspect_error::InspectError;
+use crate::wasm_inspect::module_info::ModuleInfo;
+
+// 解析后的模块，执行时需要的信息全部自有，不借用调用方的字节。函数体不预先解码，
+// 执行时按偏移逐条读取，只预先算出每个块的 else / end 位置供跳转使用
+pub(super) struct Module {
+    pub bytes: Vec<u8>,
+    pub info: ModuleInfo,
+    // GC 提案的结构体 / 数组类型为 None
+    pub types: Vec<Option<FuncType>>,
+    pub functions: Vec<Function>,
+    pub tables: Vec<TableDef>,
+    pub memories: Vec<MemoryDef>,
+    pub globals: Vec<GlobalDef>,
+    pub elements: Vec<ElementDef>,
+    pub data: Vec<DataDef>,
+    pub start: Option<u32>,
+    // 指令偏移 -> 反汇编文本
+    texts: HashMap<usize, Box<str>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FuncType {
+    pub params: Vec<ValType>,
+    pub results: Vec<ValType>,
+}
+
+pub(super) enum Function {
+    Import {
+        module: String,
+        name: String,
+        type_index: u32,
+    },
+    Local(LocalFunction),
+}
+
+pub(super) struct LocalFunction {
+    pub type_index: u32,
+    // 不含参数
+    pub locals: Vec<ValType>,
+    // 第一条指令到函数体末尾
+    pub body: Range<usize>,
+    // block / loop / if 指令的偏移 -> 对应的 else 和 end
+    pub blocks: HashMap<usize, Block>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Block {
+    pub else_offset: Option<usize>,
+    pub end: usize,
+}
+
+pub(super) struct TableDef {
+    pub null: Value,
+    pub initial: u64,
+    pub maximum: Option<u64>,
+}
+
+pub(super) struct MemoryDef {
+    pub initial: u64,
+    pub maximum: Option<u64>,
+    pub memory64: bool,
+    pub page_size: u64,
+}
+
+pub(super) struct GlobalDef {
+    pub ty: ValType,
+    // 导入的全局变量为 None ，实例化时取类型的默认值
+    pub init: Option<ConstExpr>,
+}
+
+pub(super) enum SegmentMode {
+    Passive,
+    Declared,
+    Active { index: u32, offset: ConstExpr },
+}
+
+pub(super) struct ElementDef {
+    pub mode: SegmentMode,
+    pub items: Vec<ConstExpr>,
+}
+
+pub(super) struct DataDef {
+    pub mode: SegmentMode,
+    // 数据内容在文件中的范围
+    pub range: Range<usize>,
+}
+
+// 常量表达式：MVP 的常量、global.get 、引用，以及 extended-const 的加减乘
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ConstOp {
+    Push(Value),
+    GlobalGet(u32),
+    I32Add,
+    I32Sub,
+    I32Mul,
+    I64Add,
+    I64Sub,
+    I64Mul,
+}
+
+pub(super) type ConstExpr = Vec<ConstOp>;
+
+impl Module {
+    pub fn load(bytes: &[u8]) -> Result<Self, InspectError> {
+        let info = ModuleInfo::parse(bytes)?;
+        let mut module = Module {
+            bytes: bytes.to_vec(),
+            info,
+            types: Vec::new(),
+            functions: Vec::new(),
+            tables: Vec::new(),
+            memories: Vec::new(),
+            globals: Vec::new(),
+            elements: Vec::new(),
+            data: Vec::new(),
+            start: None,
+            texts: HashMap::new(),
+        };
+        let mut next_body = 0;
+        for payload in Parser::new(0).parse_all(bytes) {
+            match payload? {
+                Payload::TypeSection(reader) => {
+                    for rec_group in reader {
+                        for sub_type in rec_group?.into_types() {
+                            module.types.push(match &sub_type.composite_type.inner {
+                                CompositeInnerType::Func(ty) => Some(FuncType {
+                                    params: ty.params().to_vec(),
+                                    results: ty.results().to_vec(),
+                                }),
+                                _ => None,
+                            });
+                        }
+                    }
+                }
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        match import.ty {
+                            TypeRef::Func(type_index) | TypeRef::FuncExact(type_index) => {
+                                module.functions.push(Function::Import {
+                                    module: import.module.to_string(),
+                                    name: import.name.to_string(),
+                                    type_index,
+                                })
+                            }
+                            TypeRef::Table(ty) => module.tables.push(TableDef {
+                                null: null_ref(ty.element_type.heap_type()),
+                                initial: ty.initial,
+                                maximum: ty.maximum,
+                            }),
+                            TypeRef::Memory(ty) => module.memories.push(memory_def(ty)),
+                            TypeRef::Global(ty) => module.globals.push(GlobalDef {
+                                ty: ty.content_type,
+                                init: None,
+                            }),
+                            TypeRef::Tag(_) => {
+                                return Err(InspectError::Unsupported("exception tags".into()));
+                            }
+                        }
+                    }
+                }
+                Payload::FunctionSection(reader) => {
+                    for type_index in reader {
+                        module.functions.push(Function::Local(LocalFunction {
+                            type_index: type_index?,
+                            locals: Vec::new(),
+                            body: 0..0,
+                            blocks: HashMap::new(),
+                        }));
+                    }
+                }
+                Payload::TableSection(reader) => {
+                    for table in reader {
+                        let ty = table?.ty;
+                        module.tables.push(TableDef {
+                            null: null_ref(ty.element_type.heap_type()),
+                            initial: ty.initial,
+                            maximum: ty.maximum,
+                        });
+                    }
+                }
+                Payload::MemorySection(reader) => {
+                    for memory in reader {
+                        module.memories.push(memory_def(memory?));
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        let global = global?;
+                        module.globals.push(GlobalDef {
+                            ty: global.ty.content_type,
+                            init: Some(const_expr(global.init_expr.get_operators_reader())?),
+                        });
+                    }
+                }
+                Payload::StartSection { func, .. } => module.start = Some(func),
+                Payload::ElementSection(reader) => {
+                    for element in reader {
+                        let element = element?;
+                        let mode = match element.kind {
+                            ElementKind::Passive => SegmentMode::Passive,
+                            ElementKind::Declared => SegmentMode::Declared,
+                            ElementKind::Active {
+                                table_index,
+                                offset_expr,
+                            } => SegmentMode::Active {
+                                index: table_index.unwrap_or(0),
+                                offset: const_expr(offset_expr.get_operators_reader())?,
+                            },
+                        };
+                        let mut items = Vec::new();
+                        match element.items {
+                            ElementItems::Functions(reader) => {
+                                for index in reader {
+                                    items.push(vec![ConstOp::Push(Value::FuncRef(Some(index?)))]);
+                                }
+                            }
+                            ElementItems::Expressions(_, reader) => {
+                                for expr in reader {
+                                    items.push(const_expr(expr?.get_operators_reader())?);
+                                }
+                            }
+                        }
+                        module.elements.push(ElementDef { mode, items });
+                    }
+                }
+                Payload::DataSection(reader) => {
+                    for data in reader {
+                        let data = data?;
+                        let mode = match data.kind {
+                            DataKind::Passive => SegmentMode::Passive,
+                            DataKind::Active {
+                                memory_index,
+                                offset_expr,
+                            } => SegmentMode::Active {
+                                index: memory_index,
+                                offset: const_expr(offset_expr.get_operators_reader())?,
+                            },
+                        };
+                        let start = data.range.end - data.data.len();
+                        module.data.push(DataDef {
+                            mode,
+                            range: start..data.range.end,
+                        });
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let mut locals = Vec::new();
+                    for local in body.get_locals_reader()? {
+                        let (count, ty) = local?;
+                        locals.extend(std::iter::repeat_n(ty, count as usize));
+                    }
+                    let operators = body.get_operators_reader()?;
+                    let start = operators.original_position();
+                    let blocks = scan_blocks(operators)?;
+                    let index = module.info.imported_function_count() + next_body;
+                    next_body += 1;
+                    if let Some(Function::Local(function)) = module.functions.get_mut(index) {
+                        function.locals = locals;
+                        function.body = start..body.range().end;
+                        function.blocks = blocks;
+                    }
+                }
+                _ => {}
+            }
+        }
+        module.texts = instruction_texts(&module.bytes, &module.info);
+        Ok(module)
+    }
+
+    pub fn func_type(&self, function: u32) -> Option<&FuncType> {
+        let type_index = match self.functions.get(function as usize)? {
+            Function::Import { type_index, .. } => *type_index,
+            Function::Local(f) => f.type_index,
+        };
+        self.types.get(type_index as usize)?.as_ref()
+    }
+
+    /// 块类型的（参数个数，结果个数）
+    pub fn block_arity(&self, block_type: BlockType) -> Result<(usize, usize), Trap> {
+        Ok(match block_type {
+            BlockType::Empty => (0, 0),
+            BlockType::Type(_) => (0, 1),
+            BlockType::FuncType(index) => {
+                let ty = self
+                    .types
+                    .get(index as usize)
+                    .and_then(Option::as_ref)
+                    .ok_or_else(|| Trap::Invalid(format!("unknown type {index}")))?;
+                (ty.params.len(), ty.results.len())
+            }
+        })
+    }
+
+    /// 指令的反汇编文本，wasmprinter 打印失败时返回 None
+    pub fn text(&self, offset: usize) -> Option<&str> {
+        self.texts.get(&offset).map(|text| &**text)
+    }
+}
+
+fn memory_def(ty: wasmparser::MemoryType) -> MemoryDef {
+    MemoryDef {
+        initial: ty.initial,
+        maximum: ty.maximum,
+        memory64: ty.memory64,
+        page_size: 1 << ty.page_size_log2.unwrap_or(16),
+    }
+}
+
+fn const_expr(mut reader: OperatorsReader) -> Result<ConstExpr, InspectError> {
+    let mut ops = Vec::new();
+    loop {
+        ops.push(match reader.read()? {
+            Operator::End => return Ok(ops),
+            Operator::I32Const { value } => ConstOp::Push(Value::I32(value)),
+            Operator::I64Const { value } => ConstOp::Push(Value::I64(value)),
+            Operator::F32Const { value } => ConstOp::Push(Value::F32(value.bits())),
+            Operator::F64Const { value } => ConstOp::Push(Value::F64(value.bits())),
+            Operator::RefNull { hty } => ConstOp::Push(null_ref(hty)),
+            Operator::RefFunc { function_index } => {
+                ConstOp::Push(Value::FuncRef(Some(function_index)))
+            }
+            Operator::GlobalGet { global_index } => ConstOp::GlobalGet(global_index),
+            Operator::I32Add => ConstOp::I32Add,
+            Operator::I32Sub => ConstOp::I32Sub,
+            Operator::I32Mul => ConstOp::I32Mul,
+            Operator::I64Add => ConstOp::I64Add,
+            Operator::I64Sub => ConstOp::I64Sub,
+            Operator::I64Mul => ConstOp::I64Mul,
+            other => {
+                return Err(InspectError::Unsupported(format!(
+                    "{other:?} in constant expression"
+                )));
+            }
+        });
+    }
+}
+
+// 一遍扫描函数体，记录每个块的 else 和 end 的偏移
+fn scan_blocks(mut reader: OperatorsReader) -> Result<HashMap<usize, Block>, BinaryReaderError> {
+    let mut blocks = HashMap::new();
+    // (块起始偏移, else 偏移)
+    let mut open: Vec<(usize, Option<usize>)> = Vec::new();
+    while !reader.eof() {
+        let offset = reader.original_position();
+        match reader.read()? {
+            Operator::Block { .. }
+            | Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::Try { .. }
+            | Operator::TryTable { .. } => open.push((offset, None)),
+            Operator::Else => {
+                if let Some(block) = open.last_mut() {
+                    block.1 = Some(offset);
+                }
+            }
+            Operator::End | Operator::Delegate { .. } => {
+                if let Some((start, else_offset)) = open.pop() {
+                    blocks.insert(
+                        start,
+                        Block {
+                            else_offset,
+                            end: offset,
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(blocks)
+}
+
+// 借用反汇编器的输出作为每条指令的文本，call 指令因此带有函数名。
+// 其他行（函数头、数据段等）的偏移不会与指令重合，一并收下
+fn instruction_texts(bytes: &[u8], info: &ModuleInfo) -> HashMap<usize, Box<str>> {
+    let Ok(named) = with_function_names(bytes, info) else {
+        return HashMap::new();
+    };
+    let mut storage = String::new();
+    let Ok(lines) = wasmprinter::Config::new().offsets_and_lines(&named, &mut storage) else {
+        return HashMap::new();
+    };
+    lines
+        .filter_map(|(offset, line)| Some((offset?, line.trim().into())))
+        .collect()
+}
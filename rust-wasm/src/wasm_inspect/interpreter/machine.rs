@@ -0,0 +1,1337 @@
+use std::collections::HashMap;
+
+use wasmparser::{BinaryReader, MemArg, Operator, OperatorsReader, OperatorsReaderAllocations};
+
+use super::TraceOptions;
+use super::loader::{ConstExpr, ConstOp, Function, LocalFunction, Module, SegmentMode};
+use super::trace::TraceEvent;
+use super::value::{Trap, Value, null_ref};
+
+// 调用深度上限。解释器不使用宿主栈递归，这个上限只是为了让无限递归尽快停下
+const MAX_CALL_DEPTH: usize = 4096;
+// 内存和表的实现上限：内存最多 1 GiB ，表最多 1000 万项，超过时 grow 返回 -1
+const MAX_MEMORY_BYTES: u64 = 1 << 30;
+const MAX_TABLE_SIZE: u64 = 10_000_000;
+// Step 事件中记录的栈顶值个数
+const STACK_SNAPSHOT: usize = 16;
+const ELSE_OPCODE: u8 = 0x05;
+
+// 实例的可变状态：内存、表、全局变量，以及尚未丢弃的段
+pub(super) struct Store {
+    pub memories: Vec<Memory>,
+    pub tables: Vec<Table>,
+    pub globals: Vec<Value>,
+    // 丢弃（elem.drop 、主动段实例化后）的元素段为空
+    pub elements: Vec<Vec<Value>>,
+    pub data_dropped: Vec<bool>,
+    // 导入函数索引 -> 桩返回值，未设置的返回各结果类型的默认值
+    pub stubs: HashMap<u32, Vec<Value>>,
+}
+
+pub(super) struct Memory {
+    pub data: Vec<u8>,
+    pub maximum: Option<u64>,
+    pub memory64: bool,
+    pub page_size: u64,
+}
+
+pub(super) struct Table {
+    pub elements: Vec<Value>,
+    pub maximum: Option<u64>,
+}
+
+impl Memory {
+    fn pages(&self) -> u64 {
+        self.data.len() as u64 / self.page_size
+    }
+
+    fn check(&self, address: u64, size: u64) -> Result<usize, Trap> {
+        match address.checked_add(size) {
+            Some(end) if end <= self.data.len() as u64 => Ok(address as usize),
+            _ => Err(Trap::MemoryOutOfBounds),
+        }
+    }
+}
+
+impl Table {
+    fn check(&self, index: u64, size: u64) -> Result<usize, Trap> {
+        match index.checked_add(size) {
+            Some(end) if end <= self.elements.len() as u64 => Ok(index as usize),
+            _ => Err(Trap::TableOutOfBounds),
+        }
+    }
+}
+
+impl Store {
+    /// 按规范的顺序实例化：全局变量、内存和表，然后依次写入主动元素段和主动数据段。
+    /// 导入的内存、表按声明的最小大小创建，导入的全局变量取默认值
+    pub fn new(module: &Module) -> Result<Self, Trap> {
+        let mut store = Store {
+            memories: Vec::new(),
+            tables: Vec::new(),
+            globals: Vec::new(),
+            elements: Vec::new(),
+            data_dropped: vec![false; module.data.len()],
+            stubs: HashMap::new(),
+        };
+        for global in &module.globals {
+            let value = match &global.init {
+                Some(expr) => store.eval_const(expr)?,
+                None => Value::zero(global.ty)?,
+            };
+            store.globals.push(value);
+        }
+        for memory in &module.memories {
+            let size = memory.initial.saturating_mul(memory.page_size);
+            if size > MAX_MEMORY_BYTES {
+                return Err(Trap::Unsupported(format!("memory of {size} bytes")));
+            }
+            store.memories.push(Memory {
+                data: vec![0; size as usize],
+                maximum: memory.maximum,
+                memory64: memory.memory64,
+                page_size: memory.page_size,
+            });
+        }
+        for table in &module.tables {
+            if table.initial > MAX_TABLE_SIZE {
+                return Err(Trap::Unsupported(format!(
+                    "table of {} elements",
+                    table.initial
+                )));
+            }
+            store.tables.push(Table {
+                elements: vec![table.null; table.initial as usize],
+                maximum: table.maximum,
+            });
+        }
+
+        for element in &module.elements {
+            let items = element
+                .items
+                .iter()
+                .map(|expr| store.eval_const(expr))
+                .collect::<Result<Vec<_>, _>>()?;
+            match &element.mode {
+                SegmentMode::Passive => store.elements.push(items),
+                SegmentMode::Declared => store.elements.push(Vec::new()),
+                SegmentMode::Active { index, offset } => {
+                    let offset = address(store.eval_const(offset)?)?;
+                    let table = table_mut(&mut store.tables, *index)?;
+                    let start = table.check(offset, items.len() as u64)?;
+                    table.elements[start..start + items.len()].copy_from_slice(&items);
+                    store.elements.push(Vec::new());
+                }
+            }
+        }
+        for (index, data) in module.data.iter().enumerate() {
+            if let SegmentMode::Active {
+                index: memory,
+                offset,
+            } = &data.mode
+            {
+                let offset = address(store.eval_const(offset)?)?;
+                let memory = memory_mut(&mut store.memories, *memory)?;
+                let start = memory.check(offset, data.range.len() as u64)?;
+                memory.data[start..start + data.range.len()]
+                    .copy_from_slice(&module.bytes[data.range.clone()]);
+                store.data_dropped[index] = true;
+            }
+        }
+        Ok(store)
+    }
+
+    fn eval_const(&self, expr: &ConstExpr) -> Result<Value, Trap> {
+        let mut stack = Vec::new();
+        for op in expr {
+            let value = match *op {
+                ConstOp::Push(value) => value,
+                ConstOp::GlobalGet(index) => *self
+                    .globals
+                    .get(index as usize)
+                    .ok_or_else(|| Trap::Invalid(format!("unknown global {index}")))?,
+                _ => {
+                    let (Some(b), Some(a)) = (stack.pop(), stack.pop()) else {
+                        return Err(Trap::Invalid("constant expression stack underflow".into()));
+                    };
+                    match (*op, a, b) {
+                        (ConstOp::I32Add, Value::I32(a), Value::I32(b)) => {
+                            Value::I32(a.wrapping_add(b))
+                        }
+                        (ConstOp::I32Sub, Value::I32(a), Value::I32(b)) => {
+                            Value::I32(a.wrapping_sub(b))
+                        }
+                        (ConstOp::I32Mul, Value::I32(a), Value::I32(b)) => {
+                            Value::I32(a.wrapping_mul(b))
+                        }
+                        (ConstOp::I64Add, Value::I64(a), Value::I64(b)) => {
+                            Value::I64(a.wrapping_add(b))
+                        }
+                        (ConstOp::I64Sub, Value::I64(a), Value::I64(b)) => {
+                            Value::I64(a.wrapping_sub(b))
+                        }
+                        (ConstOp::I64Mul, Value::I64(a), Value::I64(b)) => {
+                            Value::I64(a.wrapping_mul(b))
+                        }
+                        _ => return Err(Trap::Invalid("constant expression type mismatch".into())),
+                    }
+                }
+            };
+            stack.push(value);
+        }
+        match stack[..] {
+            [value] => Ok(value),
+            _ => Err(Trap::Invalid(
+                "constant expression must produce one value".into(),
+            )),
+        }
+    }
+}
+
+// 地址、长度等 i32 / i64 操作数按无符号解释
+fn address(value: Value) -> Result<u64, Trap> {
+    match value {
+        Value::I32(v) => Ok(v as u32 as u64),
+        Value::I64(v) => Ok(v as u64),
+        other => Err(type_mismatch("i32 or i64", other)),
+    }
+}
+
+fn type_mismatch(expected: &str, found: Value) -> Trap {
+    Trap::Invalid(format!(
+        "type mismatch: expected {expected}, found {}",
+        found.type_name()
+    ))
+}
+
+fn memory_mut(memories: &mut [Memory], index: u32) -> Result<&mut Memory, Trap> {
+    memories
+        .get_mut(index as usize)
+        .ok_or_else(|| Trap::Invalid(format!("unknown memory {index}")))
+}
+
+fn table_mut(tables: &mut [Table], index: u32) -> Result<&mut Table, Trap> {
+    tables
+        .get_mut(index as usize)
+        .ok_or_else(|| Trap::Invalid(format!("unknown table {index}")))
+}
+
+struct Frame {
+    function: u32,
+    locals: Vec<Value>,
+    // 下一条要执行的指令的偏移
+    pc: usize,
+    stack_base: usize,
+    label_base: usize,
+    arity: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Label {
+    // 进入块时的栈高度（不含块参数）
+    height: usize,
+    // 跳转到这个标签时带走的值的个数：loop 是参数个数，其余是结果个数
+    arity: usize,
+    is_loop: bool,
+    // 跳转目标：loop 是循环体的第一条指令，其余是 end 之后
+    target: usize,
+    // 对应的 end 指令
+    end: usize,
+}
+
+/// 一次调用的执行过程
+pub(super) struct Run<'a> {
+    module: &'a Module,
+    store: &'a mut Store,
+    options: &'a TraceOptions,
+    pub trace: Vec<TraceEvent>,
+    pub truncated: bool,
+    pub steps: u64,
+    stack: Vec<Value>,
+    frames: Vec<Frame>,
+    labels: Vec<Label>,
+    allocs: Option<OperatorsReaderAllocations>,
+}
+
+macro_rules! unary {
+    ($self:ident, $pop:ident, $ctor:expr, |$a:ident| $body:expr) => {{
+        let $a = $self.$pop()?;
+        $self.stack.push($ctor($body));
+    }};
+}
+
+macro_rules! binary {
+    ($self:ident, $pop:ident, $ctor:expr, |$a:ident, $b:ident| $body:expr) => {{
+        let $b = $self.$pop()?;
+        let $a = $self.$pop()?;
+        $self.stack.push($ctor($body));
+    }};
+}
+
+fn bool_value(b: bool) -> Value {
+    Value::I32(b as i32)
+}
+
+fn f32_value(v: f32) -> Value {
+    Value::F32(v.to_bits())
+}
+
+fn f64_value(v: f64) -> Value {
+    Value::F64(v.to_bits())
+}
+
+impl<'a> Run<'a> {
+    pub fn new(module: &'a Module, store: &'a mut Store, options: &'a TraceOptions) -> Self {
+        Run {
+            module,
+            store,
+            options,
+            trace: Vec::new(),
+            truncated: false,
+            steps: 0,
+            stack: Vec::new(),
+            frames: Vec::new(),
+            labels: Vec::new(),
+            allocs: None,
+        }
+    }
+
+    /// 调用函数直到返回。参数的个数和类型由调用方检查
+    pub fn invoke(&mut self, function: u32, args: &[Value]) -> Result<Vec<Value>, Trap> {
+        self.stack.extend_from_slice(args);
+        self.call(function)?;
+        while !self.frames.is_empty() {
+            self.step()?;
+        }
+        Ok(std::mem::take(&mut self.stack))
+    }
+
+    fn record(&mut self, event: impl FnOnce(&Self) -> TraceEvent) {
+        if self.trace.len() < self.options.trace_limit {
+            let event = event(self);
+            self.trace.push(event);
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    fn frame(&self) -> Result<&Frame, Trap> {
+        self.frames
+            .last()
+            .ok_or_else(|| Trap::Invalid("no active frame".into()))
+    }
+
+    fn frame_mut(&mut self) -> Result<&mut Frame, Trap> {
+        self.frames
+            .last_mut()
+            .ok_or_else(|| Trap::Invalid("no active frame".into()))
+    }
+
+    fn step(&mut self) -> Result<(), Trap> {
+        let module = self.module;
+        let frame = self.frame()?;
+        let (function, pc) = (frame.function, frame.pc);
+        let Some(Function::Local(code)) = module.functions.get(function as usize) else {
+            return Err(Trap::Invalid(format!("function {function} has no body")));
+        };
+        if pc >= code.body.end {
+            return Err(Trap::Invalid("function body ends without `end`".into()));
+        }
+        self.steps += 1;
+        if self.steps > self.options.max_steps {
+            return Err(Trap::StepLimit(self.options.max_steps));
+        }
+
+        // else 必须在 if 块内才能解码，单独读取一条指令时特殊处理
+        let (op, next) = if module.bytes[pc] == ELSE_OPCODE {
+            (Operator::Else, pc + 1)
+        } else {
+            let reader = BinaryReader::new(&module.bytes[pc..code.body.end], pc);
+            let allocs = self.allocs.take().unwrap_or_default();
+            let mut reader = OperatorsReader::new_with_allocs(reader, allocs);
+            let op = reader.read()?;
+            let next = reader.original_position();
+            self.allocs = Some(reader.into_allocations());
+            (op, next)
+        };
+        if self.options.record_steps {
+            let step = self.steps;
+            self.record(|run| {
+                let base = run.frames.last().map_or(0, |f| f.stack_base);
+                let top = base.max(run.stack.len().saturating_sub(STACK_SNAPSHOT));
+                TraceEvent::Step {
+                    step,
+                    depth: run.frames.len(),
+                    function,
+                    offset: pc,
+                    text: match module.text(pc) {
+                        Some(text) => text.to_string(),
+                        None => format!("{op:?}"),
+                    },
+                    stack: run.stack[top..].to_vec(),
+                }
+            });
+        }
+        self.frame_mut()?.pc = next;
+        self.execute(op, pc, next, code)
+    }
+
+    fn jump(&mut self, pc: usize) -> Result<(), Trap> {
+        self.frame_mut()?.pc = pc;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Value, Trap> {
+        let base = self.frames.last().map_or(0, |f| f.stack_base);
+        if self.stack.len() > base
+            && let Some(value) = self.stack.pop()
+        {
+            return Ok(value);
+        }
+        Err(Trap::Invalid("operand stack underflow".into()))
+    }
+
+    fn pop_n(&mut self, n: usize) -> Result<Vec<Value>, Trap> {
+        let base = self.frames.last().map_or(0, |f| f.stack_base);
+        if self.stack.len() < base + n {
+            return Err(Trap::Invalid("operand stack underflow".into()));
+        }
+        Ok(self.stack.split_off(self.stack.len() - n))
+    }
+
+    fn pop_i32(&mut self) -> Result<i32, Trap> {
+        match self.pop()? {
+            Value::I32(v) => Ok(v),
+            other => Err(type_mismatch("i32", other)),
+        }
+    }
+
+    fn pop_i64(&mut self) -> Result<i64, Trap> {
+        match self.pop()? {
+            Value::I64(v) => Ok(v),
+            other => Err(type_mismatch("i64", other)),
+        }
+    }
+
+    fn pop_f32(&mut self) -> Result<f32, Trap> {
+        match self.pop()? {
+            Value::F32(bits) => Ok(f32::from_bits(bits)),
+            other => Err(type_mismatch("f32", other)),
+        }
+    }
+
+    fn pop_f64(&mut self) -> Result<f64, Trap> {
+        match self.pop()? {
+            Value::F64(bits) => Ok(f64::from_bits(bits)),
+            other => Err(type_mismatch("f64", other)),
+        }
+    }
+
+    fn pop_address(&mut self) -> Result<u64, Trap> {
+        let value = self.pop()?;
+        address(value)
+    }
+
+    fn local(&mut self, index: u32) -> Result<&mut Value, Trap> {
+        self.frame_mut()?
+            .locals
+            .get_mut(index as usize)
+            .ok_or_else(|| Trap::Invalid(format!("unknown local {index}")))
+    }
+
+    fn global(&mut self, index: u32) -> Result<&mut Value, Trap> {
+        self.store
+            .globals
+            .get_mut(index as usize)
+            .ok_or_else(|| Trap::Invalid(format!("unknown global {index}")))
+    }
+
+    fn call(&mut self, function: u32) -> Result<(), Trap> {
+        let module = self.module;
+        let ty = module
+            .func_type(function)
+            .ok_or_else(|| Trap::Invalid(format!("unknown function {function}")))?;
+        let args = self.pop_n(ty.params.len())?;
+        match &module.functions[function as usize] {
+            Function::Import {
+                module: import_module,
+                name,
+                ..
+            } => {
+                let results = match self.store.stubs.get(&function) {
+                    Some(results) => results.clone(),
+                    None => ty
+                        .results
+                        .iter()
+                        .map(|&ty| Value::zero(ty))
+                        .collect::<Result<_, _>>()?,
+                };
+                let step = self.steps;
+                self.record(|_| TraceEvent::HostCall {
+                    step,
+                    function,
+                    module: import_module.clone(),
+                    name: name.clone(),
+                    args: args.clone(),
+                    results: results.clone(),
+                });
+                self.stack.extend(results);
+            }
+            Function::Local(code) => {
+                if self.frames.len() >= MAX_CALL_DEPTH {
+                    return Err(Trap::CallStackExhausted);
+                }
+                let step = self.steps;
+                let depth = self.frames.len() + 1;
+                self.record(|_| TraceEvent::Call {
+                    step,
+                    depth,
+                    function,
+                    name: module.info.function_name(function).map(str::to_string),
+                    args: args.clone(),
+                });
+                let mut locals = args;
+                for &ty in &code.locals {
+                    locals.push(Value::zero(ty)?);
+                }
+                self.frames.push(Frame {
+                    function,
+                    locals,
+                    pc: code.body.start,
+                    stack_base: self.stack.len(),
+                    label_base: self.labels.len(),
+                    arity: ty.results.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn return_from_frame(&mut self) -> Result<(), Trap> {
+        let arity = self.frame()?.arity;
+        let results = self.pop_n(arity)?;
+        let Some(frame) = self.frames.pop() else {
+            return Err(Trap::Invalid("no active frame".into()));
+        };
+        self.stack.truncate(frame.stack_base);
+        self.labels.truncate(frame.label_base);
+        let step = self.steps;
+        let depth = self.frames.len() + 1;
+        self.record(|_| TraceEvent::Return {
+            step,
+            depth,
+            function: frame.function,
+            results: results.clone(),
+        });
+        self.stack.extend(results);
+        Ok(())
+    }
+
+    // br 到第 depth 层标签；最外层（函数体）的标签等同于 return
+    fn branch(&mut self, depth: u32) -> Result<(), Trap> {
+        let label_base = self.frame()?.label_base;
+        let depth = depth as usize;
+        if depth >= self.labels.len() - label_base {
+            return self.return_from_frame();
+        }
+        let index = self.labels.len() - 1 - depth;
+        let label = self.labels[index];
+        let values = self.pop_n(label.arity)?;
+        self.stack.truncate(label.height);
+        self.stack.extend(values);
+        self.labels
+            .truncate(if label.is_loop { index + 1 } else { index });
+        self.jump(label.target)
+    }
+
+    fn enter_block(
+        &mut self,
+        code: &LocalFunction,
+        pc: usize,
+        params: usize,
+        arity: usize,
+        is_loop: bool,
+        target: usize,
+    ) -> Result<(), Trap> {
+        let block = code
+            .blocks
+            .get(&pc)
+            .ok_or_else(|| Trap::Invalid(format!("unterminated block at offset {pc}")))?;
+        let base = self.frame()?.stack_base;
+        if self.stack.len() < base + params {
+            return Err(Trap::Invalid("operand stack underflow".into()));
+        }
+        self.labels.push(Label {
+            height: self.stack.len() - params,
+            arity,
+            is_loop,
+            target: if is_loop { target } else { block.end + 1 },
+            end: block.end,
+        });
+        Ok(())
+    }
+
+    fn memory(&mut self, index: u32) -> Result<&mut Memory, Trap> {
+        memory_mut(&mut self.store.memories, index)
+    }
+
+    fn table(&mut self, index: u32) -> Result<&mut Table, Trap> {
+        table_mut(&mut self.store.tables, index)
+    }
+
+    // 读取 size 字节，按小端组成整数
+    fn load(&mut self, memarg: &MemArg, size: u64) -> Result<u64, Trap> {
+        let address = self
+            .pop_address()?
+            .checked_add(memarg.offset)
+            .ok_or(Trap::MemoryOutOfBounds)?;
+        let memory = self.memory(memarg.memory)?;
+        let start = memory.check(address, size)?;
+        let mut buf = [0; 8];
+        buf[..size as usize].copy_from_slice(&memory.data[start..start + size as usize]);
+        let value = u64::from_le_bytes(buf);
+        if self.options.record_memory {
+            let step = self.steps;
+            self.record(|_| TraceEvent::Read {
+                step,
+                memory: memarg.memory,
+                address,
+                size,
+                value: Some(value),
+            });
+        }
+        Ok(value)
+    }
+
+    // 写入 value 的低 size 字节。值在栈顶，地址在它下面
+    fn store(&mut self, memarg: &MemArg, size: u64, value: u64) -> Result<(), Trap> {
+        let address = self
+            .pop_address()?
+            .checked_add(memarg.offset)
+            .ok_or(Trap::MemoryOutOfBounds)?;
+        let memory = self.memory(memarg.memory)?;
+        let start = memory.check(address, size)?;
+        memory.data[start..start + size as usize]
+            .copy_from_slice(&value.to_le_bytes()[..size as usize]);
+        if self.options.record_memory {
+            let step = self.steps;
+            self.record(|_| TraceEvent::Write {
+                step,
+                memory: memarg.memory,
+                address,
+                size,
+                value: Some(value & (u64::MAX >> (64 - 8 * size))),
+            });
+        }
+        Ok(())
+    }
+
+    fn record_bulk(&mut self, write: bool, memory: u32, address: u64, size: u64) {
+        if self.options.record_memory && size > 0 {
+            let step = self.steps;
+            self.record(|_| match write {
+                true => TraceEvent::Write {
+                    step,
+                    memory,
+                    address,
+                    size,
+                    value: None,
+                },
+                false => TraceEvent::Read {
+                    step,
+                    memory,
+                    address,
+                    size,
+                    value: None,
+                },
+            });
+        }
+    }
+
+    fn execute(
+        &mut self,
+        op: Operator<'a>,
+        pc: usize,
+        next: usize,
+        code: &'a LocalFunction,
+    ) -> Result<(), Trap> {
+        let module = self.module;
+        match op {
+            // 控制指令
+            Operator::Unreachable => return Err(Trap::Unreachable),
+            Operator::Nop => {}
+            Operator::Block { blockty } => {
+                let (params, results) = module.block_arity(blockty)?;
+                self.enter_block(code, pc, params, results, false, next)?;
+            }
+            Operator::Loop { blockty } => {
+                let (params, _) = module.block_arity(blockty)?;
+                self.enter_block(code, pc, params, params, true, next)?;
+            }
+            Operator::If { blockty } => {
+                let condition = self.pop_i32()?;
+                let (params, results) = module.block_arity(blockty)?;
+                self.enter_block(code, pc, params, results, false, next)?;
+                if condition == 0 {
+                    // 没有 else 时直接执行 end ，由它弹出标签
+                    let block = code.blocks[&pc];
+                    self.jump(block.else_offset.map_or(block.end, |e| e + 1))?;
+                }
+            }
+            Operator::Else => {
+                // then 分支执行完，跳到 end
+                let label = self
+                    .labels
+                    .last()
+                    .copied()
+                    .ok_or_else(|| Trap::Invalid("`else` outside of a block".into()))?;
+                self.jump(label.end)?;
+            }
+            Operator::End => {
+                if self.labels.len() > self.frame()?.label_base {
+                    self.labels.pop();
+                } else {
+                    self.return_from_frame()?;
+                }
+            }
+            Operator::Br { relative_depth } => self.branch(relative_depth)?,
+            Operator::BrIf { relative_depth } => {
+                if self.pop_i32()? != 0 {
+                    self.branch(relative_depth)?;
+                }
+            }
+            Operator::BrTable { targets } => {
+                let index = self.pop_i32()? as u32;
+                let depth = match targets.targets().nth(index as usize) {
+                    Some(depth) => depth?,
+                    None => targets.default(),
+                };
+                self.branch(depth)?;
+            }
+            Operator::Return => self.return_from_frame()?,
+            Operator::Call { function_index } => self.call(function_index)?,
+            Operator::CallIndirect {
+                type_index,
+                table_index,
+            } => {
+                let index = self.pop_address()?;
+                let table = self.table(table_index)?;
+                let entry = *table
+                    .elements
+                    .get(index as usize)
+                    .ok_or(Trap::UndefinedElement)?;
+                let Value::FuncRef(Some(function)) = entry else {
+                    return Err(Trap::UninitializedElement);
+                };
+                let expected = module
+                    .types
+                    .get(type_index as usize)
+                    .and_then(Option::as_ref);
+                if expected.is_none() || module.func_type(function) != expected {
+                    return Err(Trap::IndirectCallTypeMismatch);
+                }
+                self.call(function)?;
+            }
+
+            // 参数指令
+            Operator::Drop => {
+                self.pop()?;
+            }
+            Operator::Select | Operator::TypedSelect { .. } => {
+                let condition = self.pop_i32()?;
+                let b = self.pop()?;
+                let a = self.pop()?;
+                self.stack.push(if condition != 0 { a } else { b });
+            }
+
+            // 变量指令
+            Operator::LocalGet { local_index } => {
+                let value = *self.local(local_index)?;
+                self.stack.push(value);
+            }
+            Operator::LocalSet { local_index } => {
+                let value = self.pop()?;
+                *self.local(local_index)? = value;
+            }
+            Operator::LocalTee { local_index } => {
+                let value = self.pop()?;
+                *self.local(local_index)? = value;
+                self.stack.push(value);
+            }
+            Operator::GlobalGet { global_index } => {
+                let value = *self.global(global_index)?;
+                self.stack.push(value);
+            }
+            Operator::GlobalSet { global_index } => {
+                let value = self.pop()?;
+                *self.global(global_index)? = value;
+            }
+
+            // 表指令
+            Operator::TableGet { table } => {
+                let index = self.pop_address()?;
+                let table = self.table(table)?;
+                let start = table.check(index, 1)?;
+                let value = table.elements[start];
+                self.stack.push(value);
+            }
+            Operator::TableSet { table } => {
+                let value = self.pop()?;
+                let index = self.pop_address()?;
+                let table = self.table(table)?;
+                let start = table.check(index, 1)?;
+                table.elements[start] = value;
+            }
+            Operator::TableSize { table } => {
+                let size = self.table(table)?.elements.len();
+                self.stack.push(Value::I32(size as i32));
+            }
+            Operator::TableGrow { table } => {
+                let delta = self.pop_address()?;
+                let init = self.pop()?;
+                let table = self.table(table)?;
+                let old = table.elements.len() as u64;
+                let limit = table.maximum.unwrap_or(u32::MAX as u64).min(MAX_TABLE_SIZE);
+                let result = match old.checked_add(delta) {
+                    Some(new) if new <= limit => {
+                        table.elements.resize(new as usize, init);
+                        old as i32
+                    }
+                    _ => -1,
+                };
+                self.stack.push(Value::I32(result));
+            }
+            Operator::TableFill { table } => {
+                let n = self.pop_address()?;
+                let value = self.pop()?;
+                let index = self.pop_address()?;
+                let table = self.table(table)?;
+                let start = table.check(index, n)?;
+                table.elements[start..start + n as usize].fill(value);
+            }
+            Operator::TableCopy {
+                dst_table,
+                src_table,
+            } => {
+                let n = self.pop_address()?;
+                let src = self.pop_address()?;
+                let dst = self.pop_address()?;
+                let src = self.table(src_table)?.check(src, n)?;
+                let items = self.table(src_table)?.elements[src..src + n as usize].to_vec();
+                let table = self.table(dst_table)?;
+                let dst = table.check(dst, n)?;
+                table.elements[dst..dst + n as usize].copy_from_slice(&items);
+            }
+            Operator::TableInit { elem_index, table } => {
+                let n = self.pop_address()?;
+                let src = self.pop_address()?;
+                let dst = self.pop_address()?;
+                let segment = self
+                    .store
+                    .elements
+                    .get(elem_index as usize)
+                    .ok_or_else(|| {
+                        Trap::Invalid(format!("unknown element segment {elem_index}"))
+                    })?;
+                let items = match src.checked_add(n) {
+                    Some(end) if end <= segment.len() as u64 => {
+                        segment[src as usize..end as usize].to_vec()
+                    }
+                    _ => return Err(Trap::TableOutOfBounds),
+                };
+                let table = self.table(table)?;
+                let dst = table.check(dst, n)?;
+                table.elements[dst..dst + n as usize].copy_from_slice(&items);
+            }
+            Operator::ElemDrop { elem_index } => {
+                if let Some(segment) = self.store.elements.get_mut(elem_index as usize) {
+                    *segment = Vec::new();
+                }
+            }
+
+            // 内存指令
+            Operator::I32Load { memarg } => {
+                let v = self.load(&memarg, 4)?;
+                self.stack.push(Value::I32(v as u32 as i32));
+            }
+            Operator::I64Load { memarg } => {
+                let v = self.load(&memarg, 8)?;
+                self.stack.push(Value::I64(v as i64));
+            }
+            Operator::F32Load { memarg } => {
+                let v = self.load(&memarg, 4)?;
+                self.stack.push(Value::F32(v as u32));
+            }
+            Operator::F64Load { memarg } => {
+                let v = self.load(&memarg, 8)?;
+                self.stack.push(Value::F64(v));
+            }
+            Operator::I32Load8S { memarg } => {
+                let v = self.load(&memarg, 1)?;
+                self.stack.push(Value::I32(v as u8 as i8 as i32));
+            }
+            Operator::I32Load8U { memarg } => {
+                let v = self.load(&memarg, 1)?;
+                self.stack.push(Value::I32(v as u8 as i32));
+            }
+            Operator::I32Load16S { memarg } => {
+                let v = self.load(&memarg, 2)?;
+                self.stack.push(Value::I32(v as u16 as i16 as i32));
+            }
+            Operator::I32Load16U { memarg } => {
+                let v = self.load(&memarg, 2)?;
+                self.stack.push(Value::I32(v as u16 as i32));
+            }
+            Operator::I64Load8S { memarg } => {
+                let v = self.load(&memarg, 1)?;
+                self.stack.push(Value::I64(v as u8 as i8 as i64));
+            }
+            Operator::I64Load8U { memarg } => {
+                let v = self.load(&memarg, 1)?;
+                self.stack.push(Value::I64(v as u8 as i64));
+            }
+            Operator::I64Load16S { memarg } => {
+                let v = self.load(&memarg, 2)?;
+                self.stack.push(Value::I64(v as u16 as i16 as i64));
+            }
+            Operator::I64Load16U { memarg } => {
+                let v = self.load(&memarg, 2)?;
+                self.stack.push(Value::I64(v as u16 as i64));
+            }
+            Operator::I64Load32S { memarg } => {
+                let v = self.load(&memarg, 4)?;
+                self.stack.push(Value::I64(v as u32 as i32 as i64));
+            }
+            Operator::I64Load32U { memarg } => {
+                let v = self.load(&memarg, 4)?;
+                self.stack.push(Value::I64(v as u32 as i64));
+            }
+            Operator::I32Store { memarg } => {
+                let v = self.pop_i32()?;
+                self.store(&memarg, 4, v as u32 as u64)?;
+            }
+            Operator::I32Store8 { memarg } => {
+                let v = self.pop_i32()?;
+                self.store(&memarg, 1, v as u32 as u64)?;
+            }
+            Operator::I32Store16 { memarg } => {
+                let v = self.pop_i32()?;
+                self.store(&memarg, 2, v as u32 as u64)?;
+            }
+            Operator::I64Store { memarg } => {
+                let v = self.pop_i64()?;
+                self.store(&memarg, 8, v as u64)?;
+            }
+            Operator::I64Store8 { memarg } => {
+                let v = self.pop_i64()?;
+                self.store(&memarg, 1, v as u64)?;
+            }
+            Operator::I64Store16 { memarg } => {
+                let v = self.pop_i64()?;
+                self.store(&memarg, 2, v as u64)?;
+            }
+            Operator::I64Store32 { memarg } => {
+                let v = self.pop_i64()?;
+                self.store(&memarg, 4, v as u64)?;
+            }
+            Operator::F32Store { memarg } => {
+                let v = self.pop_f32()?;
+                self.store(&memarg, 4, v.to_bits() as u64)?;
+            }
+            Operator::F64Store { memarg } => {
+                let v = self.pop_f64()?;
+                self.store(&memarg, 8, v.to_bits())?;
+            }
+            Operator::MemorySize { mem } => {
+                let memory = self.memory(mem)?;
+                let pages = memory.pages();
+                let value = match memory.memory64 {
+                    true => Value::I64(pages as i64),
+                    false => Value::I32(pages as i32),
+                };
+                self.stack.push(value);
+            }
+            Operator::MemoryGrow { mem } => {
+                let delta = self.pop_address()?;
+                let memory = self.memory(mem)?;
+                let old = memory.pages();
+                let limit = memory
+                    .maximum
+                    .unwrap_or(u64::MAX)
+                    .min(MAX_MEMORY_BYTES / memory.page_size);
+                let result = match old.checked_add(delta) {
+                    Some(new) if new <= limit => {
+                        memory.data.resize((new * memory.page_size) as usize, 0);
+                        old as i64
+                    }
+                    _ => -1,
+                };
+                let value = match memory.memory64 {
+                    true => Value::I64(result),
+                    false => Value::I32(result as i32),
+                };
+                self.stack.push(value);
+            }
+            Operator::MemoryFill { mem } => {
+                let n = self.pop_address()?;
+                let value = self.pop_i32()?;
+                let dst = self.pop_address()?;
+                let memory = self.memory(mem)?;
+                let start = memory.check(dst, n)?;
+                memory.data[start..start + n as usize].fill(value as u8);
+                self.record_bulk(true, mem, dst, n);
+            }
+            Operator::MemoryCopy { dst_mem, src_mem } => {
+                let n = self.pop_address()?;
+                let src = self.pop_address()?;
+                let dst = self.pop_address()?;
+                let src_start = self.memory(src_mem)?.check(src, n)?;
+                let dst_start = self.memory(dst_mem)?.check(dst, n)?;
+                if src_mem == dst_mem {
+                    let memory = self.memory(dst_mem)?;
+                    memory
+                        .data
+                        .copy_within(src_start..src_start + n as usize, dst_start);
+                } else {
+                    let bytes =
+                        self.memory(src_mem)?.data[src_start..src_start + n as usize].to_vec();
+                    self.memory(dst_mem)?.data[dst_start..dst_start + n as usize]
+                        .copy_from_slice(&bytes);
+                }
+                self.record_bulk(false, src_mem, src, n);
+                self.record_bulk(true, dst_mem, dst, n);
+            }
+            Operator::MemoryInit { data_index, mem } => {
+                let n = self.pop_address()?;
+                let src = self.pop_address()?;
+                let dst = self.pop_address()?;
+                let segment = module
+                    .data
+                    .get(data_index as usize)
+                    .ok_or_else(|| Trap::Invalid(format!("unknown data segment {data_index}")))?;
+                let len = match self.store.data_dropped[data_index as usize] {
+                    true => 0,
+                    false => segment.range.len() as u64,
+                };
+                if src.checked_add(n).is_none_or(|end| end > len) {
+                    return Err(Trap::MemoryOutOfBounds);
+                }
+                let from = segment.range.start + src as usize;
+                let memory = self.memory(mem)?;
+                let start = memory.check(dst, n)?;
+                memory.data[start..start + n as usize]
+                    .copy_from_slice(&module.bytes[from..from + n as usize]);
+                self.record_bulk(true, mem, dst, n);
+            }
+            Operator::DataDrop { data_index } => {
+                if let Some(dropped) = self.store.data_dropped.get_mut(data_index as usize) {
+                    *dropped = true;
+                }
+            }
+
+            // 常量和引用
+            Operator::I32Const { value } => self.stack.push(Value::I32(value)),
+            Operator::I64Const { value } => self.stack.push(Value::I64(value)),
+            Operator::F32Const { value } => self.stack.push(Value::F32(value.bits())),
+            Operator::F64Const { value } => self.stack.push(Value::F64(value.bits())),
+            Operator::RefNull { hty } => self.stack.push(null_ref(hty)),
+            Operator::RefIsNull => {
+                let value = match self.pop()? {
+                    Value::FuncRef(r) | Value::ExternRef(r) => r.is_none(),
+                    other => return Err(type_mismatch("reference", other)),
+                };
+                self.stack.push(bool_value(value));
+            }
+            Operator::RefFunc { function_index } => {
+                self.stack.push(Value::FuncRef(Some(function_index)))
+            }
+
+            // i32 比较和运算
+            Operator::I32Eqz => unary!(self, pop_i32, bool_value, |a| a == 0),
+            Operator::I32Eq => binary!(self, pop_i32, bool_value, |a, b| a == b),
+            Operator::I32Ne => binary!(self, pop_i32, bool_value, |a, b| a != b),
+            Operator::I32LtS => binary!(self, pop_i32, bool_value, |a, b| a < b),
+            Operator::I32LtU => binary!(self, pop_i32, bool_value, |a, b| (a as u32) < b as u32),
+            Operator::I32GtS => binary!(self, pop_i32, bool_value, |a, b| a > b),
+            Operator::I32GtU => binary!(self, pop_i32, bool_value, |a, b| a as u32 > b as u32),
+            Operator::I32LeS => binary!(self, pop_i32, bool_value, |a, b| a <= b),
+            Operator::I32LeU => binary!(self, pop_i32, bool_value, |a, b| a as u32 <= b as u32),
+            Operator::I32GeS => binary!(self, pop_i32, bool_value, |a, b| a >= b),
+            Operator::I32GeU => binary!(self, pop_i32, bool_value, |a, b| a as u32 >= b as u32),
+            Operator::I32Clz => unary!(self, pop_i32, Value::I32, |a| a.leading_zeros() as i32),
+            Operator::I32Ctz => unary!(self, pop_i32, Value::I32, |a| a.trailing_zeros() as i32),
+            Operator::I32Popcnt => unary!(self, pop_i32, Value::I32, |a| a.count_ones() as i32),
+            Operator::I32Add => binary!(self, pop_i32, Value::I32, |a, b| a.wrapping_add(b)),
+            Operator::I32Sub => binary!(self, pop_i32, Value::I32, |a, b| a.wrapping_sub(b)),
+            Operator::I32Mul => binary!(self, pop_i32, Value::I32, |a, b| a.wrapping_mul(b)),
+            Operator::I32DivS => binary!(self, pop_i32, Value::I32, |a, b| match (a, b) {
+                (_, 0) => return Err(Trap::DivideByZero),
+                (i32::MIN, -1) => return Err(Trap::IntegerOverflow),
+                _ => a / b,
+            }),
+            Operator::I32DivU => binary!(self, pop_i32, Value::I32, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => (a as u32 / b as u32) as i32,
+            }),
+            Operator::I32RemS => binary!(self, pop_i32, Value::I32, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => a.wrapping_rem(b),
+            }),
+            Operator::I32RemU => binary!(self, pop_i32, Value::I32, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => (a as u32 % b as u32) as i32,
+            }),
+            Operator::I32And => binary!(self, pop_i32, Value::I32, |a, b| a & b),
+            Operator::I32Or => binary!(self, pop_i32, Value::I32, |a, b| a | b),
+            Operator::I32Xor => binary!(self, pop_i32, Value::I32, |a, b| a ^ b),
+            Operator::I32Shl => binary!(self, pop_i32, Value::I32, |a, b| a.wrapping_shl(b as u32)),
+            Operator::I32ShrS => {
+                binary!(self, pop_i32, Value::I32, |a, b| a.wrapping_shr(b as u32))
+            }
+            Operator::I32ShrU => binary!(self, pop_i32, Value::I32, |a, b| (a as u32)
+                .wrapping_shr(b as u32)
+                as i32),
+            Operator::I32Rotl => binary!(self, pop_i32, Value::I32, |a, b| a.rotate_left(b as u32)),
+            Operator::I32Rotr => {
+                binary!(self, pop_i32, Value::I32, |a, b| a.rotate_right(b as u32))
+            }
+
+            // i64 比较和运算
+            Operator::I64Eqz => unary!(self, pop_i64, bool_value, |a| a == 0),
+            Operator::I64Eq => binary!(self, pop_i64, bool_value, |a, b| a == b),
+            Operator::I64Ne => binary!(self, pop_i64, bool_value, |a, b| a != b),
+            Operator::I64LtS => binary!(self, pop_i64, bool_value, |a, b| a < b),
+            Operator::I64LtU => binary!(self, pop_i64, bool_value, |a, b| (a as u64) < b as u64),
+            Operator::I64GtS => binary!(self, pop_i64, bool_value, |a, b| a > b),
+            Operator::I64GtU => binary!(self, pop_i64, bool_value, |a, b| a as u64 > b as u64),
+            Operator::I64LeS => binary!(self, pop_i64, bool_value, |a, b| a <= b),
+            Operator::I64LeU => binary!(self, pop_i64, bool_value, |a, b| a as u64 <= b as u64),
+            Operator::I64GeS => binary!(self, pop_i64, bool_value, |a, b| a >= b),
+            Operator::I64GeU => binary!(self, pop_i64, bool_value, |a, b| a as u64 >= b as u64),
+            Operator::I64Clz => unary!(self, pop_i64, Value::I64, |a| a.leading_zeros() as i64),
+            Operator::I64Ctz => unary!(self, pop_i64, Value::I64, |a| a.trailing_zeros() as i64),
+            Operator::I64Popcnt => unary!(self, pop_i64, Value::I64, |a| a.count_ones() as i64),
+            Operator::I64Add => binary!(self, pop_i64, Value::I64, |a, b| a.wrapping_add(b)),
+            Operator::I64Sub => binary!(self, pop_i64, Value::I64, |a, b| a.wrapping_sub(b)),
+            Operator::I64Mul => binary!(self, pop_i64, Value::I64, |a, b| a.wrapping_mul(b)),
+            Operator::I64DivS => binary!(self, pop_i64, Value::I64, |a, b| match (a, b) {
+                (_, 0) => return Err(Trap::DivideByZero),
+                (i64::MIN, -1) => return Err(Trap::IntegerOverflow),
+                _ => a / b,
+            }),
+            Operator::I64DivU => binary!(self, pop_i64, Value::I64, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => (a as u64 / b as u64) as i64,
+            }),
+            Operator::I64RemS => binary!(self, pop_i64, Value::I64, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => a.wrapping_rem(b),
+            }),
+            Operator::I64RemU => binary!(self, pop_i64, Value::I64, |a, b| match b {
+                0 => return Err(Trap::DivideByZero),
+                _ => (a as u64 % b as u64) as i64,
+            }),
+            Operator::I64And => binary!(self, pop_i64, Value::I64, |a, b| a & b),
+            Operator::I64Or => binary!(self, pop_i64, Value::I64, |a, b| a | b),
+            Operator::I64Xor => binary!(self, pop_i64, Value::I64, |a, b| a ^ b),
+            Operator::I64Shl => binary!(self, pop_i64, Value::I64, |a, b| a.wrapping_shl(b as u32)),
+            Operator::I64ShrS => {
+                binary!(self, pop_i64, Value::I64, |a, b| a.wrapping_shr(b as u32))
+            }
+            Operator::I64ShrU => binary!(self, pop_i64, Value::I64, |a, b| (a as u64)
+                .wrapping_shr(b as u32)
+                as i64),
+            Operator::I64Rotl => binary!(self, pop_i64, Value::I64, |a, b| a.rotate_left(b as u32)),
+            Operator::I64Rotr => {
+                binary!(self, pop_i64, Value::I64, |a, b| a.rotate_right(b as u32))
+            }
+
+            // f32 比较和运算。abs / neg / copysign 只改符号位，NaN 的负载不变
+            Operator::F32Eq => binary!(self, pop_f32, bool_value, |a, b| a == b),
+            Operator::F32Ne => binary!(self, pop_f32, bool_value, |a, b| a != b),
+            Operator::F32Lt => binary!(self, pop_f32, bool_value, |a, b| a < b),
+            Operator::F32Gt => binary!(self, pop_f32, bool_value, |a, b| a > b),
+            Operator::F32Le => binary!(self, pop_f32, bool_value, |a, b| a <= b),
+            Operator::F32Ge => binary!(self, pop_f32, bool_value, |a, b| a >= b),
+            Operator::F32Abs => unary!(self, pop_f32, f32_value, |a| a.abs()),
+            Operator::F32Neg => unary!(self, pop_f32, f32_value, |a| -a),
+            Operator::F32Ceil => unary!(self, pop_f32, f32_value, |a| quiet_f32(a.ceil())),
+            Operator::F32Floor => unary!(self, pop_f32, f32_value, |a| quiet_f32(a.floor())),
+            Operator::F32Trunc => unary!(self, pop_f32, f32_value, |a| quiet_f32(a.trunc())),
+            Operator::F32Nearest => {
+                unary!(self, pop_f32, f32_value, |a| quiet_f32(a.round_ties_even()))
+            }
+            Operator::F32Sqrt => unary!(self, pop_f32, f32_value, |a| a.sqrt()),
+            Operator::F32Add => binary!(self, pop_f32, f32_value, |a, b| a + b),
+            Operator::F32Sub => binary!(self, pop_f32, f32_value, |a, b| a - b),
+            Operator::F32Mul => binary!(self, pop_f32, f32_value, |a, b| a * b),
+            Operator::F32Div => binary!(self, pop_f32, f32_value, |a, b| a / b),
+            Operator::F32Min => binary!(self, pop_f32, f32_value, |a, b| wasm_min(
+                a as f64, b as f64
+            ) as f32),
+            Operator::F32Max => binary!(self, pop_f32, f32_value, |a, b| wasm_max(
+                a as f64, b as f64
+            ) as f32),
+            Operator::F32Copysign => binary!(self, pop_f32, f32_value, |a, b| a.copysign(b)),
+
+            // f64 比较和运算
+            Operator::F64Eq => binary!(self, pop_f64, bool_value, |a, b| a == b),
+            Operator::F64Ne => binary!(self, pop_f64, bool_value, |a, b| a != b),
+            Operator::F64Lt => binary!(self, pop_f64, bool_value, |a, b| a < b),
+            Operator::F64Gt => binary!(self, pop_f64, bool_value, |a, b| a > b),
+            Operator::F64Le => binary!(self, pop_f64, bool_value, |a, b| a <= b),
+            Operator::F64Ge => binary!(self, pop_f64, bool_value, |a, b| a >= b),
+            Operator::F64Abs => unary!(self, pop_f64, f64_value, |a| a.abs()),
+            Operator::F64Neg => unary!(self, pop_f64, f64_value, |a| -a),
+            Operator::F64Ceil => unary!(self, pop_f64, f64_value, |a| quiet_f64(a.ceil())),
+            Operator::F64Floor => unary!(self, pop_f64, f64_value, |a| quiet_f64(a.floor())),
+            Operator::F64Trunc => unary!(self, pop_f64, f64_value, |a| quiet_f64(a.trunc())),
+            Operator::F64Nearest => {
+                unary!(self, pop_f64, f64_value, |a| quiet_f64(a.round_ties_even()))
+            }
+            Operator::F64Sqrt => unary!(self, pop_f64, f64_value, |a| a.sqrt()),
+            Operator::F64Add => binary!(self, pop_f64, f64_value, |a, b| a + b),
+            Operator::F64Sub => binary!(self, pop_f64, f64_value, |a, b| a - b),
+            Operator::F64Mul => binary!(self, pop_f64, f64_value, |a, b| a * b),
+            Operator::F64Div => binary!(self, pop_f64, f64_value, |a, b| a / b),
+            Operator::F64Min => binary!(self, pop_f64, f64_value, |a, b| wasm_min(a, b)),
+            Operator::F64Max => binary!(self, pop_f64, f64_value, |a, b| wasm_max(a, b)),
+            Operator::F64Copysign => binary!(self, pop_f64, f64_value, |a, b| a.copysign(b)),
+
+            // 类型转换
+            Operator::I32WrapI64 => unary!(self, pop_i64, Value::I32, |a| a as i32),
+            Operator::I64ExtendI32S => unary!(self, pop_i32, Value::I64, |a| a as i64),
+            Operator::I64ExtendI32U => unary!(self, pop_i32, Value::I64, |a| a as u32 as i64),
+            Operator::I32TruncF32S => unary!(self, pop_f32, Value::I32, |a| trunc(
+                a as f64, I32_MIN, I32_END
+            )? as i32),
+            Operator::I32TruncF32U => unary!(self, pop_f32, Value::I32, |a| trunc(
+                a as f64, 0.0, U32_END
+            )? as u32
+                as i32),
+            Operator::I32TruncF64S => {
+                unary!(self, pop_f64, Value::I32, |a| trunc(a, I32_MIN, I32_END)?
+                    as i32)
+            }
+            Operator::I32TruncF64U => unary!(
+                self,
+                pop_f64,
+                Value::I32,
+                |a| trunc(a, 0.0, U32_END)? as u32 as i32
+            ),
+            Operator::I64TruncF32S => unary!(self, pop_f32, Value::I64, |a| trunc(
+                a as f64, I64_MIN, I64_END
+            )? as i64),
+            Operator::I64TruncF32U => unary!(self, pop_f32, Value::I64, |a| trunc(
+                a as f64, 0.0, U64_END
+            )? as u64
+                as i64),
+            Operator::I64TruncF64S => {
+                unary!(self, pop_f64, Value::I64, |a| trunc(a, I64_MIN, I64_END)?
+                    as i64)
+            }
+            Operator::I64TruncF64U => unary!(
+                self,
+                pop_f64,
+                Value::I64,
+                |a| trunc(a, 0.0, U64_END)? as u64 as i64
+            ),
+            // Rust 的浮点转整数本身就是饱和的，NaN 转成 0 ，与 trunc_sat 一致
+            Operator::I32TruncSatF32S => unary!(self, pop_f32, Value::I32, |a| a as i32),
+            Operator::I32TruncSatF32U => unary!(self, pop_f32, Value::I32, |a| a as u32 as i32),
+            Operator::I32TruncSatF64S => unary!(self, pop_f64, Value::I32, |a| a as i32),
+            Operator::I32TruncSatF64U => unary!(self, pop_f64, Value::I32, |a| a as u32 as i32),
+            Operator::I64TruncSatF32S => unary!(self, pop_f32, Value::I64, |a| a as i64),
+            Operator::I64TruncSatF32U => unary!(self, pop_f32, Value::I64, |a| a as u64 as i64),
+            Operator::I64TruncSatF64S => unary!(self, pop_f64, Value::I64, |a| a as i64),
+            Operator::I64TruncSatF64U => unary!(self, pop_f64, Value::I64, |a| a as u64 as i64),
+            // 整数转浮点按就近舍入（ties to even），与 Rust 的 as 相同
+            Operator::F32ConvertI32S => unary!(self, pop_i32, f32_value, |a| a as f32),
+            Operator::F32ConvertI32U => unary!(self, pop_i32, f32_value, |a| a as u32 as f32),
+            Operator::F32ConvertI64S => unary!(self, pop_i64, f32_value, |a| a as f32),
+            Operator::F32ConvertI64U => unary!(self, pop_i64, f32_value, |a| a as u64 as f32),
+            Operator::F32DemoteF64 => unary!(self, pop_f64, f32_value, |a| a as f32),
+            Operator::F64ConvertI32S => unary!(self, pop_i32, f64_value, |a| a as f64),
+            Operator::F64ConvertI32U => unary!(self, pop_i32, f64_value, |a| a as u32 as f64),
+            Operator::F64ConvertI64S => unary!(self, pop_i64, f64_value, |a| a as f64),
+            Operator::F64ConvertI64U => unary!(self, pop_i64, f64_value, |a| a as u64 as f64),
+            Operator::F64PromoteF32 => unary!(self, pop_f32, f64_value, |a| a as f64),
+            Operator::I32ReinterpretF32 => {
+                unary!(self, pop_f32, Value::I32, |a| a.to_bits() as i32)
+            }
+            Operator::I64ReinterpretF64 => {
+                unary!(self, pop_f64, Value::I64, |a| a.to_bits() as i64)
+            }
+            Operator::F32ReinterpretI32 => unary!(self, pop_i32, Value::F32, |a| a as u32),
+            Operator::F64ReinterpretI64 => unary!(self, pop_i64, Value::F64, |a| a as u64),
+
+            // 符号扩展
+            Operator::I32Extend8S => unary!(self, pop_i32, Value::I32, |a| a as i8 as i32),
+            Operator::I32Extend16S => unary!(self, pop_i32, Value::I32, |a| a as i16 as i32),
+            Operator::I64Extend8S => unary!(self, pop_i64, Value::I64, |a| a as i8 as i64),
+            Operator::I64Extend16S => unary!(self, pop_i64, Value::I64, |a| a as i16 as i64),
+            Operator::I64Extend32S => unary!(self, pop_i64, Value::I64, |a| a as i32 as i64),
+
+            other => return Err(Trap::Unsupported(format!("{other:?}"))),
+        }
+        Ok(())
+    }
+}
+
+const I32_MIN: f64 = -2147483648.0;
+const I32_END: f64 = 2147483648.0;
+const U32_END: f64 = 4294967296.0;
+const I64_MIN: f64 = -9223372036854775808.0;
+const I64_END: f64 = 9223372036854775808.0;
+const U64_END: f64 = 18446744073709551616.0;
+
+// 浮点数截断成整数：NaN 和截断后超出 [min, end) 的值会 trap 。f32 转 f64 是精确的，共用这一个函数
+fn trunc(value: f64, min: f64, end: f64) -> Result<f64, Trap> {
+    if value.is_nan() {
+        return Err(Trap::InvalidConversion);
+    }
+    let truncated = value.trunc();
+    if truncated < min || truncated >= end {
+        return Err(Trap::IntegerOverflow);
+    }
+    Ok(truncated)
+}
+
+// ceil / floor / trunc / nearest 对 NaN 原样返回输入，signaling NaN 需要置上 quiet 位才是 arithmetic NaN
+fn quiet_f32(value: f32) -> f32 {
+    if value.is_nan() {
+        f32::from_bits(value.to_bits() | 0x0040_0000)
+    } else {
+        value
+    }
+}
+
+fn quiet_f64(value: f64) -> f64 {
+    if value.is_nan() {
+        f64::from_bits(value.to_bits() | 0x0008_0000_0000_0000)
+    } else {
+        value
+    }
+}
+
+// wasm 的 min / max ：任一操作数是 NaN 时结果是 NaN ，-0 小于 +0 。
+// f32 提升到 f64 计算再转回，结果不变
+fn wasm_min(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else if a == b {
+        f64::from_bits(a.to_bits() | b.to_bits())
+    } else {
+        a.min(b)
+    }
+}
+
+fn wasm_max(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else if a == b {
+        f64::from_bits(a.to_bits() & b.to_bits())
+    } else {
+        a.max(b)
+    }
+}
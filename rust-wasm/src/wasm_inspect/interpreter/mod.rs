@@ -0,0 +1,793 @@
+// 动态分析用的 wasm 解释器：实现了 MVP 以及 rustc 默认启用的 bulk memory 、sign-ext 、
+// nontrapping-fptoint 、multi-value 和 reference-types ，不支持 SIMD 、线程和异常。
+// 没有跑完整的规范测试套件，各条指令的语义由 tests/wast 下按规范测试格式写的用例检查
+// - 实例化前用 wasmparser 校验，未通过校验的模块直接报 InvalidWasm ；通过校验但用到不支持的指令时
+//   执行到该指令才 trap
+// - 导入函数不执行，返回桩值（默认是各结果类型的零值），调用记录为 host_call 事件
+// - 每条指令、每次内存读写、每次调用和返回都记录为事件，超过 trace_limit 后只执行不记录
+mod loader;
+mod machine;
+mod trace;
+mod value;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasmparser::Validator;
+
+use self::loader::{Function, Module};
+use self::machine::{Run, Store};
+pub use self::trace::TraceEvent;
+pub use self::value::{Trap, Value};
+use super::inspect_error::InspectError;
+
+/// 执行和记录的上限
+#[derive(Debug, Clone)]
+pub struct TraceOptions {
+    // 超过后以 "step limit" trap ，防止死循环卡住页面
+    pub max_steps: u64,
+    // 最多记录的事件数
+    pub trace_limit: usize,
+    pub record_steps: bool,
+    pub record_memory: bool,
+}
+
+impl Default for TraceOptions {
+    fn default() -> Self {
+        TraceOptions {
+            max_steps: 10_000_000,
+            trace_limit: 100_000,
+            record_steps: true,
+            record_memory: true,
+        }
+    }
+}
+
+/// 一次调用的结果。trap 时 results 为空，trace 记录到 trap 之前
+#[derive(Debug, Clone, Serialize)]
+pub struct Execution {
+    pub function: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_name: Option<String>,
+    pub results: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trap: Option<Trap>,
+    pub steps: u64,
+    // 事件数超过 trace_limit ，后面的事件没有记录
+    pub truncated: bool,
+    pub trace: Vec<TraceEvent>,
+}
+
+/// 一个模块实例。内存、表和全局变量在多次调用之间保留，
+/// 可以先调用 `__wbindgen_malloc` 分配内存、写入参数，再调用导出函数
+#[wasm_bindgen]
+pub struct WasmInterpreter {
+    module: Module,
+    store: Store,
+    options: TraceOptions,
+}
+
+impl WasmInterpreter {
+    pub fn options_mut(&mut self) -> &mut TraceOptions {
+        &mut self.options
+    }
+
+    /// 调用函数（索引、名字、导出名或导入的 "module.name"），参数个数和类型必须与签名一致
+    pub fn invoke(&mut self, function: &str, args: &[Value]) -> Result<Execution, InspectError> {
+        let index = self.resolve(function)?;
+        let ty = self
+            .module
+            .func_type(index)
+            .ok_or_else(|| InspectError::Unsupported(format!("type of function {function}")))?;
+        if args.len() != ty.params.len()
+            || args
+                .iter()
+                .zip(&ty.params)
+                .any(|(arg, &ty)| !arg.matches(ty))
+        {
+            return Err(InspectError::InvalidArguments(format!(
+                "{function} expects ({}), got ({})",
+                ty.params
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                args.iter()
+                    .map(Value::type_name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )));
+        }
+
+        let mut run = Run::new(&self.module, &mut self.store, &self.options);
+        let (results, trap) = match run.invoke(index, args) {
+            Ok(results) => (results, None),
+            Err(trap) => (Vec::new(), Some(trap)),
+        };
+        Ok(Execution {
+            function: index,
+            function_name: self.module.info.function_name(index).map(str::to_string),
+            results,
+            trap,
+            steps: run.steps,
+            truncated: run.truncated,
+            trace: run.trace,
+        })
+    }
+
+    /// 参数以 JSON 数组给出，按形参类型转换，见 [`Value::from_json`]
+    pub fn invoke_json(&mut self, function: &str, args: &str) -> Result<Execution, InspectError> {
+        let index = self.resolve(function)?;
+        let params = self
+            .module
+            .func_type(index)
+            .map(|ty| ty.params.clone())
+            .unwrap_or_default();
+        let args = parse_values(args, &params)?;
+        self.invoke(function, &args)
+    }
+
+    /// 设置导入函数的桩返回值
+    pub fn set_stub(&mut self, import: &str, results: &[Value]) -> Result<(), InspectError> {
+        let index = self.resolve(import)?;
+        let Function::Import { .. } = &self.module.functions[index as usize] else {
+            return Err(InspectError::InvalidArguments(format!(
+                "{import} is not an import"
+            )));
+        };
+        let expected = &self
+            .module
+            .func_type(index)
+            .ok_or_else(|| InspectError::Unsupported(format!("type of function {import}")))?
+            .results;
+        if results.len() != expected.len()
+            || results.iter().zip(expected).any(|(v, &ty)| !v.matches(ty))
+        {
+            return Err(InspectError::InvalidArguments(format!(
+                "{import} returns {} values",
+                expected.len()
+            )));
+        }
+        self.store.stubs.insert(index, results.to_vec());
+        Ok(())
+    }
+
+    /// 第 index 块内存的全部内容
+    pub fn memory(&self, index: u32) -> Option<&[u8]> {
+        self.store
+            .memories
+            .get(index as usize)
+            .map(|m| m.data.as_slice())
+    }
+
+    fn resolve(&self, function: &str) -> Result<u32, InspectError> {
+        self.module
+            .info
+            .find_function(function)
+            .map(|f| f.index)
+            .ok_or_else(|| InspectError::FunctionNotFound(function.to_string()))
+    }
+
+    fn memory_range(
+        &self,
+        address: u64,
+        size: usize,
+    ) -> Result<std::ops::Range<usize>, InspectError> {
+        let len = self.memory(0).map_or(0, <[u8]>::len) as u64;
+        match address.checked_add(size as u64) {
+            Some(end) if end <= len => Ok(address as usize..end as usize),
+            _ => Err(InspectError::MemoryOutOfBounds { address, size }),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl WasmInterpreter {
+    /// 校验、解析并实例化模块，有 start 函数时执行它（不记录事件）
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8]) -> Result<WasmInterpreter, InspectError> {
+        Validator::new().validate_all(bytes)?;
+        let module = Module::load(bytes)?;
+        let store = Store::new(&module).map_err(InspectError::Instantiation)?;
+        let mut interpreter = WasmInterpreter {
+            module,
+            store,
+            options: TraceOptions::default(),
+        };
+        if let Some(start) = interpreter.module.start {
+            let options = TraceOptions {
+                trace_limit: 0,
+                ..TraceOptions::default()
+            };
+            Run::new(&interpreter.module, &mut interpreter.store, &options)
+                .invoke(start, &[])
+                .map_err(InspectError::Instantiation)?;
+        }
+        Ok(interpreter)
+    }
+
+    /// 调用函数，args 是 JSON 数组，返回 JSON 形式的 [`Execution`]
+    pub fn call(&mut self, function: &str, args: &str) -> Result<String, InspectError> {
+        let execution = self.invoke_json(function, args)?;
+        Ok(serde_json::to_string(&execution).unwrap_or_default())
+    }
+
+    /// 设置导入函数（"module.name" 或索引）的桩返回值，results 是 JSON 数组
+    pub fn stub_import(&mut self, import: &str, results: &str) -> Result<(), InspectError> {
+        let index = self.resolve(import)?;
+        let types = self
+            .module
+            .func_type(index)
+            .map(|ty| ty.results.clone())
+            .unwrap_or_default();
+        let results = parse_values(results, &types)?;
+        self.set_stub(import, &results)
+    }
+
+    /// 读取第 0 块内存
+    pub fn read_memory(&self, address: u32, len: u32) -> Result<Vec<u8>, InspectError> {
+        let range = self.memory_range(address as u64, len as usize)?;
+        Ok(self.store.memories[0].data[range].to_vec())
+    }
+
+    /// 写入第 0 块内存，例如传给 wasm-bindgen 导出函数的字符串
+    pub fn write_memory(&mut self, address: u32, data: &[u8]) -> Result<(), InspectError> {
+        let range = self.memory_range(address as u64, data.len())?;
+        self.store.memories[0].data[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.options.max_steps = max_steps as u64;
+    }
+
+    pub fn set_trace_limit(&mut self, trace_limit: u32) {
+        self.options.trace_limit = trace_limit as usize;
+    }
+
+    pub fn set_record_steps(&mut self, record: bool) {
+        self.options.record_steps = record;
+    }
+
+    pub fn set_record_memory(&mut self, record: bool) {
+        self.options.record_memory = record;
+    }
+}
+
+fn parse_values(json: &str, types: &[wasmparser::ValType]) -> Result<Vec<Value>, InspectError> {
+    let json: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| InspectError::InvalidArguments(format!("expected a JSON array: {e}")))?;
+    if json.len() != types.len() {
+        return Err(InspectError::InvalidArguments(format!(
+            "expected {} values, got {}",
+            types.len(),
+            json.len()
+        )));
+    }
+    json.iter()
+        .zip(types)
+        .map(|(json, &ty)| {
+            Value::from_json(ty, json).ok_or_else(|| {
+                InspectError::InvalidArguments(format!("{json} is not a valid {ty}"))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+
+    fn instantiate(wat: &str) -> WasmInterpreter {
+        WasmInterpreter::new(&wat::parse_str(wat).unwrap()).unwrap()
+    }
+
+    fn results(interpreter: &mut WasmInterpreter, function: &str, args: &[Value]) -> Vec<Value> {
+        let execution = interpreter.invoke(function, args).unwrap();
+        assert_eq!(execution.trap, None, "{function} trapped");
+        execution.results
+    }
+
+    fn trap(interpreter: &mut WasmInterpreter, function: &str, args: &[Value]) -> Option<Trap> {
+        interpreter.invoke(function, args).unwrap().trap
+    }
+
+    #[test]
+    fn control_flow_test() {
+        let mut interpreter = instantiate(
+            r#"
+            (module
+                (func $fac (export "fac") (param i64) (result i64)
+                    (if (result i64) (i64.eqz (local.get 0))
+                        (then (i64.const 1))
+                        (else (i64.mul (local.get 0)
+                            (call $fac (i64.sub (local.get 0) (i64.const 1)))))))
+                (func (export "sum") (param i32) (result i32) (local i32)
+                    (block $done
+                        (loop $next
+                            (br_if $done (i32.eqz (local.get 0)))
+                            (local.set 1 (i32.add (local.get 1) (local.get 0)))
+                            (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                            (br $next)))
+                    (local.get 1))
+                (func (export "switch") (param i32) (result i32)
+                    (block $c
+                        (block $b
+                            (block $a (br_table $a $b $c (local.get 0)))
+                            (return (i32.const 10)))
+                        (return (i32.const 20)))
+                    (i32.const 30))
+                (func (export "swap") (param i32 i32) (result i32 i32)
+                    (local.get 0) (local.get 1)
+                    (block (param i32 i32) (result i32 i32)
+                        (local.set 0) (local.set 1) (local.get 0) (local.get 1)))
+                (func (export "pick") (param i32) (result i32)
+                    (select (i32.const 1) (i32.const 2) (local.get 0))))
+            "#,
+        );
+        assert_eq!(
+            results(&mut interpreter, "fac", &[Value::I64(20)]),
+            [Value::I64(2432902008176640000)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "sum", &[Value::I32(100)]),
+            [Value::I32(5050)]
+        );
+        for (arg, expected) in [(0, 10), (1, 20), (2, 30), (9, 30)] {
+            assert_eq!(
+                results(&mut interpreter, "switch", &[Value::I32(arg)]),
+                [Value::I32(expected)]
+            );
+        }
+        assert_eq!(
+            results(&mut interpreter, "swap", &[Value::I32(1), Value::I32(2)]),
+            [Value::I32(2), Value::I32(1)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "pick", &[Value::I32(0)]),
+            [Value::I32(2)]
+        );
+    }
+
+    #[test]
+    fn numeric_semantics_test() {
+        let mut interpreter = instantiate(
+            r#"
+            (module
+                (func (export "min") (param f32 f32) (result f32) (f32.min (local.get 0) (local.get 1)))
+                (func (export "max") (param f64 f64) (result f64) (f64.max (local.get 0) (local.get 1)))
+                (func (export "nearest") (param f64) (result f64) (f64.nearest (local.get 0)))
+                (func (export "sat") (param f32) (result i32) (i32.trunc_sat_f32_s (local.get 0)))
+                (func (export "to_u64") (param i64) (result f32) (f32.convert_i64_u (local.get 0)))
+                (func (export "rotl") (param i32 i32) (result i32) (i32.rotl (local.get 0) (local.get 1)))
+                (func (export "shr_u") (param i64 i64) (result i64) (i64.shr_u (local.get 0) (local.get 1)))
+                (func (export "rem_s") (param i32 i32) (result i32) (i32.rem_s (local.get 0) (local.get 1)))
+                (func (export "extend8") (param i32) (result i32) (i32.extend8_s (local.get 0))))
+            "#,
+        );
+        let f32v = |v: f32| Value::F32(v.to_bits());
+        let f64v = |v: f64| Value::F64(v.to_bits());
+        // -0 小于 +0 ，NaN 传播
+        assert_eq!(
+            results(&mut interpreter, "min", &[f32v(0.0), f32v(-0.0)]),
+            [f32v(-0.0)]
+        );
+        let [Value::F64(bits)] = results(&mut interpreter, "max", &[f64v(f64::NAN), f64v(1.0)])[..]
+        else {
+            panic!("expected one f64");
+        };
+        assert!(f64::from_bits(bits).is_nan());
+        assert_eq!(
+            results(&mut interpreter, "nearest", &[f64v(2.5)]),
+            [f64v(2.0)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "nearest", &[f64v(-3.5)]),
+            [f64v(-4.0)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "sat", &[f32v(f32::NAN)]),
+            [Value::I32(0)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "sat", &[f32v(3e9)]),
+            [Value::I32(i32::MAX)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "to_u64", &[Value::I64(-1)]),
+            [f32v(18446744073709551615u64 as f32)]
+        );
+        assert_eq!(
+            results(
+                &mut interpreter,
+                "rotl",
+                &[Value::I32(0x8000_0001u32 as i32), Value::I32(33)]
+            ),
+            [Value::I32(3)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "shr_u", &[Value::I64(-1), Value::I64(68)]),
+            [Value::I64(0x0fff_ffff_ffff_ffff)]
+        );
+        assert_eq!(
+            results(
+                &mut interpreter,
+                "rem_s",
+                &[Value::I32(i32::MIN), Value::I32(-1)]
+            ),
+            [Value::I32(0)]
+        );
+        assert_eq!(
+            results(&mut interpreter, "extend8", &[Value::I32(0x80)]),
+            [Value::I32(-128)]
+        );
+    }
+
+    #[test]
+    fn traps_test() {
+        let mut interpreter = instantiate(
+            r#"
+            (module
+                (type $unary (func (param i32) (result i32)))
+                (memory 1)
+                (table 3 funcref)
+                (elem (i32.const 0) $id $nullary)
+                (func $id (param i32) (result i32) (local.get 0))
+                (func $nullary (result i32) (i32.const 0))
+                (func (export "div") (param i32 i32) (result i32) (i32.div_s (local.get 0) (local.get 1)))
+                (func (export "trunc") (param f32) (result i32) (i32.trunc_f32_u (local.get 0)))
+                (func (export "load") (param i32) (result i32) (i32.load offset=4 (local.get 0)))
+                (func (export "unreachable") (unreachable))
+                (func $recurse (export "recurse") (call $recurse))
+                (func (export "spin") (loop $l (br $l)))
+                (func (export "indirect") (param i32) (result i32)
+                    (call_indirect (type $unary) (i32.const 5) (local.get 0))))
+            "#,
+        );
+        let i32s = |a: i32, b: i32| [Value::I32(a), Value::I32(b)];
+        assert_eq!(
+            trap(&mut interpreter, "div", &i32s(1, 0)),
+            Some(Trap::DivideByZero)
+        );
+        assert_eq!(
+            trap(&mut interpreter, "div", &i32s(i32::MIN, -1)),
+            Some(Trap::IntegerOverflow)
+        );
+        let nan = [Value::F32(f32::NAN.to_bits())];
+        assert_eq!(
+            trap(&mut interpreter, "trunc", &nan),
+            Some(Trap::InvalidConversion)
+        );
+        let big = [Value::F32(5e9f32.to_bits())];
+        assert_eq!(
+            trap(&mut interpreter, "trunc", &big),
+            Some(Trap::IntegerOverflow)
+        );
+        let minus = [Value::F32((-0.9f32).to_bits())];
+        assert_eq!(results(&mut interpreter, "trunc", &minus), [Value::I32(0)]);
+        // offset 也计入越界检查
+        assert_eq!(
+            results(&mut interpreter, "load", &[Value::I32(65528)]),
+            [Value::I32(0)]
+        );
+        assert_eq!(
+            trap(&mut interpreter, "load", &[Value::I32(65529)]),
+            Some(Trap::MemoryOutOfBounds)
+        );
+        assert_eq!(
+            trap(&mut interpreter, "unreachable", &[]),
+            Some(Trap::Unreachable)
+        );
+        assert_eq!(
+            trap(&mut interpreter, "recurse", &[]),
+            Some(Trap::CallStackExhausted)
+        );
+        interpreter.options_mut().max_steps = 1000;
+        assert_eq!(
+            trap(&mut interpreter, "spin", &[]),
+            Some(Trap::StepLimit(1000))
+        );
+        assert_eq!(
+            results(&mut interpreter, "indirect", &[Value::I32(0)]),
+            [Value::I32(5)]
+        );
+        assert_eq!(
+            trap(&mut interpreter, "indirect", &[Value::I32(1)]),
+            Some(Trap::IndirectCallTypeMismatch)
+        );
+        assert_eq!(
+            trap(&mut interpreter, "indirect", &[Value::I32(2)]),
+            Some(Trap::UninitializedElement)
+        );
+        assert_eq!(
+            trap(&mut interpreter, "indirect", &[Value::I32(3)]),
+            Some(Trap::UndefinedElement)
+        );
+    }
+
+    #[test]
+    fn bulk_memory_test() {
+        let mut interpreter = instantiate(
+            r#"
+            (module
+                (memory 1)
+                (data $passive "secret")
+                (table 2 funcref)
+                (elem $funcs func $seven)
+                (func $seven (result i32) (i32.const 7))
+                (func (export "init") (memory.init $passive (i32.const 16) (i32.const 0) (i32.const 6)))
+                (func (export "drop") (data.drop $passive))
+                (func (export "copy") (memory.copy (i32.const 18) (i32.const 16) (i32.const 4)))
+                (func (export "fill") (memory.fill (i32.const 32) (i32.const 0x41) (i32.const 3)))
+                (func (export "call_seven") (result i32)
+                    (table.init $funcs (i32.const 1) (i32.const 0) (i32.const 1))
+                    (elem.drop $funcs)
+                    (call_indirect (result i32) (i32.const 1)))
+                (func (export "table_size") (result i32)
+                    (drop (table.grow (ref.null func) (i32.const 3)))
+                    (table.size)))
+            "#,
+        );
+        results(&mut interpreter, "init", &[]);
+        assert_eq!(&interpreter.memory(0).unwrap()[16..22], b"secret");
+        // 重叠的 memory.copy 按 memmove 语义
+        results(&mut interpreter, "copy", &[]);
+        assert_eq!(&interpreter.memory(0).unwrap()[16..22], b"sesecr");
+        results(&mut interpreter, "fill", &[]);
+        assert_eq!(&interpreter.memory(0).unwrap()[32..36], b"AAA\0");
+        results(&mut interpreter, "drop", &[]);
+        assert_eq!(
+            trap(&mut interpreter, "init", &[]),
+            Some(Trap::MemoryOutOfBounds)
+        );
+
+        assert_eq!(
+            results(&mut interpreter, "call_seven", &[]),
+            [Value::I32(7)]
+        );
+        // 元素段已丢弃
+        assert_eq!(
+            trap(&mut interpreter, "call_seven", &[]),
+            Some(Trap::TableOutOfBounds)
+        );
+        assert_eq!(
+            results(&mut interpreter, "table_size", &[]),
+            [Value::I32(5)]
+        );
+    }
+
+    // 与 xor_demo 相同的算法：密钥 "hctf" 放在数据段，按下标循环读取
+    const XOR_WAT: &str = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (import "env" "now" (func $now (result i32)))
+            (memory (export "memory") 17)
+            (data (i32.const 1048576) "hctf")
+            (func (export "xor") (param $ptr i32) (param $len i32) (local $i i32)
+                (call $log (local.get $len))
+                (block $done
+                    (loop $next
+                        (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                        (i32.store8 (i32.add (local.get $ptr) (local.get $i))
+                            (i32.xor
+                                (i32.load8_u (i32.add (local.get $ptr) (local.get $i)))
+                                (i32.load8_u offset=1048576
+                                    (i32.and (local.get $i) (i32.const 3)))))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br $next))))
+            (func (export "later") (result i32) (i32.add (call $now) (i32.const 1))))
+    "#;
+
+    #[test]
+    fn trace_xor_key_loads_test() {
+        let mut interpreter = instantiate(XOR_WAT);
+        interpreter.write_memory(256, b"hans7").unwrap();
+        let execution = interpreter
+            .invoke("xor", &[Value::I32(256), Value::I32(5)])
+            .unwrap();
+        assert_eq!(execution.trap, None);
+        let expected: Vec<u8> = b"hans7"
+            .iter()
+            .zip(b"hctf".iter().cycle())
+            .map(|(a, b)| a ^ b)
+            .collect();
+        assert_eq!(interpreter.read_memory(256, 5).unwrap(), expected);
+
+        // 从 trace 中找出读取密钥的指令
+        let key_reads: Vec<_> = execution
+            .trace
+            .iter()
+            .filter_map(|event| match event {
+                TraceEvent::Read {
+                    step,
+                    address: address @ 1048576..1048580,
+                    value,
+                    ..
+                } => Some((*step, *address, value.unwrap() as u8)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            key_reads.iter().map(|&(_, _, b)| b).collect::<Vec<_>>(),
+            b"hctfh"
+        );
+        for (step, address, _) in key_reads {
+            let text = execution.trace.iter().find_map(|event| match event {
+                TraceEvent::Step { step: s, text, .. } if *s == step => Some(text.as_str()),
+                _ => None,
+            });
+            assert_eq!(
+                text,
+                Some("i32.load8_u offset=1048576"),
+                "read at {address:#x}"
+            );
+        }
+        let writes = execution
+            .trace
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    TraceEvent::Write {
+                        address: 256..261,
+                        size: 1,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(writes, 5);
+
+        assert!(matches!(
+            execution.trace.first(),
+            Some(TraceEvent::Call { depth: 1, name: Some(name), .. }) if name == "xor"
+        ));
+        assert!(matches!(
+            execution.trace[1],
+            TraceEvent::Step { step: 1, depth: 1, ref stack, .. } if stack.is_empty()
+        ));
+        assert!(matches!(
+            execution.trace.last(),
+            Some(TraceEvent::Return { depth: 1, .. })
+        ));
+        assert_eq!(execution.function_name.as_deref(), Some("xor"));
+        assert!(!execution.truncated);
+    }
+
+    #[test]
+    fn host_stub_test() {
+        let mut interpreter = instantiate(XOR_WAT);
+        // 未设置桩值时导入函数返回 0
+        assert_eq!(results(&mut interpreter, "later", &[]), [Value::I32(1)]);
+        interpreter.set_stub("env.now", &[Value::I32(41)]).unwrap();
+        let execution = interpreter.invoke("later", &[]).unwrap();
+        assert_eq!(execution.results, [Value::I32(42)]);
+        assert!(execution.trace.iter().any(|event| matches!(
+            event,
+            TraceEvent::HostCall { module, name, results, .. }
+                if module == "env" && name == "now" && results == &[Value::I32(41)]
+        )));
+
+        let execution = interpreter
+            .invoke("xor", &[Value::I32(0), Value::I32(7)])
+            .unwrap();
+        assert!(matches!(
+            &execution.trace[3],
+            TraceEvent::HostCall { args, .. } if args == &[Value::I32(7)]
+        ));
+        assert!(matches!(
+            interpreter.set_stub("env.now", &[Value::I64(1)]),
+            Err(InspectError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            interpreter.set_stub("xor", &[]),
+            Err(InspectError::InvalidArguments(_))
+        ));
+    }
+
+    #[test]
+    fn trace_options_test() {
+        let mut interpreter = instantiate(XOR_WAT);
+        interpreter.options_mut().trace_limit = 10;
+        let execution = interpreter
+            .invoke("xor", &[Value::I32(0), Value::I32(100)])
+            .unwrap();
+        assert_eq!(execution.trap, None);
+        assert_eq!(execution.trace.len(), 10);
+        assert!(execution.truncated);
+
+        let options = interpreter.options_mut();
+        options.trace_limit = usize::MAX;
+        options.record_steps = false;
+        options.record_memory = false;
+        let execution = interpreter
+            .invoke("xor", &[Value::I32(0), Value::I32(100)])
+            .unwrap();
+        // 只剩调用、导入调用和返回
+        assert_eq!(execution.trace.len(), 3);
+        assert!(execution.steps > 1000);
+    }
+
+    #[test]
+    fn instantiation_test() {
+        let mut interpreter = instantiate(
+            r#"
+            (module
+                (global $g (mut i64) (i64.const 0))
+                (func $init (global.set $g (i64.const -9007199254740993)))
+                (start $init)
+                (func (export "get") (result i64) (global.get $g)))
+            "#,
+        );
+        assert_eq!(
+            results(&mut interpreter, "get", &[]),
+            [Value::I64(-9007199254740993)]
+        );
+
+        let bytes = wat::parse_str(r#"(module (memory 1) (data (i32.const 65535) "ab"))"#).unwrap();
+        let err = WasmInterpreter::new(&bytes).err().unwrap();
+        assert!(matches!(
+            err,
+            InspectError::Instantiation(Trap::MemoryOutOfBounds)
+        ));
+        let err = RustWasmError::from(err);
+        assert_eq!(err.code, ErrorCode::InvalidWasm);
+        assert_eq!(err.details["trap"], "out of bounds memory access");
+
+        let bytes = wat::parse_str(r#"(module (func $f unreachable) (start $f))"#).unwrap();
+        assert!(matches!(
+            WasmInterpreter::new(&bytes),
+            Err(InspectError::Instantiation(Trap::Unreachable))
+        ));
+
+        // 校验失败的模块在实例化前被拒绝
+        let bytes = wat::parse_str("(module (func (result i32) (i64.const 0)))").unwrap();
+        assert!(matches!(
+            WasmInterpreter::new(&bytes),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+    }
+
+    #[test]
+    fn json_api_test() {
+        let mut interpreter = instantiate(XOR_WAT);
+        interpreter.write_memory(16, b"hans7").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&interpreter.call("xor", "[16, \"5\"]").unwrap()).unwrap();
+        assert_eq!(json["function_name"], "xor");
+        assert_eq!(json["trace"][0]["kind"], "call");
+        assert_eq!(
+            json["trace"][0]["args"][0],
+            serde_json::json!({"type": "i32", "value": 16})
+        );
+        let key_read = json["trace"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|event| event["kind"] == "read" && event["address"] == 1048576)
+            .unwrap();
+        assert_eq!(key_read["value"], "0x68");
+        assert!(json.get("trap").is_none());
+
+        interpreter.stub_import("env.now", "[\"0x10\"]").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&interpreter.call("later", "[]").unwrap()).unwrap();
+        assert_eq!(json["results"][0]["value"], 17);
+
+        assert!(matches!(
+            interpreter.call("xor", "[1]"),
+            Err(InspectError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            interpreter.call("xor", "[1, 1.5]"),
+            Err(InspectError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            interpreter.call("missing", "[]"),
+            Err(InspectError::FunctionNotFound(_))
+        ));
+        let err = RustWasmError::from(interpreter.read_memory(17 * 65536 - 2, 4).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert_eq!(err.details["size"], 4);
+    }
+}
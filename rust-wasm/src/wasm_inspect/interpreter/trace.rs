@@ -0,0 +1,79 @@
+use serde::{Serialize, Serializer};
+
+use super::value::Value;
+
+/// 执行过程中的一个事件。step 是事件发生时已执行的指令条数，读写事件与所在指令的 step 相同
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceEvent {
+    // 即将执行的指令，stack 是当前函数操作数栈顶部的若干个值（栈顶在最后）
+    Step {
+        step: u64,
+        depth: usize,
+        function: u32,
+        offset: usize,
+        text: String,
+        stack: Vec<Value>,
+    },
+    // 内存读写。value 是按小端读出 / 写入的整数，memory.copy 等批量操作没有 value
+    Read {
+        step: u64,
+        memory: u32,
+        address: u64,
+        size: u64,
+        #[serde(serialize_with = "hex", skip_serializing_if = "Option::is_none")]
+        value: Option<u64>,
+    },
+    Write {
+        step: u64,
+        memory: u32,
+        address: u64,
+        size: u64,
+        #[serde(serialize_with = "hex", skip_serializing_if = "Option::is_none")]
+        value: Option<u64>,
+    },
+    Call {
+        step: u64,
+        depth: usize,
+        function: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        args: Vec<Value>,
+    },
+    Return {
+        step: u64,
+        depth: usize,
+        function: u32,
+        results: Vec<Value>,
+    },
+    // 调用导入函数：解释器不执行宿主代码，返回桩值
+    HostCall {
+        step: u64,
+        function: u32,
+        module: String,
+        name: String,
+        args: Vec<Value>,
+        results: Vec<Value>,
+    },
+}
+
+impl TraceEvent {
+    pub fn step(&self) -> u64 {
+        match self {
+            TraceEvent::Step { step, .. }
+            | TraceEvent::Read { step, .. }
+            | TraceEvent::Write { step, .. }
+            | TraceEvent::Call { step, .. }
+            | TraceEvent::Return { step, .. }
+            | TraceEvent::HostCall { step, .. } => *step,
+        }
+    }
+}
+
+// 64 位的值超出 JS 数字的精度，以十六进制字符串输出
+fn hex<S: Serializer>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_str(&format!("{value:#x}")),
+        None => serializer.serialize_none(),
+    }
+}
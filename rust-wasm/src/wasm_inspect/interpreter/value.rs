@@ -0,0 +1,185 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use thiserror::Error;
+use wasmparser::{AbstractHeapType, HeapType, RefType, ValType};
+
+/// 解释器中的值。浮点数保存位模式，NaN 的负载原样保留
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    I32(i32),
+    I64(i64),
+    F32(u32),
+    F64(u64),
+    // 引用类型：函数索引；externref 是宿主给出的不透明编号。None 为 null
+    FuncRef(Option<u32>),
+    ExternRef(Option<u32>),
+}
+
+impl Value {
+    /// 类型的默认值（局部变量、导入的全局变量的初值）
+    pub fn zero(ty: ValType) -> Result<Self, Trap> {
+        Ok(match ty {
+            ValType::I32 => Value::I32(0),
+            ValType::I64 => Value::I64(0),
+            ValType::F32 => Value::F32(0),
+            ValType::F64 => Value::F64(0),
+            ValType::Ref(ty) => null_ref(ty.heap_type()),
+            ValType::V128 => return Err(Trap::Unsupported("v128".into())),
+        })
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::I32(_) => "i32",
+            Value::I64(_) => "i64",
+            Value::F32(_) => "f32",
+            Value::F64(_) => "f64",
+            Value::FuncRef(_) => "funcref",
+            Value::ExternRef(_) => "externref",
+        }
+    }
+
+    pub fn matches(&self, ty: ValType) -> bool {
+        match (self, ty) {
+            (Value::I32(_), ValType::I32)
+            | (Value::I64(_), ValType::I64)
+            | (Value::F32(_), ValType::F32)
+            | (Value::F64(_), ValType::F64) => true,
+            (Value::FuncRef(_), ValType::Ref(ty)) => !is_extern(ty),
+            (Value::ExternRef(_), ValType::Ref(ty)) => is_extern(ty),
+            _ => false,
+        }
+    }
+
+    /// 把 JSON 参数按形参类型转换成值：整数和浮点数可以是数字或字符串（i64 超出 2^53 时只能用字符串），
+    /// 引用是 null 或编号
+    pub fn from_json(ty: ValType, json: &serde_json::Value) -> Option<Self> {
+        let text = match json {
+            serde_json::Value::String(s) => Some(s.trim()),
+            _ => None,
+        };
+        Some(match ty {
+            ValType::I32 => Value::I32(match text {
+                Some(s) => parse_int(s)? as i32,
+                None => json.as_i64().or_else(|| json.as_u64().map(|v| v as i64))? as i32,
+            }),
+            ValType::I64 => Value::I64(match text {
+                Some(s) => parse_int(s)? as i64,
+                None => json.as_i64().or_else(|| json.as_u64().map(|v| v as i64))?,
+            }),
+            ValType::F32 => {
+                Value::F32((text.map_or(json.as_f64(), |s| s.parse().ok())? as f32).to_bits())
+            }
+            ValType::F64 => Value::F64(text.map_or(json.as_f64(), |s| s.parse().ok())?.to_bits()),
+            ValType::Ref(ty) => {
+                let index = match json {
+                    serde_json::Value::Null => None,
+                    _ => Some(u32::try_from(json.as_u64()?).ok()?),
+                };
+                if is_extern(ty) {
+                    Value::ExternRef(index)
+                } else {
+                    Value::FuncRef(index)
+                }
+            }
+            ValType::V128 => return None,
+        })
+    }
+}
+
+// 十进制（可带负号）或 0x 开头的十六进制，按 i128 解析后由调用方截断
+fn parse_int(s: &str) -> Option<i128> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => i128::from_str_radix(hex, 16).ok()?,
+        None => digits.parse::<i128>().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
+pub(super) fn null_ref(heap_type: HeapType) -> Value {
+    match heap_type {
+        HeapType::Abstract {
+            ty: AbstractHeapType::Extern | AbstractHeapType::NoExtern,
+            ..
+        } => Value::ExternRef(None),
+        _ => Value::FuncRef(None),
+    }
+}
+
+fn is_extern(ty: RefType) -> bool {
+    matches!(null_ref(ty.heap_type()), Value::ExternRef(_))
+}
+
+// JSON 中为 {"type": "i32", "value": 104}。i64 用字符串避免 JS 丢失精度，
+// 非有限的浮点数用 "NaN" / "inf" / "-inf"
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Value", 2)?;
+        state.serialize_field("type", self.type_name())?;
+        match *self {
+            Value::I32(v) => state.serialize_field("value", &v)?,
+            Value::I64(v) => state.serialize_field("value", &v.to_string())?,
+            Value::F32(bits) => serialize_float(&mut state, f32::from_bits(bits) as f64)?,
+            Value::F64(bits) => serialize_float(&mut state, f64::from_bits(bits))?,
+            Value::FuncRef(index) | Value::ExternRef(index) => {
+                state.serialize_field("value", &index)?
+            }
+        }
+        state.end()
+    }
+}
+
+fn serialize_float<S: SerializeStruct>(state: &mut S, value: f64) -> Result<(), S::Error> {
+    if value.is_finite() {
+        state.serialize_field("value", &value)
+    } else {
+        state.serialize_field("value", &value.to_string())
+    }
+}
+
+/// 执行中止的原因，消息与规范测试（spec test）中的 assert_trap 一致
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Trap {
+    #[error("unreachable")]
+    Unreachable,
+    #[error("integer divide by zero")]
+    DivideByZero,
+    #[error("integer overflow")]
+    IntegerOverflow,
+    #[error("invalid conversion to integer")]
+    InvalidConversion,
+    #[error("out of bounds memory access")]
+    MemoryOutOfBounds,
+    #[error("out of bounds table access")]
+    TableOutOfBounds,
+    #[error("undefined element")]
+    UndefinedElement,
+    #[error("uninitialized element")]
+    UninitializedElement,
+    #[error("indirect call type mismatch")]
+    IndirectCallTypeMismatch,
+    #[error("call stack exhausted")]
+    CallStackExhausted,
+    #[error("step limit of {0} exceeded")]
+    StepLimit(u64),
+    #[error("unsupported instruction: {0}")]
+    Unsupported(String),
+    // 模块已通过校验，正常情况下不会出现；保留给加载器中防御性的检查
+    #[error("invalid module: {0}")]
+    Invalid(String),
+}
+
+impl From<wasmparser::BinaryReaderError> for Trap {
+    fn from(e: wasmparser::BinaryReaderError) -> Self {
+        Trap::Invalid(format!("{} (at offset {})", e.message(), e.offset()))
+    }
+}
+
+impl Serialize for Trap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
@@ -0,0 +1,10 @@
+pub mod crypto_scan;
+pub mod decompiler;
+pub mod disassembler;
+pub mod inspect_error;
+pub mod interpreter;
+pub mod module_info;
+pub mod obfuscator;
+pub mod patcher;
+pub mod signatures;
+pub mod strings;
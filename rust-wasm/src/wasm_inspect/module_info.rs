@@ -0,0 +1,774 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasmparser::{
+    BinaryReaderError, CompositeInnerType, ConstExpr, DataKind, ElementItems, ElementKind,
+    Encoding, ExternalKind, KnownCustom, Name, NameMap, Operator, Parser, Payload, TypeRef,
+};
+
+use super::inspect_error::InspectError;
+
+// 一个 .wasm 模块的结构概览：每个 section 的位置，以及类型、导入导出、函数、表、内存、全局变量、
+// 数据段和 name / producers 等自定义 section 的内容。只做解析不做校验，指令内容不展开
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ModuleInfo {
+    pub size: usize,
+    pub version: u16,
+    pub sections: Vec<SectionInfo>,
+    pub types: Vec<TypeInfo>,
+    pub imports: Vec<ImportInfo>,
+    pub exports: Vec<ExportInfo>,
+    pub functions: Vec<FunctionInfo>,
+    pub tables: Vec<TableInfo>,
+    pub memories: Vec<MemoryInfo>,
+    pub globals: Vec<GlobalInfo>,
+    pub elements: Vec<ElementInfo>,
+    pub data: Vec<DataInfo>,
+    pub start: Option<u32>,
+    pub custom_sections: Vec<CustomSectionInfo>,
+    pub names: NameInfo,
+    pub producers: Vec<ProducerField>,
+    // name / producers section 损坏时不影响其余部分，错误记录在这里
+    pub warnings: Vec<String>,
+}
+
+/// section 内容（不含 id 和长度）在文件中的位置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionInfo {
+    pub id: u8,
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub offset: usize,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeInfo {
+    pub index: u32,
+    pub kind: &'static str,
+    pub params: Vec<String>,
+    pub results: Vec<String>,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportInfo {
+    pub module: String,
+    pub name: String,
+    pub kind: &'static str,
+    // 在对应种类（函数 / 表 / 内存 / 全局变量）的索引空间中的索引
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_index: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportInfo {
+    pub name: String,
+    pub kind: &'static str,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FunctionInfo {
+    pub index: u32,
+    pub type_index: u32,
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    // 导入函数为 "module.name"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<String>,
+    pub exports: Vec<String>,
+    // 函数体（含局部变量声明）在文件中的位置，导入函数没有函数体
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_size: Option<usize>,
+    pub locals: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableInfo {
+    pub index: u32,
+    pub element_type: String,
+    pub initial: u64,
+    pub maximum: Option<u64>,
+    pub imported: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemoryInfo {
+    pub index: u32,
+    // 单位是页（64 KiB）
+    pub initial: u64,
+    pub maximum: Option<u64>,
+    pub shared: bool,
+    pub memory64: bool,
+    pub imported: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GlobalInfo {
+    pub index: u32,
+    pub value_type: String,
+    pub mutable: bool,
+    // 导入的全局变量没有初始化表达式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init: Option<String>,
+    pub imported: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElementInfo {
+    pub index: u32,
+    pub mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_expr: Option<String>,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataInfo {
+    pub index: u32,
+    pub mode: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_index: Option<u32>,
+    // 偏移是常量时给出线性内存中的起始地址，其余情况只有表达式文本
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_expr: Option<String>,
+    pub size: usize,
+    // 数据内容在文件中的位置
+    pub file_offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CustomSectionInfo {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+}
+
+// name section 中的名字。索引作为 key ，序列化成 JSON 对象时是字符串
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NameInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    pub functions: BTreeMap<u32, String>,
+    pub locals: BTreeMap<u32, BTreeMap<u32, String>>,
+    pub globals: BTreeMap<u32, String>,
+    pub data: BTreeMap<u32, String>,
+}
+
+/// producers section 的一个字段，例如 `language: Rust` 、`processed-by: rustc 1.89.0`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProducerField {
+    pub name: String,
+    pub values: Vec<ProducerValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProducerValue {
+    pub name: String,
+    pub version: String,
+}
+
+impl ModuleInfo {
+    /// 解析模块的全部 section 。不支持 component model 的组件
+    pub fn parse(bytes: &[u8]) -> Result<Self, InspectError> {
+        let mut info = ModuleInfo {
+            size: bytes.len(),
+            ..Default::default()
+        };
+        let mut defined_functions = 0;
+        for payload in Parser::new(0).parse_all(bytes) {
+            let payload = payload?;
+            if let Some((id, range)) = payload.as_section() {
+                let name = match &payload {
+                    Payload::CustomSection(reader) => Some(reader.name().to_string()),
+                    _ => None,
+                };
+                info.sections.push(SectionInfo {
+                    id,
+                    kind: section_kind(id),
+                    name,
+                    offset: range.start,
+                    size: range.len(),
+                });
+            }
+            match payload {
+                Payload::Version { num, encoding, .. } => {
+                    if encoding == Encoding::Component {
+                        return Err(InspectError::Component);
+                    }
+                    info.version = num;
+                }
+                Payload::TypeSection(reader) => {
+                    for rec_group in reader {
+                        for sub_type in rec_group?.into_types() {
+                            info.push_type(&sub_type.composite_type.inner);
+                        }
+                    }
+                }
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        info.push_import(import?);
+                    }
+                }
+                Payload::FunctionSection(reader) => {
+                    for type_index in reader {
+                        info.push_function(type_index?, None);
+                    }
+                }
+                Payload::TableSection(reader) => {
+                    for table in reader {
+                        let table = table?;
+                        info.tables.push(TableInfo {
+                            index: info.tables.len() as u32,
+                            element_type: table.ty.element_type.to_string(),
+                            initial: table.ty.initial,
+                            maximum: table.ty.maximum,
+                            imported: false,
+                        });
+                    }
+                }
+                Payload::MemorySection(reader) => {
+                    for memory in reader {
+                        info.push_memory(memory?, false);
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        let global = global?;
+                        let init = const_expr_text(&global.init_expr)?;
+                        info.push_global(global.ty, Some(init));
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        info.exports.push(ExportInfo {
+                            name: export.name.to_string(),
+                            kind: external_kind_name(export.kind),
+                            index: export.index,
+                        });
+                    }
+                }
+                Payload::StartSection { func, .. } => info.start = Some(func),
+                Payload::ElementSection(reader) => {
+                    for element in reader {
+                        let element = element?;
+                        let (mode, table_index, offset_expr) = match element.kind {
+                            ElementKind::Passive => ("passive", None, None),
+                            ElementKind::Declared => ("declared", None, None),
+                            ElementKind::Active {
+                                table_index,
+                                offset_expr,
+                            } => (
+                                "active",
+                                Some(table_index.unwrap_or(0)),
+                                Some(const_expr_text(&offset_expr)?),
+                            ),
+                        };
+                        let count = match element.items {
+                            ElementItems::Functions(items) => items.count(),
+                            ElementItems::Expressions(_, items) => items.count(),
+                        };
+                        info.elements.push(ElementInfo {
+                            index: info.elements.len() as u32,
+                            mode,
+                            table_index,
+                            offset_expr,
+                            count,
+                        });
+                    }
+                }
+                Payload::DataSection(reader) => {
+                    for data in reader {
+                        let data = data?;
+                        let (mode, memory_index, offset, offset_expr) = match data.kind {
+                            DataKind::Passive => ("passive", None, None, None),
+                            DataKind::Active {
+                                memory_index,
+                                offset_expr,
+                            } => (
+                                "active",
+                                Some(memory_index),
+                                const_expr_value(&offset_expr),
+                                Some(const_expr_text(&offset_expr)?),
+                            ),
+                        };
+                        info.data.push(DataInfo {
+                            index: info.data.len() as u32,
+                            mode,
+                            memory_index,
+                            offset,
+                            offset_expr,
+                            size: data.data.len(),
+                            // 数据内容位于数据段的末尾
+                            file_offset: data.range.end - data.data.len(),
+                        });
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let locals = body
+                        .get_locals_reader()?
+                        .into_iter()
+                        .try_fold(0u32, |total, local| {
+                            local.map(|(count, _)| total.saturating_add(count))
+                        })?;
+                    let index = info.imported_function_count() + defined_functions;
+                    defined_functions += 1;
+                    let range = body.range();
+                    if let Some(function) = info.functions.get_mut(index) {
+                        function.body_offset = Some(range.start);
+                        function.body_size = Some(range.len());
+                        function.locals = locals;
+                    }
+                }
+                Payload::CustomSection(reader) => {
+                    info.custom_sections.push(CustomSectionInfo {
+                        name: reader.name().to_string(),
+                        offset: reader.data_offset(),
+                        size: reader.data().len(),
+                    });
+                    let result = match reader.as_known() {
+                        KnownCustom::Name(names) => info.read_names(names),
+                        KnownCustom::Producers(producers) => info.read_producers(producers),
+                        _ => Ok(()),
+                    };
+                    if let Err(e) = result {
+                        info.warnings
+                            .push(format!("custom section {:?}: {e}", reader.name()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        info.resolve_function_names();
+        Ok(info)
+    }
+
+    /// 函数的可读名字：优先 name section ，其次导出名（wasm-bindgen 导出的函数都有），
+    /// 再次导入的 "module.name"
+    pub fn function_name(&self, index: u32) -> Option<&str> {
+        let function = self.functions.get(index as usize)?;
+        function
+            .name
+            .as_deref()
+            .or_else(|| function.exports.first().map(String::as_str))
+            .or(function.import.as_deref())
+    }
+
+    /// 按索引（十进制数字）、name section 中的名字、导出名或导入的 "module.name" 查找函数
+    pub fn find_function(&self, query: &str) -> Option<&FunctionInfo> {
+        if let Ok(index) = query.parse::<usize>() {
+            return self.functions.get(index);
+        }
+        self.functions.iter().find(|f| {
+            f.name.as_deref() == Some(query)
+                || f.import.as_deref() == Some(query)
+                || f.exports.iter().any(|e| e == query)
+        })
+    }
+
+    pub fn imported_function_count(&self) -> usize {
+        self.functions
+            .iter()
+            .take_while(|f| f.import.is_some())
+            .count()
+    }
+
+    fn push_type(&mut self, inner: &CompositeInnerType) {
+        let index = self.types.len() as u32;
+        let (kind, params, results) = match inner {
+            CompositeInnerType::Func(func) => (
+                "func",
+                func.params().iter().map(ToString::to_string).collect(),
+                func.results().iter().map(ToString::to_string).collect(),
+            ),
+            CompositeInnerType::Array(_) => ("array", Vec::new(), Vec::new()),
+            CompositeInnerType::Struct(_) => ("struct", Vec::new(), Vec::new()),
+            CompositeInnerType::Cont(_) => ("cont", Vec::new(), Vec::new()),
+        };
+        self.types.push(TypeInfo {
+            index,
+            kind,
+            params,
+            results,
+            signature: inner.to_string(),
+        });
+    }
+
+    fn push_import(&mut self, import: wasmparser::Import) {
+        let (kind, index, type_index) = match import.ty {
+            TypeRef::Func(type_index) | TypeRef::FuncExact(type_index) => {
+                let qualified = format!("{}.{}", import.module, import.name);
+                let index = self.push_function(type_index, Some(qualified));
+                ("func", index, Some(type_index))
+            }
+            TypeRef::Table(ty) => {
+                let index = self.tables.len() as u32;
+                self.tables.push(TableInfo {
+                    index,
+                    element_type: ty.element_type.to_string(),
+                    initial: ty.initial,
+                    maximum: ty.maximum,
+                    imported: true,
+                });
+                ("table", index, None)
+            }
+            TypeRef::Memory(ty) => ("memory", self.push_memory(ty, true), None),
+            TypeRef::Global(ty) => ("global", self.push_global(ty, None), None),
+            TypeRef::Tag(ty) => ("tag", 0, Some(ty.func_type_idx)),
+        };
+        self.imports.push(ImportInfo {
+            module: import.module.to_string(),
+            name: import.name.to_string(),
+            kind,
+            index,
+            type_index,
+        });
+    }
+
+    fn push_function(&mut self, type_index: u32, import: Option<String>) -> u32 {
+        let index = self.functions.len() as u32;
+        let signature = self
+            .types
+            .get(type_index as usize)
+            .map(|ty| ty.signature.clone())
+            .unwrap_or_else(|| format!("(type {type_index})"));
+        self.functions.push(FunctionInfo {
+            index,
+            type_index,
+            signature,
+            name: None,
+            import,
+            exports: Vec::new(),
+            body_offset: None,
+            body_size: None,
+            locals: 0,
+        });
+        index
+    }
+
+    fn push_memory(&mut self, ty: wasmparser::MemoryType, imported: bool) -> u32 {
+        let index = self.memories.len() as u32;
+        self.memories.push(MemoryInfo {
+            index,
+            initial: ty.initial,
+            maximum: ty.maximum,
+            shared: ty.shared,
+            memory64: ty.memory64,
+            imported,
+        });
+        index
+    }
+
+    fn push_global(&mut self, ty: wasmparser::GlobalType, init: Option<String>) -> u32 {
+        let index = self.globals.len() as u32;
+        self.globals.push(GlobalInfo {
+            index,
+            value_type: ty.content_type.to_string(),
+            mutable: ty.mutable,
+            imported: init.is_none(),
+            init,
+        });
+        index
+    }
+
+    fn read_names(
+        &mut self,
+        reader: wasmparser::NameSectionReader,
+    ) -> Result<(), BinaryReaderError> {
+        for name in reader {
+            match name? {
+                Name::Module { name, .. } => self.names.module = Some(name.to_string()),
+                Name::Function(map) => read_name_map(map, &mut self.names.functions)?,
+                Name::Global(map) => read_name_map(map, &mut self.names.globals)?,
+                Name::Data(map) => read_name_map(map, &mut self.names.data)?,
+                Name::Local(indirect) => {
+                    for naming in indirect {
+                        let naming = naming?;
+                        let locals = self.names.locals.entry(naming.index).or_default();
+                        read_name_map(naming.names, locals)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn read_producers(
+        &mut self,
+        reader: wasmparser::ProducersSectionReader,
+    ) -> Result<(), BinaryReaderError> {
+        for field in reader {
+            let field = field?;
+            let values = field
+                .values
+                .into_iter()
+                .map(|value| {
+                    value.map(|value| ProducerValue {
+                        name: value.name.to_string(),
+                        version: value.version.to_string(),
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            self.producers.push(ProducerField {
+                name: field.name.to_string(),
+                values,
+            });
+        }
+        Ok(())
+    }
+
+    // name section 和导出都解析完之后才能给函数填上名字
+    fn resolve_function_names(&mut self) {
+        for (index, name) in &self.names.functions {
+            if let Some(function) = self.functions.get_mut(*index as usize) {
+                function.name = Some(name.clone());
+            }
+        }
+        for export in &self.exports {
+            if export.kind == "func"
+                && let Some(function) = self.functions.get_mut(export.index as usize)
+            {
+                function.exports.push(export.name.clone());
+            }
+        }
+    }
+}
+
+fn section_kind(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "data_count",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+fn external_kind_name(kind: ExternalKind) -> &'static str {
+    match kind {
+        ExternalKind::Func | ExternalKind::FuncExact => "func",
+        ExternalKind::Table => "table",
+        ExternalKind::Memory => "memory",
+        ExternalKind::Global => "global",
+        ExternalKind::Tag => "tag",
+    }
+}
+
+fn read_name_map(map: NameMap, out: &mut BTreeMap<u32, String>) -> Result<(), BinaryReaderError> {
+    for naming in map {
+        let naming = naming?;
+        out.insert(naming.index, naming.name.to_string());
+    }
+    Ok(())
+}
+
+// 常量表达式的文本形式，例如 "i32.const 1048576" ，多条指令（extended-const）用 "; " 连接
+fn const_expr_text(expr: &ConstExpr) -> Result<String, BinaryReaderError> {
+    let mut reader = expr.get_operators_reader();
+    let mut parts = Vec::new();
+    loop {
+        let text = match reader.read()? {
+            Operator::End => break,
+            Operator::I32Const { value } => format!("i32.const {value}"),
+            Operator::I64Const { value } => format!("i64.const {value}"),
+            Operator::F32Const { value } => format!("f32.const {}", f32::from_bits(value.bits())),
+            Operator::F64Const { value } => format!("f64.const {}", f64::from_bits(value.bits())),
+            Operator::GlobalGet { global_index } => format!("global.get {global_index}"),
+            Operator::RefNull { hty } => format!("ref.null {hty:?}"),
+            Operator::RefFunc { function_index } => format!("ref.func {function_index}"),
+            Operator::I32Add => "i32.add".into(),
+            Operator::I32Sub => "i32.sub".into(),
+            Operator::I32Mul => "i32.mul".into(),
+            Operator::I64Add => "i64.add".into(),
+            Operator::I64Sub => "i64.sub".into(),
+            Operator::I64Mul => "i64.mul".into(),
+            other => format!("{other:?}"),
+        };
+        parts.push(text);
+    }
+    Ok(parts.join("; "))
+}
+
+// 只有一条 i32.const / i64.const 时能确定地址。i32 地址按无符号解释
+fn const_expr_value(expr: &ConstExpr) -> Option<u64> {
+    let mut reader = expr.get_operators_reader();
+    let value = match reader.read().ok()? {
+        Operator::I32Const { value } => value as u32 as u64,
+        Operator::I64Const { value } => value as u64,
+        _ => return None,
+    };
+    matches!(reader.read().ok()?, Operator::End).then_some(value)
+}
+
+pub fn parse_module(bytes: &[u8]) -> Result<ModuleInfo, InspectError> {
+    ModuleInfo::parse(bytes)
+}
+
+/// 解析 .wasm 文件，以 JSON 形式返回模块结构，前端据此展示正在逆向的模块
+#[wasm_bindgen]
+pub fn inspect_wasm(bytes: &[u8]) -> Result<String, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    Ok(serde_json::to_string(&info).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+    use serde_json::Value;
+
+    const SAMPLE_WAT: &str = r#"
+        (module $sample
+            (type $bin (func (param i32 i32) (result i32)))
+            (import "env" "log" (func $log (param i32)))
+            (import "env" "mem" (memory 1 16))
+            (import "env" "base" (global $base i32))
+            (table 2 funcref)
+            (global $counter (mut i64) (i64.const 7))
+            (func $add (type $bin) (local i32 i64) (i32.add (local.get 0) (local.get 1)))
+            (func $main (call $log (i32.const 42)))
+            (export "add" (func $add))
+            (export "run" (func $main))
+            (start $main)
+            (elem (i32.const 0) $add $main)
+            (data (i32.const 1048576) "hello")
+            (data (global.get $base) "world!")
+            (data "passive"))
+    "#;
+
+    fn sample() -> ModuleInfo {
+        ModuleInfo::parse(&wat::parse_str(SAMPLE_WAT).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parse_module_overview_test() {
+        let info = sample();
+        assert_eq!(info.version, 1);
+        assert_eq!(
+            info.types[0].signature,
+            "(func (param i32 i32) (result i32))"
+        );
+        assert_eq!(info.types[0].params, ["i32", "i32"]);
+        assert_eq!(info.imports.len(), 3);
+        assert_eq!(info.imports[1].kind, "memory");
+        assert_eq!(info.memories[0].maximum, Some(16));
+        assert!(info.memories[0].imported);
+        assert_eq!(info.tables[0].element_type, "funcref");
+        assert!(info.globals[0].imported);
+        assert_eq!(info.globals[1].init.as_deref(), Some("i64.const 7"));
+        assert_eq!(info.start, Some(2));
+        assert_eq!(info.elements[0].count, 2);
+        assert_eq!(info.elements[0].offset_expr.as_deref(), Some("i32.const 0"));
+    }
+
+    #[test]
+    fn parse_module_functions_test() {
+        let info = sample();
+        assert_eq!(info.imported_function_count(), 1);
+        assert_eq!(info.functions.len(), 3);
+        assert_eq!(info.functions[0].import.as_deref(), Some("env.log"));
+        assert_eq!(info.functions[0].body_offset, None);
+
+        let add = &info.functions[1];
+        assert_eq!(add.name.as_deref(), Some("add"));
+        assert_eq!(add.exports, ["add"]);
+        assert_eq!(add.locals, 2);
+        assert_eq!(add.signature, info.types[0].signature);
+        // 函数体从局部变量声明开始：1 组 i32 、1 组 i64
+        let bytes = wat::parse_str(SAMPLE_WAT).unwrap();
+        let start = add.body_offset.unwrap();
+        assert_eq!(&bytes[start..start + 5], [0x02, 0x01, 0x7f, 0x01, 0x7e]);
+
+        assert_eq!(info.function_name(2), Some("main"));
+        assert_eq!(info.function_name(3), None);
+        assert_eq!(info.names.module.as_deref(), Some("sample"));
+    }
+
+    #[test]
+    fn parse_module_data_segments_test() {
+        let info = sample();
+        let bytes = wat::parse_str(SAMPLE_WAT).unwrap();
+        assert_eq!(info.data.len(), 3);
+        assert_eq!(info.data[0].offset, Some(0x10_0000));
+        let hello = &info.data[0];
+        assert_eq!(
+            &bytes[hello.file_offset..hello.file_offset + hello.size],
+            b"hello"
+        );
+        assert_eq!(info.data[1].offset, None);
+        assert_eq!(info.data[1].offset_expr.as_deref(), Some("global.get 0"));
+        assert_eq!(info.data[2].mode, "passive");
+        assert_eq!(info.data[2].memory_index, None);
+    }
+
+    #[test]
+    fn parse_module_sections_test() {
+        let info = sample();
+        let kinds: Vec<_> = info.sections.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                "type", "import", "function", "table", "global", "export", "start", "element",
+                "code", "data", "custom"
+            ]
+        );
+        let name_section = info.sections.last().unwrap();
+        assert_eq!(name_section.name.as_deref(), Some("name"));
+        assert_eq!(info.custom_sections[0].name, "name");
+        assert!(info.warnings.is_empty());
+    }
+
+    #[test]
+    fn inspect_wasm_json_test() {
+        let bytes = wat::parse_str(r#"(module (func (export "f") (result i32) i32.const 1))"#);
+        let json: Value = serde_json::from_str(&inspect_wasm(&bytes.unwrap()).unwrap()).unwrap();
+        assert_eq!(json["exports"][0]["name"], "f");
+        assert_eq!(json["exports"][0]["kind"], "func");
+        assert_eq!(json["functions"][0]["signature"], "(func (result i32))");
+        assert_eq!(json["functions"][0]["exports"][0], "f");
+        assert!(json["functions"][0].get("import").is_none());
+    }
+
+    #[test]
+    fn inspect_wasm_invalid_input_test() {
+        let err = RustWasmError::from(ModuleInfo::parse(b"not wasm").unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidWasm);
+        assert_eq!(err.module, "wasm_inspect");
+        assert_eq!(err.details["offset"], 0);
+
+        // 合法头部后截断的 section
+        let mut bytes = wat::parse_str("(module (func))").unwrap();
+        bytes.truncate(bytes.len() - 2);
+        assert!(matches!(
+            ModuleInfo::parse(&bytes),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+
+        let component = wat::parse_str("(component)").unwrap();
+        let err = RustWasmError::from(ModuleInfo::parse(&component).unwrap_err());
+        assert_eq!(err.code, ErrorCode::UnsupportedFormat);
+    }
+}
@@ -0,0 +1,56 @@
+use wasm_encoder::Instruction;
+
+use super::Rng;
+
+// 常量拆分：每个 i32.const / i64.const 换成两个随机常量的异或、和或差，
+// 例如 0x66746368 ("hctf") 不再原样出现在代码中
+
+pub(super) fn split<'a>(instructions: Vec<Instruction<'a>>, rng: &mut Rng) -> Vec<Instruction<'a>> {
+    let mut out = Vec::with_capacity(instructions.len() * 2);
+    for instruction in instructions {
+        match instruction {
+            Instruction::I32Const(value) => {
+                let mask = rng.next_u32() as i32;
+                out.extend(match rng.below(3) {
+                    0 => [
+                        Instruction::I32Const(value ^ mask),
+                        Instruction::I32Const(mask),
+                        Instruction::I32Xor,
+                    ],
+                    1 => [
+                        Instruction::I32Const(value.wrapping_sub(mask)),
+                        Instruction::I32Const(mask),
+                        Instruction::I32Add,
+                    ],
+                    _ => [
+                        Instruction::I32Const(value.wrapping_add(mask)),
+                        Instruction::I32Const(mask),
+                        Instruction::I32Sub,
+                    ],
+                });
+            }
+            Instruction::I64Const(value) => {
+                let mask = rng.next_u64() as i64;
+                out.extend(match rng.below(3) {
+                    0 => [
+                        Instruction::I64Const(value ^ mask),
+                        Instruction::I64Const(mask),
+                        Instruction::I64Xor,
+                    ],
+                    1 => [
+                        Instruction::I64Const(value.wrapping_sub(mask)),
+                        Instruction::I64Const(mask),
+                        Instruction::I64Add,
+                    ],
+                    _ => [
+                        Instruction::I64Const(value.wrapping_add(mask)),
+                        Instruction::I64Const(mask),
+                        Instruction::I64Sub,
+                    ],
+                });
+            }
+            instruction => out.push(instruction),
+        }
+    }
+    out
+}
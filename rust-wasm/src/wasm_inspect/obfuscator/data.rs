@@ -0,0 +1,83 @@
+use wasm_encoder::{BlockType, Instruction, MemArg};
+
+// 数据段加密：主动段的内容与 xorshift32 密钥流异或，
+// 由 start 函数在实例化时原地解密，之后再调用原来的 start 函数
+
+/// 一个加密的数据段
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Segment {
+    pub index: u32,
+    pub address: u32,
+    pub len: u32,
+    // 密钥流的初始状态，非零
+    pub seed: u32,
+}
+
+fn xorshift(mut s: u32) -> u32 {
+    s ^= s << 13;
+    s ^= s >> 17;
+    s ^= s << 5;
+    s
+}
+
+/// 加密和解密是同一个运算
+pub(super) fn apply_keystream(data: &mut [u8], seed: u32) {
+    let mut s = seed;
+    for byte in data {
+        s = xorshift(s);
+        *byte ^= s as u8;
+    }
+}
+
+/// 解密所有段后调用 start 的函数体，类型为 [] -> [] ，有两个 i32 局部变量
+pub(super) fn decryptor(segments: &[Segment], start: Option<u32>) -> Vec<Instruction<'static>> {
+    use Instruction::*;
+    // 局部变量 0 是当前地址，1 是密钥流状态
+    let (p, s) = (0, 1);
+    let byte = MemArg {
+        offset: 0,
+        align: 0,
+        memory_index: 0,
+    };
+    let mut out = Vec::new();
+    for segment in segments {
+        out.extend([
+            I32Const(segment.address as i32),
+            LocalSet(p),
+            I32Const(segment.seed as i32),
+            LocalSet(s),
+            Loop(BlockType::Empty),
+        ]);
+        for (bits, shift) in [(13, I32Shl), (17, I32ShrU), (5, I32Shl)] {
+            out.extend([
+                LocalGet(s),
+                LocalGet(s),
+                I32Const(bits),
+                shift,
+                I32Xor,
+                LocalSet(s),
+            ]);
+        }
+        out.extend([
+            LocalGet(p),
+            LocalGet(p),
+            I32Load8U(byte),
+            LocalGet(s),
+            I32Xor,
+            I32Store8(byte),
+            LocalGet(p),
+            I32Const(1),
+            I32Add,
+            LocalTee(p),
+            I32Const(segment.address.wrapping_add(segment.len) as i32),
+            I32Ne,
+            BrIf(0),
+            End,
+        ]);
+    }
+    if let Some(start) = start {
+        out.push(Call(start));
+    }
+    out.push(End);
+    out
+}
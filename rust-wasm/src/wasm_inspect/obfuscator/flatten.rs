@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use wasm_encoder::{BlockType, Instruction};
+use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
+
+use super::Rng;
+use crate::wasm_inspect::inspect_error::InspectError;
+
+// 控制流平坦化：把函数体顶层的语句切成若干段，打乱顺序后放进一个分发循环
+//
+//     local.set $state (初始状态)
+//     loop $dispatch
+//       block block ... block            ;; n 层
+//         (local.get $state ^ key) br_table
+//       end 第 0 个位置的段，设置下一个状态，br $dispatch
+//       end 第 1 个位置的段 ...
+//     end
+//     unreachable
+//
+// 只在顶层、操作数栈为空且可达的位置切分，各段之间不传递栈上的值；
+// 段内跳到函数标签（即返回）的分支深度加上外面多出的层数
+
+/// 每个目标函数可以切分的位置：第几条指令之后
+pub(super) fn split_points(
+    bytes: &[u8],
+    targets: &HashSet<u32>,
+) -> Result<Vec<(u32, HashSet<usize>)>, InspectError> {
+    let mut points = Vec::new();
+    let mut validator = Validator::new();
+    let mut allocations = FuncValidatorAllocations::default();
+    for payload in Parser::new(0).parse_all(bytes) {
+        let ValidPayload::Func(func, body) = validator.payload(&payload?)? else {
+            continue;
+        };
+        if !targets.contains(&func.index) {
+            continue;
+        }
+        let index = func.index;
+        let mut func = func.into_validator(std::mem::take(&mut allocations));
+        func.read_locals(&mut body.get_binary_reader())?;
+        let mut reader = body.get_operators_reader()?;
+        let mut at = HashSet::new();
+        let mut i = 0;
+        while !reader.eof() {
+            let offset = reader.original_position();
+            func.op(offset, &reader.read()?)?;
+            // br 、return 之后的死代码依赖多态的操作数栈，不能挪到新的段开头
+            if func.control_stack_height() == 1
+                && func.operand_stack_height() == 0
+                && func.get_control_frame(0).is_some_and(|f| !f.unreachable)
+            {
+                at.insert(i);
+            }
+            i += 1;
+        }
+        points.push((index, at));
+        allocations = func.into_allocations();
+    }
+    Ok(points)
+}
+
+pub(super) fn flatten<'a>(
+    instructions: Vec<Instruction<'a>>,
+    points: &HashSet<usize>,
+    state: u32,
+    rng: &mut Rng,
+) -> Result<Vec<Instruction<'a>>, InspectError> {
+    // 最后一条是函数的 end
+    let count = instructions.len().saturating_sub(1);
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    for (i, instruction) in instructions.into_iter().take(count).enumerate() {
+        chunk.push(instruction);
+        if points.contains(&i) {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    let n = chunks.len();
+    if n < 2 {
+        return Ok(chunks
+            .into_iter()
+            .flatten()
+            .chain([Instruction::End])
+            .collect());
+    }
+
+    // 第 i 段的状态值和它在分发循环中的位置
+    let states = rng.permutation(n);
+    let positions = rng.permutation(n);
+    let key = rng.next_u32() as i32;
+    let mut table = vec![0; n];
+    for i in 0..n {
+        table[states[i]] = positions[i] as u32;
+    }
+    let mut by_position: Vec<_> = chunks.into_iter().enumerate().collect();
+    by_position.sort_by_key(|(i, _)| positions[*i]);
+
+    let mut out = vec![
+        Instruction::I32Const(states[0] as i32 ^ key),
+        Instruction::LocalSet(state),
+        Instruction::Loop(BlockType::Empty),
+    ];
+    out.extend((0..n).map(|_| Instruction::Block(BlockType::Empty)));
+    out.extend([
+        Instruction::LocalGet(state),
+        Instruction::I32Const(key),
+        Instruction::I32Xor,
+        Instruction::BrTable(table.into(), 0),
+    ]);
+    for (position, (i, chunk)) in by_position.into_iter().enumerate() {
+        out.push(Instruction::End);
+        // 这一段外面还有 n - 1 - position 层 block 和分发循环
+        let extra = (n - position) as u32;
+        retarget(chunk, extra, &mut out)?;
+        if i + 1 == n {
+            out.push(Instruction::Return);
+        } else {
+            out.extend([
+                Instruction::I32Const(states[i + 1] as i32 ^ key),
+                Instruction::LocalSet(state),
+                Instruction::Br(extra - 1),
+            ]);
+        }
+    }
+    out.extend([Instruction::End, Instruction::Unreachable, Instruction::End]);
+    Ok(out)
+}
+
+// 把跳出段的分支（目标是函数标签）的深度加 extra
+fn retarget<'a>(
+    chunk: Vec<Instruction<'a>>,
+    extra: u32,
+    out: &mut Vec<Instruction<'a>>,
+) -> Result<(), InspectError> {
+    let mut depth = 0;
+    let shift = |label: u32, depth: u32| if label >= depth { label + extra } else { label };
+    for instruction in chunk {
+        let instruction = match instruction {
+            Instruction::Block(_) | Instruction::Loop(_) | Instruction::If(_) => {
+                depth += 1;
+                instruction
+            }
+            Instruction::End => {
+                depth -= 1;
+                instruction
+            }
+            Instruction::Br(l) => Instruction::Br(shift(l, depth)),
+            Instruction::BrIf(l) => Instruction::BrIf(shift(l, depth)),
+            Instruction::BrOnNull(l) => Instruction::BrOnNull(shift(l, depth)),
+            Instruction::BrOnNonNull(l) => Instruction::BrOnNonNull(shift(l, depth)),
+            Instruction::BrTable(labels, default) => Instruction::BrTable(
+                labels.iter().map(|&l| shift(l, depth)).collect(),
+                shift(default, depth),
+            ),
+            Instruction::TryTable(..)
+            | Instruction::Try(_)
+            | Instruction::Delegate(_)
+            | Instruction::Rethrow(_)
+            | Instruction::BrOnCast { .. }
+            | Instruction::BrOnCastFail { .. } => {
+                return Err(InspectError::Unsupported(format!(
+                    "flattening functions with {instruction:?}"
+                )));
+            }
+            instruction => instruction,
+        };
+        out.push(instruction);
+    }
+    Ok(())
+}
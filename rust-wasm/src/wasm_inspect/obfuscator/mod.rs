@@ -0,0 +1,795 @@
+mod constants;
+mod data;
+mod flatten;
+mod opaque;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use wasm_encoder::reencode::{self, Reencode, utils};
+use wasm_encoder::{
+    CodeSection, DataSection, ExportSection, Function, FunctionSection, Instruction, Module,
+    SectionId, StartSection, TypeSection, ValType,
+};
+use wasmparser::{Parser, Validator};
+
+use super::inspect_error::InspectError;
+use super::module_info::ModuleInfo;
+use data::Segment;
+
+// 混淆 wasm 模块，用来把题目做得更难：
+// - 加密主动数据段，由新增的 start 函数在实例化时解密
+// - 在函数中插入不透明谓词和永远不会执行的分支
+// - 对指定的函数做控制流平坦化
+// - 把 i32 / i64 常量拆成两个常量的运算
+// - 把导出名换成随机的名字
+// 每个 pass 都由 seed 决定，相同的输入和选项得到相同的输出；混淆前后模块的行为一致。
+// 与 patcher 一样用 Reencode 重新编码，结果经过校验。
+// 被动数据段（memory.init ，如 wasm-bindgen 的多线程构建）和共享内存上的数据段不加密
+
+/// 混淆选项，JSON 中省略的项取默认值（不启用）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ObfuscateOptions {
+    pub seed: u64,
+    pub encrypt_data: bool,
+    pub opaque_predicates: bool,
+    // 要平坦化的函数，名字或索引
+    pub flatten: Vec<String>,
+    pub split_constants: bool,
+    pub mangle_exports: bool,
+    // 混淆导出名时保留原名的导出，如 "memory"
+    pub keep_exports: Vec<String>,
+}
+
+/// 混淆结果：新的 .wasm 和原导出名到新导出名的映射
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ObfuscatedWasm {
+    bytes: Vec<u8>,
+    exports: BTreeMap<String, String>,
+}
+
+impl ObfuscatedWasm {
+    pub fn export_names(&self) -> &BTreeMap<String, String> {
+        &self.exports
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[wasm_bindgen]
+impl ObfuscatedWasm {
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// 导出名映射，JSON 对象
+    #[wasm_bindgen(getter)]
+    pub fn exports(&self) -> String {
+        serde_json::to_string(&self.exports).unwrap_or_default()
+    }
+}
+
+// 各 pass 的随机数序列编号
+const EXPORTS: u64 = 1;
+const DATA: u64 = 2;
+const FLATTEN: u64 = 3;
+const OPAQUE: u64 = 4;
+const CONSTANTS: u64 = 5;
+
+/// SplitMix64 。每个 pass 、每个函数从 seed 派生出独立的序列，
+/// 一个 pass 的开关或改动不影响其他 pass 的结果
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64, pass: u64, index: u32) -> Self {
+        let stream = (pass << 32) | index as u64;
+        let mut rng = Rng(seed ^ stream.wrapping_mul(0xd1b5_4a32_d192_ed03));
+        rng.next_u64();
+        rng
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    // [0, n)
+    fn below(&mut self, n: u32) -> u32 {
+        ((self.next_u32() as u64 * n as u64) >> 32) as u32
+    }
+
+    fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut items: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            let j = self.below(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+        items
+    }
+}
+
+struct Obfuscator<'a> {
+    options: &'a ObfuscateOptions,
+    // 每个函数的参数类型
+    params: Vec<Vec<String>>,
+    flatten: HashMap<u32, HashSet<usize>>,
+    // 按数据段索引排列的加密段
+    segments: Vec<Segment>,
+    exports: BTreeMap<String, String>,
+    // 解密函数的类型索引和函数索引
+    decryptor: Option<(u32, u32)>,
+    start: Option<u32>,
+    start_written: bool,
+    next_function: u32,
+    next_data: u32,
+}
+
+impl<'a> Obfuscator<'a> {
+    fn new(
+        bytes: &[u8],
+        info: &ModuleInfo,
+        options: &'a ObfuscateOptions,
+    ) -> Result<Self, InspectError> {
+        let params = info
+            .functions
+            .iter()
+            .map(|f| info.types[f.type_index as usize].params.clone())
+            .collect();
+
+        let mut targets = HashSet::new();
+        for query in &options.flatten {
+            let function = info
+                .find_function(query)
+                .ok_or_else(|| InspectError::FunctionNotFound(query.clone()))?;
+            if function.import.is_some() {
+                return Err(InspectError::ImportedFunction(query.clone()));
+            }
+            targets.insert(function.index);
+        }
+        let flatten = if targets.is_empty() {
+            HashMap::new()
+        } else {
+            flatten::split_points(bytes, &targets)?
+                .into_iter()
+                .collect()
+        };
+
+        let mut segments = Vec::new();
+        let mut decryptor = None;
+        if options.encrypt_data {
+            segments = encryptable_segments(info, options.seed);
+            // 解密函数追加在类型、函数和代码 section 的末尾，这些 section 必须存在
+            if !segments.is_empty() && info.functions.len() == info.imported_function_count() {
+                return Err(InspectError::Unsupported(
+                    "encrypting data of a module without functions".into(),
+                ));
+            }
+            if !segments.is_empty() {
+                decryptor = Some((info.types.len() as u32, info.functions.len() as u32));
+            }
+        }
+
+        let mut exports = BTreeMap::new();
+        if options.mangle_exports {
+            let mut rng = Rng::new(options.seed, EXPORTS, 0);
+            let mut used: HashSet<String> = options.keep_exports.iter().cloned().collect();
+            for export in &info.exports {
+                if options.keep_exports.contains(&export.name) {
+                    continue;
+                }
+                let name = loop {
+                    let name = format!("_{:08x}", rng.next_u32());
+                    if used.insert(name.clone()) {
+                        break name;
+                    }
+                };
+                exports.insert(export.name.clone(), name);
+            }
+        }
+
+        Ok(Obfuscator {
+            options,
+            params,
+            flatten,
+            segments,
+            exports,
+            decryptor,
+            start: None,
+            start_written: false,
+            next_function: info.imported_function_count() as u32,
+            next_data: 0,
+        })
+    }
+}
+
+// 内存 0 上偏移为常量的非空主动段，与其他段重叠的不加密（解密的顺序会破坏后写入的内容）
+fn encryptable_segments(info: &ModuleInfo, seed: u64) -> Vec<Segment> {
+    let Some(memory) = info.memories.first() else {
+        return Vec::new();
+    };
+    if memory.memory64 || memory.shared {
+        return Vec::new();
+    }
+    let active: Vec<_> = info
+        .data
+        .iter()
+        .filter(|d| d.memory_index == Some(0) && d.size > 0)
+        .filter_map(|d| Some((d.index, d.offset?, d.size as u64)))
+        .collect();
+    let mut rng = Rng::new(seed, DATA, 0);
+    active
+        .iter()
+        .filter(|&&(index, start, size)| {
+            !active
+                .iter()
+                .any(|&(other, s, n)| other != index && s < start + size && start < s + n)
+        })
+        .map(|&(index, address, size)| Segment {
+            index,
+            address: address as u32,
+            len: size as u32,
+            seed: rng.next_u32() | 1,
+        })
+        .collect()
+}
+
+fn encode(locals: Vec<(u32, ValType)>, instructions: &[Instruction<'_>]) -> Function {
+    let mut f = Function::new(locals);
+    for instruction in instructions {
+        f.instruction(instruction);
+    }
+    f
+}
+
+impl Reencode for Obfuscator<'_> {
+    type Error = InspectError;
+
+    fn parse_function_body(
+        &mut self,
+        code: &mut CodeSection,
+        func: wasmparser::FunctionBody<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let index = self.next_function;
+        self.next_function += 1;
+        let points = self.flatten.remove(&index);
+        let options = self.options;
+        if points.is_none() && !options.opaque_predicates && !options.split_constants {
+            return utils::parse_function_body(self, code, func);
+        }
+
+        let params = &self.params[index as usize];
+        let mut i32_locals: Vec<u32> = (0..params.len() as u32)
+            .filter(|&i| params[i as usize] == "i32")
+            .collect();
+        let mut next_local = params.len() as u32;
+        let mut locals = Vec::new();
+        for pair in func.get_locals_reader()? {
+            let (count, ty) = pair?;
+            let ty = self.val_type(ty)?;
+            if ty == ValType::I32 {
+                i32_locals.extend(next_local..next_local + count);
+            }
+            next_local += count;
+            locals.push((count, ty));
+        }
+        let mut instructions = Vec::new();
+        let mut reader = func.get_operators_reader()?;
+        while !reader.eof() {
+            instructions.push(self.instruction(reader.read()?)?);
+        }
+
+        let seed = options.seed;
+        if let Some(points) = points {
+            let state = next_local;
+            next_local += 1;
+            locals.push((1, ValType::I32));
+            instructions = flatten::flatten(
+                instructions,
+                &points,
+                state,
+                &mut Rng::new(seed, FLATTEN, index),
+            )
+            .map_err(reencode::Error::UserError)?;
+        }
+        if options.opaque_predicates {
+            let mut rng = Rng::new(seed, OPAQUE, index);
+            // 用一个已有的 i32 局部变量，没有时新增一个
+            let x = if i32_locals.is_empty() {
+                locals.push((1, ValType::I32));
+                next_local
+            } else {
+                i32_locals[rng.below(i32_locals.len() as u32) as usize]
+            };
+            instructions = opaque::insert(instructions, x, &mut rng);
+        }
+        if options.split_constants {
+            instructions = constants::split(instructions, &mut Rng::new(seed, CONSTANTS, index));
+        }
+        code.function(&encode(locals, &instructions));
+        Ok(())
+    }
+
+    fn parse_type_section(
+        &mut self,
+        types: &mut TypeSection,
+        section: wasmparser::TypeSectionReader<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        utils::parse_type_section(self, types, section)?;
+        if self.decryptor.is_some() {
+            types.ty().function([], []);
+        }
+        Ok(())
+    }
+
+    fn parse_function_section(
+        &mut self,
+        functions: &mut FunctionSection,
+        section: wasmparser::FunctionSectionReader<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        utils::parse_function_section(self, functions, section)?;
+        if let Some((ty, _)) = self.decryptor {
+            functions.function(ty);
+        }
+        Ok(())
+    }
+
+    fn parse_code_section(
+        &mut self,
+        code: &mut CodeSection,
+        section: wasmparser::CodeSectionReader<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        utils::parse_code_section(self, code, section)?;
+        if let Some((_, index)) = self.decryptor {
+            let mut instructions = data::decryptor(&self.segments, self.start);
+            if self.options.split_constants {
+                instructions = constants::split(
+                    instructions,
+                    &mut Rng::new(self.options.seed, CONSTANTS, index),
+                );
+            }
+            code.function(&encode(vec![(2, ValType::I32)], &instructions));
+        }
+        Ok(())
+    }
+
+    fn parse_data(
+        &mut self,
+        data: &mut DataSection,
+        datum: wasmparser::Data<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let index = self.next_data;
+        self.next_data += 1;
+        let (
+            Some(segment),
+            wasmparser::DataKind::Active {
+                memory_index,
+                offset_expr,
+            },
+        ) = (self.segments.iter().find(|s| s.index == index), &datum.kind)
+        else {
+            return utils::parse_data(self, data, datum);
+        };
+        let mut content = datum.data.to_vec();
+        data::apply_keystream(&mut content, segment.seed);
+        let memory_index = self.memory_index(*memory_index)?;
+        let offset = self.const_expr(offset_expr.clone())?;
+        data.active(memory_index, &offset, content);
+        Ok(())
+    }
+
+    fn parse_export(
+        &mut self,
+        exports: &mut ExportSection,
+        export: wasmparser::Export<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let kind = self.export_kind(export.kind)?;
+        let index = self.external_index(export.kind, export.index)?;
+        let name = self
+            .exports
+            .get(export.name)
+            .map(String::as_str)
+            .unwrap_or(export.name);
+        exports.export(name, kind, index);
+        Ok(())
+    }
+
+    // 原来的 start 函数改由解密函数在最后调用
+    fn start_section(&mut self, start: u32) -> Result<u32, reencode::Error<InspectError>> {
+        let start = self.function_index(start)?;
+        self.start_written = true;
+        match self.decryptor {
+            Some((_, decryptor)) => {
+                self.start = Some(start);
+                Ok(decryptor)
+            }
+            None => Ok(start),
+        }
+    }
+
+    // 没有 start section 的模块在它应在的位置补上一个
+    fn intersperse_section_hook(
+        &mut self,
+        module: &mut Module,
+        _after: Option<SectionId>,
+        before: Option<SectionId>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let start_passed = matches!(
+            before,
+            None | Some(
+                SectionId::Element | SectionId::DataCount | SectionId::Code | SectionId::Data
+            )
+        );
+        if let Some((_, decryptor)) = self.decryptor
+            && start_passed
+            && !self.start_written
+        {
+            module.section(&StartSection {
+                function_index: decryptor,
+            });
+            self.start_written = true;
+        }
+        Ok(())
+    }
+}
+
+/// 依次执行启用的 pass ，返回重新编码并通过校验的模块
+pub fn obfuscate_module(
+    bytes: &[u8],
+    options: &ObfuscateOptions,
+) -> Result<ObfuscatedWasm, InspectError> {
+    // ModuleInfo::parse 不做校验，后面按索引取类型、函数的地方默认模块是合法的
+    Validator::new().validate_all(bytes)?;
+    let info = ModuleInfo::parse(bytes)?;
+    let mut obfuscator = Obfuscator::new(bytes, &info, options)?;
+    let mut module = Module::new();
+    obfuscator
+        .parse_core_module(&mut module, Parser::new(0), bytes)
+        .map_err(|e| match e {
+            reencode::Error::UserError(e) => e,
+            reencode::Error::ParseError(e) => e.into(),
+            e => InspectError::Unsupported(e.to_string()),
+        })?;
+    let obfuscated = module.finish();
+    Validator::new()
+        .validate_all(&obfuscated)
+        .map_err(|e| InspectError::PatchValidation {
+            offset: e.offset(),
+            message: e.message().to_string(),
+        })?;
+    Ok(ObfuscatedWasm {
+        bytes: obfuscated,
+        exports: obfuscator.exports,
+    })
+}
+
+/// 按 JSON 形式的 [`ObfuscateOptions`] 混淆模块
+#[wasm_bindgen]
+pub fn obfuscate_wasm(bytes: &[u8], options_json: &str) -> Result<ObfuscatedWasm, InspectError> {
+    let options: ObfuscateOptions = serde_json::from_str(options_json)
+        .map_err(|e| InspectError::InvalidArguments(format!("options: {e}")))?;
+    obfuscate_module(bytes, &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+    use crate::wasm_inspect::disassembler::disassemble_wasm_function;
+    use crate::wasm_inspect::interpreter::{Value, WasmInterpreter};
+    use crate::wasm_inspect::strings::extract_strings;
+
+    // 手写的 xor_demo::encrypt_username ：与 "hctf" 循环异或后做标准 base64 ，
+    // 输入已经 trim ，输出写到 out ，返回输出长度。仿照 rustc 的输出，数据段从 1048576 开始
+    const WAT: &str = r#"
+        (module
+            (memory (export "memory") 17)
+            (global $__stack_pointer (mut i32) (i32.const 1048576))
+            (data (i32.const 1048576) "hctf")
+            (data (i32.const 1048580) "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+            (func $string_xor (param $dst i32) (param $src i32) (param $len i32) (local $i i32)
+                (br_if 0 (i32.eqz (local.get $len)))
+                (loop
+                    (i32.store8 (i32.add (local.get $dst) (local.get $i))
+                        (i32.xor
+                            (i32.load8_u (i32.add (local.get $src) (local.get $i)))
+                            (i32.load8_u offset=1048576 (i32.and (local.get $i) (i32.const 3)))))
+                    (br_if 0 (i32.ne
+                        (local.tee $i (i32.add (local.get $i) (i32.const 1)))
+                        (local.get $len)))))
+            (func $base64_encode (param $src i32) (param $len i32) (param $out i32) (result i32)
+                (local $i i32) (local $o i32) (local $n i32)
+                (block $tail
+                    (loop $groups
+                        (br_if $tail (i32.gt_u (i32.add (local.get $i) (i32.const 3)) (local.get $len)))
+                        (local.set $n (i32.or
+                            (i32.or
+                                (i32.shl (i32.load8_u (i32.add (local.get $src) (local.get $i))) (i32.const 16))
+                                (i32.shl (i32.load8_u offset=1 (i32.add (local.get $src) (local.get $i))) (i32.const 8)))
+                            (i32.load8_u offset=2 (i32.add (local.get $src) (local.get $i)))))
+                        (call $emit (i32.add (local.get $out) (local.get $o)) (local.get $n) (i32.const 4))
+                        (local.set $i (i32.add (local.get $i) (i32.const 3)))
+                        (local.set $o (i32.add (local.get $o) (i32.const 4)))
+                        (br $groups)))
+                (block $done
+                    (block $two
+                        (block $one
+                            (br_table $done $one $two (i32.sub (local.get $len) (local.get $i))))
+                        (call $emit (i32.add (local.get $out) (local.get $o))
+                            (i32.shl (i32.load8_u (i32.add (local.get $src) (local.get $i))) (i32.const 16))
+                            (i32.const 2))
+                        (i32.store16 offset=2 (i32.add (local.get $out) (local.get $o)) (i32.const 0x3d3d))
+                        (return (i32.add (local.get $o) (i32.const 4))))
+                    (call $emit (i32.add (local.get $out) (local.get $o))
+                        (i32.or
+                            (i32.shl (i32.load8_u (i32.add (local.get $src) (local.get $i))) (i32.const 16))
+                            (i32.shl (i32.load8_u offset=1 (i32.add (local.get $src) (local.get $i))) (i32.const 8)))
+                        (i32.const 3))
+                    (i32.store8 offset=3 (i32.add (local.get $out) (local.get $o)) (i32.const 61))
+                    (local.set $o (i32.add (local.get $o) (i32.const 4))))
+                (local.get $o))
+            (func $emit (param $dst i32) (param $n i32) (param $count i32) (local $k i32)
+                (loop $chars
+                    (i32.store8 (i32.add (local.get $dst) (local.get $k))
+                        (i32.load8_u offset=1048580 (i32.and
+                            (i32.shr_u (local.get $n) (i32.sub (i32.const 18) (i32.mul (local.get $k) (i32.const 6))))
+                            (i32.const 63))))
+                    (br_if $chars (i32.lt_u
+                        (local.tee $k (i32.add (local.get $k) (i32.const 1)))
+                        (local.get $count)))))
+            (func (export "encrypt_username") (param $ptr i32) (param $len i32) (param $out i32) (result i32)
+                (call $string_xor (local.get $ptr) (local.get $ptr) (local.get $len))
+                (call $base64_encode (local.get $ptr) (local.get $len) (local.get $out))))
+    "#;
+
+    // xor_demo 中 encrypt_username 的测试向量
+    const VECTORS: [(&str, &str); 7] = [
+        ("hans7", "AAIaFV8="),
+        ("HANS", "ICI6NQ=="),
+        ("爱拼才会赢", "j+vFgOPfku/lh8j8gNbW"),
+        ("Acm_L0ver", "KQAZOSRTAgMa"),
+        ("hctf", "AAAAAA=="),
+        ("有空 格", "jv/9gcHZVIDI3w=="),
+        ("", ""),
+    ];
+
+    fn module() -> Vec<u8> {
+        wat::parse_str(WAT).unwrap()
+    }
+
+    fn all_passes(seed: u64) -> ObfuscateOptions {
+        ObfuscateOptions {
+            seed,
+            encrypt_data: true,
+            opaque_predicates: true,
+            flatten: vec![
+                "string_xor".into(),
+                "base64_encode".into(),
+                "emit".into(),
+                "encrypt_username".into(),
+            ],
+            split_constants: true,
+            mangle_exports: true,
+            keep_exports: vec!["memory".into()],
+        }
+    }
+
+    fn encrypt_username(bytes: &[u8], export: &str, name: &str) -> String {
+        let mut interpreter = WasmInterpreter::new(bytes).unwrap();
+        interpreter.write_memory(1024, name.as_bytes()).unwrap();
+        let args = [
+            Value::I32(1024),
+            Value::I32(name.len() as i32),
+            Value::I32(4096),
+        ];
+        let execution = interpreter.invoke(export, &args).unwrap();
+        assert_eq!(execution.trap, None);
+        let Value::I32(len) = execution.results[0] else {
+            panic!("{:?}", execution.results);
+        };
+        String::from_utf8(interpreter.read_memory(4096, len as u32).unwrap()).unwrap()
+    }
+
+    fn assert_equivalent(bytes: &[u8], export: &str) {
+        for (name, expected) in VECTORS {
+            assert_eq!(encrypt_username(bytes, export, name), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn obfuscate_equivalence_test() {
+        let bytes = module();
+        assert_equivalent(&bytes, "encrypt_username");
+        for seed in [0, 1, 42, 0xdead_beef] {
+            let obfuscated = obfuscate_module(&bytes, &all_passes(seed)).unwrap();
+            let export = &obfuscated.export_names()["encrypt_username"];
+            assert_ne!(export, "encrypt_username");
+            assert!(obfuscated.bytes.len() > bytes.len());
+            assert_equivalent(&obfuscated.bytes, export);
+        }
+
+        // 每个 pass 单独启用
+        let all = all_passes(7);
+        for options in [
+            ObfuscateOptions {
+                encrypt_data: true,
+                ..Default::default()
+            },
+            ObfuscateOptions {
+                opaque_predicates: true,
+                ..Default::default()
+            },
+            ObfuscateOptions {
+                flatten: all.flatten.clone(),
+                ..Default::default()
+            },
+            ObfuscateOptions {
+                split_constants: true,
+                ..Default::default()
+            },
+        ] {
+            let obfuscated = obfuscate_module(&bytes, &options).unwrap();
+            assert!(obfuscated.export_names().is_empty());
+            assert_equivalent(&obfuscated.bytes, "encrypt_username");
+        }
+    }
+
+    #[test]
+    fn obfuscate_reproducible_test() {
+        let bytes = module();
+        let first = obfuscate_module(&bytes, &all_passes(1)).unwrap();
+        let second = obfuscate_module(&bytes, &all_passes(1)).unwrap();
+        assert_eq!(first.bytes, second.bytes);
+        assert_eq!(first.exports, second.exports);
+        let other = obfuscate_module(&bytes, &all_passes(2)).unwrap();
+        assert_ne!(first.bytes, other.bytes);
+        assert_ne!(first.exports, other.exports);
+
+        // 保留的导出和导出名映射
+        let info = ModuleInfo::parse(&first.bytes).unwrap();
+        let exports: Vec<_> = info.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            exports,
+            ["memory", first.exports["encrypt_username"].as_str()]
+        );
+        assert_eq!(
+            serde_json::from_str::<BTreeMap<String, String>>(&first.exports()).unwrap(),
+            first.exports
+        );
+    }
+
+    #[test]
+    fn obfuscate_data_test() {
+        let bytes = module();
+        let texts = |bytes: &[u8]| -> Vec<String> {
+            extract_strings(bytes, 4)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.text)
+                .collect()
+        };
+        assert!(texts(&bytes).contains(&"hctf".to_string()));
+        let options = ObfuscateOptions {
+            encrypt_data: true,
+            ..Default::default()
+        };
+        let obfuscated = obfuscate_module(&bytes, &options).unwrap().into_bytes();
+        assert!(!texts(&obfuscated).iter().any(|t| t.contains("hctf")));
+        assert!(!texts(&obfuscated).iter().any(|t| t.contains("ABCDEFGH")));
+        let info = ModuleInfo::parse(&obfuscated).unwrap();
+        assert_eq!(info.start, Some(info.functions.len() as u32 - 1));
+        let interpreter = WasmInterpreter::new(&obfuscated).unwrap();
+        assert_eq!(interpreter.read_memory(1048576, 4).unwrap(), b"hctf");
+
+        // 原有的 start 函数在解密之后执行
+        let with_start = wat::parse_str(
+            r#"(module
+                (memory 1)
+                (global $first (mut i32) (i32.const 0))
+                (data (i32.const 16) "secret")
+                (func $init (global.set $first (i32.load8_u (i32.const 16))))
+                (start $init)
+                (func (export "first") (result i32) (global.get $first)))"#,
+        )
+        .unwrap();
+        let obfuscated = obfuscate_module(&with_start, &options)
+            .unwrap()
+            .into_bytes();
+        assert!(texts(&obfuscated).is_empty());
+        let mut interpreter = WasmInterpreter::new(&obfuscated).unwrap();
+        let execution = interpreter.invoke("first", &[]).unwrap();
+        assert_eq!(execution.results, [Value::I32(b's' as i32)]);
+
+        // 没有函数的模块放不下解密函数
+        let bare = wat::parse_str(r#"(module (memory 1) (data (i32.const 0) "hctf"))"#).unwrap();
+        assert!(matches!(
+            obfuscate_module(&bare, &options),
+            Err(InspectError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn obfuscate_code_test() {
+        let bytes = module();
+        let options = ObfuscateOptions {
+            seed: 3,
+            flatten: vec!["base64_encode".into()],
+            split_constants: true,
+            ..Default::default()
+        };
+        let obfuscated = obfuscate_module(&bytes, &options).unwrap().into_bytes();
+        let original = disassemble_wasm_function(&bytes, "base64_encode", false).unwrap();
+        let flattened = disassemble_wasm_function(&obfuscated, "base64_encode", false).unwrap();
+        // 顶层的三段放进分发循环，跳转表多了一个
+        assert_eq!(original.matches("br_table").count(), 1);
+        assert_eq!(flattened.matches("br_table").count(), 2);
+        // 0x3d3d ("==") 被拆开
+        assert!(original.contains("i32.const 15677"));
+        assert!(!flattened.contains("i32.const 15677"));
+        // 没有指定的函数不平坦化
+        let emit = disassemble_wasm_function(&obfuscated, "emit", false).unwrap();
+        assert!(!emit.contains("br_table"));
+    }
+
+    #[test]
+    fn obfuscate_errors_test() {
+        let bytes = module();
+        let flatten = |function: &str| {
+            obfuscate_module(
+                &bytes,
+                &ObfuscateOptions {
+                    flatten: vec![function.into()],
+                    ..Default::default()
+                },
+            )
+            .unwrap_err()
+        };
+        assert!(matches!(
+            flatten("missing"),
+            InspectError::FunctionNotFound(_)
+        ));
+        let imported = wat::parse_str(r#"(module (import "env" "f" (func $f)))"#).unwrap();
+        assert!(matches!(
+            obfuscate_module(
+                &imported,
+                &ObfuscateOptions {
+                    flatten: vec!["env.f".into()],
+                    ..Default::default()
+                }
+            ),
+            Err(InspectError::ImportedFunction(_))
+        ));
+
+        // 改坏任意一个字节只返回错误，不会 panic
+        let options = all_passes(9);
+        for i in 0..bytes.len() {
+            for flip in [0x01, 0x80, 0xff] {
+                let mut mutated = bytes.clone();
+                mutated[i] ^= flip;
+                if let Err(e) = obfuscate_module(&mutated, &options) {
+                    assert!(!matches!(e, InspectError::PatchValidation { .. }), "{e}");
+                }
+            }
+        }
+        assert!(matches!(
+            obfuscate_module(&bytes[..bytes.len() - 1], &options),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+
+        let err = RustWasmError::from(obfuscate_wasm(&bytes, r#"{"seed": "x"}"#).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert!(obfuscate_wasm(&bytes, r#"{"seed": 5, "split_constants": true}"#).is_ok());
+    }
+}
@@ -0,0 +1,86 @@
+use wasm_encoder::{BlockType, Instruction};
+
+use super::Rng;
+
+// 不透明谓词：插入恒为假的条件和永远不会执行的分支，分支里是改写局部变量的无用运算。
+// 条件对任意 x 都成立，在 2^32 取模下同样成立：
+// - x * (x + 1) 是偶数
+// - 奇数的平方模 8 余 1
+// - 平方数模 4 只能是 0 或 1
+
+/// 平均每多少条指令插入一个
+const INTERVAL: u32 = 12;
+
+/// 在函数开头和随机的指令边界插入不透明谓词，x 是一个 i32 局部变量
+pub(super) fn insert<'a>(
+    instructions: Vec<Instruction<'a>>,
+    x: u32,
+    rng: &mut Rng,
+) -> Vec<Instruction<'a>> {
+    let mut out = Vec::with_capacity(instructions.len() * 2);
+    predicate(x, rng, &mut out);
+    // 插入的指令不改变操作数栈，放在任意两条指令之间都合法
+    for (i, instruction) in instructions.into_iter().enumerate() {
+        if i > 0 && rng.below(INTERVAL) == 0 {
+            predicate(x, rng, &mut out);
+        }
+        out.push(instruction);
+    }
+    out
+}
+
+fn predicate(x: u32, rng: &mut Rng, out: &mut Vec<Instruction<'_>>) {
+    use Instruction::*;
+    match rng.below(3) {
+        // (x * (x + 1)) & 1
+        0 => out.extend([
+            LocalGet(x),
+            LocalGet(x),
+            I32Const(1),
+            I32Add,
+            I32Mul,
+            I32Const(1),
+            I32And,
+        ]),
+        // ((x | 1) * (x | 1)) & 7 != 1
+        1 => out.extend([
+            LocalGet(x),
+            I32Const(1),
+            I32Or,
+            LocalGet(x),
+            I32Const(1),
+            I32Or,
+            I32Mul,
+            I32Const(7),
+            I32And,
+            I32Const(1),
+            I32Ne,
+        ]),
+        // (x * x) & 3 == 2
+        _ => out.extend([
+            LocalGet(x),
+            LocalGet(x),
+            I32Mul,
+            I32Const(3),
+            I32And,
+            I32Const(2),
+            I32Eq,
+        ]),
+    }
+    out.push(If(BlockType::Empty));
+    for _ in 0..1 + rng.below(3) {
+        let op = match rng.below(4) {
+            0 => I32Add,
+            1 => I32Xor,
+            2 => I32Mul,
+            _ => I32Rotl,
+        };
+        out.extend([
+            LocalGet(x),
+            I32Const(rng.next_u32() as i32),
+            op,
+            LocalSet(x),
+        ]);
+    }
+    out.push(End);
+}
@@ -0,0 +1,781 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use wasm_encoder::reencode::{self, Reencode, utils};
+use wasm_encoder::{
+    CodeSection, DataSection, ExportKind, ExportSection, Function, Ieee32, Ieee64, Instruction,
+    Module, SectionId,
+};
+use wasmparser::{Operator, Parser, ValType, Validator};
+
+use super::inspect_error::InspectError;
+use super::interpreter::Value;
+use super::module_info::ModuleInfo;
+
+// 修改 wasm 二进制：改常量、把指令替换为 nop 、把函数体换成直接返回常量、改写数据段、
+// 重命名或新增导出。
+// - 用 wasm-encoder 的 Reencode 逐个 section 重新编码，未修改的部分原样保留（包括 name 、
+//   producers 等自定义 section），修改后用 wasmparser 校验，不合法的结果不会返回
+// - 指令按文件偏移定位，与反汇编（显示偏移时）和字符串交叉引用中的偏移一致
+// - 把指令换成 nop 不会调整操作数栈，例如 nop 掉 br_if 会留下多余的条件值，校验会报错；
+//   去掉校验逻辑一般用 return_const 替换整个函数
+
+/// 一项修改，JSON 中以 "op" 区分，例如
+/// `{"op": "set_const", "offset": 1234, "value": 0}`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Patch {
+    // 修改 offset 处的 i32.const / i64.const 的立即数
+    SetConst {
+        offset: usize,
+        value: i64,
+    },
+    // 把 offset 处的指令替换为 nop
+    Nop {
+        offset: usize,
+    },
+    // 函数体替换为依次压入 values 后返回，values 与结果类型一一对应，格式同解释器的参数
+    ReturnConst {
+        function: String,
+        #[serde(default)]
+        values: Vec<serde_json::Value>,
+    },
+    // 覆盖从线性内存地址 address 开始的数据段内容，不能跨段，也不能超出段的末尾
+    WriteData {
+        address: u64,
+        bytes: Vec<u8>,
+    },
+    RenameExport {
+        name: String,
+        new_name: String,
+    },
+    // 导出一个函数（名字或索引）
+    AddExport {
+        name: String,
+        function: String,
+    },
+}
+
+enum InstructionPatch {
+    Const(i64),
+    Nop,
+}
+
+enum FunctionPatch {
+    Instructions(HashMap<usize, InstructionPatch>),
+    Return(Vec<Value>),
+}
+
+struct Patcher<'a> {
+    functions: HashMap<u32, FunctionPatch>,
+    // 数据段索引 → (段内偏移, 新内容)
+    data: HashMap<u32, Vec<(usize, &'a [u8])>>,
+    renames: HashMap<&'a str, &'a str>,
+    added: Vec<(&'a str, u32)>,
+    // 当前正在编码的函数和数据段的索引
+    next_function: u32,
+    next_data: u32,
+    exports_written: bool,
+}
+
+impl<'a> Patcher<'a> {
+    fn new(info: &ModuleInfo, patches: &'a [Patch]) -> Result<Self, InspectError> {
+        let mut patcher = Patcher {
+            functions: HashMap::new(),
+            data: HashMap::new(),
+            renames: HashMap::new(),
+            added: Vec::new(),
+            next_function: info.imported_function_count() as u32,
+            next_data: 0,
+            exports_written: false,
+        };
+        for patch in patches {
+            match patch {
+                Patch::SetConst { offset, value } => {
+                    patcher.patch_instruction(info, *offset, InstructionPatch::Const(*value))?
+                }
+                Patch::Nop { offset } => {
+                    patcher.patch_instruction(info, *offset, InstructionPatch::Nop)?
+                }
+                Patch::ReturnConst { function, values } => {
+                    let index = defined_function(info, function)?;
+                    if patcher.functions.contains_key(&index) {
+                        return Err(invalid(format!("function {function} is already patched")));
+                    }
+                    let f = &info.functions[index as usize];
+                    let results = &info.types[f.type_index as usize].results;
+                    if values.len() != results.len() {
+                        return Err(invalid(format!(
+                            "{function} returns {} values, got {}",
+                            results.len(),
+                            values.len()
+                        )));
+                    }
+                    let values = results
+                        .iter()
+                        .zip(values)
+                        .map(|(ty, json)| {
+                            number_type(ty)
+                                .and_then(|ty| Value::from_json(ty, json))
+                                .ok_or_else(|| invalid(format!("{json} is not a valid {ty}")))
+                        })
+                        .collect::<Result<_, _>>()?;
+                    patcher
+                        .functions
+                        .insert(index, FunctionPatch::Return(values));
+                }
+                Patch::WriteData { address, bytes } => {
+                    let end = address.saturating_add(bytes.len() as u64);
+                    let data = info
+                        .data
+                        .iter()
+                        .find(|d| {
+                            d.offset
+                                .is_some_and(|base| base <= *address && end <= base + d.size as u64)
+                        })
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "{} bytes at {address:#x} are not inside one data segment",
+                                bytes.len()
+                            ))
+                        })?;
+                    let start = (address - data.offset.unwrap_or_default()) as usize;
+                    patcher
+                        .data
+                        .entry(data.index)
+                        .or_default()
+                        .push((start, bytes));
+                }
+                Patch::RenameExport { name, new_name } => {
+                    if !info.exports.iter().any(|e| e.name == *name) {
+                        return Err(invalid(format!("no export named {name}")));
+                    }
+                    patcher.renames.insert(name, new_name);
+                }
+                Patch::AddExport { name, function } => {
+                    let index = info
+                        .find_function(function)
+                        .ok_or_else(|| InspectError::FunctionNotFound(function.clone()))?
+                        .index;
+                    patcher.added.push((name, index));
+                }
+            }
+        }
+        Ok(patcher)
+    }
+
+    fn patch_instruction(
+        &mut self,
+        info: &ModuleInfo,
+        offset: usize,
+        patch: InstructionPatch,
+    ) -> Result<(), InspectError> {
+        let function = info
+            .functions
+            .iter()
+            .find(|f| {
+                f.body_offset
+                    .zip(f.body_size)
+                    .is_some_and(|(start, size)| (start..start + size).contains(&offset))
+            })
+            .ok_or_else(|| invalid(format!("offset {offset:#x} is not inside a function body")))?;
+        let patches = self
+            .functions
+            .entry(function.index)
+            .or_insert_with(|| FunctionPatch::Instructions(HashMap::new()));
+        match patches {
+            FunctionPatch::Instructions(patches) => {
+                patches.insert(offset, patch);
+                Ok(())
+            }
+            FunctionPatch::Return(_) => Err(invalid(format!(
+                "function {} is already replaced by return_const",
+                function.index
+            ))),
+        }
+    }
+}
+
+impl Reencode for Patcher<'_> {
+    type Error = InspectError;
+
+    fn parse_function_body(
+        &mut self,
+        code: &mut CodeSection,
+        func: wasmparser::FunctionBody<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let index = self.next_function;
+        self.next_function += 1;
+        let mut patches = match self.functions.remove(&index) {
+            None => return utils::parse_function_body(self, code, func),
+            Some(FunctionPatch::Return(values)) => {
+                let mut f = Function::new([]);
+                for value in values {
+                    f.instruction(&const_instruction(value));
+                }
+                f.instruction(&Instruction::End);
+                code.function(&f);
+                return Ok(());
+            }
+            Some(FunctionPatch::Instructions(patches)) => patches,
+        };
+
+        let mut f = self.new_function_with_parsed_locals(&func)?;
+        let mut reader = func.get_operators_reader()?;
+        while !reader.eof() {
+            let offset = reader.original_position();
+            let op = reader.read()?;
+            let instruction = match (patches.remove(&offset), op) {
+                (None, op) => self.instruction(op)?,
+                (Some(InstructionPatch::Const(value)), Operator::I32Const { .. }) => {
+                    // 允许按无符号数给出，如 0xffffffff
+                    let value = i32::try_from(value)
+                        .or_else(|_| u32::try_from(value).map(|v| v as i32))
+                        .map_err(|_| user_error(format!("{value} does not fit in i32")))?;
+                    Instruction::I32Const(value)
+                }
+                (Some(InstructionPatch::Const(value)), Operator::I64Const { .. }) => {
+                    Instruction::I64Const(value)
+                }
+                (Some(InstructionPatch::Const(_)), _) => {
+                    return Err(user_error(format!(
+                        "instruction at {offset:#x} is not i32.const or i64.const"
+                    )));
+                }
+                // 去掉块结构会改变嵌套关系，后面的 end 全部错位
+                (
+                    Some(InstructionPatch::Nop),
+                    Operator::Block { .. }
+                    | Operator::Loop { .. }
+                    | Operator::If { .. }
+                    | Operator::Else
+                    | Operator::End,
+                ) => {
+                    return Err(user_error(format!(
+                        "block instruction at {offset:#x} cannot be replaced by nop"
+                    )));
+                }
+                (Some(InstructionPatch::Nop), _) => Instruction::Nop,
+            };
+            f.instruction(&instruction);
+        }
+        if let Some(offset) = patches.keys().min() {
+            return Err(user_error(format!(
+                "no instruction starts at offset {offset:#x}"
+            )));
+        }
+        code.function(&f);
+        Ok(())
+    }
+
+    fn parse_data(
+        &mut self,
+        data: &mut DataSection,
+        datum: wasmparser::Data<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let index = self.next_data;
+        self.next_data += 1;
+        let (
+            Some(writes),
+            wasmparser::DataKind::Active {
+                memory_index,
+                offset_expr,
+            },
+        ) = (self.data.remove(&index), &datum.kind)
+        else {
+            return utils::parse_data(self, data, datum);
+        };
+        let mut content = datum.data.to_vec();
+        for (start, bytes) in writes {
+            content[start..start + bytes.len()].copy_from_slice(bytes);
+        }
+        let memory_index = self.memory_index(*memory_index)?;
+        let offset = self.const_expr(offset_expr.clone())?;
+        data.active(memory_index, &offset, content);
+        Ok(())
+    }
+
+    fn parse_export(
+        &mut self,
+        exports: &mut ExportSection,
+        export: wasmparser::Export<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let name = self
+            .renames
+            .get(export.name)
+            .copied()
+            .unwrap_or(export.name);
+        let kind = self.export_kind(export.kind)?;
+        let index = self.external_index(export.kind, export.index)?;
+        exports.export(name, kind, index);
+        Ok(())
+    }
+
+    fn parse_export_section(
+        &mut self,
+        exports: &mut ExportSection,
+        section: wasmparser::ExportSectionReader<'_>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        utils::parse_export_section(self, exports, section)?;
+        for &(name, index) in &self.added {
+            exports.export(name, ExportKind::Func, index);
+        }
+        self.exports_written = true;
+        Ok(())
+    }
+
+    // 没有导出 section 的模块在它应在的位置补上一个
+    fn intersperse_section_hook(
+        &mut self,
+        module: &mut Module,
+        _after: Option<SectionId>,
+        before: Option<SectionId>,
+    ) -> Result<(), reencode::Error<InspectError>> {
+        let export_passed = matches!(
+            before,
+            None | Some(
+                SectionId::Start
+                    | SectionId::Element
+                    | SectionId::DataCount
+                    | SectionId::Code
+                    | SectionId::Data
+            )
+        );
+        if export_passed && !self.exports_written && !self.added.is_empty() {
+            let mut exports = ExportSection::new();
+            for &(name, index) in &self.added {
+                exports.export(name, ExportKind::Func, index);
+            }
+            module.section(&exports);
+            self.exports_written = true;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: String) -> InspectError {
+    InspectError::InvalidPatch(message)
+}
+
+fn user_error(message: String) -> reencode::Error<InspectError> {
+    reencode::Error::UserError(invalid(message))
+}
+
+fn defined_function(info: &ModuleInfo, query: &str) -> Result<u32, InspectError> {
+    let function = info
+        .find_function(query)
+        .ok_or_else(|| InspectError::FunctionNotFound(query.to_string()))?;
+    if function.import.is_some() {
+        return Err(InspectError::ImportedFunction(query.to_string()));
+    }
+    Ok(function.index)
+}
+
+fn number_type(ty: &str) -> Option<ValType> {
+    Some(match ty {
+        "i32" => ValType::I32,
+        "i64" => ValType::I64,
+        "f32" => ValType::F32,
+        "f64" => ValType::F64,
+        _ => return None,
+    })
+}
+
+fn const_instruction(value: Value) -> Instruction<'static> {
+    match value {
+        Value::I32(v) => Instruction::I32Const(v),
+        Value::I64(v) => Instruction::I64Const(v),
+        Value::F32(bits) => Instruction::F32Const(Ieee32::new(bits)),
+        Value::F64(bits) => Instruction::F64Const(Ieee64::new(bits)),
+        Value::FuncRef(_) | Value::ExternRef(_) => unreachable!("number_type 只接受数值类型"),
+    }
+}
+
+/// 依次应用修改，返回重新编码并通过校验的模块
+pub fn patch_module(bytes: &[u8], patches: &[Patch]) -> Result<Vec<u8>, InspectError> {
+    // 与 obfuscate_module 相同：ModuleInfo::parse 不做校验，Patcher 按索引取类型、函数时默认模块是合法的
+    Validator::new().validate_all(bytes)?;
+    let info = ModuleInfo::parse(bytes)?;
+    let mut patcher = Patcher::new(&info, patches)?;
+    let mut module = Module::new();
+    patcher
+        .parse_core_module(&mut module, Parser::new(0), bytes)
+        .map_err(|e| match e {
+            reencode::Error::UserError(e) => e,
+            reencode::Error::ParseError(e) => e.into(),
+            e => InspectError::Unsupported(e.to_string()),
+        })?;
+    let patched = module.finish();
+    Validator::new()
+        .validate_all(&patched)
+        .map_err(|e| InspectError::PatchValidation {
+            offset: e.offset(),
+            message: e.message().to_string(),
+        })?;
+    Ok(patched)
+}
+
+/// 应用 JSON 数组形式的修改，返回修改后的 .wasm
+#[wasm_bindgen]
+pub fn patch_wasm(bytes: &[u8], patches_json: &str) -> Result<Vec<u8>, InspectError> {
+    let patches: Vec<Patch> =
+        serde_json::from_str(patches_json).map_err(|e| invalid(e.to_string()))?;
+    patch_module(bytes, &patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+    use crate::wasm_inspect::interpreter::{TraceEvent, WasmInterpreter};
+    use crate::wasm_inspect::module_info::DataInfo;
+    use wasmparser::Payload;
+
+    // 仿照 rustc 的输出：数据段从 1048576 开始，有 name section ，导出 memory
+    const WAT: &str = r#"
+        (module
+            (import "env" "log" (func $log (param i32)))
+            (memory (export "memory") 17)
+            (data (i32.const 1048576) "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+            (data (i32.const 1048640) "hctf")
+            (func $verify (export "verify") (param i32) (result i32)
+                (call $log (local.get 0))
+                (i32.eq (local.get 0) (i32.const 1234)))
+            (func $key_byte (param i32) (result i32)
+                (i32.load8_u offset=1048640 (i32.and (local.get 0) (i32.const 3))))
+            (func (export "alphabet_at") (param i32) (result i32)
+                (i32.load8_u offset=1048576 (local.get 0))))
+    "#;
+
+    fn module() -> Vec<u8> {
+        wat::parse_str(WAT).unwrap()
+    }
+
+    // 第 function 个函数中第一条满足 pred 的指令的文件偏移
+    fn offset_of(bytes: &[u8], function: u32, pred: impl Fn(&Operator) -> bool) -> usize {
+        let info = ModuleInfo::parse(bytes).unwrap();
+        let defined = function as usize - info.imported_function_count();
+        for payload in Parser::new(0).parse_all(bytes) {
+            if let Payload::CodeSectionEntry(body) = payload.unwrap()
+                && body.range().start == info.functions[function as usize].body_offset.unwrap()
+            {
+                let mut reader = body.get_operators_reader().unwrap();
+                while !reader.eof() {
+                    let offset = reader.original_position();
+                    if pred(&reader.read().unwrap()) {
+                        return offset;
+                    }
+                }
+            }
+        }
+        panic!("no matching instruction in defined function {defined}");
+    }
+
+    fn call(bytes: &[u8], function: &str, arg: i32) -> Value {
+        let mut interpreter = WasmInterpreter::new(bytes).unwrap();
+        let execution = interpreter.invoke(function, &[Value::I32(arg)]).unwrap();
+        assert_eq!(execution.trap, None);
+        execution.results[0]
+    }
+
+    #[test]
+    fn patch_instructions_test() {
+        let bytes = module();
+        let const_offset = offset_of(&bytes, 1, |op| matches!(op, Operator::I32Const { .. }));
+        let call_offset = offset_of(&bytes, 1, |op| matches!(op, Operator::Call { .. }));
+        let get_offset = offset_of(&bytes, 1, |op| matches!(op, Operator::LocalGet { .. }));
+        assert_eq!(call(&bytes, "verify", 42), Value::I32(0));
+
+        // nop 掉 call 需要同时去掉它的参数
+        let patched = patch_module(
+            &bytes,
+            &[
+                Patch::SetConst {
+                    offset: const_offset,
+                    value: 42,
+                },
+                Patch::Nop { offset: get_offset },
+                Patch::Nop {
+                    offset: call_offset,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(call(&patched, "verify", 42), Value::I32(1));
+        let execution = WasmInterpreter::new(&patched)
+            .unwrap()
+            .invoke("verify", &[Value::I32(42)])
+            .unwrap();
+        assert!(
+            !execution
+                .trace
+                .iter()
+                .any(|e| matches!(e, TraceEvent::HostCall { .. }))
+        );
+        // 1234 、call 和 local.get 各少一个字节，其余部分原样保留，name section 仍在
+        assert_eq!(patched.len(), bytes.len() - 3);
+        let info = ModuleInfo::parse(&patched).unwrap();
+        assert_eq!(info.function_name(2), Some("key_byte"));
+        assert_eq!(
+            info.data,
+            ModuleInfo::parse(&bytes)
+                .unwrap()
+                .data
+                .into_iter()
+                .map(|d| DataInfo {
+                    file_offset: d.file_offset - 3,
+                    ..d
+                })
+                .collect::<Vec<_>>()
+        );
+
+        // 只 nop 掉 call 会留下一个 i32 ，校验失败
+        let err = patch_module(
+            &bytes,
+            &[Patch::Nop {
+                offset: call_offset,
+            }],
+        )
+        .unwrap_err();
+        assert!(matches!(err, InspectError::PatchValidation { .. }));
+        assert_eq!(RustWasmError::from(err).code, ErrorCode::InvalidWasm);
+
+        // 0xffffffff 按无符号数接受，超出 32 位的拒绝
+        assert!(
+            patch_module(
+                &bytes,
+                &[Patch::SetConst {
+                    offset: const_offset,
+                    value: 0xffff_ffff,
+                }],
+            )
+            .is_ok()
+        );
+        for patch in [
+            Patch::SetConst {
+                offset: const_offset,
+                value: 1 << 32,
+            },
+            Patch::SetConst {
+                offset: call_offset,
+                value: 0,
+            },
+            Patch::SetConst {
+                offset: const_offset + 1,
+                value: 0,
+            },
+            Patch::Nop { offset: 0 },
+        ] {
+            assert!(
+                matches!(
+                    patch_module(&bytes, std::slice::from_ref(&patch)),
+                    Err(InspectError::InvalidPatch(_))
+                ),
+                "{patch:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn patch_data_test() {
+        let bytes = module();
+        let alphabet = b"ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210-_";
+        let patched = patch_module(
+            &bytes,
+            &[
+                Patch::WriteData {
+                    address: 1048576,
+                    bytes: alphabet.to_vec(),
+                },
+                Patch::WriteData {
+                    address: 1048641,
+                    bytes: b"CT".to_vec(),
+                },
+            ],
+        )
+        .unwrap();
+        let interpreter = WasmInterpreter::new(&patched).unwrap();
+        assert_eq!(interpreter.read_memory(1048576, 64).unwrap(), alphabet);
+        assert_eq!(interpreter.read_memory(1048640, 4).unwrap(), b"hCTf");
+        assert_eq!(call(&patched, "alphabet_at", 63), Value::I32(b'_' as i32));
+
+        // 跨段或超出段的末尾
+        for (address, len) in [(1048636, 8), (1048642, 4), (0, 1)] {
+            assert!(matches!(
+                patch_module(
+                    &bytes,
+                    &[Patch::WriteData {
+                        address,
+                        bytes: vec![0; len],
+                    }],
+                ),
+                Err(InspectError::InvalidPatch(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn patch_return_const_test() {
+        let bytes = module();
+        let patched = patch_module(
+            &bytes,
+            &[
+                Patch::ReturnConst {
+                    function: "verify".into(),
+                    values: vec![serde_json::json!(1)],
+                },
+                Patch::ReturnConst {
+                    function: "key_byte".into(),
+                    values: vec![serde_json::json!("0x58")],
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(call(&patched, "verify", 0), Value::I32(1));
+        assert_eq!(call(&patched, "key_byte", 7), Value::I32(0x58));
+        assert!(patched.len() < bytes.len());
+
+        let err = |patch: Patch| patch_module(&bytes, &[patch]).unwrap_err();
+        assert!(matches!(
+            err(Patch::ReturnConst {
+                function: "verify".into(),
+                values: vec![],
+            }),
+            InspectError::InvalidPatch(_)
+        ));
+        assert!(matches!(
+            err(Patch::ReturnConst {
+                function: "verify".into(),
+                values: vec![serde_json::json!(1.5)],
+            }),
+            InspectError::InvalidPatch(_)
+        ));
+        assert!(matches!(
+            err(Patch::ReturnConst {
+                function: "env.log".into(),
+                values: vec![],
+            }),
+            InspectError::ImportedFunction(_)
+        ));
+    }
+
+    #[test]
+    fn patch_exports_test() {
+        let bytes = module();
+        let patched = patch_module(
+            &bytes,
+            &[
+                Patch::RenameExport {
+                    name: "verify".into(),
+                    new_name: "check".into(),
+                },
+                Patch::AddExport {
+                    name: "key_byte".into(),
+                    function: "key_byte".into(),
+                },
+            ],
+        )
+        .unwrap();
+        let info = ModuleInfo::parse(&patched).unwrap();
+        let exports: Vec<_> = info.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(exports, ["memory", "check", "alphabet_at", "key_byte"]);
+        assert_eq!(call(&patched, "key_byte", 1), Value::I32(b'c' as i32));
+
+        // 没有导出 section 的模块
+        let bare = wat::parse_str(
+            r#"(module (func $f (result i32) (i32.const 7)) (data "x") (memory 1))"#,
+        )
+        .unwrap();
+        let patched = patch_module(
+            &bare,
+            &[Patch::AddExport {
+                name: "f".into(),
+                function: "0".into(),
+            }],
+        )
+        .unwrap();
+        assert_eq!(ModuleInfo::parse(&patched).unwrap().exports[0].name, "f");
+
+        // 重名的导出不能通过校验
+        assert!(matches!(
+            patch_module(
+                &bytes,
+                &[Patch::RenameExport {
+                    name: "verify".into(),
+                    new_name: "memory".into(),
+                }],
+            ),
+            Err(InspectError::PatchValidation { .. })
+        ));
+        assert!(matches!(
+            patch_module(
+                &bytes,
+                &[Patch::RenameExport {
+                    name: "missing".into(),
+                    new_name: "x".into(),
+                }],
+            ),
+            Err(InspectError::InvalidPatch(_))
+        ));
+    }
+
+    // 函数段引用了不存在的类型 5 ：打补丁前就应当报 InvalidWasm ，而不是按索引取类型时 panic
+    #[test]
+    fn patch_malformed_module_test() {
+        let bad_type = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic / version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type: [] -> []
+            0x03, 0x02, 0x01, 0x05, // function: type 5
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code
+        ];
+        let patches = [Patch::ReturnConst {
+            function: "0".into(),
+            values: vec![],
+        }];
+        assert!(matches!(
+            patch_module(&bad_type, &patches),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+
+        // 改坏任意一个字节只返回错误，不会 panic
+        let bytes = module();
+        let patches = [
+            Patch::ReturnConst {
+                function: "verify".into(),
+                values: vec![1.into()],
+            },
+            Patch::WriteData {
+                address: 1048640,
+                bytes: b"xxxx".to_vec(),
+            },
+        ];
+        for i in 0..bytes.len() {
+            for flip in [0x01, 0x80, 0xff] {
+                let mut mutated = bytes.clone();
+                mutated[i] ^= flip;
+                let _ = patch_module(&mutated, &patches);
+            }
+        }
+    }
+
+    #[test]
+    fn patch_wasm_json_test() {
+        let bytes = module();
+        let patched = patch_wasm(
+            &bytes,
+            r#"[
+                {"op": "write_data", "address": 1048640, "bytes": [120, 120, 120, 120]},
+                {"op": "return_const", "function": "verify", "values": [1]},
+                {"op": "rename_export", "name": "alphabet_at", "new_name": "at"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(call(&patched, "at", 0), Value::I32(b'A' as i32));
+        assert_eq!(call(&patched, "key_byte", 0), Value::I32(b'x' as i32));
+
+        let err = RustWasmError::from(patch_wasm(&bytes, r#"[{"op": "delete"}]"#).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert!(err.details.contains_key("patch"));
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasmparser::{BlockType, FunctionBody, MemArg, Operator, Parser, Payload};
+
+use super::disassembler::replace_function_names;
+use super::inspect_error::InspectError;
+use super::module_info::ModuleInfo;
+
+// 类似 IDA FLIRT 的库函数识别：从带名字的构建中给每个函数生成签名，再到去掉名字的构建中
+// 按签名找回 aes 、sha2 、base64 等库函数的名字。
+// - 签名是规范化后指令序列的哈希。与模块布局有关的立即数（常量、函数 / 全局变量 / 数据段索引、
+//   数据段地址）替换为 `?`，类型索引换成类型的签名；局部变量索引、跳转深度和结构体字段偏移保留
+// - Rust 符号哈希 `::h0123456789abcdef` 、crate 消歧义哈希和链接器给重名函数加的 `[n]` 每次编译
+//   都可能变化，生成签名时去掉
+// - 少于 MIN_INSTRUCTIONS 条指令的函数太常见，不生成签名；同一个哈希对应多个名字时不匹配
+
+const MIN_INSTRUCTIONS: usize = 8;
+// 大于这个值的内存访问偏移是数据段地址，否则是结构体字段偏移
+const MAX_FIELD_OFFSET: u64 = 0xffff;
+
+/// 一个函数的签名
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FunctionSignature {
+    pub name: String,
+    // 规范化后指令序列的 FNV-1a 哈希，16 位十六进制
+    pub hash: String,
+    // 指令条数（含函数末尾的 end），哈希相同时再比较一次
+    pub length: usize,
+}
+
+/// 按签名识别出的一个函数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignatureMatch {
+    pub function: u32,
+    // 识别前的名字（name section 中的名字、导出名）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_name: Option<String>,
+    pub name: String,
+    pub hash: String,
+}
+
+/// 给模块中有名字（name section 或导出名）且不太短的函数生成签名，按名字排序
+pub fn generate_signatures(bytes: &[u8]) -> Result<Vec<FunctionSignature>, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    let mut signatures = Vec::new();
+    for (function, body) in function_bodies(bytes, &info)? {
+        let Some(name) = info.function_name(function) else {
+            continue;
+        };
+        let (hash, length) = fingerprint(&info, function, &body)?;
+        if length >= MIN_INSTRUCTIONS {
+            signatures.push(FunctionSignature {
+                name: normalize_name(name),
+                hash,
+                length,
+            });
+        }
+    }
+    signatures.sort();
+    signatures.dedup();
+    Ok(signatures)
+}
+
+/// 用签名识别模块中的函数，按函数索引排序
+pub fn match_signatures(
+    bytes: &[u8],
+    signatures: &[FunctionSignature],
+) -> Result<Vec<SignatureMatch>, InspectError> {
+    // 哈希 -> 签名，同一哈希有多个名字时为 None
+    let mut table: HashMap<&str, Option<&FunctionSignature>> = HashMap::new();
+    for signature in signatures {
+        table
+            .entry(&signature.hash)
+            .and_modify(|entry| {
+                if entry.is_some_and(|s| s.name != signature.name || s.length != signature.length) {
+                    *entry = None;
+                }
+            })
+            .or_insert(Some(signature));
+    }
+
+    let info = ModuleInfo::parse(bytes)?;
+    let mut matches = Vec::new();
+    for (function, body) in function_bodies(bytes, &info)? {
+        let (hash, length) = fingerprint(&info, function, &body)?;
+        if let Some(Some(signature)) = table.get(hash.as_str())
+            && signature.length == length
+        {
+            matches.push(SignatureMatch {
+                function,
+                previous_name: info.function_name(function).map(str::to_string),
+                name: signature.name.clone(),
+                hash,
+            });
+        }
+    }
+    Ok(matches)
+}
+
+/// 识别模块中的函数并把名字写入 name section ，返回新的 .wasm
+pub fn apply_signatures(
+    bytes: &[u8],
+    signatures: &[FunctionSignature],
+) -> Result<Vec<u8>, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    let mut names: BTreeMap<u32, &str> = (0..info.functions.len() as u32)
+        .filter_map(|i| info.function_name(i).map(|name| (i, name)))
+        .collect();
+    let matches = match_signatures(bytes, signatures)?;
+    for m in &matches {
+        names.insert(m.function, &m.name);
+    }
+    let names: Vec<_> = names.into_iter().collect();
+    replace_function_names(bytes, &names)
+}
+
+/// 生成模块中函数的签名，以 JSON 数组返回，可以保存为签名文件
+#[wasm_bindgen]
+pub fn generate_wasm_signatures(bytes: &[u8]) -> Result<String, InspectError> {
+    Ok(serde_json::to_string(&generate_signatures(bytes)?).unwrap_or_default())
+}
+
+/// 用签名文件（generate_wasm_signatures 的输出）识别函数，以 JSON 数组返回匹配结果
+#[wasm_bindgen]
+pub fn match_wasm_signatures(bytes: &[u8], signatures_json: &str) -> Result<String, InspectError> {
+    let matches = match_signatures(bytes, &parse_signatures(signatures_json)?)?;
+    Ok(serde_json::to_string(&matches).unwrap_or_default())
+}
+
+/// 用签名文件识别函数，返回把识别出的名字写入 name section 后的 .wasm
+#[wasm_bindgen]
+pub fn apply_wasm_signatures(bytes: &[u8], signatures_json: &str) -> Result<Vec<u8>, InspectError> {
+    apply_signatures(bytes, &parse_signatures(signatures_json)?)
+}
+
+fn parse_signatures(json: &str) -> Result<Vec<FunctionSignature>, InspectError> {
+    serde_json::from_str(json)
+        .map_err(|e| InspectError::InvalidArguments(format!("signatures: {e}")))
+}
+
+// 模块中定义的函数：(函数索引, 函数体)
+fn function_bodies<'a>(
+    bytes: &'a [u8],
+    info: &ModuleInfo,
+) -> Result<Vec<(u32, FunctionBody<'a>)>, InspectError> {
+    let mut bodies = Vec::new();
+    let mut next = info.imported_function_count() as u32;
+    for payload in Parser::new(0).parse_all(bytes) {
+        if let Payload::CodeSectionEntry(body) = payload? {
+            bodies.push((next, body));
+            next += 1;
+        }
+    }
+    Ok(bodies)
+}
+
+// 去掉名字中每次编译都可能变化的部分，同一个函数在 debug 和 release 构建中得到相同的名字：
+// - Rust 符号哈希：`alloc::raw_vec::finish_grow::h0123456789abcdef` -> `alloc::raw_vec::finish_grow`，
+//   wasm-bindgen 生成的 `... externref shim` 等后缀之前的哈希也去掉
+// - v0 符号中 crate 的消歧义哈希：`core[c5930c85a12de822]::fmt::write` -> `core::fmt::write`
+// - 链接器给重名函数加的序号：`<String as Write>::write_str[3]` -> `<String as Write>::write_str`
+fn normalize_name(name: &str) -> String {
+    let is_hash = |s: &str| s.len() == 16 && s.bytes().all(|b| b.is_ascii_hexdigit());
+    let mut out = String::with_capacity(name.len());
+    let mut rest = name;
+    while !rest.is_empty() {
+        if let Some(hash) = rest.strip_prefix("::h").and_then(|r| r.get(..16))
+            && is_hash(hash)
+            && !rest[19..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        {
+            rest = &rest[19..];
+        } else if let Some(hash) = rest.strip_prefix('[').and_then(|r| r.get(..16))
+            && is_hash(hash)
+            && rest[17..].starts_with(']')
+        {
+            rest = &rest[18..];
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if let Some((base, index)) = out.strip_suffix(']').and_then(|s| s.rsplit_once('['))
+        && !base.is_empty()
+        && !index.is_empty()
+        && index.bytes().all(|b| b.is_ascii_digit())
+    {
+        out.truncate(base.len());
+    }
+    out
+}
+
+// 64 位 FNV-1a ，每个记号之后加一个换行分隔
+struct Fnv(u64);
+
+impl Fnv {
+    fn write(&mut self, token: &str) {
+        for b in token.bytes().chain([b'\n']) {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+// 函数签名（类型 + 局部变量 + 规范化的指令）的哈希和指令条数
+fn fingerprint(
+    info: &ModuleInfo,
+    function: u32,
+    body: &FunctionBody,
+) -> Result<(String, usize), InspectError> {
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    let type_index = info.functions[function as usize].type_index;
+    hasher.write(signature(info, type_index));
+    for pair in body.get_locals_reader()? {
+        let (count, ty) = pair?;
+        hasher.write(&format!("local {count} {ty}"));
+    }
+    let mut length = 0;
+    let mut reader = body.get_operators_reader()?;
+    while !reader.eof() {
+        hasher.write(&normalize(info, &reader.read()?));
+        length += 1;
+    }
+    Ok((format!("{:016x}", hasher.0), length))
+}
+
+fn signature(info: &ModuleInfo, type_index: u32) -> &str {
+    info.types
+        .get(type_index as usize)
+        .map_or("?", |t| t.signature.as_str())
+}
+
+// 指令名，如 `I32Const { value: 1 }` -> `I32Const`
+fn name(op: &Operator) -> String {
+    let text = format!("{op:?}");
+    text.split([' ', '{'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn block_type(info: &ModuleInfo, block_type: BlockType) -> String {
+    match block_type {
+        BlockType::Empty => String::new(),
+        BlockType::Type(ty) => ty.to_string(),
+        BlockType::FuncType(index) => signature(info, index).to_string(),
+    }
+}
+
+fn normalize(info: &ModuleInfo, op: &Operator) -> String {
+    match op {
+        Operator::I32Const { .. }
+        | Operator::I64Const { .. }
+        | Operator::F32Const { .. }
+        | Operator::F64Const { .. }
+        | Operator::Call { .. }
+        | Operator::ReturnCall { .. }
+        | Operator::RefFunc { .. }
+        | Operator::GlobalGet { .. }
+        | Operator::GlobalSet { .. }
+        | Operator::MemoryInit { .. }
+        | Operator::DataDrop { .. }
+        | Operator::TableInit { .. }
+        | Operator::ElemDrop { .. } => format!("{} ?", name(op)),
+        Operator::CallIndirect { type_index, .. }
+        | Operator::ReturnCallIndirect { type_index, .. } => {
+            format!("{} {}", name(op), signature(info, *type_index))
+        }
+        Operator::Block { blockty } | Operator::Loop { blockty } | Operator::If { blockty } => {
+            format!("{} {}", name(op), block_type(info, *blockty))
+        }
+        _ => match mem_arg(op) {
+            Some(memarg) if memarg.offset > MAX_FIELD_OFFSET => {
+                format!("{} {} ?", name(op), memarg.align)
+            }
+            _ => format!("{op:?}"),
+        },
+    }
+}
+
+fn mem_arg(op: &Operator) -> Option<MemArg> {
+    match op {
+        Operator::I32Load { memarg }
+        | Operator::I64Load { memarg }
+        | Operator::F32Load { memarg }
+        | Operator::F64Load { memarg }
+        | Operator::I32Load8S { memarg }
+        | Operator::I32Load8U { memarg }
+        | Operator::I32Load16S { memarg }
+        | Operator::I32Load16U { memarg }
+        | Operator::I64Load8S { memarg }
+        | Operator::I64Load8U { memarg }
+        | Operator::I64Load16S { memarg }
+        | Operator::I64Load16U { memarg }
+        | Operator::I64Load32S { memarg }
+        | Operator::I64Load32U { memarg }
+        | Operator::I32Store { memarg }
+        | Operator::I64Store { memarg }
+        | Operator::F32Store { memarg }
+        | Operator::F64Store { memarg }
+        | Operator::I32Store8 { memarg }
+        | Operator::I32Store16 { memarg }
+        | Operator::I64Store8 { memarg }
+        | Operator::I64Store16 { memarg }
+        | Operator::I64Store32 { memarg } => Some(*memarg),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 同一份库代码的两次构建：带名字的 debug 构建，以及去掉名字、多一个导入和全局变量、
+    // 函数顺序和数据段地址都不同的 release 构建。sha256 的轮常量也不同，常量被屏蔽后仍能匹配
+    fn build(release: bool) -> Vec<u8> {
+        let (import, global, base, k) = if release {
+            (
+                r#"(import "env" "abort" (func $abort))"#,
+                "(global $heap_base i32 (i32.const 0))",
+                1049000,
+                0x71374491,
+            )
+        } else {
+            ("", "", 1048576, 0x428a2f98)
+        };
+        let encode = format!(
+            r#"(func $base64::encode::h0123456789abcdef (param i32 i32 i32) (local i32)
+                (block
+                    (br_if 0 (i32.eqz (local.get 1)))
+                    (loop
+                        (i32.store8 (i32.add (local.get 2) (local.get 3))
+                            (i32.load8_u offset={base}
+                                (i32.and (i32.load8_u (i32.add (local.get 0) (local.get 3))) (i32.const 63))))
+                        (br_if 0 (i32.ne (local.tee 3 (i32.add (local.get 3) (i32.const 1))) (local.get 1))))))"#
+        );
+        let compress = format!(
+            r#"(func $sha2::sha256::compress::hfedcba9876543210 (param i32) (result i32)
+                (call $sha2::sha256::round::h00000000deadbeef
+                    (i32.load offset=4 (local.get 0))
+                    (i32.add (i32.load (local.get 0)) (i32.const {k}))))"#
+        );
+        let round = r#"(func $sha2::sha256::round::h00000000deadbeef (param i32 i32) (result i32)
+                (i32.xor
+                    (i32.xor (i32.rotr (local.get 0) (i32.const 6)) (i32.rotr (local.get 0) (i32.const 11)))
+                    (i32.add (i32.rotr (local.get 0) (i32.const 25)) (local.get 1))))"#;
+        // 代码完全相同的两个泛型实例
+        let twins = r#"(func $core::fmt::write_u8::h1111111111111111 (param i32) (result i32)
+                (i32.add (i32.mul (local.get 0) (local.get 0)) (i32.shl (local.get 0) (i32.const 1))))
+            (func $core::fmt::write_i8::h2222222222222222 (param i32) (result i32)
+                (i32.add (i32.mul (local.get 0) (local.get 0)) (i32.shl (local.get 0) (i32.const 1))))"#;
+        let tiny = r#"(func $core::ptr::drop_in_place::h3333333333333333 (param i32))"#;
+        let main = if release {
+            r#"(func (export "encrypt_username") (param i32 i32 i32)
+                (call $base64::encode::h0123456789abcdef (local.get 0) (local.get 1) (local.get 2))
+                (call $abort))"#
+        } else {
+            r#"(func (export "encrypt_username") (param i32 i32 i32)
+                (call $base64::encode::h0123456789abcdef (local.get 0) (local.get 1) (local.get 2)))"#
+        };
+        let functions = if release {
+            [compress.as_str(), main, twins, round, tiny, &encode].join("\n")
+        } else {
+            [encode.as_str(), round, &compress, twins, tiny, main].join("\n")
+        };
+        let wat = format!(
+            r#"(module {import} (memory 17) {global}
+                (global $__stack_pointer (mut i32) (i32.const {base}))
+                {functions})"#
+        );
+        let bytes = wat::parse_str(wat).unwrap();
+        if release {
+            // wat 总会为 $名字 生成 name section ，release 构建把函数名去掉
+            replace_function_names(&bytes, &[]).unwrap()
+        } else {
+            bytes
+        }
+    }
+
+    fn names(signatures: &[FunctionSignature]) -> Vec<&str> {
+        signatures.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn generate_signatures_test() {
+        let signatures = generate_signatures(&build(false)).unwrap();
+        // 去掉了 Rust 符号哈希，drop_in_place 和只有一个调用的 encrypt_username 太短没有签名
+        assert_eq!(
+            names(&signatures),
+            [
+                "base64::encode",
+                "core::fmt::write_i8",
+                "core::fmt::write_u8",
+                "sha2::sha256::compress",
+                "sha2::sha256::round",
+            ]
+        );
+        assert!(signatures.iter().all(|s| s.hash.len() == 16));
+        assert_eq!(signatures[1].hash, signatures[2].hash);
+        assert_ne!(signatures[0].hash, signatures[4].hash);
+        assert_eq!(normalize_name("main"), "main");
+        assert_eq!(normalize_name("a::hxyz"), "a::hxyz");
+        assert_eq!(
+            normalize_name("a::h0123456789abcdefg"),
+            "a::h0123456789abcdefg"
+        );
+        assert_eq!(
+            normalize_name("js_sys::Object::new::h0123456789abcdef externref shim"),
+            "js_sys::Object::new externref shim"
+        );
+        assert_eq!(
+            normalize_name(
+                "<alloc[3ca501edff3f0c7c]::string::String as core[c5930c85a12de822]::fmt::Write>::write_str[3]"
+            ),
+            "<alloc::string::String as core::fmt::Write>::write_str"
+        );
+        assert_eq!(normalize_name("<[f64]>::sort[12]"), "<[f64]>::sort");
+        assert_eq!(normalize_name("[3]"), "[3]");
+    }
+
+    #[test]
+    fn match_signatures_test() {
+        let debug = build(false);
+        let release = build(true);
+        assert!(
+            ModuleInfo::parse(&release)
+                .unwrap()
+                .functions
+                .iter()
+                .all(|f| f.name.is_none())
+        );
+
+        let signatures = generate_signatures(&debug).unwrap();
+        let matches = match_signatures(&release, &signatures).unwrap();
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.function, m.name.as_str()))
+            .collect();
+        // 导入的 abort 占用索引 0 ；两个完全相同的函数无法区分
+        assert_eq!(
+            found,
+            [
+                (1, "sha2::sha256::compress"),
+                (5, "sha2::sha256::round"),
+                (7, "base64::encode"),
+            ]
+        );
+        assert!(matches.iter().all(|m| m.previous_name.is_none()));
+
+        // 签名文件来自同一个构建时，原有的名字带着 Rust 符号哈希
+        let matches = match_signatures(&debug, &signatures).unwrap();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(
+            matches[0].previous_name.as_deref(),
+            Some("base64::encode::h0123456789abcdef")
+        );
+    }
+
+    #[test]
+    fn apply_wasm_signatures_test() {
+        let json = generate_wasm_signatures(&build(false)).unwrap();
+        let renamed = apply_wasm_signatures(&build(true), &json).unwrap();
+        let info = ModuleInfo::parse(&renamed).unwrap();
+        assert_eq!(info.function_name(1), Some("sha2::sha256::compress"));
+        assert_eq!(info.function_name(7), Some("base64::encode"));
+        assert_eq!(info.function_name(2), Some("encrypt_username"));
+        assert_eq!(info.function_name(3), None);
+        wasmparser::Validator::new().validate_all(&renamed).unwrap();
+
+        let matches: serde_json::Value =
+            serde_json::from_str(&match_wasm_signatures(&build(true), &json).unwrap()).unwrap();
+        assert_eq!(matches[0]["name"], "sha2::sha256::compress");
+        assert!(matches[0].get("previous_name").is_none());
+
+        assert!(matches!(
+            match_wasm_signatures(&build(true), "{}"),
+            Err(InspectError::InvalidArguments(_))
+        ));
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasmparser::{Operator, Parser, Payload};
+
+use super::inspect_error::InspectError;
+use super::module_info::ModuleInfo;
+
+// 从数据段中提取可打印字符串，并找出在代码中用 i32.const 加载其地址的函数。
+// - Rust 的字符串字面量在 .rodata 中首尾相接，没有 '\0' 分隔，"hans7" 和 "hans7_sha_bytes"
+//   会连成一串。代码中引用的地址就是字符串的开头，按落在串中间的引用地址把它切开
+// - UTF-16LE 只报告至少一半是 ASCII 字符的串，否则任意两个字节都可能被当成汉字
+
+/// 数据段中的一个字符串
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WasmString {
+    pub text: String,
+    // ascii 、utf-8 或 utf-16le
+    pub encoding: &'static str,
+    // 所在数据段的索引
+    pub segment: u32,
+    // 文件偏移和占用的字节数
+    pub file_offset: usize,
+    pub size: usize,
+    // 主动数据段的偏移是常量时，加载到线性内存后的地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<u64>,
+    pub xrefs: Vec<StringXref>,
+}
+
+/// 加载字符串地址的一条 i32.const 指令
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StringXref {
+    pub function: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_name: Option<String>,
+    // 指令的文件偏移
+    pub offset: usize,
+}
+
+/// 提取数据段中至少 `min_len` 个字符的字符串，按文件偏移排序
+pub fn extract_strings(bytes: &[u8], min_len: usize) -> Result<Vec<WasmString>, InspectError> {
+    let info = ModuleInfo::parse(bytes)?;
+    let min_len = min_len.max(1);
+    let refs = address_refs(bytes, &info)?;
+
+    let mut strings = Vec::new();
+    for data in &info.data {
+        let content = &bytes[data.file_offset..data.file_offset + data.size];
+        let mut push = |start: usize, end: usize, text: String, encoding| {
+            let address = data.offset.map(|base| base + start as u64);
+            let xrefs = address
+                .and_then(|address| refs.get(&address))
+                .cloned()
+                .unwrap_or_default();
+            strings.push(WasmString {
+                text,
+                encoding,
+                segment: data.index,
+                file_offset: data.file_offset + start,
+                size: end - start,
+                address,
+                xrefs,
+            });
+        };
+
+        for (start, end) in utf8_runs(content) {
+            let run = std::str::from_utf8(&content[start..end]).unwrap_or_default();
+            // 串中间被引用的地址是另一个字符串的开头
+            let mut splits = vec![0];
+            if let Some(base) = data.offset {
+                let inner = base + start as u64 + 1..base + end as u64;
+                splits.extend(
+                    refs.range(inner)
+                        .map(|(&address, _)| (address - base) as usize - start)
+                        .filter(|&split| run.is_char_boundary(split)),
+                );
+            }
+            splits.push(run.len());
+            for piece in splits.windows(2) {
+                let text = &run[piece[0]..piece[1]];
+                if text.chars().count() >= min_len {
+                    let encoding = if text.is_ascii() { "ascii" } else { "utf-8" };
+                    push(
+                        start + piece[0],
+                        start + piece[1],
+                        text.to_string(),
+                        encoding,
+                    );
+                }
+            }
+        }
+
+        for (start, end) in utf16_runs(content, min_len) {
+            let units: Vec<u16> = content[start..end]
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            push(start, end, String::from_utf16_lossy(&units), "utf-16le");
+        }
+    }
+    strings.sort_by_key(|s| s.file_offset);
+    Ok(strings)
+}
+
+/// 提取数据段中的字符串及其交叉引用，以 JSON 数组返回
+#[wasm_bindgen]
+pub fn extract_wasm_strings(bytes: &[u8], min_len: usize) -> Result<String, InspectError> {
+    Ok(serde_json::to_string(&extract_strings(bytes, min_len)?).unwrap_or_default())
+}
+
+// 代码中落在主动数据段地址范围内的 i32.const 值 -> 引用它的指令
+fn address_refs(
+    bytes: &[u8],
+    info: &ModuleInfo,
+) -> Result<BTreeMap<u64, Vec<StringXref>>, InspectError> {
+    let ranges: Vec<_> = info
+        .data
+        .iter()
+        .filter_map(|d| d.offset.map(|base| base..base + d.size as u64))
+        .collect();
+    let mut refs: BTreeMap<u64, Vec<StringXref>> = BTreeMap::new();
+    if ranges.is_empty() {
+        return Ok(refs);
+    }
+
+    let mut function = info.imported_function_count() as u32;
+    for payload in Parser::new(0).parse_all(bytes) {
+        let Payload::CodeSectionEntry(body) = payload? else {
+            continue;
+        };
+        let mut reader = body.get_operators_reader()?;
+        while !reader.eof() {
+            let offset = reader.original_position();
+            let Operator::I32Const { value } = reader.read()? else {
+                continue;
+            };
+            let address = value as u32 as u64;
+            if ranges.iter().any(|range| range.contains(&address)) {
+                refs.entry(address).or_default().push(StringXref {
+                    function,
+                    function_name: info.function_name(function).map(str::to_string),
+                    offset,
+                });
+            }
+        }
+        function += 1;
+    }
+    Ok(refs)
+}
+
+// 由可打印字符组成的 UTF-8 片段，返回字节范围
+fn utf8_runs(data: &[u8]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+    while i < data.len() {
+        match printable_utf8_len(&data[i..]) {
+            0 => {
+                if let Some(start) = start.take() {
+                    runs.push((start, i));
+                }
+                i += 1;
+            }
+            len => {
+                start.get_or_insert(i);
+                i += len;
+            }
+        }
+    }
+    if let Some(start) = start {
+        runs.push((start, data.len()));
+    }
+    runs
+}
+
+// 开头一个可打印字符的 UTF-8 字节数，不是可打印字符时返回 0
+fn printable_utf8_len(data: &[u8]) -> usize {
+    let width = match data[0] {
+        0x20..=0x7e | b'\t' | b'\n' | b'\r' => return 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return 0,
+    };
+    match data.get(..width).map(std::str::from_utf8) {
+        Some(Ok(c)) if !c.chars().any(char::is_control) => width,
+        _ => 0,
+    }
+}
+
+// UTF-16LE 片段，字符串可能从奇数偏移开始，两种对齐都要找
+fn utf16_runs(data: &[u8], min_len: usize) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    for align in 0..2 {
+        let mut start = None;
+        let mut ascii = 0;
+        let mut i = align;
+        loop {
+            let unit = data.get(i..i + 2).map(|u| u16::from_le_bytes([u[0], u[1]]));
+            if let Some(unit) = unit.filter(|&u| printable_utf16(u)) {
+                start.get_or_insert(i);
+                if unit < 0x80 {
+                    ascii += 1;
+                }
+            } else if let Some(start) = start.take() {
+                let units = (i - start) / 2;
+                if units >= min_len && ascii * 2 >= units {
+                    runs.push((start, i));
+                }
+                ascii = 0;
+            }
+            if unit.is_none() {
+                break;
+            }
+            i += 2;
+        }
+    }
+    runs
+}
+
+// ASCII 可打印字符、CJK 统一汉字、中文标点和全角字符
+fn printable_utf16(unit: u16) -> bool {
+    matches!(unit, 0x20..=0x7e | 0x09 | 0x0a | 0x0d | 0x3000..=0x303f | 0x4e00..=0x9fff | 0xff00..=0xffef)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 模仿 rustc 的 .rodata ：字符串首尾相接，代码用 i32.const 加载地址
+    const SAMPLE_WAT: &str = r#"
+        (module
+            (memory 17)
+            (data (i32.const 1048576) "hans7hans7_sha_bytesinvalid key\00\01\02")
+            (data (i32.const 2048) "\ff\00h\00e\00l\00l\00o\00\00\00")
+            (data (i32.const 4096) "\01\e5\af\86\e9\92\a5\e9\94\99\e8\af\af\00")
+            (data "passive secret")
+            (func (export "get_str_sha256") (result i32) (i32.const 1048576))
+            (func (export "get_bytes_sha256") (result i32)
+                (i32.add (i32.const 1048581) (i32.const 15)))
+            (func (result i32) (i32.const 1048596)))
+    "#;
+
+    fn sample() -> Vec<u8> {
+        wat::parse_str(SAMPLE_WAT).unwrap()
+    }
+
+    fn texts(strings: &[WasmString]) -> Vec<&str> {
+        strings.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn extract_strings_split_by_xrefs_test() {
+        let bytes = sample();
+        let strings = extract_strings(&bytes, 4).unwrap();
+        assert_eq!(
+            texts(&strings),
+            [
+                "hans7",
+                "hans7_sha_bytes",
+                "invalid key",
+                "hello",
+                "密钥错误",
+                "passive secret"
+            ]
+        );
+
+        let salt = &strings[0];
+        assert_eq!(salt.encoding, "ascii");
+        assert_eq!(salt.address, Some(1048576));
+        assert_eq!(salt.size, 5);
+        assert_eq!(&bytes[salt.file_offset..salt.file_offset + 5], b"hans7");
+        assert_eq!(salt.xrefs.len(), 1);
+        assert_eq!(salt.xrefs[0].function, 0);
+        assert_eq!(
+            salt.xrefs[0].function_name.as_deref(),
+            Some("get_str_sha256")
+        );
+
+        let bytes_salt = &strings[1];
+        assert_eq!(bytes_salt.address, Some(1048581));
+        assert_eq!(
+            bytes_salt.xrefs[0].function_name.as_deref(),
+            Some("get_bytes_sha256")
+        );
+        // 第三个函数没有名字
+        assert_eq!(strings[2].xrefs[0].function, 2);
+        assert_eq!(strings[2].xrefs[0].function_name, None);
+    }
+
+    #[test]
+    fn extract_strings_encodings_test() {
+        let strings = extract_strings(&sample(), 4).unwrap();
+        let utf16 = &strings[3];
+        assert_eq!(utf16.encoding, "utf-16le");
+        assert_eq!(utf16.address, Some(2050));
+        assert_eq!(utf16.size, 10);
+        assert!(utf16.xrefs.is_empty());
+        let chinese = &strings[4];
+        assert_eq!(chinese.encoding, "utf-8");
+        assert_eq!((chinese.address, chinese.size), (Some(4097), 12));
+        // 被动数据段没有地址
+        assert_eq!(strings[5].segment, 3);
+        assert_eq!(strings[5].address, None);
+    }
+
+    #[test]
+    fn extract_strings_min_len_test() {
+        let bytes = sample();
+        assert_eq!(
+            texts(&extract_strings(&bytes, 6).unwrap()),
+            ["hans7_sha_bytes", "invalid key", "passive secret"]
+        );
+        // 0 按 1 处理
+        assert_eq!(
+            extract_strings(&bytes, 0).unwrap(),
+            extract_strings(&bytes, 1).unwrap()
+        );
+
+        let json = extract_wasm_strings(&bytes, 4).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["text"], "hans7");
+        assert_eq!(parsed[0]["xrefs"][0]["function_name"], "get_str_sha256");
+        assert!(parsed[5].get("address").is_none());
+        assert!(matches!(
+            extract_wasm_strings(b"\0asm\x01\0\0\0\x0b", 4),
+            Err(InspectError::InvalidWasm { .. })
+        ));
+    }
+}
@@ -1,14 +1,26 @@
 use base64::prelude::*;
+use rust_wasm_macros::sealed;
 use wasm_bindgen::prelude::*;
 
-fn string_xor(bytes: &[u8]) -> Vec<u8> {
-    let s_box = b"hctf";
-    let encrypted = bytes
+use crate::secret::{Sealed, secret_into_string};
+use crate::xor_demo_error::XorError;
+
+pub static XOR_KEY: Sealed = sealed!(b"hctf");
+
+// 循环使用 key 的每个字节做异或，key 为空时原样返回
+pub(crate) fn xor_with_key(bytes: &[u8], key: &[u8]) -> Vec<u8> {
+    if key.is_empty() {
+        return bytes.to_vec();
+    }
+    bytes
         .iter()
         .enumerate()
-        .map(|(i, &b)| b ^ s_box[i % s_box.len()])
-        .collect();
-    encrypted
+        .map(|(i, &b)| b ^ key[i % key.len()])
+        .collect()
+}
+
+fn string_xor(bytes: &[u8]) -> Vec<u8> {
+    xor_with_key(bytes, &XOR_KEY.reveal())
 }
 
 #[wasm_bindgen]
@@ -22,17 +34,17 @@ pub fn encrypt_username(name: &str) -> String {
 }
 
 #[wasm_bindgen]
-pub fn decrypt_to_username(encrypted: &str) -> Result<String, String> {
-    let name_bytes = BASE64_STANDARD
-        .decode(encrypted.trim())
-        .map_err(|e| format!("[rust-wasm] Invalid Base64: {}", e))?;
+pub fn decrypt_to_username(encrypted: &str) -> Result<String, XorError> {
+    let name_bytes = BASE64_STANDARD.decode(encrypted.trim())?;
     let decrypted = string_xor(&name_bytes);
-    String::from_utf8(decrypted).map_err(|e| format!("[rust-wasm] Invalid UTF-8: {}", e))
+    Ok(secret_into_string(decrypted)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{ErrorCode, RustWasmError};
+    use proptest::prelude::*;
 
     #[test]
     fn encrypt_username_basic_test() {
@@ -71,7 +83,7 @@ mod tests {
     }
 
     #[test]
-    fn decrypt_to_username_basic_test() -> Result<(), String> {
+    fn decrypt_to_username_basic_test() -> Result<(), XorError> {
         let result1 = decrypt_to_username("AAIaFV8=")?;
         assert_eq!(result1, "hans7");
         let result2 = decrypt_to_username("ICI6NQ==")?;
@@ -89,7 +101,7 @@ mod tests {
     }
 
     #[test]
-    fn decrypt_to_username_empty_input_test() -> Result<(), String> {
+    fn decrypt_to_username_empty_input_test() -> Result<(), XorError> {
         let result = decrypt_to_username("")?;
         assert_eq!(result, "");
 
@@ -97,7 +109,7 @@ mod tests {
     }
 
     #[test]
-    fn decrypt_to_username_equal_to_box_test() -> Result<(), String> {
+    fn decrypt_to_username_equal_to_box_test() -> Result<(), XorError> {
         let result = decrypt_to_username("AAAAAA==")?;
         assert_eq!(result, "hctf");
 
@@ -105,7 +117,7 @@ mod tests {
     }
 
     #[test]
-    fn decrypt_to_username_with_spaces_test() -> Result<(), String> {
+    fn decrypt_to_username_with_spaces_test() -> Result<(), XorError> {
         let result1 = decrypt_to_username("\t  KQAZOSRTAgMa  \n \t  ")?;
         assert_eq!(result1, "Acm_L0ver");
         let result2 = decrypt_to_username("   AAAAAA==\n\t   ")?;
@@ -118,16 +130,36 @@ mod tests {
     fn decrypt_to_username_invalid_base64_test() {
         let result = decrypt_to_username("AAAAAAA==invalid");
         assert!(result.is_err());
-        let err = result.err().unwrap();
-        assert!(err.contains("[rust-wasm] Invalid Base64"));
+        let err = RustWasmError::from(result.err().unwrap());
+        assert_eq!(err.code, ErrorCode::InvalidBase64);
+        assert_eq!(err.module, "xor_demo");
+        assert!(err.message.contains("Invalid Base64"));
     }
 
     #[test]
     fn decrypt_to_username_invalid_utf8_test() {
-        let corrupted = BASE64_STANDARD.encode(&[0xFF, 0xFF, 0xFF]);
+        let corrupted = BASE64_STANDARD.encode([0xFF, 0xFF, 0xFF]);
         let result = decrypt_to_username(&corrupted);
         assert!(result.is_err());
-        let err = result.err().unwrap();
-        assert!(err.contains("[rust-wasm] Invalid UTF-8"));
+        let err = RustWasmError::from(result.err().unwrap());
+        assert_eq!(err.code, ErrorCode::InvalidUtf8);
+        assert!(err.message.contains("Invalid UTF-8"));
+    }
+
+    proptest! {
+        #[test]
+        fn xor_with_key_is_involution_prop(data: Vec<u8>, key: Vec<u8>) {
+            prop_assert_eq!(xor_with_key(&xor_with_key(&data, &key), &key), data);
+        }
+
+        #[test]
+        fn username_roundtrip_prop(name in "\\PC*") {
+            prop_assert_eq!(decrypt_to_username(&encrypt_username(&name)).unwrap(), name.trim());
+        }
+
+        #[test]
+        fn decrypt_to_username_never_panics_prop(encrypted in "\\PC*") {
+            let _ = decrypt_to_username(&encrypted);
+        }
     }
 }
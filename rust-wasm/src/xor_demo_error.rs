@@ -0,0 +1,31 @@
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ErrorCode, RustWasmError, base64_decode_error};
+
+#[derive(Debug, Error)]
+pub enum XorError {
+    #[error("Invalid Base64: {0}")]
+    InvalidBase64(#[from] base64::DecodeError),
+    #[error("Invalid UTF-8: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+}
+
+impl From<XorError> for RustWasmError {
+    fn from(e: XorError) -> Self {
+        let message = e.to_string();
+        match e {
+            XorError::InvalidBase64(inner) => base64_decode_error(inner, "xor_demo", message),
+            XorError::InvalidUtf8(inner) => {
+                RustWasmError::new(ErrorCode::InvalidUtf8, "xor_demo", message)
+                    .with_detail("valid_up_to", inner.valid_up_to())
+            }
+        }
+    }
+}
+
+impl From<XorError> for JsValue {
+    fn from(e: XorError) -> Self {
+        RustWasmError::from(e).into()
+    }
+}
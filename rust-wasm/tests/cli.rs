@@ -0,0 +1,130 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use rust_wasm::{
+    aes_cbc_encrypt, encode_custom_base64, encrypt_username, get_bytes_sha256, get_str_sha512,
+};
+
+fn cli(args: &[&str], stdin: &[u8]) -> (bool, Vec<u8>, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust-wasm-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn rust-wasm-cli");
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    let out = child.wait_with_output().unwrap();
+    (
+        out.status.success(),
+        out.stdout,
+        String::from_utf8_lossy(&out.stderr).into_owned(),
+    )
+}
+
+fn cli_text(args: &[&str], stdin: &str) -> String {
+    let (ok, stdout, stderr) = cli(args, stdin.as_bytes());
+    assert!(ok, "rust-wasm-cli {:?} failed: {}", args, stderr);
+    String::from_utf8(stdout)
+        .unwrap()
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+#[test]
+fn cli_matches_wasm_exports_test() -> anyhow::Result<()> {
+    for name in ["hans7", "  爱拼才会赢 \t", "Acm_L0ver"] {
+        assert_eq!(cli_text(&["xor", "encrypt"], name), encrypt_username(name));
+    }
+    assert_eq!(
+        cli_text(&["xor", "decrypt", "--text", "AAIaFV8="], ""),
+        "hans7"
+    );
+
+    let (key, iv) = ("1234567890123456", "abcdef9876543210");
+    let aes = cli_text(&["aes", "encrypt", "--key", key, "--iv", iv], "有空 格\n");
+    assert_eq!(
+        aes,
+        aes_cbc_encrypt("有空 格", key.as_bytes(), iv.as_bytes())?
+    );
+    let plain = cli_text(&["aes", "decrypt", "--key", key, "--iv", iv], &aes);
+    assert_eq!(plain, "有空 格");
+
+    assert_eq!(
+        cli_text(
+            &["sha", "--algorithm", "sha512", "--default-salt"],
+            "hello world\n"
+        ),
+        get_str_sha512("hello world")?
+    );
+    let (ok, stdout, _) = cli(&["sha", "--binary", "--default-salt"], b"hello world");
+    assert!(ok);
+    assert_eq!(
+        String::from_utf8(stdout)?.trim_end(),
+        get_bytes_sha256(b"hello world")
+    );
+
+    assert_eq!(
+        cli_text(
+            &["base64", "encode", "--hans7", "--text", "爱拼才会赢💪"],
+            ""
+        ),
+        encode_custom_base64("爱拼才会赢💪")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cli_output_formats_test() {
+    let args = ["sha", "--text", "hello world", "--truncate", "16"];
+    assert_eq!(cli_text(&args, ""), "b94d27b9934d3e08");
+
+    let (ok, raw, _) = cli(&["sha", "--output-format", "raw"], b"hello world");
+    assert!(ok);
+    assert_eq!(raw.len(), 32);
+    // raw 输出时 --truncate 按字节计数
+    let args = ["sha", "--output-format", "raw", "--truncate", "5"];
+    let (ok, raw, _) = cli(&args, b"hello world");
+    assert!(ok);
+    assert_eq!(raw, [0xb9, 0x4d, 0x27, 0xb9, 0x93]);
+
+    let hex = cli_text(&["xor", "encrypt", "--output-format", "hex"], "hctf");
+    assert_eq!(hex, "00000000");
+    let back = cli_text(&["xor", "decrypt", "--input-format", "hex"], &hex);
+    assert_eq!(back, "hctf");
+}
+
+#[test]
+fn cli_binary_roundtrip_test() {
+    let data = [0x00u8, 0xFF, 0x0A, 0x20, 0x80];
+    let (ok, encoded, _) = cli(&["base64", "encode", "--binary"], &data);
+    assert!(ok);
+    let (ok, decoded, _) = cli(&["base64", "decode"], &encoded);
+    assert!(ok);
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn cli_recipe_test() {
+    let recipe = r#"{"steps": [{"op": "xor"}, {"op": "base64"}]}"#;
+    assert_eq!(
+        cli_text(&["recipe", "encrypt", recipe, "--text", "hans7"], ""),
+        encrypt_username("hans7")
+    );
+    assert_eq!(
+        cli_text(&["recipe", "decrypt", recipe], "AAIaFV8="),
+        "hans7"
+    );
+}
+
+#[test]
+fn cli_reports_errors_test() {
+    let (ok, _, stderr) = cli(&["aes", "encrypt", "--key", "short", "--iv", "x"], b"hi");
+    assert!(!ok);
+    assert!(stderr.contains("invalid key length"));
+
+    let (ok, _, stderr) = cli(&["hash", "md5"], b"hi");
+    assert!(!ok);
+    assert!(stderr.contains("unknown hasher: md5"));
+}
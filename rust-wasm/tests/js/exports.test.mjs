@@ -0,0 +1,236 @@
+// wasm-pack --target nodejs 产物的 JS 边界测试：直接调用生成的胶水代码，检查
+// 错误对象的 name / code / module / details 、Uint8Array 的传入和返回，以及每个导出函数都被覆盖。
+//
+// 构建：wasm-pack build --release --target nodejs --out-dir target/node-test
+// 运行：node --test tests/js/
+// 产物目录可以用环境变量 RUST_WASM_PKG 指定，默认 target/node-test
+
+import assert from 'node:assert/strict';
+import { createHash } from 'node:crypto';
+import { createRequire } from 'node:module';
+import path from 'node:path';
+import { test } from 'node:test';
+import { fileURLToPath } from 'node:url';
+
+const crateDir = path.resolve(path.dirname(fileURLToPath(import.meta.url)), '../..');
+const pkgDir = path.resolve(crateDir, process.env.RUST_WASM_PKG ?? 'target/node-test');
+const exports = createRequire(import.meta.url)(path.join(pkgDir, 'rust_wasm.js'));
+
+// 记录测试中用到的导出，最后一个用例据此检查没有漏测的导出函数
+const used = new Set();
+const wasm = new Proxy(exports, {
+  get(target, name) {
+    used.add(name);
+    return target[name];
+  },
+});
+
+const bytes = (s) => new TextEncoder().encode(s);
+const KEY = bytes('1234567890123456');
+const IV = bytes('abcdef9876543210');
+// 这几个常量在 wasm 里是 sealed! 加密的，JS 侧拿不到，取值见 README
+const STR_SALT = 'hans7';
+const BYTES_SALT = 'hans7_sha_bytes';
+const XOR_KEY = bytes('hctf');
+const ALPHABET = 'ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/';
+const HELLO_SHA256 = 'b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9';
+
+// 检查抛出的错误，返回 details 供进一步检查
+function assertThrows(fn, code, module) {
+  let details;
+  assert.throws(fn, (err) => {
+    assert.ok(err instanceof Error, 'thrown value should be an Error instance');
+    assert.equal(err.name, 'RustWasmError');
+    assert.equal(err.code, code);
+    assert.equal(err.module, module);
+    assert.equal(typeof err.details, 'object');
+    assert.notEqual(err.details, null);
+    details = err.details;
+    return true;
+  });
+  return details;
+}
+
+function assertBytes(actual, expected) {
+  assert.ok(actual instanceof Uint8Array, 'expected a Uint8Array');
+  assert.deepEqual([...actual], [...expected]);
+}
+
+test('xor exports', () => {
+  assert.equal(wasm.encrypt_username('hans7'), 'AAIaFV8=');
+  assert.equal(wasm.decrypt_to_username('AAIaFV8='), 'hans7');
+
+  const details = assertThrows(() => wasm.decrypt_to_username('!!!'), 'INVALID_BASE64', 'xor_demo');
+  assert.equal(details.reason, 'invalid_byte');
+  assert.equal(details.offset, 0);
+  // 0x97 ^ 'h' = 0xFF ，不是合法 UTF-8
+  assertThrows(() => wasm.decrypt_to_username('lw=='), 'INVALID_UTF8', 'xor_demo');
+});
+
+test('aes_cbc exports', () => {
+  const cipher = wasm.aes_cbc_encrypt('有空 格', KEY, IV);
+  assert.equal(wasm.aes_cbc_decrypt(cipher, KEY, IV), '有空 格');
+
+  const details = assertThrows(() => wasm.aes_cbc_encrypt('hi', bytes('short'), IV), 'BAD_KEY_LEN', 'aes_cbc');
+  assert.equal(details.expected, 16);
+  assert.equal(details.actual, 5);
+  assertThrows(() => wasm.aes_cbc_decrypt(cipher, KEY, bytes('bad iv')), 'BAD_IV_LEN', 'aes_cbc');
+  assertThrows(() => wasm.aes_cbc_decrypt('not base64!', KEY, IV), 'INVALID_BASE64', 'aes_cbc');
+});
+
+test('custom_base64 exports', () => {
+  const encoded = wasm.encode_custom_base64('爱拼才会赢💪');
+  assert.equal(wasm.decode_custom_base64(encoded), '爱拼才会赢💪');
+  assert.equal(wasm.encode_base64_with_alphabet('hello', ALPHABET), wasm.encode_custom_base64('hello'));
+  assert.equal(wasm.decode_base64_with_alphabet(encoded, ALPHABET), '爱拼才会赢💪');
+  // 标准码表时与 Buffer 的结果一致
+  const standard = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+  assert.equal(
+    wasm.encode_base64_with_alphabet('有空 格', standard),
+    Buffer.from('有空 格').toString('base64'),
+  );
+
+  assertThrows(() => wasm.encode_custom_base64(''), 'EMPTY_INPUT', 'custom_base64');
+  assertThrows(() => wasm.encode_base64_with_alphabet('x', 'ABC'), 'INVALID_ALPHABET', 'custom_base64');
+  assertThrows(() => wasm.decode_base64_with_alphabet('@@@@', ALPHABET), 'INVALID_BASE64', 'custom_base64');
+  assertThrows(() => wasm.decode_custom_base64('@@@@'), 'INVALID_BASE64', 'custom_base64');
+});
+
+test('str sha exports', () => {
+  assert.equal(wasm.get_str_sha256_pure('hello world'), HELLO_SHA256);
+  assert.equal(wasm.get_str_sha512_pure('hello world').length, 128);
+  assert.equal(
+    wasm.get_str_sha256('hello world'),
+    '2379e070457dd223d988bef6ae2c199780a0b3da25aba546980c9ab181ea259c',
+  );
+  assert.equal(wasm.get_str_sha256_with_salt('hello world', STR_SALT), wasm.get_str_sha256('hello world'));
+  assert.equal(wasm.get_str_sha512_with_salt('hello world', STR_SALT), wasm.get_str_sha512('hello world'));
+  assert.equal(wasm.get_str_sha_formatted('hello world', '', 'sha256', 'hex', 16), HELLO_SHA256.slice(0, 16));
+  // truncate 可以省略，对应 Rust 侧的 None
+  assert.equal(wasm.get_str_sha_formatted('hello world', '', 'sha256', 'hex'), HELLO_SHA256);
+  const raw = wasm.get_str_sha_raw('hello world', '', 'sha-512');
+  assert.ok(raw instanceof Uint8Array);
+  assert.equal(raw.length, 64);
+
+  assertThrows(() => wasm.get_str_sha256_pure('   '), 'INVALID_INPUT', 'sha_demo');
+  const details = assertThrows(() => wasm.get_str_sha_raw('x', '', 'md5'), 'UNSUPPORTED_ALGORITHM', 'sha_demo');
+  assert.equal(details.algorithm, 'md5');
+  assertThrows(
+    () => wasm.get_str_sha_formatted('x', '', 'sha256', 'base32'),
+    'UNSUPPORTED_FORMAT',
+    'sha_demo',
+  );
+});
+
+test('bytes sha exports', () => {
+  const data = bytes('hello world');
+  assert.equal(wasm.get_bytes_sha256_pure(data), HELLO_SHA256);
+  assert.equal(wasm.get_bytes_sha512_pure(data).length, 128);
+  assert.equal(
+    wasm.get_bytes_sha256(data),
+    '44dd428c749a4827523345f20f93b42eb7c6f1bbc97128488aff0bc12db8b32c',
+  );
+  assert.equal(wasm.get_bytes_sha256_with_salt(data, BYTES_SALT), wasm.get_bytes_sha256(data));
+  assert.equal(wasm.get_bytes_sha512_with_salt(data, BYTES_SALT), wasm.get_bytes_sha512(data));
+
+  // 空的 Uint8Array 也是合法输入
+  assert.equal(wasm.get_bytes_sha256_pure(new Uint8Array()).length, 64);
+  assertBytes(wasm.get_bytes_sha_raw(data, '', 'sha256'), Buffer.from(HELLO_SHA256, 'hex'));
+  assert.equal(
+    wasm.get_bytes_sha_formatted(data, '', 'sha256', 'base64url'),
+    'uU0nuZNNPgilLlLX2n2r-sSE7-N6U4DukIj3rOLvzek',
+  );
+
+  assertThrows(
+    () => wasm.get_bytes_sha_formatted(data, '', 'sha1', 'hex'),
+    'UNSUPPORTED_ALGORITHM',
+    'sha_demo',
+  );
+});
+
+test('recipe exports', () => {
+  const recipe = JSON.stringify({ steps: [{ op: 'xor' }, { op: 'base64' }] });
+  assert.equal(wasm.validate_recipe(recipe), true);
+  assert.equal(wasm.run_recipe_str(recipe, 'hans7'), 'AAIaFV8=');
+  assert.equal(wasm.run_recipe_reverse_str(recipe, 'AAIaFV8='), 'hans7');
+
+  const encrypted = wasm.run_recipe(recipe, new Uint8Array([0, 255]));
+  assert.ok(encrypted instanceof Uint8Array);
+  assertBytes(wasm.run_recipe_reverse(recipe, encrypted), [0, 255]);
+
+  assertThrows(() => wasm.validate_recipe('not json'), 'INVALID_RECIPE', 'pipeline');
+  assertThrows(() => wasm.validate_recipe('{"steps": []}'), 'EMPTY_RECIPE', 'pipeline');
+
+  const hash = JSON.stringify({ steps: [{ op: 'sha256' }] });
+  assert.equal(wasm.validate_recipe(hash), false);
+  const details = assertThrows(() => wasm.run_recipe_reverse(hash, bytes('x')), 'NOT_REVERSIBLE', 'pipeline');
+  assert.equal(details.op, 'sha256');
+  assertThrows(() => wasm.run_recipe_str(hash, 'x'), 'INVALID_UTF8', 'pipeline');
+});
+
+// 默认构建是标量版本：没有 simd128 ，也没有线程池
+test('parallel hash exports', () => {
+  const capabilities = JSON.parse(wasm.hash_capabilities());
+  assert.equal(capabilities.simd128, false);
+  assert.deepEqual(capabilities.simd_accelerated, []);
+  assert.equal(capabilities.threads, false);
+  assert.equal(capabilities.thread_pool_size, 1);
+
+  assert.equal(
+    wasm.get_bytes_blake3(bytes('abc')),
+    '6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85',
+  );
+  // H(le64(chunk_size) || H(chunk_0) || ...)，用 node:crypto 独立计算
+  const data = bytes('hello world');
+  const sha256 = (chunk) => createHash('sha256').update(chunk).digest();
+  const chunkSize = Buffer.alloc(8);
+  chunkSize.writeBigUInt64LE(4n);
+  const chunks = [0, 4, 8].map((start) => sha256(data.subarray(start, start + 4)));
+  assert.equal(
+    wasm.get_bytes_sha_chunked(data, 'sha256', 4),
+    sha256(Buffer.concat([chunkSize, ...chunks])).toString('hex'),
+  );
+  assertThrows(() => wasm.get_bytes_sha_chunked(bytes('abc'), 'sha256', 0), 'INVALID_INPUT', 'sha_demo');
+});
+
+test('registry exports', () => {
+  const list = JSON.parse(wasm.list_algorithms());
+  assert.equal(list.hashers.length, 3);
+
+  const data = bytes('hello world');
+  assert.equal(wasm.hash_by_name(' SHA256 ', data, 'hex'), wasm.get_bytes_sha256_pure(data));
+  // RFC 4231 test case 2
+  const tag = wasm.decode_by_name('hex', '5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843');
+  assert.ok(tag instanceof Uint8Array);
+  assert.equal(wasm.verify_hmac_by_name('sha256', bytes('Jefe'), bytes('what do ya want for nothing?'), tag), true);
+  assert.equal(wasm.verify_hmac_by_name('sha256', bytes('Jefe'), bytes('what do ya want for nothing!'), tag), false);
+  assertBytes(wasm.decode_by_name('custom_base64', wasm.encode_by_name('custom_base64', data)), data);
+
+  const encrypted = wasm.encrypt_by_name('aes_cbc', data, KEY, IV);
+  assert.ok(encrypted instanceof Uint8Array);
+  assertBytes(wasm.decrypt_by_name('aes_cbc', encrypted, KEY, IV), data);
+  assert.equal(
+    wasm.encode_by_name('base64', wasm.encrypt_by_name('xor', bytes('hans7'), XOR_KEY, new Uint8Array())),
+    wasm.encrypt_username('hans7'),
+  );
+
+  const details = assertThrows(() => wasm.hash_by_name('md5', data, 'hex'), 'UNKNOWN_ALGORITHM', 'algorithm');
+  assert.equal(details.kind, 'hasher');
+  assert.equal(details.name, 'md5');
+  assertThrows(() => wasm.verify_hmac_by_name('md5', data, data, tag), 'UNKNOWN_ALGORITHM', 'algorithm');
+  assertThrows(
+    () => wasm.encrypt_by_name('xor', bytes('x'), new Uint8Array(), new Uint8Array()),
+    'INVALID_KEY',
+    'algorithm',
+  );
+  assertThrows(() => wasm.decode_by_name('hex', 'abc'), 'INVALID_ENCODING', 'algorithm');
+  // 包装的底层错误保留原始 module
+  assertThrows(() => wasm.encrypt_by_name('aes_cbc', bytes('x'), bytes('short'), IV), 'BAD_KEY_LEN', 'aes_cbc');
+});
+
+// 必须放在最后：新增导出函数时要在上面补上用例
+test('every export is covered', () => {
+  const functions = Object.keys(exports).filter((name) => typeof exports[name] === 'function');
+  const missing = functions.filter((name) => !used.has(name));
+  assert.deepEqual(missing, [], `exports without tests: ${missing.join(', ')}`);
+});
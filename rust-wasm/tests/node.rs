@@ -0,0 +1,289 @@
+//! 在 Node 中运行的 wasm-bindgen-test 集成测试，覆盖 JS 边界：
+//! 错误转换成带 code / module / details 的 Error 对象、Uint8Array 与 &[u8] / Vec<u8> 的互转。
+//! 运行：`wasm-pack test --node`（不需要浏览器）
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, JSON, Reflect, Uint8Array};
+use rust_wasm::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+const KEY: &[u8] = b"1234567890123456";
+const IV: &[u8] = b"abcdef9876543210";
+
+// 生成的胶水代码对 Err 分支的处理：先 Into<JsValue> ，再 throw
+fn thrown<T: std::fmt::Debug, E: Into<JsValue>>(result: Result<T, E>) -> js_sys::Error {
+    let value: JsValue = result.expect_err("expected an error").into();
+    value
+        .dyn_into::<js_sys::Error>()
+        .expect("thrown value should be an Error instance")
+}
+
+fn prop(obj: &JsValue, key: &str) -> JsValue {
+    Reflect::get(obj, &key.into()).unwrap()
+}
+
+fn assert_js_error(err: &js_sys::Error, code: &str, module: &str) -> JsValue {
+    assert_eq!(String::from(err.name()), "RustWasmError");
+    assert_eq!(prop(err, "code").as_string().as_deref(), Some(code));
+    assert_eq!(prop(err, "module").as_string().as_deref(), Some(module));
+    let details = prop(err, "details");
+    assert!(details.is_object(), "details should be a plain object");
+    details
+}
+
+// 经过一次 JS 侧的 Uint8Array ，模拟前端传入的字节
+fn from_js(data: &[u8]) -> Vec<u8> {
+    Uint8Array::from(data).to_vec()
+}
+
+fn to_js(data: Vec<u8>) -> Uint8Array {
+    Uint8Array::from(&data[..])
+}
+
+#[wasm_bindgen_test]
+fn xor_exports_test() {
+    assert_eq!(encrypt_username("hans7"), "AAIaFV8=");
+    assert_eq!(decrypt_to_username("AAIaFV8=").unwrap(), "hans7");
+
+    let err = thrown(decrypt_to_username("!!!"));
+    let details = assert_js_error(&err, "INVALID_BASE64", "xor_demo");
+    assert_eq!(
+        prop(&details, "reason").as_string().as_deref(),
+        Some("invalid_byte")
+    );
+    assert_eq!(prop(&details, "offset").as_f64(), Some(0.0));
+
+    // 0x97 ^ 'h' = 0xFF ，不是合法 UTF-8
+    let err = thrown(decrypt_to_username("lw=="));
+    assert_js_error(&err, "INVALID_UTF8", "xor_demo");
+}
+
+#[wasm_bindgen_test]
+fn aes_cbc_exports_test() {
+    let cipher = aes_cbc_encrypt("有空 格", KEY, IV).unwrap();
+    assert_eq!(aes_cbc_decrypt(&cipher, KEY, IV).unwrap(), "有空 格");
+
+    let err = thrown(aes_cbc_encrypt("hi", &from_js(b"short"), IV));
+    let details = assert_js_error(&err, "BAD_KEY_LEN", "aes_cbc");
+    assert_eq!(prop(&details, "expected").as_f64(), Some(16.0));
+    assert_eq!(prop(&details, "actual").as_f64(), Some(5.0));
+
+    let err = thrown(aes_cbc_decrypt(&cipher, KEY, b"bad iv"));
+    assert_js_error(&err, "BAD_IV_LEN", "aes_cbc");
+
+    let err = thrown(aes_cbc_decrypt("not base64!", KEY, IV));
+    assert_js_error(&err, "INVALID_BASE64", "aes_cbc");
+}
+
+#[wasm_bindgen_test]
+fn custom_base64_exports_test() {
+    let alphabet = HANS7_CUSTOM_ALPHABET_STR.reveal_str();
+    let encoded = encode_custom_base64("爱拼才会赢💪").unwrap();
+    assert_eq!(decode_custom_base64(&encoded).unwrap(), "爱拼才会赢💪");
+    assert_eq!(
+        encode_base64_with_alphabet("hello", &alphabet).unwrap(),
+        encode_custom_base64("hello").unwrap()
+    );
+    assert_eq!(
+        decode_base64_with_alphabet(&encoded, &alphabet).unwrap(),
+        "爱拼才会赢💪"
+    );
+
+    let bytes = decode_bytes_with_alphabet(
+        &encode_bytes_with_alphabet(&from_js(&[0, 255, 128]), &alphabet).unwrap(),
+        &alphabet,
+    )
+    .unwrap();
+    assert_eq!(to_js(bytes).to_vec(), [0, 255, 128]);
+
+    assert_js_error(
+        &thrown(encode_custom_base64("")),
+        "EMPTY_INPUT",
+        "custom_base64",
+    );
+    assert_js_error(
+        &thrown(encode_base64_with_alphabet("x", "ABC")),
+        "INVALID_ALPHABET",
+        "custom_base64",
+    );
+    assert_js_error(
+        &thrown(decode_custom_base64("@@@@")),
+        "INVALID_BASE64",
+        "custom_base64",
+    );
+}
+
+#[wasm_bindgen_test]
+fn str_sha_exports_test() {
+    let hello = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+    assert_eq!(get_str_sha256_pure("hello world").unwrap(), hello);
+    assert_eq!(get_str_sha512_pure("hello world").unwrap().len(), 128);
+    assert_eq!(
+        get_str_sha256("hello world").unwrap(),
+        "2379e070457dd223d988bef6ae2c199780a0b3da25aba546980c9ab181ea259c"
+    );
+    assert_eq!(
+        get_str_sha256_with_salt("hello world", &DEFAULT_STR_SALT.reveal_str()).unwrap(),
+        get_str_sha256("hello world").unwrap()
+    );
+    assert_eq!(
+        get_str_sha512_with_salt("hello world", &DEFAULT_STR_SALT.reveal_str()).unwrap(),
+        get_str_sha512("hello world").unwrap()
+    );
+    assert_eq!(
+        get_str_sha_formatted("hello world", "", "sha256", "hex", Some(16)).unwrap(),
+        &hello[..16]
+    );
+    assert_eq!(
+        to_js(get_str_sha_raw("hello world", "", "sha-512").unwrap()).length(),
+        64
+    );
+
+    let err = thrown(get_str_sha256_pure("   "));
+    assert_js_error(&err, "INVALID_INPUT", "sha_demo");
+    let err = thrown(get_str_sha_raw("x", "", "md5"));
+    let details = assert_js_error(&err, "UNSUPPORTED_ALGORITHM", "sha_demo");
+    assert_eq!(
+        prop(&details, "algorithm").as_string().as_deref(),
+        Some("md5")
+    );
+    let err = thrown(get_str_sha_formatted("x", "", "sha256", "base32", None));
+    assert_js_error(&err, "UNSUPPORTED_FORMAT", "sha_demo");
+}
+
+#[wasm_bindgen_test]
+fn bytes_sha_exports_test() {
+    let data = from_js(b"hello world");
+    assert_eq!(
+        get_bytes_sha256_pure(&data),
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+    assert_eq!(get_bytes_sha512_pure(&data).len(), 128);
+    assert_eq!(
+        get_bytes_sha256(&data),
+        "44dd428c749a4827523345f20f93b42eb7c6f1bbc97128488aff0bc12db8b32c"
+    );
+    assert_eq!(
+        get_bytes_sha256_with_salt(&data, &DEFAULT_BYTES_SALT.reveal_str()),
+        get_bytes_sha256(&data)
+    );
+    assert_eq!(
+        get_bytes_sha512_with_salt(&data, &DEFAULT_BYTES_SALT.reveal_str()),
+        get_bytes_sha512(&data)
+    );
+
+    // 空的 Uint8Array 也是合法输入
+    assert_eq!(get_bytes_sha256_pure(&from_js(&[])).len(), 64);
+    let raw = get_bytes_sha_raw(&data, "", "sha256").unwrap();
+    assert_eq!(to_js(raw.clone()).to_vec(), raw);
+    assert_eq!(
+        get_bytes_sha_formatted(&data, "", "sha256", "base64url", None).unwrap(),
+        "uU0nuZNNPgilLlLX2n2r-sSE7-N6U4DukIj3rOLvzek"
+    );
+
+    let err = thrown(get_bytes_sha_formatted(&data, "", "sha1", "hex", None));
+    assert_js_error(&err, "UNSUPPORTED_ALGORITHM", "sha_demo");
+}
+
+#[wasm_bindgen_test]
+fn recipe_exports_test() {
+    let recipe = r#"{"steps": [{"op": "xor"}, {"op": "base64"}]}"#;
+    assert!(validate_recipe(recipe).unwrap());
+    assert_eq!(run_recipe_str(recipe, "hans7").unwrap(), "AAIaFV8=");
+    assert_eq!(run_recipe_reverse_str(recipe, "AAIaFV8=").unwrap(), "hans7");
+
+    let encrypted = to_js(run_recipe(recipe, &from_js(&[0, 255])).unwrap());
+    let decrypted = run_recipe_reverse(recipe, &encrypted.to_vec()).unwrap();
+    assert_eq!(decrypted, [0, 255]);
+
+    let err = thrown(validate_recipe("not json"));
+    assert_js_error(&err, "INVALID_RECIPE", "pipeline");
+    let err = thrown(validate_recipe(r#"{"steps": []}"#));
+    assert_js_error(&err, "EMPTY_RECIPE", "pipeline");
+
+    let hash = r#"{"steps": [{"op": "sha256"}]}"#;
+    let err = thrown(run_recipe_reverse(hash, b"x"));
+    let details = assert_js_error(&err, "NOT_REVERSIBLE", "pipeline");
+    assert_eq!(prop(&details, "op").as_string().as_deref(), Some("sha256"));
+    let err = thrown(run_recipe_str(hash, "x"));
+    assert_js_error(&err, "INVALID_UTF8", "pipeline");
+}
+
+// wasm-pack test 使用标量构建：没有 simd128 ，也没有线程池
+#[wasm_bindgen_test]
+fn parallel_hash_exports_test() {
+    let capabilities = JSON::parse(&hash_capabilities()).unwrap();
+    assert_eq!(prop(&capabilities, "simd128").as_bool(), Some(false));
+    let accelerated: Array = prop(&capabilities, "simd_accelerated").dyn_into().unwrap();
+    assert_eq!(accelerated.length(), 0);
+    assert_eq!(prop(&capabilities, "threads").as_bool(), Some(false));
+    assert_eq!(prop(&capabilities, "thread_pool_size").as_f64(), Some(1.0));
+
+    assert_eq!(
+        get_bytes_blake3(&from_js(b"abc")),
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+    );
+    let err = thrown(get_bytes_sha_chunked(&from_js(b"abc"), "sha256", 0));
+    assert_js_error(&err, "INVALID_INPUT", "sha_demo");
+}
+
+#[wasm_bindgen_test]
+fn registry_exports_test() {
+    let list = JSON::parse(&list_algorithms()).unwrap();
+    let hashers: Array = prop(&list, "hashers").dyn_into().unwrap();
+    assert_eq!(hashers.length(), 3);
+
+    let data = from_js(b"hello world");
+    assert_eq!(
+        hash_by_name(" SHA256 ", &data, "hex").unwrap(),
+        get_bytes_sha256_pure(&data)
+    );
+    let tag = decode_by_name(
+        "hex",
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+    )
+    .unwrap();
+    assert!(verify_hmac_by_name("sha256", b"Jefe", b"what do ya want for nothing?", &tag).unwrap());
+    let encoded = encode_by_name("custom_base64", &data).unwrap();
+    assert_eq!(decode_by_name("custom_base64", &encoded).unwrap(), data);
+
+    let encrypted = to_js(encrypt_by_name("aes_cbc", &data, KEY, IV).unwrap());
+    assert_eq!(
+        decrypt_by_name("aes_cbc", &encrypted.to_vec(), KEY, IV).unwrap(),
+        data
+    );
+    assert_eq!(
+        encode_by_name(
+            "base64",
+            &encrypt_by_name("xor", b"hans7", &XOR_KEY.reveal(), &[]).unwrap()
+        )
+        .unwrap(),
+        encrypt_username("hans7")
+    );
+
+    let err = thrown(hash_by_name("md5", &data, "hex"));
+    let details = assert_js_error(&err, "UNKNOWN_ALGORITHM", "algorithm");
+    assert_eq!(
+        prop(&details, "kind").as_string().as_deref(),
+        Some("hasher")
+    );
+    assert_eq!(prop(&details, "name").as_string().as_deref(), Some("md5"));
+    assert_js_error(
+        &thrown(encrypt_by_name("xor", b"x", &[], &[])),
+        "INVALID_KEY",
+        "algorithm",
+    );
+    assert_js_error(
+        &thrown(decode_by_name("hex", "abc")),
+        "INVALID_ENCODING",
+        "algorithm",
+    );
+    // 包装的底层错误保留原始 module
+    assert_js_error(
+        &thrown(encrypt_by_name("aes_cbc", b"x", b"short", IV)),
+        "BAD_KEY_LEN",
+        "aes_cbc",
+    );
+}
@@ -0,0 +1,317 @@
+//! 在 wasm-pack 构建出的真实 `rust_wasm_bg.wasm` 上检查 wasm_inspect 的各个工具，
+//! 手写的 WAT 覆盖不到 rustc 实际生成的代码。需要 wasm32-unknown-unknown target 和 wasm-pack ：
+//! `cargo test --features inspect --test real_wasm -- --ignored`
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use rust_wasm::{
+    ModuleInfo, ObfuscateOptions, Patch, WasmInterpreter, WasmValue, generate_signatures,
+    match_signatures, obfuscate_module, patch_module,
+};
+
+// xor_demo 中 encrypt_username 的测试向量
+const VECTORS: [(&str, &str); 7] = [
+    ("hans7", "AAIaFV8="),
+    ("HANS", "ICI6NQ=="),
+    ("爱拼才会赢", "j+vFgOPfku/lh8j8gNbW"),
+    ("Acm_L0ver", "KQAZOSRTAgMa"),
+    ("hctf", "AAAAAA=="),
+    ("有空 格", "jv/9gcHZVIDI3w=="),
+    ("", ""),
+];
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn wasm_pack(profile: &str) -> Vec<u8> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("real-wasm");
+    let out_dir = target_dir.join(format!("pkg-{}", profile));
+    let status = Command::new("wasm-pack")
+        .current_dir(&manifest_dir)
+        .args([
+            "build",
+            &format!("--{}", profile),
+            "--no-opt",
+            "--target",
+            "web",
+        ])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg("--")
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run wasm-pack，是否已安装 wasm-pack ？");
+    assert!(
+        status.success(),
+        "wasm-pack build failed，是否已安装 wasm32-unknown-unknown target？"
+    );
+    std::fs::read(out_dir.join("rust_wasm_bg.wasm")).unwrap()
+}
+
+// 与前端加载的产物相同：默认 feature 、--target web 。同一个测试进程只构建一次
+fn release_wasm() -> &'static [u8] {
+    static WASM: OnceLock<Vec<u8>> = OnceLock::new();
+    WASM.get_or_init(|| wasm_pack("release"))
+}
+
+fn dev_wasm() -> &'static [u8] {
+    static WASM: OnceLock<Vec<u8>> = OnceLock::new();
+    WASM.get_or_init(|| wasm_pack("dev"))
+}
+
+fn i32_results(results: &[WasmValue]) -> Vec<i32> {
+    results
+        .iter()
+        .map(|value| match value {
+            WasmValue::I32(v) => *v,
+            value => panic!("expected i32, got {:?}", value),
+        })
+        .collect()
+}
+
+fn invoke(interpreter: &mut WasmInterpreter, function: &str, args: &[i32]) -> Vec<i32> {
+    let args: Vec<_> = args.iter().map(|&v| WasmValue::I32(v)).collect();
+    let execution = interpreter.invoke(function, &args).unwrap();
+    assert_eq!(execution.trap, None, "{} trapped", function);
+    i32_results(&execution.results)
+}
+
+// 按 wasm-bindgen 的约定调用 `fn(&str) -> String` 的导出：
+// __wbindgen_malloc 分配并写入参数，以 (ptr, len) 调用，返回 (ptr, len) ，读出后 __wbindgen_free
+fn call_str(
+    interpreter: &mut WasmInterpreter,
+    exports: &dyn Fn(&str) -> String,
+    function: &str,
+    input: &str,
+) -> String {
+    let len = input.len() as i32;
+    let ptr = invoke(interpreter, &exports("__wbindgen_malloc"), &[len, 1])[0];
+    interpreter
+        .write_memory(ptr as u32, input.as_bytes())
+        .unwrap();
+    let result = invoke(interpreter, &exports(function), &[ptr, len]);
+    let output = interpreter
+        .read_memory(result[0] as u32, result[1] as u32)
+        .unwrap();
+    invoke(
+        interpreter,
+        &exports("__wbindgen_free"),
+        &[result[0], result[1], 1],
+    );
+    String::from_utf8(output).unwrap()
+}
+
+// 按导出名找到函数索引。name section 里也有叫 encrypt_username 的内部函数，
+// 直接按名字调用会先找到它
+fn export_index(info: &ModuleInfo, export: &str) -> String {
+    info.functions
+        .iter()
+        .find(|f| f.exports.iter().any(|e| e == export))
+        .unwrap_or_else(|| panic!("no export named {}", export))
+        .index
+        .to_string()
+}
+
+fn new_interpreter(bytes: &[u8]) -> WasmInterpreter {
+    let mut interpreter = WasmInterpreter::new(bytes).unwrap();
+    // 逐条记录的 trace 对真实模块太大，这里只关心结果
+    interpreter.set_record_steps(false);
+    interpreter.set_record_memory(false);
+    interpreter
+}
+
+// rename 把原导出名映射为模块中的导出名
+fn assert_encrypt_username(bytes: &[u8], rename: &dyn Fn(&str) -> String) {
+    let info = ModuleInfo::parse(bytes).unwrap();
+    let exports = |name: &str| export_index(&info, &rename(name));
+    let mut interpreter = new_interpreter(bytes);
+    for (name, expected) in VECTORS {
+        let actual = call_str(&mut interpreter, &exports, "encrypt_username", name);
+        assert_eq!(actual, expected, "{}", name);
+    }
+}
+
+#[test]
+#[ignore = "需要 wasm32-unknown-unknown target 和 wasm-pack"]
+fn obfuscate_real_wasm_test() {
+    let bytes = release_wasm();
+    assert_encrypt_username(bytes, &|name| name.to_string());
+
+    for seed in [0, 1, 42, 0xdead_beef] {
+        let options = ObfuscateOptions {
+            seed,
+            encrypt_data: true,
+            opaque_predicates: true,
+            // 按名字找到的是 name section 中的内部函数，即实际做异或和 base64 的那个
+            flatten: vec!["encrypt_username".into()],
+            split_constants: true,
+            mangle_exports: true,
+            keep_exports: vec!["memory".into()],
+        };
+        let obfuscated = obfuscate_module(bytes, &options).unwrap();
+        let exports = obfuscated.export_names().clone();
+        assert_ne!(exports["encrypt_username"], "encrypt_username");
+        let obfuscated = obfuscated.into_bytes();
+        assert!(obfuscated.len() > bytes.len());
+        assert_encrypt_username(&obfuscated, &|name| exports[name].clone());
+    }
+}
+
+// XOR_KEY 是 sealed! 加密的，数据段里没有明文 "hctf" ；base64 crate 的标准码表是明文，
+// 把它换成大小写互换的码表，encrypt_username 的输出也应当大小写互换
+#[test]
+#[ignore = "需要 wasm32-unknown-unknown target 和 wasm-pack"]
+fn patch_real_wasm_alphabet_test() {
+    let bytes = release_wasm();
+    let info = ModuleInfo::parse(bytes).unwrap();
+    let swapped: Vec<u8> = STANDARD_ALPHABET
+        .iter()
+        .map(|b| match b {
+            b'A'..=b'Z' => b.to_ascii_lowercase(),
+            _ => b.to_ascii_uppercase(),
+        })
+        .collect();
+
+    let mut patches = Vec::new();
+    for segment in &info.data {
+        let Some(base) = segment.offset else {
+            continue;
+        };
+        let content = &bytes[segment.file_offset..segment.file_offset + segment.size];
+        for (pos, window) in content.windows(64).enumerate() {
+            if window == STANDARD_ALPHABET {
+                patches.push(Patch::WriteData {
+                    address: base + pos as u64,
+                    bytes: swapped.clone(),
+                });
+            }
+        }
+    }
+    assert!(
+        !patches.is_empty(),
+        "standard base64 alphabet not found in data"
+    );
+
+    let patched = patch_module(bytes, &patches).unwrap();
+    wasmparser::Validator::new().validate_all(&patched).unwrap();
+    assert!(
+        !patched
+            .windows(64)
+            .any(|window| window == STANDARD_ALPHABET)
+    );
+
+    let info = ModuleInfo::parse(&patched).unwrap();
+    let exports = |name: &str| export_index(&info, name);
+    let mut interpreter = new_interpreter(&patched);
+    for (name, expected) in VECTORS {
+        let actual = call_str(&mut interpreter, &exports, "encrypt_username", name);
+        let expected: String = expected
+            .chars()
+            .map(|c| match c {
+                'A'..='Z' => c.to_ascii_lowercase(),
+                _ => c.to_ascii_uppercase(),
+            })
+            .collect();
+        assert_eq!(actual, expected, "{}", name);
+    }
+}
+
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> usize {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+// 去掉 name section ，模拟发布时 strip 过的构建
+fn strip_names(bytes: &[u8]) -> Vec<u8> {
+    let mut out = bytes[..8].to_vec();
+    let mut pos = 8;
+    while pos < bytes.len() {
+        let start = pos;
+        let id = bytes[pos];
+        pos += 1;
+        let len = read_leb128(bytes, &mut pos);
+        let end = pos + len;
+        let is_name = id == 0 && {
+            let name_len = read_leb128(bytes, &mut pos);
+            &bytes[pos..pos + name_len] == b"name"
+        };
+        if !is_name {
+            out.extend_from_slice(&bytes[start..end]);
+        }
+        pos = end;
+    }
+    out
+}
+
+// 签名的真实用法：用自己编译的 debug 构建生成签名，识别 strip 过的 release 构建。
+// release 构建原本的名字（同样经过签名的名字规范化）作为标准答案
+#[test]
+#[ignore = "需要 wasm32-unknown-unknown target 和 wasm-pack"]
+fn signatures_debug_to_release_test() {
+    let debug = dev_wasm();
+    let release = release_wasm();
+    let stripped = strip_names(release);
+    assert!(
+        ModuleInfo::parse(&stripped)
+            .unwrap()
+            .functions
+            .iter()
+            .all(|f| f.name.is_none())
+    );
+
+    let truth: HashMap<u32, String> =
+        match_signatures(release, &generate_signatures(release).unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.function, m.name))
+            .collect();
+    let matches = match_signatures(&stripped, &generate_signatures(debug).unwrap()).unwrap();
+    let mut wrong = Vec::new();
+    for m in &matches {
+        if truth.get(&m.function) != Some(&m.name) {
+            wrong.push(format!(
+                "{}: {} vs {:?}",
+                m.function,
+                m.name,
+                truth.get(&m.function)
+            ));
+        }
+    }
+    println!(
+        "{} matches, {} differ from the release names",
+        matches.len(),
+        wrong.len()
+    );
+    for line in &wrong {
+        println!("  {}", line);
+    }
+
+    // 两个构建共用预编译的 std ，这部分应当能对上
+    let found: Vec<_> = matches.iter().map(|m| m.name.as_str()).collect();
+    for name in ["core::fmt::write", "memcmp", "<core::fmt::Formatter>::pad"] {
+        assert!(found.contains(&name), "{} not found", name);
+    }
+    assert!(matches.len() >= 50, "only {} matches", matches.len());
+    // 同一个泛型函数在 debug 和 release 中的名字写法可能不同，例如
+    // `<&u8 as Debug>::fmt` 和 `<&T as Debug>::fmt` ；release 中代码相同的多个函数没有唯一的名字，
+    // 也算作不一致。其余的都应当一致
+    assert!(
+        wrong.len() * 10 <= matches.len(),
+        "too many wrong matches: {:?}",
+        wrong
+    );
+}
@@ -0,0 +1,114 @@
+//! 按 feature 分别用 wasm-pack 构建，检查前端实际加载的 `rust_wasm_bg.wasm` 的体积预算。
+//! 需要 `rustup target add wasm32-unknown-unknown` 和 wasm-pack ，CI 中每次提交都会运行：
+//! `cargo test --test size_report -- --ignored --nocapture`
+//!
+//! 构建时加 `--no-opt` ，不依赖下载 wasm-opt ，结果也更稳定；统计时去掉自定义 section
+//! （name 、producers 等，发布时 wasm-opt 会去掉），剩下的代码和数据就是体积的上限。
+//! cargo 直接产出的 .wasm 还带着 wasm-bindgen 的描述信息，比实际下载的大得多，不能用来算预算。
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// (features, 预算 KiB)。前端按页面加载单个 feature 的包，预算约为当前体积加 20% 余量
+const MINIMAL_BUDGETS: [(&str, u64); 6] = [
+    ("xor", 100),
+    ("base64", 110),
+    ("sha", 135),
+    ("aes", 120),
+    ("blake3", 48),
+    ("inspect", 2600),
+];
+
+const FULL_BUDGET: u64 = 450;
+
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> usize {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+// 去掉自定义 section 后的字节数
+fn shipped_size(wasm: &Path) -> u64 {
+    let bytes =
+        std::fs::read(wasm).unwrap_or_else(|e| panic!("cannot read {}: {}", wasm.display(), e));
+    assert_eq!(&bytes[..4], b"\0asm", "{} is not wasm", wasm.display());
+    let mut size = 8;
+    let mut pos = 8;
+    while pos < bytes.len() {
+        let start = pos;
+        let id = bytes[pos];
+        pos += 1;
+        let len = read_leb128(&bytes, &mut pos);
+        pos += len;
+        if id != 0 {
+            size += pos - start;
+        }
+    }
+    size as u64
+}
+
+fn build_wasm(features: Option<&str>) -> u64 {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("size-report");
+    let out_dir = target_dir.join(format!("pkg-{}", features.unwrap_or("default")));
+    let mut cmd = Command::new("wasm-pack");
+    cmd.current_dir(&manifest_dir)
+        .args(["build", "--release", "--no-opt", "--target", "web"])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg("--")
+        .arg("--target-dir")
+        .arg(&target_dir);
+    if let Some(features) = features {
+        cmd.args(["--no-default-features", "--features", features]);
+    }
+    let status = cmd
+        .status()
+        .expect("failed to run wasm-pack，是否已安装 wasm-pack ？");
+    assert!(
+        status.success(),
+        "wasm-pack build failed for features {:?}，是否已安装 wasm32-unknown-unknown target？",
+        features
+    );
+    shipped_size(&out_dir.join("rust_wasm_bg.wasm"))
+}
+
+#[test]
+#[ignore = "需要 wasm32-unknown-unknown target 和 wasm-pack ，且会多次 release 构建"]
+fn per_feature_wasm_size_budget_test() {
+    let full = build_wasm(None);
+    println!("{:<10} {:>10} {:>10}", "features", "bytes", "budget");
+    println!("{:<10} {:>10} {:>10}", "default", full, FULL_BUDGET * 1024);
+
+    let mut over_budget = Vec::new();
+    if full > FULL_BUDGET * 1024 {
+        over_budget.push(format!("default: {} > {} KiB", full, FULL_BUDGET));
+    }
+    for (features, budget) in MINIMAL_BUDGETS {
+        let size = build_wasm(Some(features));
+        println!("{:<10} {:>10} {:>10}", features, size, budget * 1024);
+        if size > budget * 1024 {
+            over_budget.push(format!("{}: {} > {} KiB", features, size, budget));
+        }
+        // 单个算法的包必须比完整包小，否则说明 feature 划分没有起作用。
+        // inspect 是单独打包的逆向工具，不在默认构建中
+        if features != "inspect" {
+            assert!(
+                size < full,
+                "{} build ({} bytes) is not smaller than the default build ({} bytes)",
+                features,
+                size,
+                full
+            );
+        }
+    }
+    assert!(over_budget.is_empty(), "over budget: {:?}", over_budget);
+}
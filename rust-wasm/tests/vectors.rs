@@ -0,0 +1,399 @@
+//! 数据驱动的标准测试向量：遍历 tests/vectors 下的所有文件，按格式分派给 registry 中的算法。
+//! 新算法注册到 registry 后，放入对应格式的向量文件即可得到覆盖，不需要改测试代码：
+//! - `*.rsp`：NIST CAVP 格式
+//!   - 含 MD 的记录：按文件名前缀查 hasher ，例如 SHA256ShortMsg.rsp → sha256
+//!   - 含 Mac 的记录：CAVP HMAC.rsp 的约定，`[L = n]` 选择 output_len 为 n 的 hasher
+//!   - 含 KEY / IV 的记录：按文件名中的模式查 cipher ，例如 CBCGFSbox128.rsp → aes_cbc
+//! - `*.txt`：RFC 4648 第 10 节的 `BASE64("f") = "Zg=="` 行，BASE16 对应 hex encoder
+//! - `*.json`：Project Wycheproof 格式，按 algorithm 字段分派
+//!
+//! 找不到对应算法的文件直接失败，避免向量被悄悄跳过。
+//! 运行：`cargo test --test vectors -- --nocapture` 可以看到每个文件的用例数
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use rust_wasm::{Cipher, Hasher, registry};
+use serde::Deserialize;
+
+// CAVP AES 文件名以模式开头，后面是测试类型和 key 长度
+const CAVP_MODES: [&str; 7] = ["CBC", "ECB", "OFB", "CFB128", "CFB8", "CFB1", "CTR"];
+const CAVP_HASH_SUFFIXES: [&str; 3] = ["ShortMsg", "LongMsg", "Monte"];
+
+fn vectors_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("vectors")
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("read_dir {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn hex(s: &str) -> Result<Vec<u8>> {
+    Ok(registry().encoder("hex")?.decode(s)?)
+}
+
+fn file_stem(path: &Path) -> &str {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+}
+
+// ---------- NIST CAVP .rsp ----------
+
+struct RspSection {
+    // "[ENCRYPT]" → ("encrypt", {})，"[L = 32]" → ("l = 32", {"l": "32"})
+    name: String,
+    params: HashMap<String, String>,
+    records: Vec<HashMap<String, String>>,
+}
+
+fn parse_key_value(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim().to_ascii_lowercase(), value.trim().to_string()))
+}
+
+fn parse_rsp(text: &str) -> Vec<RspSection> {
+    let mut sections = vec![RspSection {
+        name: String::new(),
+        params: HashMap::new(),
+        records: Vec::new(),
+    }];
+    let mut record = HashMap::new();
+    let flush = |record: &mut HashMap<String, String>, sections: &mut Vec<RspSection>| {
+        if !record.is_empty() {
+            sections
+                .last_mut()
+                .unwrap()
+                .records
+                .push(std::mem::take(record));
+        }
+    };
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            flush(&mut record, &mut sections);
+        } else if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            flush(&mut record, &mut sections);
+            sections.push(RspSection {
+                name: header.trim().to_ascii_lowercase(),
+                params: parse_key_value(header).into_iter().collect(),
+                records: Vec::new(),
+            });
+        } else if let Some((key, value)) = parse_key_value(line) {
+            record.insert(key, value);
+        }
+    }
+    flush(&mut record, &mut sections);
+    sections
+}
+
+fn field<'a>(record: &'a HashMap<String, String>, key: &str) -> Result<&'a str> {
+    record
+        .get(key)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("record has no {} field: {:?}", key, record))
+}
+
+fn run_rsp(path: &Path) -> Result<usize> {
+    let sections = parse_rsp(&fs::read_to_string(path)?);
+    let stem = file_stem(path);
+    let mut cases = 0;
+    for section in &sections {
+        for record in &section.records {
+            let count = record.get("count").map(String::as_str).unwrap_or("-");
+            let check = if record.contains_key("md") {
+                check_cavp_hash(stem, record)
+            } else if record.contains_key("mac") {
+                check_cavp_hmac(section, record)
+            } else if record.contains_key("key") {
+                check_cavp_cipher(stem, section, record)
+            } else {
+                Err(anyhow!("unrecognized record: {:?}", record))
+            };
+            check.with_context(|| format!("[{}] COUNT = {}", section.name, count))?;
+            cases += 1;
+        }
+    }
+    Ok(cases)
+}
+
+fn check_cavp_hash(stem: &str, record: &HashMap<String, String>) -> Result<()> {
+    let name = CAVP_HASH_SUFFIXES
+        .iter()
+        .find_map(|suffix| stem.strip_suffix(suffix))
+        .unwrap_or(stem);
+    let hasher = registry().hasher(name)?;
+    // Len 是比特数，Len = 0 时 Msg 为占位的 00
+    let len: usize = field(record, "len")?.parse()?;
+    ensure!(
+        len.is_multiple_of(8),
+        "bit-oriented message (Len = {}) is not supported",
+        len
+    );
+    let msg = hex(field(record, "msg")?)?;
+    let msg = msg.get(..len / 8).context("Msg shorter than Len")?;
+    let expected = field(record, "md")?;
+    let actual = hasher.hash(msg).to_hex();
+    ensure!(
+        actual == expected,
+        "{}: got {}, want {}",
+        name,
+        actual,
+        expected
+    );
+    Ok(())
+}
+
+fn check_cavp_hmac(section: &RspSection, record: &HashMap<String, String>) -> Result<()> {
+    let output_len: usize = section
+        .params
+        .get("l")
+        .context("HMAC section has no L parameter")?
+        .parse()?;
+    let hasher = registry()
+        .hashers()
+        .find(|h| h.output_len() == output_len)
+        .ok_or_else(|| anyhow!("no registered hasher with output_len {}", output_len))?;
+    let tlen: usize = field(record, "tlen")?.parse()?;
+    let key = hex(field(record, "key")?)?;
+    let msg = hex(field(record, "msg")?)?;
+    let expected = field(record, "mac")?;
+    let actual = hasher.hmac(&key, &msg).truncated(tlen).to_hex();
+    ensure!(
+        actual == expected,
+        "hmac {}: got {}, want {}",
+        hasher.name(),
+        actual,
+        expected
+    );
+    Ok(())
+}
+
+// CAVP 的 AES 向量没有填充，而 registry 的分组密码固定使用 PKCS7 ：
+// 加密结果的前 len(PLAINTEXT) 字节应等于 CIPHERTEXT ，后面多出一个填充分组；
+// 解密时把这个填充分组接到 CIPHERTEXT 后面，应还原出 PLAINTEXT
+fn check_cavp_cipher(
+    stem: &str,
+    section: &RspSection,
+    record: &HashMap<String, String>,
+) -> Result<()> {
+    let mode = CAVP_MODES
+        .iter()
+        .find(|mode| stem.starts_with(*mode))
+        .ok_or_else(|| anyhow!("cannot infer cipher mode from file name {}", stem))?;
+    let cipher = registry().cipher(&format!("aes_{}", mode.to_ascii_lowercase()))?;
+    let key = hex(field(record, "key")?)?;
+    let iv = hex(record.get("iv").map(String::as_str).unwrap_or_default())?;
+    let plain = hex(field(record, "plaintext")?)?;
+    let expected = hex(field(record, "ciphertext")?)?;
+
+    let encrypted = cipher.encrypt(&plain, &key, &iv)?;
+    ensure!(
+        encrypted.len() > expected.len() && encrypted[..expected.len()] == expected[..],
+        "{} encrypt: got {}, want prefix {}",
+        cipher.name(),
+        registry().encoder("hex")?.encode(&encrypted),
+        field(record, "ciphertext")?
+    );
+    if section.name == "decrypt" {
+        let mut padded = expected.clone();
+        padded.extend_from_slice(&encrypted[expected.len()..]);
+        let decrypted = cipher.decrypt(&padded, &key, &iv)?;
+        ensure!(decrypted == plain, "{} decrypt mismatch", cipher.name());
+    }
+    Ok(())
+}
+
+// ---------- RFC 4648 ----------
+
+// BASE64("fo") = "Zm8="
+fn parse_rfc4648_line(line: &str) -> Option<(&str, &str, &str)> {
+    let (name, rest) = line.split_once("(\"")?;
+    let (input, rest) = rest.split_once("\")")?;
+    let output = rest.trim().strip_prefix('=')?.trim();
+    Some((
+        name.trim(),
+        input,
+        output.strip_prefix('"')?.strip_suffix('"')?,
+    ))
+}
+
+fn run_rfc4648(path: &Path) -> Result<usize> {
+    let mut cases = 0;
+    for line in fs::read_to_string(path)?.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, input, expected) =
+            parse_rfc4648_line(line).ok_or_else(|| anyhow!("malformed line: {}", line))?;
+        let encoder_name = if name == "BASE16" { "hex" } else { name };
+        let encoder = registry().encoder(encoder_name).context(line.to_string())?;
+        let encoded = encoder.encode(input.as_bytes());
+        // RFC 4648 第 8 节：base16 不区分大小写
+        let matches = if name == "BASE16" {
+            encoded.eq_ignore_ascii_case(expected)
+        } else {
+            encoded == expected
+        };
+        ensure!(matches, "{}: got {:?}", line, encoded);
+        ensure!(
+            encoder.decode(expected)? == input.as_bytes(),
+            "{}: decode mismatch",
+            line
+        );
+        cases += 1;
+    }
+    Ok(cases)
+}
+
+// ---------- Project Wycheproof ----------
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofFile {
+    algorithm: String,
+    test_groups: Vec<WycheproofGroup>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofGroup {
+    key_size: usize,
+    tag_size: Option<usize>,
+    tests: Vec<WycheproofCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofCase {
+    tc_id: u32,
+    key: String,
+    #[serde(default)]
+    iv: String,
+    msg: String,
+    #[serde(default)]
+    ct: String,
+    #[serde(default)]
+    tag: String,
+    // valid / invalid / acceptable ，acceptable 两种结果都接受，不检查
+    result: String,
+}
+
+fn run_wycheproof(path: &Path) -> Result<usize> {
+    let file: WycheproofFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let mut cases = 0;
+    for group in &file.test_groups {
+        // AES-CBC-PKCS5 → aes_cbc ，HMACSHA256 → sha256 的 HMAC
+        if let Some(hash_name) = file.algorithm.strip_prefix("HMAC") {
+            let hasher = registry().hasher(hash_name)?;
+            for case in &group.tests {
+                check_wycheproof_mac(hasher, group, case)
+                    .with_context(|| format!("tcId {}", case.tc_id))?;
+                cases += 1;
+            }
+        } else {
+            let name = file.algorithm.trim_end_matches("-PKCS5");
+            let cipher = registry().cipher(name)?;
+            // 与跳过整个文件一样，不支持的 key 长度直接失败，不能让测试组被悄悄跳过
+            if let Some(len) = cipher.key_len() {
+                ensure!(
+                    len * 8 == group.key_size,
+                    "{} has {}-bit keys, group uses keySize {}",
+                    name,
+                    len * 8,
+                    group.key_size
+                );
+            }
+            for case in &group.tests {
+                check_wycheproof_cipher(cipher, case)
+                    .with_context(|| format!("tcId {}", case.tc_id))?;
+                cases += 1;
+            }
+        }
+    }
+    Ok(cases)
+}
+
+fn check_wycheproof_cipher(cipher: &dyn Cipher, case: &WycheproofCase) -> Result<()> {
+    let key = hex(&case.key)?;
+    let iv = hex(&case.iv)?;
+    let msg = hex(&case.msg)?;
+    let ct = hex(&case.ct)?;
+    match case.result.as_str() {
+        "valid" => {
+            ensure!(cipher.encrypt(&msg, &key, &iv)? == ct, "encrypt mismatch");
+            ensure!(cipher.decrypt(&ct, &key, &iv)? == msg, "decrypt mismatch");
+        }
+        "invalid" => ensure!(
+            cipher.decrypt(&ct, &key, &iv).is_err(),
+            "invalid ciphertext was accepted"
+        ),
+        "acceptable" => {}
+        other => bail!("unknown result {:?}", other),
+    }
+    Ok(())
+}
+
+fn check_wycheproof_mac(
+    hasher: &dyn Hasher,
+    group: &WycheproofGroup,
+    case: &WycheproofCase,
+) -> Result<()> {
+    let tag_len = group.tag_size.context("MAC group has no tagSize")? / 8;
+    let tag = hasher
+        .hmac(&hex(&case.key)?, &hex(&case.msg)?)
+        .truncated(tag_len)
+        .to_hex();
+    match case.result.as_str() {
+        "valid" => ensure!(tag == case.tag, "got {}, want {}", tag, case.tag),
+        "invalid" => ensure!(tag != case.tag, "modified tag was accepted"),
+        "acceptable" => {}
+        other => bail!("unknown result {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn conformance_vectors_test() -> Result<()> {
+    let mut files = Vec::new();
+    collect_files(&vectors_dir(), &mut files)?;
+    files.sort();
+    ensure!(!files.is_empty(), "no vector files found");
+
+    let mut failures = Vec::new();
+    for path in &files {
+        let result = match path.extension().and_then(|e| e.to_str()) {
+            Some("rsp") => run_rsp(path),
+            Some("txt") => run_rfc4648(path),
+            Some("json") => run_wycheproof(path),
+            _ => Err(anyhow!("unsupported vector file type")),
+        };
+        let relative = path.strip_prefix(vectors_dir()).unwrap_or(path);
+        match result {
+            Ok(cases) => {
+                println!("{:<45} {:>4} cases", relative.display(), cases);
+                if cases == 0 {
+                    failures.push(format!("{}: no test cases", relative.display()));
+                }
+            }
+            Err(e) => failures.push(format!("{}: {:#}", relative.display(), e)),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "failed vectors:\n{}",
+        failures.join("\n")
+    );
+    Ok(())
+}
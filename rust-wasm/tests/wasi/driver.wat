@@ -0,0 +1,47 @@
+;; 在 wasmtime 中按 src/wasi.rs 文档的流程调用独立 WASM 模块，任何一步不符合预期都会 trap ：
+;;   cargo build-wasi
+;;   wasmtime run --preload rw=target/wasm32-wasip1/release/rust_wasm.wasm --invoke run tests/wasi/driver.wat
+;; 内存是 rust_wasm.wasm 导出的那一块，所以这里不能有数据段，输入用 store 指令写入 rw_alloc 申请的缓冲区
+(module
+  (import "rw" "memory" (memory 0))
+  (import "rw" "rw_alloc" (func $alloc (param i32) (result i32)))
+  (import "rw" "rw_free" (func $free (param i32 i32)))
+  (import "rw" "rw_xor_encrypt" (func $xor_encrypt (param i32 i32 i32 i32) (result i32)))
+
+  (func $check (param i32)
+    (if (i32.eqz (local.get 0)) (then unreachable)))
+
+  (func (export "run") (result i32)
+    (local $input i32) (local $out_len i32) (local $out i32)
+    ;; len 为 0 时返回 NULL
+    (call $check (i32.eqz (call $alloc (i32.const 0))))
+
+    ;; 1. 输入 "hans7"
+    (local.set $input (call $alloc (i32.const 5)))
+    (call $check (local.get $input))
+    (i32.store (local.get $input) (i32.const 0x736e6168))
+    (i32.store8 offset=4 (local.get $input) (i32.const 0x37))
+
+    ;; 2. out 为 NULL 时查询输出长度：BufferTooSmall ，*out_len = 8
+    (local.set $out_len (call $alloc (i32.const 4)))
+    (i32.store (local.get $out_len) (i32.const 0))
+    (call $check (i32.eq
+      (call $xor_encrypt (local.get $input) (i32.const 5) (i32.const 0) (local.get $out_len))
+      (i32.const 2)))
+    (call $check (i32.eq (i32.load (local.get $out_len)) (i32.const 8)))
+
+    ;; 3. 文本输出多申请 1 字节放结尾的 NUL
+    (local.set $out (call $alloc (i32.const 9)))
+    (i32.store (local.get $out_len) (i32.const 9))
+    (call $check (i32.eqz
+      (call $xor_encrypt (local.get $input) (i32.const 5) (local.get $out) (local.get $out_len))))
+
+    ;; 4. 结果为 "AAIaFV8=\0"
+    (call $check (i32.eq (i32.load (local.get $out_len)) (i32.const 8)))
+    (call $check (i64.eq (i64.load (local.get $out)) (i64.const 0x3d38564661494141)))
+    (call $check (i32.eqz (i32.load8_u offset=8 (local.get $out))))
+
+    (call $free (local.get $out) (i32.const 9))
+    (call $free (local.get $out_len) (i32.const 4))
+    (call $free (local.get $input) (i32.const 5))
+    (i32.const 0)))
@@ -0,0 +1,262 @@
+//! 用规范测试（spec test）格式的脚本检查 WasmInterpreter 每条指令的语义，
+//! 脚本在 tests/wast 下，按 i32 / i64 / f32 / f64 / 类型转换 / 内存 / bulk memory / 控制流分文件。
+//! 支持 module 、invoke 、assert_return 、assert_trap 、assert_exhaustion 、assert_invalid 和 assert_malformed
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rust_wasm::{InspectError, ModuleInfo, Trap, WasmInterpreter, WasmValue as Value};
+use wast::core::{AbstractHeapType, HeapType, NanPattern, WastArgCore, WastRetCore};
+use wast::parser::{self, ParseBuffer};
+use wast::{QuoteWat, Wast, WastArg, WastDirective, WastExecute, WastInvoke, WastRet};
+
+struct Instance {
+    interpreter: WasmInterpreter,
+    info: ModuleInfo,
+}
+
+impl Instance {
+    fn new(bytes: &[u8]) -> Result<Self, InspectError> {
+        Ok(Instance {
+            interpreter: WasmInterpreter::new(bytes)?,
+            info: ModuleInfo::parse(bytes)?,
+        })
+    }
+
+    // 按导出名调用。name section 里可能有同名的其他函数，因此先换成函数索引
+    fn invoke(&mut self, invoke: &WastInvoke) -> Result<Vec<Value>, Trap> {
+        let index = self
+            .info
+            .functions
+            .iter()
+            .find(|f| f.exports.iter().any(|e| e == invoke.name))
+            .unwrap_or_else(|| panic!("no export named {}", invoke.name))
+            .index;
+        let args: Vec<Value> = invoke.args.iter().map(arg).collect();
+        let execution = self
+            .interpreter
+            .invoke(&index.to_string(), &args)
+            .unwrap_or_else(|e| panic!("invoke {}: {e}", invoke.name));
+        match execution.trap {
+            Some(trap) => Err(trap),
+            None => Ok(execution.results),
+        }
+    }
+}
+
+fn is_extern(heap: &HeapType) -> bool {
+    matches!(
+        heap,
+        HeapType::Abstract {
+            ty: AbstractHeapType::Extern | AbstractHeapType::NoExtern,
+            ..
+        }
+    )
+}
+
+fn arg(arg: &WastArg) -> Value {
+    match arg {
+        WastArg::Core(WastArgCore::I32(v)) => Value::I32(*v),
+        WastArg::Core(WastArgCore::I64(v)) => Value::I64(*v),
+        WastArg::Core(WastArgCore::F32(v)) => Value::F32(v.bits),
+        WastArg::Core(WastArgCore::F64(v)) => Value::F64(v.bits),
+        WastArg::Core(WastArgCore::RefNull(heap)) if is_extern(heap) => Value::ExternRef(None),
+        WastArg::Core(WastArgCore::RefNull(_)) => Value::FuncRef(None),
+        WastArg::Core(WastArgCore::RefExtern(v)) => Value::ExternRef(Some(*v)),
+        other => panic!("unsupported argument {other:?}"),
+    }
+}
+
+// canonical NaN 的尾数只有最高位为 1 ；arithmetic NaN 的尾数最高位为 1 ，其余任意。符号位都不限
+fn float_matches(pattern: NanPattern<u64>, bits: u64, width: u32) -> bool {
+    let mantissa = if width == 32 { 23 } else { 52 };
+    let exponent = ((1u64 << (width - 1 - mantissa)) - 1) << mantissa;
+    let quiet = 1u64 << (mantissa - 1);
+    let abs = bits & !(1u64 << (width - 1));
+    match pattern {
+        NanPattern::CanonicalNan => abs == exponent | quiet,
+        NanPattern::ArithmeticNan => abs & (exponent | quiet) == exponent | quiet,
+        NanPattern::Value(expected) => bits == expected,
+    }
+}
+
+fn nan_pattern<T>(pattern: &NanPattern<T>, bits: impl Fn(&T) -> u64) -> NanPattern<u64> {
+    match pattern {
+        NanPattern::CanonicalNan => NanPattern::CanonicalNan,
+        NanPattern::ArithmeticNan => NanPattern::ArithmeticNan,
+        NanPattern::Value(v) => NanPattern::Value(bits(v)),
+    }
+}
+
+fn ret_matches(expected: &WastRet, actual: &Value) -> bool {
+    let WastRet::Core(expected) = expected else {
+        panic!("unsupported result {expected:?}");
+    };
+    match (expected, actual) {
+        (WastRetCore::I32(e), Value::I32(a)) => e == a,
+        (WastRetCore::I64(e), Value::I64(a)) => e == a,
+        (WastRetCore::F32(pattern), Value::F32(a)) => {
+            float_matches(nan_pattern(pattern, |v| v.bits as u64), *a as u64, 32)
+        }
+        (WastRetCore::F64(pattern), Value::F64(a)) => {
+            float_matches(nan_pattern(pattern, |v| v.bits), *a, 64)
+        }
+        (WastRetCore::RefNull(_), Value::FuncRef(None) | Value::ExternRef(None)) => true,
+        (WastRetCore::RefExtern(None), Value::ExternRef(Some(_))) => true,
+        (WastRetCore::RefExtern(Some(e)), Value::ExternRef(Some(a))) => e == a,
+        (WastRetCore::RefFunc(_), Value::FuncRef(Some(_))) => true,
+        _ => false,
+    }
+}
+
+fn run_script(path: &Path) -> Vec<String> {
+    let text = fs::read_to_string(path).unwrap();
+    let buffer = ParseBuffer::new(&text).unwrap();
+    let script =
+        parser::parse::<Wast>(&buffer).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    let mut instance: Option<Instance> = None;
+    let mut failures = Vec::new();
+    let mut fail = |span: wast::token::Span, message: String| {
+        let (line, _) = span.linecol_in(&text);
+        failures.push(format!("{}:{}: {message}", path.display(), line + 1));
+    };
+
+    for directive in script.directives {
+        let span = directive.span();
+        match directive {
+            WastDirective::Module(mut module) => {
+                let bytes = module.encode().unwrap();
+                match Instance::new(&bytes) {
+                    Ok(new) => instance = Some(new),
+                    Err(e) => {
+                        fail(span, format!("module failed to instantiate: {e}"));
+                        instance = None;
+                    }
+                }
+            }
+            WastDirective::Invoke(invoke) => {
+                let Some(instance) = instance.as_mut() else {
+                    fail(span, "no module instantiated".into());
+                    continue;
+                };
+                if let Err(trap) = instance.invoke(&invoke) {
+                    fail(span, format!("{} trapped: {trap}", invoke.name));
+                }
+            }
+            WastDirective::AssertReturn {
+                exec: WastExecute::Invoke(invoke),
+                results,
+                ..
+            } => {
+                let Some(instance) = instance.as_mut() else {
+                    fail(span, "no module instantiated".into());
+                    continue;
+                };
+                match instance.invoke(&invoke) {
+                    Ok(actual)
+                        if actual.len() == results.len()
+                            && results.iter().zip(&actual).all(|(e, a)| ret_matches(e, a)) => {}
+                    Ok(actual) => fail(
+                        span,
+                        format!("{}: expected {results:?}, got {actual:?}", invoke.name),
+                    ),
+                    Err(trap) => fail(span, format!("{} trapped: {trap}", invoke.name)),
+                }
+            }
+            WastDirective::AssertTrap {
+                exec: WastExecute::Invoke(invoke),
+                message,
+                ..
+            } => {
+                let Some(instance) = instance.as_mut() else {
+                    fail(span, "no module instantiated".into());
+                    continue;
+                };
+                match instance.invoke(&invoke) {
+                    Err(trap) if trap.to_string().starts_with(message) => {}
+                    other => fail(
+                        span,
+                        format!("{}: expected trap {message:?}, got {other:?}", invoke.name),
+                    ),
+                }
+            }
+            // 实例化时（数据段 / 元素段越界、start 函数）trap
+            WastDirective::AssertTrap {
+                exec: WastExecute::Wat(mut module),
+                message,
+                ..
+            } => {
+                let bytes = module.encode().unwrap();
+                match WasmInterpreter::new(&bytes) {
+                    Err(InspectError::Instantiation(trap))
+                        if trap.to_string().starts_with(message) => {}
+                    Err(e) => fail(span, format!("expected trap {message:?}, got {e}")),
+                    Ok(_) => fail(span, format!("expected trap {message:?}")),
+                }
+            }
+            WastDirective::AssertExhaustion { call, message, .. } => {
+                let Some(instance) = instance.as_mut() else {
+                    fail(span, "no module instantiated".into());
+                    continue;
+                };
+                match instance.invoke(&call) {
+                    Err(trap) if trap.to_string().starts_with(message) => {}
+                    other => fail(
+                        span,
+                        format!("{}: expected {message:?}, got {other:?}", call.name),
+                    ),
+                }
+            }
+            // 不合法的模块必须在 WasmInterpreter::new 中被拒绝，不能等到执行时
+            WastDirective::AssertInvalid {
+                mut module,
+                message,
+                ..
+            } => {
+                if let Ok(bytes) = module.encode()
+                    && !matches!(
+                        WasmInterpreter::new(&bytes),
+                        Err(InspectError::InvalidWasm { .. })
+                    )
+                {
+                    fail(span, format!("expected invalid module: {message}"));
+                }
+            }
+            WastDirective::AssertMalformed {
+                module: QuoteWat::Wat(mut module),
+                message,
+                ..
+            } => {
+                if let Ok(bytes) = module.encode()
+                    && WasmInterpreter::new(&bytes).is_ok()
+                {
+                    fail(span, format!("expected malformed module: {message}"));
+                }
+            }
+            // 文本格式的 assert_malformed 只检查 wat 解析器
+            WastDirective::AssertMalformed { .. } => {}
+            other => panic!("unsupported directive {other:?}"),
+        }
+    }
+    failures
+}
+
+#[test]
+fn wast_scripts_test() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/wast");
+    let mut scripts: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wast"))
+        .collect();
+    scripts.sort();
+    assert!(!scripts.is_empty());
+
+    let failures: Vec<String> = scripts.iter().flat_map(|path| run_script(path)).collect();
+    assert!(
+        failures.is_empty(),
+        "{} failures:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,141 @@
+;; bulk memory 和 reference types ：memory.fill / copy / init 、data.drop 、table.* 、elem.drop
+(module
+  (memory 1)
+  (data $passive "\aa\bb\cc\dd")
+  (data (i32.const 0) "\01\02\03\04\05\06\07\08")
+
+  (func (export "load8") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "fill") (param i32 i32 i32) (memory.fill (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "copy") (param i32 i32 i32) (memory.copy (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "init") (param i32 i32 i32) (memory.init $passive (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "drop") (data.drop $passive))
+)
+
+;; fill 只写入低 8 位
+(invoke "fill" (i32.const 100) (i32.const 0x1ff) (i32.const 3))
+(assert_return (invoke "load8" (i32.const 99)) (i32.const 0))
+(assert_return (invoke "load8" (i32.const 100)) (i32.const 0xff))
+(assert_return (invoke "load8" (i32.const 102)) (i32.const 0xff))
+(assert_return (invoke "load8" (i32.const 103)) (i32.const 0))
+
+;; 区间重叠时按 memmove 语义复制，两个方向都要正确
+(invoke "copy" (i32.const 2) (i32.const 0) (i32.const 4))
+(assert_return (invoke "load8" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "load8" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "load8" (i32.const 5)) (i32.const 4))
+(assert_return (invoke "load8" (i32.const 6)) (i32.const 7))
+(invoke "copy" (i32.const 0) (i32.const 2) (i32.const 4))
+(assert_return (invoke "load8" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "load8" (i32.const 3)) (i32.const 4))
+(assert_return (invoke "load8" (i32.const 4)) (i32.const 3))
+
+;; 长度为 0 时目标正好在末尾是合法的，越过末尾才 trap ；越界时不写入任何字节
+(invoke "fill" (i32.const 65536) (i32.const 0) (i32.const 0))
+(invoke "copy" (i32.const 65536) (i32.const 65536) (i32.const 0))
+(assert_trap (invoke "fill" (i32.const 65537) (i32.const 0) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "fill" (i32.const 65535) (i32.const 7) (i32.const 2)) "out of bounds memory access")
+(assert_return (invoke "load8" (i32.const 65535)) (i32.const 0))
+(assert_trap (invoke "copy" (i32.const 0) (i32.const 65535) (i32.const 2)) "out of bounds memory access")
+(assert_trap (invoke "copy" (i32.const 65535) (i32.const 0) (i32.const 2)) "out of bounds memory access")
+(assert_trap (invoke "copy" (i32.const 0) (i32.const 0) (i32.const -1)) "out of bounds memory access")
+(assert_return (invoke "load8" (i32.const 0)) (i32.const 1))
+
+(invoke "init" (i32.const 200) (i32.const 1) (i32.const 3))
+(assert_return (invoke "load8" (i32.const 200)) (i32.const 0xbb))
+(assert_return (invoke "load8" (i32.const 202)) (i32.const 0xdd))
+(invoke "init" (i32.const 0) (i32.const 4) (i32.const 0))
+(assert_trap (invoke "init" (i32.const 0) (i32.const 5) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "init" (i32.const 0) (i32.const 2) (i32.const 3)) "out of bounds memory access")
+(assert_trap (invoke "init" (i32.const 65535) (i32.const 0) (i32.const 2)) "out of bounds memory access")
+
+;; drop 之后段长度视为 0 ，重复 drop 合法
+(invoke "drop")
+(invoke "drop")
+(invoke "init" (i32.const 0) (i32.const 0) (i32.const 0))
+(assert_trap (invoke "init" (i32.const 0) (i32.const 0) (i32.const 1)) "out of bounds memory access")
+
+(module
+  (type $i32 (func (result i32)))
+  (table $t 4 8 funcref)
+  (table $e 2 externref)
+  (func $zero (result i32) (i32.const 0))
+  (func $one (result i32) (i32.const 1))
+  (func $two (result i32) (i32.const 2))
+  (func $add (param i32 i32) (result i32) (i32.add (local.get 0) (local.get 1)))
+  (elem (table $t) (i32.const 0) func $zero $one)
+  (elem $passive func $two $one $zero)
+  (elem declare func $add)
+
+  (func (export "call") (param i32) (result i32) (call_indirect $t (type $i32) (local.get 0)))
+  (func (export "is_null") (param i32) (result i32) (ref.is_null (table.get $t (local.get 0))))
+  (func (export "size") (result i32) (table.size $t))
+  (func (export "grow") (param i32) (result i32) (table.grow $t (ref.null func) (local.get 0)))
+  (func (export "fill") (param i32 i32) (table.fill $t (local.get 0) (ref.func $two) (local.get 1)))
+  (func (export "clear") (param i32) (table.set $t (local.get 0) (ref.null func)))
+  (func (export "copy") (param i32 i32 i32) (table.copy $t $t (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "init") (param i32 i32 i32) (table.init $t $passive (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "drop") (elem.drop $passive))
+  (func (export "set_add") (param i32) (table.set $t (local.get 0) (ref.func $add)))
+  (func (export "set_extern") (param i32 externref) (table.set $e (local.get 0) (local.get 1)))
+  (func (export "get_extern") (param i32) (result externref) (table.get $e (local.get 0)))
+  (func (export "func_ref") (result funcref) (ref.func $add))
+)
+
+(assert_return (invoke "call" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 1))
+(assert_trap (invoke "call" (i32.const 2)) "uninitialized element")
+(assert_trap (invoke "call" (i32.const 4)) "undefined element")
+(assert_trap (invoke "call" (i32.const -1)) "undefined element")
+(invoke "set_add" (i32.const 3))
+(assert_trap (invoke "call" (i32.const 3)) "indirect call type mismatch")
+(assert_return (invoke "is_null" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "is_null" (i32.const 3)) (i32.const 0))
+(assert_trap (invoke "is_null" (i32.const 4)) "out of bounds table access")
+(assert_return (invoke "func_ref") (ref.func))
+
+(invoke "init" (i32.const 1) (i32.const 0) (i32.const 3))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 0))
+(assert_trap (invoke "init" (i32.const 2) (i32.const 0) (i32.const 3)) "out of bounds table access")
+(assert_trap (invoke "init" (i32.const 0) (i32.const 2) (i32.const 2)) "out of bounds table access")
+(invoke "drop")
+(invoke "init" (i32.const 0) (i32.const 0) (i32.const 0))
+(assert_trap (invoke "init" (i32.const 0) (i32.const 0) (i32.const 1)) "out of bounds table access")
+
+;; table.copy 重叠时同样按 memmove 语义
+(invoke "copy" (i32.const 0) (i32.const 1) (i32.const 3))
+(assert_return (invoke "call" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "call" (i32.const 2)) (i32.const 0))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 0))
+(assert_trap (invoke "copy" (i32.const 3) (i32.const 0) (i32.const 2)) "out of bounds table access")
+
+(invoke "clear" (i32.const 0))
+(assert_trap (invoke "call" (i32.const 0)) "uninitialized element")
+(invoke "fill" (i32.const 2) (i32.const 2))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 2))
+(assert_trap (invoke "fill" (i32.const 3) (i32.const 2)) "out of bounds table access")
+(invoke "fill" (i32.const 4) (i32.const 0))
+
+(assert_return (invoke "size") (i32.const 4))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 4))
+(assert_return (invoke "size") (i32.const 6))
+(assert_trap (invoke "call" (i32.const 5)) "uninitialized element")
+(assert_return (invoke "grow" (i32.const 3)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 6))
+(assert_return (invoke "size") (i32.const 8))
+
+(assert_return (invoke "get_extern" (i32.const 0)) (ref.null extern))
+(invoke "set_extern" (i32.const 1) (ref.extern 7))
+(assert_return (invoke "get_extern" (i32.const 1)) (ref.extern 7))
+(assert_trap (invoke "set_extern" (i32.const 2) (ref.null extern)) "out of bounds table access")
+
+;; 越界的元素段在实例化时 trap
+(assert_trap
+  (module (table 1 funcref) (func $f) (elem (i32.const 1) $f))
+  "out of bounds table access"
+)
+
+(assert_invalid (module (func (drop (table.size 0)))) "unknown table")
+(assert_invalid (module (memory 1) (func (data.drop 0))) "unknown data segment")
+(assert_invalid (module (table 1 funcref) (func (drop (ref.func 0)))) "undeclared function reference")
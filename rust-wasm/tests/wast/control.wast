@@ -0,0 +1,195 @@
+;; 控制流、调用、全局变量和 trap ：block / loop / if 的结果、多返回值、br_table 、递归、调用栈耗尽、start 函数
+(module
+  (global $counter (mut i32) (i32.const 0))
+  (global $base i64 (i64.const 40))
+
+  (func (export "unreachable") (unreachable))
+  (func (export "unreachable_after_value") (result i32) (i32.const 1) (unreachable))
+  (func (export "local_default") (result i64) (local i64) (local.get 0))
+  (func (export "select") (param i32) (result i32) (select (i32.const 10) (i32.const 20) (local.get 0)))
+  (func (export "select_f64") (param i32) (result f64)
+    (select (result f64) (f64.const -0x0p+0) (f64.const nan:0x4000000000000) (local.get 0))
+  )
+
+  (func (export "if") (param i32) (result i32)
+    (if (result i32) (local.get 0) (then (i32.const 1)) (else (i32.const 2)))
+  )
+  (func (export "if_no_else") (param i32) (result i32)
+    (local i32)
+    (if (local.get 0) (then (local.set 1 (i32.const 7))))
+    (local.get 1)
+  )
+
+  ;; br 带出 block 的结果，栈上多余的值被丢弃
+  (func (export "br_value") (result i32)
+    (block (result i32) (i32.const 9) (i32.const 1) (br 0 (i32.const 2)) (drop) (drop) (i32.const 3))
+  )
+  (func (export "br_if") (param i32) (result i32)
+    (block (result i32) (drop (br_if 0 (i32.const 5) (local.get 0))) (i32.const 6))
+  )
+
+  (func (export "br_table") (param i32) (result i32)
+    (block $default
+      (block $two
+        (block $one
+          (block $zero
+            (br_table $zero $one $two $default (local.get 0))
+          )
+          (return (i32.const 100))
+        )
+        (return (i32.const 101))
+      )
+      (return (i32.const 102))
+    )
+    (i32.const 103)
+  )
+  (func (export "br_table_value") (param i32) (result i32)
+    (block $outer (result i32)
+      (i32.const 10)
+      (block $inner (param i32) (result i32)
+        (br_table $inner $outer (local.get 0))
+      )
+      (i32.const 1)
+      (i32.add)
+    )
+  )
+
+  ;; 多返回值的函数和 block ，block 也可以带参数
+  (func $pair (result i32 i64) (i32.const 1) (i64.const 2))
+  (func (export "multi") (result i64 i32)
+    (local i64)
+    (call $pair)
+    (local.set 0)
+    (drop)
+    (local.get 0)
+    (i32.const 4)
+  )
+  (func (export "swap") (param i32 i32) (result i32 i32) (local.get 1) (local.get 0))
+  (func (export "block_param") (result i32)
+    (i32.const 2)
+    (block (param i32) (result i32) (i32.const 3) (i32.mul))
+  )
+  (func (export "loop_param") (param i32) (result i32)
+    (i32.const 0)
+    (loop $l (param i32) (result i32)
+      (i32.add (local.get 0))
+      (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+      (br_if $l (i32.ne (local.get 0) (i32.const 0)))
+    )
+  )
+
+  ;; 嵌套 loop 中 br 到外层 block 跳出两层
+  (func (export "nested") (param i32) (result i32)
+    (local $i i32) (local $j i32) (local $n i32)
+    (block $done
+      (loop $outer
+        (local.set $j (i32.const 0))
+        (loop $inner
+          (local.set $n (i32.add (local.get $n) (i32.const 1)))
+          (br_if $done (i32.ge_u (local.get $n) (local.get 0)))
+          (local.set $j (i32.add (local.get $j) (i32.const 1)))
+          (br_if $inner (i32.lt_u (local.get $j) (i32.const 3)))
+        )
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br_if $outer (i32.lt_u (local.get $i) (i32.const 4)))
+      )
+    )
+    (i32.add (i32.mul (local.get $i) (i32.const 100)) (local.get $n))
+  )
+
+  (func $fac (export "fac") (param i64) (result i64)
+    (if (result i64) (i64.eqz (local.get 0))
+      (then (i64.const 1))
+      (else (i64.mul (local.get 0) (call $fac (i64.sub (local.get 0) (i64.const 1)))))
+    )
+  )
+  (func $even (export "even") (param i32) (result i32)
+    (if (result i32) (i32.eqz (local.get 0))
+      (then (i32.const 1))
+      (else (call $odd (i32.sub (local.get 0) (i32.const 1))))
+    )
+  )
+  (func $odd (param i32) (result i32)
+    (if (result i32) (i32.eqz (local.get 0))
+      (then (i32.const 0))
+      (else (call $even (i32.sub (local.get 0) (i32.const 1))))
+    )
+  )
+  (func $runaway (export "runaway") (call $runaway))
+  (func (export "early_return") (param i32) (result i32)
+    (loop (drop (br_if 1 (i32.const 5) (local.get 0))))
+    (i32.const 6)
+  )
+
+  (func (export "bump") (result i32)
+    (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+    (global.get $counter)
+  )
+  (func (export "base") (result i64) (i64.add (global.get $base) (i64.const 2)))
+)
+
+(assert_trap (invoke "unreachable") "unreachable")
+(assert_trap (invoke "unreachable_after_value") "unreachable")
+(assert_return (invoke "local_default") (i64.const 0))
+(assert_return (invoke "select" (i32.const 1)) (i32.const 10))
+(assert_return (invoke "select" (i32.const 0)) (i32.const 20))
+(assert_return (invoke "select" (i32.const -1)) (i32.const 10))
+(assert_return (invoke "select_f64" (i32.const 1)) (f64.const -0x0p+0))
+(assert_return (invoke "select_f64" (i32.const 0)) (f64.const nan:0x4000000000000))
+
+(assert_return (invoke "if" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "if" (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "if_no_else" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "if_no_else" (i32.const 3)) (i32.const 7))
+(assert_return (invoke "br_value") (i32.const 2))
+(assert_return (invoke "br_if" (i32.const 0)) (i32.const 6))
+(assert_return (invoke "br_if" (i32.const 1)) (i32.const 5))
+
+;; 超出范围的索引（包括负数按无符号解释）走默认分支
+(assert_return (invoke "br_table" (i32.const 0)) (i32.const 100))
+(assert_return (invoke "br_table" (i32.const 1)) (i32.const 101))
+(assert_return (invoke "br_table" (i32.const 2)) (i32.const 102))
+(assert_return (invoke "br_table" (i32.const 3)) (i32.const 103))
+(assert_return (invoke "br_table" (i32.const 1000)) (i32.const 103))
+(assert_return (invoke "br_table" (i32.const -1)) (i32.const 103))
+(assert_return (invoke "br_table_value" (i32.const 0)) (i32.const 11))
+(assert_return (invoke "br_table_value" (i32.const 1)) (i32.const 10))
+(assert_return (invoke "br_table_value" (i32.const 9)) (i32.const 10))
+
+(assert_return (invoke "multi") (i64.const 2) (i32.const 4))
+(assert_return (invoke "swap" (i32.const 1) (i32.const 2)) (i32.const 2) (i32.const 1))
+(assert_return (invoke "block_param") (i32.const 6))
+(assert_return (invoke "loop_param" (i32.const 4)) (i32.const 10))
+
+(assert_return (invoke "nested" (i32.const 2)) (i32.const 2))
+(assert_return (invoke "nested" (i32.const 7)) (i32.const 207))
+(assert_return (invoke "nested" (i32.const 100)) (i32.const 412))
+
+(assert_return (invoke "fac" (i64.const 20)) (i64.const 2432902008176640000))
+(assert_return (invoke "fac" (i64.const 25)) (i64.const 7034535277573963776))
+(assert_return (invoke "even" (i32.const 100)) (i32.const 1))
+(assert_return (invoke "even" (i32.const 77)) (i32.const 0))
+(assert_exhaustion (invoke "runaway") "call stack exhausted")
+(assert_return (invoke "early_return" (i32.const 1)) (i32.const 5))
+(assert_return (invoke "early_return" (i32.const 0)) (i32.const 6))
+
+(assert_return (invoke "bump") (i32.const 1))
+(assert_return (invoke "bump") (i32.const 2))
+(assert_return (invoke "base") (i64.const 42))
+
+;; start 函数在实例化时执行，trap 会让实例化失败
+(assert_trap (module (func $start (unreachable)) (start $start)) "unreachable")
+(module
+  (global $g (mut i32) (i32.const 0))
+  (func $start (global.set $g (i32.const 42)))
+  (start $start)
+  (func (export "get") (result i32) (global.get $g))
+)
+(assert_return (invoke "get") (i32.const 42))
+
+(assert_invalid (module (func (br 1))) "unknown label")
+(assert_invalid (module (global i32 (i32.const 0)) (func (global.set 0 (i32.const 1)))) "global is immutable")
+(assert_invalid (module (func (result i32) (block (result i32) (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (call 1))) "unknown function")
+(assert_malformed (module binary "\00asm\01\00\00\00\01") "unexpected end")
+(assert_malformed (module binary "\00asm\02\00\00\00") "unknown binary version")
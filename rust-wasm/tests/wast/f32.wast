@@ -0,0 +1,99 @@
+;; f32 的算术、舍入、比较和位运算指令。NaN 结果按规范用 nan:canonical / nan:arithmetic 匹配
+(module
+  (func (export "add") (param f32 f32) (result f32) (f32.add (local.get 0) (local.get 1)))
+  (func (export "sub") (param f32 f32) (result f32) (f32.sub (local.get 0) (local.get 1)))
+  (func (export "mul") (param f32 f32) (result f32) (f32.mul (local.get 0) (local.get 1)))
+  (func (export "div") (param f32 f32) (result f32) (f32.div (local.get 0) (local.get 1)))
+  (func (export "min") (param f32 f32) (result f32) (f32.min (local.get 0) (local.get 1)))
+  (func (export "max") (param f32 f32) (result f32) (f32.max (local.get 0) (local.get 1)))
+  (func (export "copysign") (param f32 f32) (result f32) (f32.copysign (local.get 0) (local.get 1)))
+  (func (export "sqrt") (param f32) (result f32) (f32.sqrt (local.get 0)))
+  (func (export "ceil") (param f32) (result f32) (f32.ceil (local.get 0)))
+  (func (export "floor") (param f32) (result f32) (f32.floor (local.get 0)))
+  (func (export "trunc") (param f32) (result f32) (f32.trunc (local.get 0)))
+  (func (export "nearest") (param f32) (result f32) (f32.nearest (local.get 0)))
+  (func (export "abs") (param f32) (result f32) (f32.abs (local.get 0)))
+  (func (export "neg") (param f32) (result f32) (f32.neg (local.get 0)))
+  (func (export "eq") (param f32 f32) (result i32) (f32.eq (local.get 0) (local.get 1)))
+  (func (export "ne") (param f32 f32) (result i32) (f32.ne (local.get 0) (local.get 1)))
+  (func (export "lt") (param f32 f32) (result i32) (f32.lt (local.get 0) (local.get 1)))
+  (func (export "le") (param f32 f32) (result i32) (f32.le (local.get 0) (local.get 1)))
+  (func (export "gt") (param f32 f32) (result i32) (f32.gt (local.get 0) (local.get 1)))
+  (func (export "ge") (param f32 f32) (result i32) (f32.ge (local.get 0) (local.get 1)))
+)
+
+(assert_return (invoke "add" (f32.const 1.5) (f32.const 2.25)) (f32.const 3.75))
+(assert_return (invoke "add" (f32.const -0x0p+0) (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "add" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "add" (f32.const 0x1p+127) (f32.const 0x1p+127)) (f32.const inf))
+(assert_return (invoke "add" (f32.const inf) (f32.const -inf)) (f32.const nan:canonical))
+(assert_return (invoke "add" (f32.const nan) (f32.const 1)) (f32.const nan:canonical))
+(assert_return (invoke "add" (f32.const nan:0x200000) (f32.const 1)) (f32.const nan:arithmetic))
+(assert_return (invoke "add" (f32.const 0x1p-149) (f32.const 0x1p-149)) (f32.const 0x1p-148))
+(assert_return (invoke "sub" (f32.const 0x0p+0) (f32.const 0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "sub" (f32.const inf) (f32.const inf)) (f32.const nan:canonical))
+(assert_return (invoke "mul" (f32.const -0x0p+0) (f32.const 1)) (f32.const -0x0p+0))
+(assert_return (invoke "mul" (f32.const inf) (f32.const 0)) (f32.const nan:canonical))
+(assert_return (invoke "mul" (f32.const -inf) (f32.const -2)) (f32.const inf))
+(assert_return (invoke "div" (f32.const 1) (f32.const 0)) (f32.const inf))
+(assert_return (invoke "div" (f32.const 1) (f32.const -0x0p+0)) (f32.const -inf))
+(assert_return (invoke "div" (f32.const 0) (f32.const 0)) (f32.const nan:canonical))
+(assert_return (invoke "div" (f32.const -nan) (f32.const 1)) (f32.const nan:canonical))
+(assert_return (invoke "div" (f32.const 7) (f32.const 2)) (f32.const 3.5))
+
+;; min / max 中 -0 小于 +0 ，任一操作数为 NaN 时结果为 NaN
+(assert_return (invoke "min" (f32.const 0x0p+0) (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "min" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "max" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "max" (f32.const 0x0p+0) (f32.const -0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "min" (f32.const nan) (f32.const 1)) (f32.const nan:canonical))
+(assert_return (invoke "min" (f32.const -inf) (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "max" (f32.const 1) (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "min" (f32.const -inf) (f32.const 1)) (f32.const -inf))
+(assert_return (invoke "max" (f32.const -inf) (f32.const 1)) (f32.const 1))
+
+;; abs / neg / copysign 只改符号位，NaN 的 payload 原样保留
+(assert_return (invoke "abs" (f32.const -nan:0x200000)) (f32.const nan:0x200000))
+(assert_return (invoke "abs" (f32.const -0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "neg" (f32.const nan:0x200000)) (f32.const -nan:0x200000))
+(assert_return (invoke "neg" (f32.const 0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "copysign" (f32.const 1) (f32.const -nan)) (f32.const -1))
+(assert_return (invoke "copysign" (f32.const -nan:0x200000) (f32.const 1)) (f32.const nan:0x200000))
+(assert_return (invoke "copysign" (f32.const inf) (f32.const -0x0p+0)) (f32.const -inf))
+
+(assert_return (invoke "sqrt" (f32.const 4)) (f32.const 2))
+(assert_return (invoke "sqrt" (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "sqrt" (f32.const -1)) (f32.const nan:canonical))
+(assert_return (invoke "sqrt" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "sqrt" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+
+;; nearest 按 ties-to-even 舍入，结果为 0 时保留符号
+(assert_return (invoke "nearest" (f32.const 2.5)) (f32.const 2))
+(assert_return (invoke "nearest" (f32.const 3.5)) (f32.const 4))
+(assert_return (invoke "nearest" (f32.const -0.5)) (f32.const -0x0p+0))
+(assert_return (invoke "nearest" (f32.const -1.5)) (f32.const -2))
+(assert_return (invoke "nearest" (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "ceil" (f32.const -0.5)) (f32.const -0x0p+0))
+(assert_return (invoke "ceil" (f32.const 1.25)) (f32.const 2))
+(assert_return (invoke "floor" (f32.const -0.5)) (f32.const -1))
+(assert_return (invoke "floor" (f32.const 0x1p-1)) (f32.const 0))
+(assert_return (invoke "trunc" (f32.const -1.75)) (f32.const -1))
+(assert_return (invoke "trunc" (f32.const -0.75)) (f32.const -0x0p+0))
+(assert_return (invoke "trunc" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "ceil" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "floor" (f32.const -nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "trunc" (f32.const nan:0x200000)) (f32.const nan:arithmetic))
+(assert_return (invoke "nearest" (f32.const -nan:0x200000)) (f32.const nan:arithmetic))
+
+;; 与 NaN 比较时只有 ne 为真，+0 与 -0 相等
+(assert_return (invoke "eq" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "ne" (f32.const nan) (f32.const nan)) (i32.const 1))
+(assert_return (invoke "lt" (f32.const nan) (f32.const 1)) (i32.const 0))
+(assert_return (invoke "le" (f32.const 1) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "gt" (f32.const nan) (f32.const -inf)) (i32.const 0))
+(assert_return (invoke "ge" (f32.const inf) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "eq" (f32.const 0x0p+0) (f32.const -0x0p+0)) (i32.const 1))
+(assert_return (invoke "lt" (f32.const -0x0p+0) (f32.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "le" (f32.const -0x0p+0) (f32.const 0x0p+0)) (i32.const 1))
+(assert_return (invoke "gt" (f32.const inf) (f32.const 0x1p+127)) (i32.const 1))
+(assert_return (invoke "ge" (f32.const -inf) (f32.const -inf)) (i32.const 1))
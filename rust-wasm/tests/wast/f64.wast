@@ -0,0 +1,99 @@
+;; f64 的算术、舍入、比较和位运算指令。NaN 结果按规范用 nan:canonical / nan:arithmetic 匹配
+(module
+  (func (export "add") (param f64 f64) (result f64) (f64.add (local.get 0) (local.get 1)))
+  (func (export "sub") (param f64 f64) (result f64) (f64.sub (local.get 0) (local.get 1)))
+  (func (export "mul") (param f64 f64) (result f64) (f64.mul (local.get 0) (local.get 1)))
+  (func (export "div") (param f64 f64) (result f64) (f64.div (local.get 0) (local.get 1)))
+  (func (export "min") (param f64 f64) (result f64) (f64.min (local.get 0) (local.get 1)))
+  (func (export "max") (param f64 f64) (result f64) (f64.max (local.get 0) (local.get 1)))
+  (func (export "copysign") (param f64 f64) (result f64) (f64.copysign (local.get 0) (local.get 1)))
+  (func (export "sqrt") (param f64) (result f64) (f64.sqrt (local.get 0)))
+  (func (export "ceil") (param f64) (result f64) (f64.ceil (local.get 0)))
+  (func (export "floor") (param f64) (result f64) (f64.floor (local.get 0)))
+  (func (export "trunc") (param f64) (result f64) (f64.trunc (local.get 0)))
+  (func (export "nearest") (param f64) (result f64) (f64.nearest (local.get 0)))
+  (func (export "abs") (param f64) (result f64) (f64.abs (local.get 0)))
+  (func (export "neg") (param f64) (result f64) (f64.neg (local.get 0)))
+  (func (export "eq") (param f64 f64) (result i32) (f64.eq (local.get 0) (local.get 1)))
+  (func (export "ne") (param f64 f64) (result i32) (f64.ne (local.get 0) (local.get 1)))
+  (func (export "lt") (param f64 f64) (result i32) (f64.lt (local.get 0) (local.get 1)))
+  (func (export "le") (param f64 f64) (result i32) (f64.le (local.get 0) (local.get 1)))
+  (func (export "gt") (param f64 f64) (result i32) (f64.gt (local.get 0) (local.get 1)))
+  (func (export "ge") (param f64 f64) (result i32) (f64.ge (local.get 0) (local.get 1)))
+)
+
+(assert_return (invoke "add" (f64.const 1.5) (f64.const 2.25)) (f64.const 3.75))
+(assert_return (invoke "add" (f64.const -0x0p+0) (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "add" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "add" (f64.const 0x1p+1023) (f64.const 0x1p+1023)) (f64.const inf))
+(assert_return (invoke "add" (f64.const inf) (f64.const -inf)) (f64.const nan:canonical))
+(assert_return (invoke "add" (f64.const nan) (f64.const 1)) (f64.const nan:canonical))
+(assert_return (invoke "add" (f64.const nan:0x4000000000000) (f64.const 1)) (f64.const nan:arithmetic))
+(assert_return (invoke "add" (f64.const 0x0.0000000000001p-1022) (f64.const 0x0.0000000000001p-1022)) (f64.const 0x0.0000000000002p-1022))
+(assert_return (invoke "sub" (f64.const 0x0p+0) (f64.const 0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "sub" (f64.const inf) (f64.const inf)) (f64.const nan:canonical))
+(assert_return (invoke "mul" (f64.const -0x0p+0) (f64.const 1)) (f64.const -0x0p+0))
+(assert_return (invoke "mul" (f64.const inf) (f64.const 0)) (f64.const nan:canonical))
+(assert_return (invoke "mul" (f64.const -inf) (f64.const -2)) (f64.const inf))
+(assert_return (invoke "div" (f64.const 1) (f64.const 0)) (f64.const inf))
+(assert_return (invoke "div" (f64.const 1) (f64.const -0x0p+0)) (f64.const -inf))
+(assert_return (invoke "div" (f64.const 0) (f64.const 0)) (f64.const nan:canonical))
+(assert_return (invoke "div" (f64.const -nan) (f64.const 1)) (f64.const nan:canonical))
+(assert_return (invoke "div" (f64.const 7) (f64.const 2)) (f64.const 3.5))
+
+;; min / max 中 -0 小于 +0 ，任一操作数为 NaN 时结果为 NaN
+(assert_return (invoke "min" (f64.const 0x0p+0) (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "min" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "max" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "max" (f64.const 0x0p+0) (f64.const -0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "min" (f64.const nan) (f64.const 1)) (f64.const nan:canonical))
+(assert_return (invoke "min" (f64.const -inf) (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "max" (f64.const 1) (f64.const nan:0x4000000000000)) (f64.const nan:arithmetic))
+(assert_return (invoke "min" (f64.const -inf) (f64.const 1)) (f64.const -inf))
+(assert_return (invoke "max" (f64.const -inf) (f64.const 1)) (f64.const 1))
+
+;; abs / neg / copysign 只改符号位，NaN 的 payload 原样保留
+(assert_return (invoke "abs" (f64.const -nan:0x4000000000000)) (f64.const nan:0x4000000000000))
+(assert_return (invoke "abs" (f64.const -0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "neg" (f64.const nan:0x4000000000000)) (f64.const -nan:0x4000000000000))
+(assert_return (invoke "neg" (f64.const 0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "copysign" (f64.const 1) (f64.const -nan)) (f64.const -1))
+(assert_return (invoke "copysign" (f64.const -nan:0x4000000000000) (f64.const 1)) (f64.const nan:0x4000000000000))
+(assert_return (invoke "copysign" (f64.const inf) (f64.const -0x0p+0)) (f64.const -inf))
+
+(assert_return (invoke "sqrt" (f64.const 4)) (f64.const 2))
+(assert_return (invoke "sqrt" (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "sqrt" (f64.const -1)) (f64.const nan:canonical))
+(assert_return (invoke "sqrt" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "sqrt" (f64.const nan:0x4000000000000)) (f64.const nan:arithmetic))
+
+;; nearest 按 ties-to-even 舍入，结果为 0 时保留符号
+(assert_return (invoke "nearest" (f64.const 2.5)) (f64.const 2))
+(assert_return (invoke "nearest" (f64.const 3.5)) (f64.const 4))
+(assert_return (invoke "nearest" (f64.const -0.5)) (f64.const -0x0p+0))
+(assert_return (invoke "nearest" (f64.const -1.5)) (f64.const -2))
+(assert_return (invoke "nearest" (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "ceil" (f64.const -0.5)) (f64.const -0x0p+0))
+(assert_return (invoke "ceil" (f64.const 1.25)) (f64.const 2))
+(assert_return (invoke "floor" (f64.const -0.5)) (f64.const -1))
+(assert_return (invoke "floor" (f64.const 0x1p-1)) (f64.const 0))
+(assert_return (invoke "trunc" (f64.const -1.75)) (f64.const -1))
+(assert_return (invoke "trunc" (f64.const -0.75)) (f64.const -0x0p+0))
+(assert_return (invoke "trunc" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "ceil" (f64.const nan:0x4000000000000)) (f64.const nan:arithmetic))
+(assert_return (invoke "floor" (f64.const -nan:0x4000000000000)) (f64.const nan:arithmetic))
+(assert_return (invoke "trunc" (f64.const nan:0x4000000000000)) (f64.const nan:arithmetic))
+(assert_return (invoke "nearest" (f64.const -nan:0x4000000000000)) (f64.const nan:arithmetic))
+
+;; 与 NaN 比较时只有 ne 为真，+0 与 -0 相等
+(assert_return (invoke "eq" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "ne" (f64.const nan) (f64.const nan)) (i32.const 1))
+(assert_return (invoke "lt" (f64.const nan) (f64.const 1)) (i32.const 0))
+(assert_return (invoke "le" (f64.const 1) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "gt" (f64.const nan) (f64.const -inf)) (i32.const 0))
+(assert_return (invoke "ge" (f64.const inf) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "eq" (f64.const 0x0p+0) (f64.const -0x0p+0)) (i32.const 1))
+(assert_return (invoke "lt" (f64.const -0x0p+0) (f64.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "le" (f64.const -0x0p+0) (f64.const 0x0p+0)) (i32.const 1))
+(assert_return (invoke "gt" (f64.const inf) (f64.const 0x1p+1023)) (i32.const 1))
+(assert_return (invoke "ge" (f64.const -inf) (f64.const -inf)) (i32.const 1))
@@ -0,0 +1,118 @@
+;; 内存的 load / store 、符号扩展、偏移量、越界 trap 以及 memory.size / memory.grow
+(module
+  (memory 1 3)
+  (data (i32.const 0) "\01\02\03\04\05\06\07\08\80\ff")
+  (data (i32.const 65532) "\aa\bb\cc\dd")
+
+  (func (export "i32.load") (param i32) (result i32) (i32.load (local.get 0)))
+  (func (export "i32.load_off") (param i32) (result i32) (i32.load offset=4 (local.get 0)))
+  (func (export "i32.load_max_off") (param i32) (result i32) (i32.load offset=0xffffffff (local.get 0)))
+  (func (export "i32.load8_s") (param i32) (result i32) (i32.load8_s (local.get 0)))
+  (func (export "i32.load8_u") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "i32.load16_s") (param i32) (result i32) (i32.load16_s (local.get 0)))
+  (func (export "i32.load16_u") (param i32) (result i32) (i32.load16_u (local.get 0)))
+  (func (export "i64.load") (param i32) (result i64) (i64.load (local.get 0)))
+  (func (export "i64.load8_s") (param i32) (result i64) (i64.load8_s (local.get 0)))
+  (func (export "i64.load16_s") (param i32) (result i64) (i64.load16_s (local.get 0)))
+  (func (export "i64.load32_s") (param i32) (result i64) (i64.load32_s (local.get 0)))
+  (func (export "i64.load32_u") (param i32) (result i64) (i64.load32_u (local.get 0)))
+  (func (export "f32.load") (param i32) (result f32) (f32.load (local.get 0)))
+  (func (export "f64.load") (param i32) (result f64) (f64.load (local.get 0)))
+
+  (func (export "i32.store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+  (func (export "i32.store8") (param i32 i32) (i32.store8 (local.get 0) (local.get 1)))
+  (func (export "i32.store16") (param i32 i32) (i32.store16 (local.get 0) (local.get 1)))
+  (func (export "i64.store") (param i32 i64) (i64.store (local.get 0) (local.get 1)))
+  (func (export "i64.store32") (param i32 i64) (i64.store32 (local.get 0) (local.get 1)))
+  (func (export "f32.store") (param i32 f32) (f32.store (local.get 0) (local.get 1)))
+  (func (export "f64.store") (param i32 f64) (f64.store (local.get 0) (local.get 1)))
+
+  (func (export "size") (result i32) (memory.size))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+)
+
+;; 小端序
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0x04030201))
+(assert_return (invoke "i32.load" (i32.const 1)) (i32.const 0x05040302))
+(assert_return (invoke "i32.load_off" (i32.const 0)) (i32.const 0x08070605))
+(assert_return (invoke "i64.load" (i32.const 0)) (i64.const 0x0807060504030201))
+(assert_return (invoke "i32.load8_s" (i32.const 8)) (i32.const -128))
+(assert_return (invoke "i32.load8_u" (i32.const 8)) (i32.const 128))
+(assert_return (invoke "i32.load16_s" (i32.const 8)) (i32.const 0xffffff80))
+(assert_return (invoke "i32.load16_u" (i32.const 8)) (i32.const 0xff80))
+(assert_return (invoke "i64.load8_s" (i32.const 9)) (i64.const -1))
+(assert_return (invoke "i64.load16_s" (i32.const 8)) (i64.const -128))
+(assert_return (invoke "i64.load32_s" (i32.const 7)) (i64.const 0x00ff8008))
+(assert_return (invoke "i64.load32_u" (i32.const 65532)) (i64.const 0xddccbbaa))
+(assert_return (invoke "i64.load32_s" (i32.const 65532)) (i64.const 0xffffffffddccbbaa))
+
+;; 最后一个字节可以访问，跨过内存末尾即 trap ，地址加偏移不回绕
+(assert_return (invoke "i32.load8_u" (i32.const 65535)) (i32.const 0xdd))
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0xddccbbaa))
+(assert_trap (invoke "i32.load" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "i32.load8_u" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "i64.load" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "i32.load" (i32.const -1)) "out of bounds memory access")
+(assert_trap (invoke "i32.load_off" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "i32.load_max_off" (i32.const 1)) "out of bounds memory access")
+(assert_trap (invoke "i32.store" (i32.const 65533) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "i64.store" (i32.const -8) (i64.const 0)) "out of bounds memory access")
+
+;; 越界的 store 不写入任何字节
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0xddccbbaa))
+
+(invoke "i32.store8" (i32.const 16) (i32.const 0x1234))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0x34))
+(invoke "i32.store16" (i32.const 16) (i32.const 0xabcdef))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0xcdef))
+(invoke "i64.store32" (i32.const 16) (i64.const 0x1122334455667788))
+(assert_return (invoke "i64.load" (i32.const 16)) (i64.const 0x55667788))
+(invoke "i64.store" (i32.const 16) (i64.const -2))
+(assert_return (invoke "i32.load" (i32.const 20)) (i32.const -1))
+(invoke "f32.store" (i32.const 24) (f32.const nan:0x200000))
+(assert_return (invoke "f32.load" (i32.const 24)) (f32.const nan:0x200000))
+(assert_return (invoke "i32.load" (i32.const 24)) (i32.const 0x7fa00000))
+(invoke "f64.store" (i32.const 32) (f64.const -0x0p+0))
+(assert_return (invoke "f64.load" (i32.const 32)) (f64.const -0x0p+0))
+(assert_return (invoke "i64.load" (i32.const 32)) (i64.const 0x8000000000000000))
+
+;; memory.grow 返回旧的页数，超过上限时返回 -1 且大小不变；新页全为 0
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 2))
+(assert_return (invoke "i32.load" (i32.const 65533)) (i32.const 0x00ddccbb))
+(assert_return (invoke "i64.load" (i32.const 131064)) (i64.const 0))
+(assert_trap (invoke "i32.load8_u" (i32.const 131072)) "out of bounds memory access")
+(assert_return (invoke "grow" (i32.const 2)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "size") (i32.const 2))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "size") (i32.const 3))
+
+;; 没有上限的内存也受 65536 页的限制
+(module
+  (memory 0)
+  (func (export "size") (result i32) (memory.size))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+  (func (export "load") (param i32) (result i32) (i32.load8_u (local.get 0)))
+)
+(assert_return (invoke "size") (i32.const 0))
+(assert_trap (invoke "load" (i32.const 0)) "out of bounds memory access")
+(assert_return (invoke "grow" (i32.const 0x10001)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "load" (i32.const 65535)) (i32.const 0))
+
+;; 越界的数据段在实例化时 trap
+(assert_trap
+  (module (memory 1) (data (i32.const 65535) "\01\02"))
+  "out of bounds memory access"
+)
+(assert_trap
+  (module (memory 0) (data (i32.const 1) ""))
+  "out of bounds memory access"
+)
+
+(assert_invalid (module (func (drop (i32.load (i32.const 0))))) "unknown memory")
+(assert_invalid (module (memory 1) (func (i32.store (i32.const 0) (i64.const 0)))) "type mismatch")
+(assert_invalid (module (memory 2 1)) "size minimum must not be greater than maximum")
@@ -7,6 +7,7 @@ import {
 } from 'lucide-react';
 import {
   FaFileAlt,
+  FaCubes,
   FaShieldAlt,
   FaTable,
 } from 'react-icons/fa';
@@ -18,6 +19,7 @@ const Base64CustomAlphabetDemo = withLazyEB(() => import('@/rustWasmEncryptDemos
 const RustWasmEncryptDemo = withLazyEB(() => import('@/rustWasmEncryptDemos/RustWasmEncryptDemo'));
 const FileShaDemo = withLazyEB(() => import('@/rustWasmEncryptDemos/shaDemo/FileShaDemo'));
 const ShaDemo = withLazyEB(() => import('@/rustWasmEncryptDemos/shaDemo/ShaDemo'));
+const WasmInspectDemo = withLazyEB(() => import('@/rustWasmEncryptDemos/wasmInspect/WasmInspectDemo'));
 
 export const routes = [
   {
@@ -56,6 +58,12 @@ export const routes = [
     element: <Base64CustomAlphabetDemo />,
     icon: <FaTable />,
   },
+  {
+    path: '/wasm-inspect',
+    name: 'Rust WASM 模块结构',
+    element: <WasmInspectDemo />,
+    icon: <FaCubes />,
+  },
   {
     path: '/404',
     name: '404',
@@ -35,7 +35,7 @@ export default function AesCbcDemo() {
       setEncOut(cipherB64);
     } catch (e) {
       console.error('加密出错', e);
-      toast.error(`加密出错：${e}`);
+      toast.error(`加密出错：${e?.message ?? e}`);
     }
   };
 
@@ -46,7 +46,7 @@ export default function AesCbcDemo() {
       setDecOut(plain);
     } catch (e) {
       console.error('解密出错', e);
-      toast.error(`解密出错：${e}`);
+      toast.error(`解密出错：${e?.message ?? e}`);
     }
   };
 
@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { Button } from '@/components/ui/button';
 import { Textarea } from '@/components/ui/textarea';
 import { Label } from '@/components/ui/label';
@@ -15,17 +15,21 @@ import {
   FaTable,
 } from 'react-icons/fa';
 import { toast } from 'sonner';
-import {
+import init, {
   encode_custom_base64,
   decode_custom_base64,
   encode_base64_with_alphabet,
   decode_base64_with_alphabet,
+  encode_by_name,
+  decode_by_name,
+  list_algorithms,
 } from '@/wasm/rust_wasm';
 import { copyToClipboard } from '@/lib/utils';
+import { toU8 } from '../utils';
 import AnswerCardForCB from './AnswerCardForCB';
 import { motion } from 'motion/react';
 
-function Base64Section({ title, description, encodeFn, decodeFn, hasAlphabet = false }) {
+function Base64Section({ title, description, encodeFn, decodeFn, hasAlphabet = false, children }) {
   // 左侧（编码侧）
   const [encodeInput, setEncodeInput] = useState('');
   const [encodeOutput, setEncodeOutput] = useState('');
@@ -74,6 +78,7 @@ function Base64Section({ title, description, encodeFn, decodeFn, hasAlphabet = f
           {title}
         </CardTitle>
         <p className="text-sm text-muted-foreground">{description}</p>
+        {children}
       </CardHeader>
       <CardContent>
         <div className="flex flex-col md:flex-row gap-6">
@@ -197,6 +202,48 @@ function Base64Section({ title, description, encodeFn, decodeFn, hasAlphabet = f
   );
 }
 
+// 编码器列表来自 list_algorithms ，Rust 侧注册的新 Encoder 会自动出现在按钮中
+function RegistryEncoderSection() {
+  const [encoders, setEncoders] = useState([]);
+  const [encoder, setEncoder] = useState('');
+
+  useEffect(() => {
+    init()
+      .then(() => {
+        const names = JSON.parse(list_algorithms()).encoders;
+        setEncoders(names);
+        setEncoder(names[0] ?? '');
+      })
+      .catch((err) => {
+        console.error('WASM 初始化失败', err);
+        toast.error('WASM 初始化失败', { description: err.toString() });
+      });
+  }, []);
+
+  return (
+    <Base64Section
+      title="注册表编码器（按名称调用）"
+      description="通过 encode_by_name / decode_by_name 调用 Rust 侧注册的编码器，解码结果按 UTF-8 显示"
+      encodeFn={(input) => encode_by_name(encoder, toU8(input))}
+      decodeFn={(input) => new TextDecoder('utf-8', { fatal: true }).decode(decode_by_name(encoder, input))}
+    >
+      <div className="flex flex-wrap gap-2 pt-2">
+        {encoders.map((name) => (
+          <Button
+            key={name}
+            size="sm"
+            variant={name === encoder ? 'default' : 'outline'}
+            onClick={() => setEncoder(name)}
+            className="font-mono"
+          >
+            {name}
+          </Button>
+        ))}
+      </div>
+    </Base64Section>
+  );
+}
+
 export default function Base64CustomAlphabetDemo() {
   return (
     <motion.div
@@ -228,6 +275,8 @@ export default function Base64CustomAlphabetDemo() {
         hasAlphabet={true}
       />
 
+      <RegistryEncoderSection />
+
       <AnswerCardForCB />
     </motion.div>
   );
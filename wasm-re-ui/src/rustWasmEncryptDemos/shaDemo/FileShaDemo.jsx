@@ -53,9 +53,38 @@ const fileShaHashConfigs = {
     icon: <FaShieldAlt className="h-4 w-4" />,
     hasCustomSalt: true,
   },
+  // 以下两种可以并行计算，浏览器支持时 worker 会加载多线程构建；simd 构建只加速 BLAKE3
+  'blake3': {
+    label: 'BLAKE3',
+    icon: <FaFile className="h-4 w-4" />,
+  },
+  'sha256-chunked': {
+    label: 'SHA256 分块(1MiB)',
+    icon: <FaFile className="h-4 w-4" />,
+  },
 };
 const totalShaMethodTypes = Object.keys(fileShaHashConfigs).length;
 
+// 只有 BLAKE3 有 SIMD 实现，多线程也只对 BLAKE3 和分块 SHA 生效，其余 SHA 在任何构建下都是标量顺序计算
+function describeBuild(info) {
+  const reasons = {};
+  info.simd_accelerated.forEach((type) => {
+    reasons[type] = ['SIMD'];
+  });
+  if (info.threads && info.thread_pool_size > 1) {
+    ['blake3', 'sha256-chunked'].forEach((type) => {
+      reasons[type] = [...(reasons[type] || []), `${info.thread_pool_size} 线程`];
+    });
+  }
+  const accelerated = Object.entries(reasons)
+    .filter(([type]) => fileShaHashConfigs[type])
+    .map(([type, reason]) => `${fileShaHashConfigs[type].label}（${reason.join(' + ')}）`);
+  if (accelerated.length === 0) {
+    return `当前使用 ${info.build} 构建，所有结果均为标量顺序计算`;
+  }
+  return `当前使用 ${info.build} 构建，加速的结果：${accelerated.join('、')}；其余 SHA 结果不受 SIMD / 多线程影响`;
+}
+
 function renderResultRow(type, results) {
   const config = fileShaHashConfigs[type];
   const value = results[type];
@@ -92,6 +121,7 @@ export default function FileShaDemo() {
   const [file, setFile] = useState(null);
   const [customSalt, setCustomSalt] = useState('');
   const [results, setResults] = useState({});
+  const [buildInfo, setBuildInfo] = useState(null);
 
   const [isProcessing, setIsProcessing] = useState(false);
   const calculationProcess = useRef({
@@ -135,6 +165,13 @@ export default function FileShaDemo() {
       fileShaWorker.onmessage = (e) => {
         const { type, result, error, status } = e.data;
 
+        if (type === 'capabilities') {
+          if (status === 'success') {
+            setBuildInfo(result);
+          }
+          return;
+        }
+
         if (status === 'success') {
           setResults(prev => ({ ...prev, [type]: result }));
         } else {
@@ -155,6 +192,7 @@ export default function FileShaDemo() {
         });
       };
 
+      fileShaWorker.postMessage({ id: crypto.randomUUID(), type: 'capabilities' });
       Object.entries(fileShaHashConfigs).forEach(([key, config]) => {
         const payload = {
           id: crypto.randomUUID(),
@@ -286,31 +324,13 @@ export default function FileShaDemo() {
               <>
                 <Separator />
                 <div className="space-y-3">
-                  {renderResultRow(
-                    'sha256-pure',
-                    results
-                  )}
-                  {renderResultRow(
-                    'sha512-pure',
-                    results
-                  )}
-                  {renderResultRow(
-                    'sha256-default-salt',
-                    results
-                  )}
-                  {renderResultRow(
-                    'sha512-default-salt',
-                    results
-                  )}
-                  {renderResultRow(
-                    'sha256-salt',
-                    results
-                  )}
-                  {renderResultRow(
-                    'sha512-salt',
-                    results
-                  )}
+                  {Object.keys(fileShaHashConfigs).map((type) => renderResultRow(type, results))}
                 </div>
+                {buildInfo && (
+                  <p className="text-xs text-muted-foreground">
+                    {describeBuild(buildInfo)}
+                  </p>
+                )}
               </>
             )}
           </CardContent>
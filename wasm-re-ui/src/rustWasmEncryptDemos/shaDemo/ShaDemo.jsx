@@ -14,28 +14,34 @@ import { FaCopy, FaHashtag, FaLock, FaShieldAlt } from 'react-icons/fa';
 import { cn, copyToClipboard } from '@/lib/utils';
 import init, {
   get_str_sha256,
-  get_str_sha256_pure,
   get_str_sha256_with_salt,
   get_str_sha512,
-  get_str_sha512_pure,
   get_str_sha512_with_salt,
+  hash_by_name,
+  list_algorithms,
 } from '@/wasm/rust_wasm';
 import AnswerCard from './AnswerCard';
+import { toU8 } from '../utils';
 import { toast } from 'sonner';
 import { motion } from 'motion/react';
 
-// 哈希配置数据 —— DRY 抽象，避免重复代码
-const shaHashConfigs = {
-  'sha256-pure': {
-    label: 'SHA256',
-    fn: (input) => get_str_sha256_pure(input),
-    icon: <FaHashtag className="h-4 w-4" />,
-  },
-  'sha512-pure': {
-    label: 'SHA512',
-    fn: (input) => get_str_sha512_pure(input),
-    icon: <FaHashtag className="h-4 w-4" />,
-  },
+// 无盐哈希按注册表生成，Rust 侧注册的新 Hasher（如 blake3）会自动出现在页面上
+function registryHashConfigs() {
+  const { hashers } = JSON.parse(list_algorithms());
+  return Object.fromEntries(
+    hashers.map(({ name }) => [
+      name,
+      {
+        label: name.toUpperCase(),
+        fn: (input) => hash_by_name(name, toU8(input), 'hex'),
+        icon: <FaHashtag className="h-4 w-4" />,
+      },
+    ])
+  );
+}
+
+// 加盐哈希配置数据 —— DRY 抽象，避免重复代码
+const saltedShaHashConfigs = {
   'sha256-default-salt': {
     label: 'SHA256+默认盐',
     fn: (input) => get_str_sha256(input),
@@ -63,14 +69,8 @@ const shaHashConfigs = {
 export default function ShaDemo() {
   const [input, setInput] = useState('');
   const [customSalt, setCustomSalt] = useState('');
-  const [results, setResults] = useState({
-    'sha256-pure': '',
-    'sha512-pure': '',
-    'sha256-default-salt': '',
-    'sha512-default-salt': '',
-    'sha256-salt': '',
-    'sha512-salt': '',
-  });
+  const [shaHashConfigs, setShaHashConfigs] = useState(saltedShaHashConfigs);
+  const [results, setResults] = useState({});
   const [wasmReady, setWasmReady] = useState(false);
 
   const shouldDisableBtn = !input.trim() || !wasmReady;
@@ -80,6 +80,7 @@ export default function ShaDemo() {
       if (wasmReady) return;
       try {
         await init();
+        setShaHashConfigs({ ...registryHashConfigs(), ...saltedShaHashConfigs });
         setWasmReady(true);
       } catch (err) {
         console.error('WASM 初始化失败', err);
@@ -210,12 +211,7 @@ export default function ShaDemo() {
               <>
                 <Separator />
                 <div className="space-y-3">
-                  {renderResultRow('sha256-pure')}
-                  {renderResultRow('sha512-pure')}
-                  {renderResultRow('sha256-default-salt')}
-                  {renderResultRow('sha512-default-salt')}
-                  {renderResultRow('sha256-salt')}
-                  {renderResultRow('sha512-salt')}
+                  {Object.keys(shaHashConfigs).map(renderResultRow)}
                 </div>
               </>
             )}
@@ -1,46 +1,92 @@
-import init, {
-  get_bytes_sha256,
-  get_bytes_sha256_pure,
-  get_bytes_sha256_with_salt,
-  get_bytes_sha512,
-  get_bytes_sha512_pure,
-  get_bytes_sha512_with_salt,
-} from '@/wasm/rust_wasm';
+// 按浏览器能力选择 wasm 构建（rust-wasm/scripts/build-wasm-variants.sh 生成）：
+// threads（SIMD + 多线程）> simd > 标量。没有生成的构建不会出现在 glob 结果中
+const wasmBuilds = import.meta.glob([
+  '/src/wasm/rust_wasm.js',
+  '/src/wasm-simd/rust_wasm.js',
+  '/src/wasm-threads/rust_wasm.js',
+]);
 
-let wasmInitialized = false;
+// 分块 SHA 的块大小，校验时必须使用相同的值
+const SHA_CHUNK_SIZE = 1 << 20;
 
-async function initRustWasm() {
-  if (wasmInitialized) {
-    return;
+// 只含一条 i8x16.popcnt 的最小模块，能通过校验说明引擎支持 SIMD
+const SIMD_PROBE = new Uint8Array([
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253, 15,
+  253, 98, 11,
+]);
+
+function detectBuilds() {
+  const simd = WebAssembly.validate(SIMD_PROBE);
+  // SharedArrayBuffer 要求页面 crossOriginIsolated（COOP / COEP 响应头）
+  const threads = simd && self.crossOriginIsolated === true && typeof SharedArrayBuffer !== 'undefined';
+  return [threads && 'threads', simd && 'simd', 'scalar'].filter(Boolean);
+}
+
+function buildPath(build) {
+  return build === 'scalar' ? '/src/wasm/rust_wasm.js' : `/src/wasm-${build}/rust_wasm.js`;
+}
+
+let wasmPromise = null;
+let loadedBuild = null;
+
+async function loadBuild(build) {
+  const wasm = await wasmBuilds[buildPath(build)]();
+  await wasm.default();
+  if (build === 'threads') {
+    await wasm.initThreadPool(navigator.hardwareConcurrency || 4);
   }
-  try {
-    await init();
-    wasmInitialized = true;
-  } catch (err) {
-    console.error('Rust WASM 初始化失败', err);
+  return wasm;
+}
+
+async function initRustWasm() {
+  if (!wasmPromise) {
+    wasmPromise = (async () => {
+      for (const build of detectBuilds()) {
+        if (!wasmBuilds[buildPath(build)]) {
+          continue;
+        }
+        try {
+          const wasm = await loadBuild(build);
+          loadedBuild = build;
+          console.info(`Rust WASM 使用 ${build} 构建`, JSON.parse(wasm.hash_capabilities()));
+          return wasm;
+        } catch (err) {
+          console.warn(`Rust WASM ${build} 构建加载失败，尝试下一个`, err);
+        }
+      }
+      throw new Error('Rust WASM 初始化失败');
+    })();
   }
+  return wasmPromise;
 }
 
 self.onmessage = async function (e) {
   const { id, type, data, salt } = e.data;
 
   try {
-    await initRustWasm();
+    const wasm = await initRustWasm();
 
     let result;
 
-    if (type === 'sha256-pure') {
-      result = get_bytes_sha256_pure(data);
+    if (type === 'capabilities') {
+      // 供页面说明当前构建实际加速了哪些结果
+      result = { build: loadedBuild, ...JSON.parse(wasm.hash_capabilities()) };
+    } else if (type === 'sha256-pure') {
+      result = wasm.get_bytes_sha256_pure(data);
     } else if (type === 'sha512-pure') {
-      result = get_bytes_sha512_pure(data);
+      result = wasm.get_bytes_sha512_pure(data);
     } else if (type === 'sha256-default-salt') {
-      result = get_bytes_sha256(data);
+      result = wasm.get_bytes_sha256(data);
     } else if (type === 'sha512-default-salt') {
-      result = get_bytes_sha512(data);
+      result = wasm.get_bytes_sha512(data);
     } else if (type === 'sha256-salt') {
-      result = get_bytes_sha256_with_salt(data, salt);
+      result = wasm.get_bytes_sha256_with_salt(data, salt);
     } else if (type === 'sha512-salt') {
-      result = get_bytes_sha512_with_salt(data, salt);
+      result = wasm.get_bytes_sha512_with_salt(data, salt);
+    } else if (type === 'blake3') {
+      result = wasm.get_bytes_blake3(data);
+    } else if (type === 'sha256-chunked') {
+      result = wasm.get_bytes_sha_chunked(data, 'sha256', SHA_CHUNK_SIZE);
     } else {
       throw new Error(`Unknown hash type: ${type}`);
     }
@@ -1,10 +1,14 @@
+// rust-wasm 抛出的是带 code / module / details 字段的 Error 对象，按 code 分支即可
 export function getToastErrorInfo(e) {
-  const strE = e.toString();
-  if (strE.includes('Invalid Base64')) return '密文格式不正确';
-  if (strE.includes('Invalid UTF-8')) return '不是合法的UTF-8字符串';
+  if (e?.code === 'INVALID_BASE64') return '密文格式不正确';
+  if (e?.code === 'INVALID_UTF8') return '不是合法的UTF-8字符串';
   return '解密时出错';
 }
 
 export function toU8(s) {
   return new TextEncoder().encode(s);
 }
+
+export function hex(n) {
+  return `0x${n.toString(16)}`;
+}
@@ -0,0 +1,55 @@
+import { useState, useEffect } from 'react';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaKey } from 'react-icons/fa';
+import { scan_crypto_constants } from '@/wasm-inspect/rust_wasm';
+import InspectTable from './InspectTable';
+import { hex } from '../utils';
+
+// 命中位置：wasm 模块中给出 section 、线性内存地址或所在函数
+function location(hit) {
+  if (hit.location === 'code') {
+    return `code · ${hit.function_name ?? `func ${hit.function}`}（${hit.matched} 个常量）`;
+  }
+  if (hit.address !== undefined) {
+    return `${hit.location} · 内存 ${hex(hit.address)}`;
+  }
+  return hit.location;
+}
+
+export default function CryptoScanCard({ bytes }) {
+  const [hits, setHits] = useState([]);
+
+  useEffect(() => {
+    setHits(JSON.parse(scan_crypto_constants(bytes)));
+  }, [bytes]);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <FaKey /> 密码学常量（{hits.length}）
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        <p className="text-sm text-muted-foreground">
+          与 IDA 的 FindCrypt 相同，按 S 盒、轮常量、CRC 表、Base64 码表等特征定位二进制中的密码算法
+        </p>
+        <InspectTable
+          columns={[
+            ['文件偏移', (h) => hex(h.offset)],
+            ['算法', (h) => h.algorithm],
+            ['特征', (h) => [h.signature, h.endian && `(${h.endian} endian)`].filter(Boolean).join(' ')],
+            ['位置', location],
+            ['码表', (h) => h.text ?? ''],
+          ]}
+          rows={hits}
+        />
+      </CardContent>
+    </Card>
+  );
+}
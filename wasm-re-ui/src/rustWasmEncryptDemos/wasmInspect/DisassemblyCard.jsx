@@ -0,0 +1,134 @@
+import { useState, useEffect } from 'react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Switch } from '@/components/ui/switch';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaCode, FaCopy } from 'react-icons/fa';
+import { toast } from 'sonner';
+import { copyToClipboard } from '@/lib/utils';
+import {
+  decompile_wasm,
+  decompile_wasm_function,
+  disassemble_wasm,
+  disassemble_wasm_function,
+} from '@/wasm-inspect/rust_wasm';
+
+// 本站模块的导出函数对应的 Rust 源文件，分析本站模块时与反汇编结果并排显示
+const rustSources = [
+  [/^aes_cbc_/, 'aes_cbc.rs', () => import('@rust-src/aes_cbc.rs?raw')],
+  [/username/, 'xor_demo.rs', () => import('@rust-src/xor_demo.rs?raw')],
+  [/^(get_bytes_sha|compute_sha)/, 'sha_demo/sha_bytes_demo.rs', () => import('@rust-src/sha_demo/sha_bytes_demo.rs?raw')],
+  [/^get_str_sha/, 'sha_demo/sha_demo.rs', () => import('@rust-src/sha_demo/sha_demo.rs?raw')],
+  [/base64/, 'custom_base64.rs', () => import('@rust-src/custom_base64.rs?raw')],
+  [/recipe/, 'pipeline/recipe.rs', () => import('@rust-src/pipeline/recipe.rs?raw')],
+  [/(_by_name|^list_algorithms)$/, 'algorithm/registry.rs', () => import('@rust-src/algorithm/registry.rs?raw')],
+];
+
+const functionName = (f) => f.name ?? f.exports[0] ?? f.import ?? String(f.index);
+
+export default function DisassemblyCard({ bytes, info, isSiteModule }) {
+  const [target, setTarget] = useState('');
+  const [offsets, setOffsets] = useState(false);
+  const [pseudo, setPseudo] = useState(false);
+  const [wat, setWat] = useState('');
+  const [shownPseudo, setShownPseudo] = useState(false);
+  const [source, setSource] = useState(null);
+
+  useEffect(() => {
+    setWat('');
+    setSource(null);
+  }, [bytes]);
+
+  const handleDisassemble = async () => {
+    try {
+      const query = target.trim();
+      if (pseudo) {
+        setWat(query ? decompile_wasm_function(bytes, query) : decompile_wasm(bytes));
+      } else {
+        setWat(query
+          ? disassemble_wasm_function(bytes, query, offsets)
+          : disassemble_wasm(bytes, offsets));
+      }
+      setShownPseudo(pseudo);
+    } catch (e) {
+      console.error('反汇编出错', e);
+      const reason = e?.code === 'UNSUPPORTED_FORMAT' ? `不支持的指令 ${e.details?.feature}` : e?.message ?? e;
+      toast.error(`${pseudo ? '反编译' : '反汇编'}出错：${reason}`);
+      return;
+    }
+
+    const fn = info.functions.find((f) => functionName(f) === target.trim() || f.exports.includes(target.trim()));
+    const entry = isSiteModule && fn && rustSources.find(([pattern]) => fn.exports.some((e) => pattern.test(e)));
+    if (entry) {
+      const [, file, load] = entry;
+      setSource({ file, text: (await load()).default });
+    } else {
+      setSource(null);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2"><FaCode /> 反汇编 / 反编译</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-col md:flex-row md:items-end gap-4">
+          <div className="flex-1">
+            <Label htmlFor="wasm-function" className="mb-2">函数（名字或索引，留空反汇编整个模块）</Label>
+            <Input
+              id="wasm-function"
+              list="wasm-function-names"
+              className="font-mono"
+              value={target}
+              onChange={(e) => setTarget(e.target.value)}
+              placeholder="aes_cbc_encrypt"
+            />
+            <datalist id="wasm-function-names">
+              {info.functions.filter((f) => f.body_offset !== undefined).map((f) => (
+                <option key={f.index} value={functionName(f)} />
+              ))}
+            </datalist>
+          </div>
+          <div className="flex items-center gap-2 h-9">
+            <Switch id="wasm-offsets" checked={offsets} disabled={pseudo} onCheckedChange={setOffsets} />
+            <Label htmlFor="wasm-offsets">显示文件偏移</Label>
+          </div>
+          <div className="flex items-center gap-2 h-9">
+            <Switch id="wasm-pseudo" checked={pseudo} onCheckedChange={setPseudo} />
+            <Label htmlFor="wasm-pseudo">反编译为伪代码</Label>
+          </div>
+          <Button onClick={handleDisassemble}>
+            <FaCode /> {pseudo ? '反编译' : '反汇编'}
+          </Button>
+        </div>
+
+        {wat && (
+          <div className={source ? 'grid grid-cols-1 lg:grid-cols-2 gap-4' : ''}>
+            <div>
+              <div className="flex items-center justify-between mb-2">
+                <Label>{shownPseudo ? '伪代码（类 C）' : 'WAT'}</Label>
+                <Button variant="ghost" size="sm" onClick={() => copyToClipboard(wat)}>
+                  <FaCopy /> 复制
+                </Button>
+              </div>
+              <pre className="bg-muted rounded-md p-3 text-xs font-mono overflow-auto max-h-[32rem]">{wat}</pre>
+            </div>
+            {source && (
+              <div>
+                <Label className="mb-2 h-8">Rust 源码：rust-wasm/src/{source.file}</Label>
+                <pre className="bg-muted rounded-md p-3 text-xs font-mono overflow-auto max-h-[32rem]">{source.text}</pre>
+              </div>
+            )}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,31 @@
+export default function InspectTable({ columns, rows }) {
+  if (!rows.length) {
+    return <p className="text-sm text-muted-foreground">无</p>;
+  }
+  return (
+    <div className="max-h-80 overflow-auto">
+      <table className="w-full text-sm font-mono">
+        <thead className="sticky top-0 bg-card">
+          <tr>
+            {columns.map(([title]) => (
+              <th key={title} className="text-left font-medium px-2 py-1 border-b border-border">
+                {title}
+              </th>
+            ))}
+          </tr>
+        </thead>
+        <tbody>
+          {rows.map((row, i) => (
+            <tr key={i} className="hover:bg-muted/50">
+              {columns.map(([title, render]) => (
+                <td key={title} className="px-2 py-1 break-all align-top">
+                  {render(row)}
+                </td>
+              ))}
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
@@ -0,0 +1,155 @@
+import { useState, useEffect } from 'react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Switch } from '@/components/ui/switch';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaDownload, FaLock, FaSearch } from 'react-icons/fa';
+import { toast } from 'sonner';
+import { obfuscate_wasm } from '@/wasm-inspect/rust_wasm';
+import InspectTable from './InspectTable';
+
+const PASSES = [
+  ['encrypt_data', '加密数据段'],
+  ['opaque_predicates', '不透明谓词'],
+  ['split_constants', '拆分常量'],
+  ['mangle_exports', '混淆导出名'],
+];
+
+const splitList = (s) => s.split(',').map((x) => x.trim()).filter(Boolean);
+
+export default function ObfuscatorCard({ bytes, name, info, onInspect }) {
+  const [seed, setSeed] = useState('1');
+  const [passes, setPasses] = useState({
+    encrypt_data: true,
+    opaque_predicates: true,
+    split_constants: true,
+    mangle_exports: false,
+  });
+  const [flatten, setFlatten] = useState('');
+  const [keepExports, setKeepExports] = useState('memory');
+  const [result, setResult] = useState(null);
+
+  useEffect(() => {
+    setFlatten('');
+    setResult(null);
+  }, [bytes]);
+
+  const handleObfuscate = () => {
+    if (!/^\d+$/.test(seed.trim())) {
+      toast.error('种子必须是非负整数');
+      return;
+    }
+    const options = {
+      seed: Number(seed),
+      ...passes,
+      flatten: splitList(flatten),
+      keep_exports: splitList(keepExports),
+    };
+    try {
+      const obfuscated = obfuscate_wasm(bytes, JSON.stringify(options));
+      setResult({ bytes: obfuscated.bytes, exports: JSON.parse(obfuscated.exports) });
+      obfuscated.free();
+    } catch (e) {
+      console.error('混淆出错', e);
+      toast.error(`混淆出错：${e?.message ?? e}`);
+      setResult(null);
+    }
+  };
+
+  const handleDownload = () => {
+    const url = URL.createObjectURL(new Blob([result.bytes], { type: 'application/wasm' }));
+    const a = document.createElement('a');
+    a.href = url;
+    a.download = name.replace(/\.wasm$/, '') + `.obf${seed.trim()}.wasm`;
+    a.click();
+    URL.revokeObjectURL(url);
+  };
+
+  const exportRows = result ? Object.entries(result.exports) : [];
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2"><FaLock /> 混淆</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          生成更难分析的练习题：加密的数据段在实例化时由新增的 start 函数解密，平坦化把指定函数的顶层语句打乱后放进
+          br_table 分发循环。相同的种子得到相同的结果，混淆前后模块的行为一致
+        </p>
+        <div className="flex flex-wrap items-center gap-6">
+          {PASSES.map(([key, label]) => (
+            <div key={key} className="flex items-center gap-2 h-9">
+              <Switch
+                id={`wasm-obf-${key}`}
+                checked={passes[key]}
+                onCheckedChange={(checked) => setPasses({ ...passes, [key]: checked })}
+              />
+              <Label htmlFor={`wasm-obf-${key}`}>{label}</Label>
+            </div>
+          ))}
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+          <div>
+            <Label htmlFor="wasm-obf-seed" className="mb-2">种子</Label>
+            <Input id="wasm-obf-seed" value={seed} onChange={(e) => setSeed(e.target.value)} />
+          </div>
+          <div>
+            <Label htmlFor="wasm-obf-flatten" className="mb-2">平坦化的函数（逗号分隔）</Label>
+            <Input
+              id="wasm-obf-flatten"
+              list="wasm-obf-functions"
+              className="font-mono"
+              value={flatten}
+              onChange={(e) => setFlatten(e.target.value)}
+            />
+            <datalist id="wasm-obf-functions">
+              {info.functions.filter((f) => f.body_offset !== undefined && f.name).map((f) => (
+                <option key={f.index} value={f.name} />
+              ))}
+            </datalist>
+          </div>
+          <div>
+            <Label htmlFor="wasm-obf-keep" className="mb-2">保留的导出名（逗号分隔）</Label>
+            <Input
+              id="wasm-obf-keep"
+              className="font-mono"
+              value={keepExports}
+              disabled={!passes.mangle_exports}
+              onChange={(e) => setKeepExports(e.target.value)}
+            />
+          </div>
+        </div>
+        <div className="flex flex-wrap items-center gap-4">
+          <Button onClick={handleObfuscate}><FaLock /> 混淆</Button>
+          {result && (
+            <>
+              <Button variant="outline" onClick={handleDownload}><FaDownload /> 下载</Button>
+              <Button variant="outline" onClick={() => onInspect(`${name} (obfuscated)`, result.bytes)}>
+                <FaSearch /> 分析混淆后的模块
+              </Button>
+              <span className="text-sm text-muted-foreground font-mono">
+                {bytes.length} → {result.bytes.length} 字节
+              </span>
+            </>
+          )}
+        </div>
+        {exportRows.length > 0 && (
+          <InspectTable
+            columns={[
+              ['原导出名', ([from]) => from],
+              ['新导出名', ([, to]) => to],
+            ]}
+            rows={exportRows}
+          />
+        )}
+      </CardContent>
+    </Card>
+  );
+}
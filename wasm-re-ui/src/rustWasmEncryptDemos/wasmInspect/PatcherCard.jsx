@@ -0,0 +1,119 @@
+import { useState, useEffect } from 'react';
+import { Button } from '@/components/ui/button';
+import { Label } from '@/components/ui/label';
+import { Textarea } from '@/components/ui/textarea';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaDownload, FaSearch, FaWrench } from 'react-icons/fa';
+import { toast } from 'sonner';
+import { extract_wasm_strings, patch_wasm } from '@/wasm-inspect/rust_wasm';
+
+const STANDARD_ALPHABET = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+const swapCase = (s) => [...s].map((c) => (c === c.toUpperCase() ? c.toLowerCase() : c.toUpperCase())).join('');
+
+// 几个常用的修改，生成后可以在文本框里继续编辑
+function presets(bytes, info) {
+  const list = [];
+  const alphabet = JSON.parse(extract_wasm_strings(bytes, 64))
+    .find((s) => s.address !== undefined && s.text.includes(STANDARD_ALPHABET));
+  if (alphabet) {
+    list.push(['大小写互换 Base64 码表', [{
+      op: 'write_data',
+      address: alphabet.address + alphabet.text.indexOf(STANDARD_ALPHABET),
+      bytes: [...new TextEncoder().encode(swapCase(STANDARD_ALPHABET))],
+    }]]);
+  }
+  const exported = info.exports.find((e) => e.kind === 'func');
+  if (exported) {
+    list.push([`重命名导出 ${exported.name}`, [
+      { op: 'rename_export', name: exported.name, new_name: `${exported.name}_patched` },
+    ]]);
+  }
+  const internal = info.functions.find((f) => f.body_offset !== undefined && f.exports.length === 0 && f.name);
+  if (internal) {
+    list.push([`导出内部函数 ${internal.name}`, [
+      { op: 'add_export', name: internal.name, function: String(internal.index) },
+    ]]);
+  }
+  return list;
+}
+
+export default function PatcherCard({ bytes, name, info, onInspect }) {
+  const [patches, setPatches] = useState('[]');
+  const [patched, setPatched] = useState(null);
+
+  useEffect(() => {
+    setPatches('[]');
+    setPatched(null);
+  }, [bytes]);
+
+  const handleApply = () => {
+    try {
+      setPatched(patch_wasm(bytes, patches));
+    } catch (e) {
+      console.error('修改出错', e);
+      const reason = e?.code === 'INVALID_WASM'
+        ? `修改后的模块不合法（偏移 ${e.details?.offset}）：${e.details?.reason}`
+        : e?.message ?? e;
+      toast.error(`修改出错：${reason}`);
+      setPatched(null);
+    }
+  };
+
+  const handleDownload = () => {
+    const url = URL.createObjectURL(new Blob([patched], { type: 'application/wasm' }));
+    const a = document.createElement('a');
+    a.href = url;
+    a.download = name.replace(/\.wasm$/, '') + '.patched.wasm';
+    a.click();
+    URL.revokeObjectURL(url);
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2"><FaWrench /> 修改二进制</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          set_const / nop 按指令的文件偏移定位（反汇编时勾选显示偏移），return_const 把函数体换成直接返回常量，
+          write_data 按线性内存地址覆盖数据段，rename_export / add_export 修改导出。修改后的模块必须通过校验
+        </p>
+        <div className="flex flex-wrap gap-2">
+          {presets(bytes, info).map(([label, value]) => (
+            <Button key={label} variant="outline" onClick={() => setPatches(JSON.stringify(value, null, 2))}>
+              {label}
+            </Button>
+          ))}
+        </div>
+        <div>
+          <Label htmlFor="wasm-patches" className="mb-2">修改（JSON 数组）</Label>
+          <Textarea
+            id="wasm-patches"
+            className="font-mono text-xs min-h-40"
+            value={patches}
+            onChange={(e) => setPatches(e.target.value)}
+          />
+        </div>
+        <div className="flex flex-wrap items-center gap-4">
+          <Button onClick={handleApply}><FaWrench /> 应用</Button>
+          {patched && (
+            <>
+              <Button variant="outline" onClick={handleDownload}><FaDownload /> 下载</Button>
+              <Button variant="outline" onClick={() => onInspect(`${name} (patched)`, patched)}>
+                <FaSearch /> 分析修改后的模块
+              </Button>
+              <span className="text-sm text-muted-foreground font-mono">
+                {bytes.length} → {patched.length} 字节
+              </span>
+            </>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,120 @@
+import { useState, useEffect } from 'react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaDownload, FaFingerprint, FaSearch } from 'react-icons/fa';
+import { toast } from 'sonner';
+import {
+  apply_wasm_signatures,
+  generate_wasm_signatures,
+  match_wasm_signatures,
+} from '@/wasm-inspect/rust_wasm';
+import InspectTable from './InspectTable';
+
+export default function SignaturesCard({ bytes, name, onInspect }) {
+  const [signatures, setSignatures] = useState(null);
+  const [matches, setMatches] = useState(null);
+
+  useEffect(() => {
+    setMatches(null);
+  }, [bytes]);
+
+  // 从当前模块（带名字的构建）生成签名文件
+  const handleGenerate = () => {
+    try {
+      const json = generate_wasm_signatures(bytes);
+      const count = JSON.parse(json).length;
+      if (count === 0) {
+        toast.error('模块中没有带名字的函数，无法生成签名');
+        return;
+      }
+      const url = URL.createObjectURL(new Blob([json], { type: 'application/json' }));
+      const a = document.createElement('a');
+      a.href = url;
+      a.download = name.replace(/\.wasm$/, '') + '.sig.json';
+      a.click();
+      URL.revokeObjectURL(url);
+      toast.success(`已生成 ${count} 个函数签名`);
+    } catch (e) {
+      console.error('生成签名出错', e);
+      toast.error(`生成签名出错：${e?.message ?? e}`);
+    }
+  };
+
+  const handleFileChange = async (e) => {
+    const file = e.target.files?.[0];
+    if (!file) return;
+    const json = await file.text();
+    try {
+      setMatches(JSON.parse(match_wasm_signatures(bytes, json)));
+      setSignatures({ file: file.name, json });
+    } catch (err) {
+      console.error('匹配签名出错', err);
+      const reason = err?.code === 'INVALID_INPUT' ? '不是签名文件' : err?.message ?? err;
+      toast.error(`匹配签名出错：${reason}`);
+      setMatches(null);
+    }
+  };
+
+  const handleApply = () => {
+    try {
+      onInspect(`${name} (renamed)`, apply_wasm_signatures(bytes, signatures.json));
+    } catch (e) {
+      console.error('重命名出错', e);
+      toast.error(`重命名出错：${e?.message ?? e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2"><FaFingerprint /> 库函数识别</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          类似 IDA 的 FLIRT ：在带名字的构建（如 debug 构建）上生成签名文件，再用它识别去掉名字的 release 构建中的
+          aes 、sha2 、base64 等库函数。签名屏蔽了常量、函数索引和数据段地址，少于 8 条指令的函数和代码完全相同的函数不参与匹配
+        </p>
+        <div className="flex flex-col md:flex-row md:items-end gap-4">
+          <Button variant="outline" onClick={handleGenerate}><FaDownload /> 生成签名文件</Button>
+          <div className="flex-1">
+            <Label htmlFor="wasm-signatures" className="mb-2">用签名文件识别当前模块</Label>
+            <Input id="wasm-signatures" type="file" accept=".json" onChange={handleFileChange} />
+          </div>
+        </div>
+
+        {matches && (
+          <>
+            <div className="flex flex-wrap items-center gap-4">
+              <span className="text-sm text-muted-foreground">
+                {signatures.file}：识别出 {matches.length} 个函数
+              </span>
+              {matches.length > 0 && (
+                <Button variant="outline" onClick={handleApply}>
+                  <FaSearch /> 分析重命名后的模块
+                </Button>
+              )}
+            </div>
+            {matches.length > 0 && (
+              <InspectTable
+                columns={[
+                  ['索引', (m) => m.function],
+                  ['原名', (m) => m.previous_name ?? ''],
+                  ['识别为', (m) => m.name],
+                  ['签名', (m) => m.hash],
+                ]}
+                rows={matches}
+              />
+            )}
+          </>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,75 @@
+import { useState, useMemo } from 'react';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaFont } from 'react-icons/fa';
+import { extract_wasm_strings } from '@/wasm-inspect/rust_wasm';
+import InspectTable from './InspectTable';
+import { hex } from '../utils';
+
+const xrefName = (x) => x.function_name ?? `func ${x.function}`;
+
+export default function StringsCard({ bytes }) {
+  const [minLen, setMinLen] = useState(4);
+  const [filter, setFilter] = useState('');
+
+  const strings = useMemo(
+    () => JSON.parse(extract_wasm_strings(bytes, Math.max(1, Number(minLen) || 1))),
+    [bytes, minLen],
+  );
+  const shown = strings.filter((s) => s.text.includes(filter)
+    || s.xrefs.some((x) => xrefName(x).includes(filter)));
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <FaFont /> 字符串（{strings.length}）
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          数据段中的 ASCII / UTF-8 / UTF-16LE 字符串，以及用 i32.const 加载其地址的函数。
+          Rust 的字符串首尾相接，按代码引用的地址切开；盐值、密钥这类静态字符串往往就在这里
+        </p>
+        <div className="flex flex-col md:flex-row gap-4">
+          <div className="w-32">
+            <Label htmlFor="strings-min-len" className="mb-2">最小长度</Label>
+            <Input
+              id="strings-min-len"
+              type="number"
+              min={1}
+              value={minLen}
+              onChange={(e) => setMinLen(e.target.value)}
+            />
+          </div>
+          <div className="flex-1">
+            <Label htmlFor="strings-filter" className="mb-2">过滤（字符串或函数名）</Label>
+            <Input
+              id="strings-filter"
+              className="font-mono"
+              value={filter}
+              onChange={(e) => setFilter(e.target.value)}
+              placeholder="hans7"
+            />
+          </div>
+        </div>
+        <InspectTable
+          columns={[
+            ['内存地址', (s) => (s.address !== undefined ? hex(s.address) : `段 ${s.segment}`)],
+            ['文件偏移', (s) => hex(s.file_offset)],
+            ['编码', (s) => s.encoding],
+            ['字符串', (s) => JSON.stringify(s.text)],
+            ['引用', (s) => s.xrefs.map(xrefName).join(', ')],
+          ]}
+          rows={shown}
+        />
+      </CardContent>
+    </Card>
+  );
+}
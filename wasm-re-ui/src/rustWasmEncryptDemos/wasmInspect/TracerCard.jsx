@@ -0,0 +1,212 @@
+import { useState, useEffect, useMemo } from 'react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Switch } from '@/components/ui/switch';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaPlay, FaShoePrints } from 'react-icons/fa';
+import { toast } from 'sonner';
+import { WasmInterpreter, extract_wasm_strings } from '@/wasm-inspect/rust_wasm';
+import InspectTable from './InspectTable';
+import { hex } from '../utils';
+
+const paramCount = (signature) => signature.match(/\(param ([^)]*)\)/)?.[1].trim().split(/\s+/).length ?? 0;
+const showValue = (v) => (v.value === null ? `${v.type} null` : `${v.type} ${v.value}`);
+
+// 按 wasm-bindgen 的约定传入字符串：__wbindgen_malloc 分配内存、写入 UTF-8 字节，参数为 (ptr, len)。
+// 返回字符串的函数有两种 ABI：第一个参数是存放 (ptr, len) 的 retptr ，或者用 multi-value 直接返回两个 i32
+function runWithString(interpreter, fn, text, extraArgs) {
+  const bytes = new TextEncoder().encode(text);
+  const malloc = (size) => JSON.parse(interpreter.call('__wbindgen_malloc', `[${size}, 1]`)).results[0].value;
+  const ptr = malloc(Math.max(bytes.length, 1));
+  interpreter.write_memory(ptr, bytes);
+  let args = [ptr, bytes.length, ...extraArgs];
+  const retptr = paramCount(fn.signature) === args.length + 1 ? malloc(16) : null;
+  if (retptr !== null) args = [retptr, ...args];
+
+  const execution = JSON.parse(interpreter.call(String(fn.index), JSON.stringify(args)));
+  let output = null;
+  if (!execution.trap) {
+    const [outPtr, outLen] = retptr !== null
+      ? new Int32Array(interpreter.read_memory(retptr, 8).buffer)
+      : execution.results.map((r) => r.value);
+    if (outLen !== undefined && outLen >= 0) {
+      output = new TextDecoder().decode(interpreter.read_memory(outPtr, outLen));
+    }
+  }
+  return { execution, output };
+}
+
+export default function TracerCard({ bytes, info }) {
+  const exported = info.functions.filter((f) => f.exports.length > 0 && f.body_offset !== undefined);
+  const [target, setTarget] = useState('');
+  const [text, setText] = useState('hans7');
+  const [stringArg, setStringArg] = useState(true);
+  const [argsJson, setArgsJson] = useState('[]');
+  const [recordSteps, setRecordSteps] = useState(true);
+  const [run, setRun] = useState(null);
+  const [cursor, setCursor] = useState(0);
+
+  useEffect(() => {
+    setRun(null);
+    setTarget(exported.some((f) => f.exports.includes('encrypt_username')) ? 'encrypt_username' : '');
+  }, [bytes]);
+
+  // 数据段中 "hctf" 的地址，用来在 trace 中标出读取密钥的指令
+  const keyAddresses = useMemo(() => {
+    const addresses = new Set();
+    for (const s of JSON.parse(extract_wasm_strings(bytes, 4))) {
+      const at = s.text.indexOf('hctf');
+      if (s.address === undefined || at < 0 || s.encoding === 'utf-16le') continue;
+      for (let i = 0; i < 4; i++) addresses.add(s.address + at + i);
+    }
+    return addresses;
+  }, [bytes]);
+
+  const handleRun = () => {
+    const fn = info.functions.find((f) => f.exports.includes(target.trim()) || f.name === target.trim()
+      || String(f.index) === target.trim());
+    if (!fn) {
+      toast.error(`找不到函数 ${target}`);
+      return;
+    }
+    let interpreter;
+    try {
+      // 每次运行都重新实例化，避免上次调用留下的内存状态
+      interpreter = new WasmInterpreter(bytes);
+      interpreter.set_record_steps(recordSteps);
+      const extra = JSON.parse(argsJson || '[]');
+      const result = stringArg
+        ? runWithString(interpreter, fn, text, extra)
+        : { execution: JSON.parse(interpreter.call(String(fn.index), argsJson || '[]')), output: null };
+      setRun(result);
+      setCursor(0);
+    } catch (e) {
+      console.error('执行出错', e);
+      toast.error(`执行出错：${e?.message ?? e}`);
+    } finally {
+      interpreter?.free();
+    }
+  };
+
+  const steps = useMemo(() => run?.execution.trace.filter((e) => e.kind === 'step') ?? [], [run]);
+  const keyReads = useMemo(
+    () => run?.execution.trace.filter((e) => e.kind === 'read' && keyAddresses.has(e.address)) ?? [],
+    [run, keyAddresses],
+  );
+  const current = steps[cursor];
+  const currentEvents = current
+    ? run.execution.trace.filter((e) => e.kind !== 'step' && e.step === current.step)
+    : [];
+  const jumpTo = (step) => setCursor(Math.max(0, steps.findIndex((s) => s.step === step)));
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2"><FaShoePrints /> 单步执行</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          在解释器中执行导出函数，记录每条指令、内存读写和调用。导入函数不执行，返回 0 。
+          读取数据段中密钥 "hctf" 的指令会单独列出
+        </p>
+        <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+          <div>
+            <Label htmlFor="tracer-function" className="mb-2">函数</Label>
+            <Input
+              id="tracer-function"
+              list="tracer-function-names"
+              className="font-mono"
+              value={target}
+              onChange={(e) => setTarget(e.target.value)}
+              placeholder="encrypt_username"
+            />
+            <datalist id="tracer-function-names">
+              {exported.map((f) => <option key={f.index} value={f.exports[0]} />)}
+            </datalist>
+          </div>
+          <div>
+            <Label htmlFor="tracer-string" className="mb-2">字符串参数</Label>
+            <Input
+              id="tracer-string"
+              className="font-mono"
+              disabled={!stringArg}
+              value={text}
+              onChange={(e) => setText(e.target.value)}
+            />
+          </div>
+          <div>
+            <Label htmlFor="tracer-args" className="mb-2">其他参数（JSON 数组）</Label>
+            <Input
+              id="tracer-args"
+              className="font-mono"
+              value={argsJson}
+              onChange={(e) => setArgsJson(e.target.value)}
+            />
+          </div>
+        </div>
+        <div className="flex flex-wrap items-center gap-6">
+          <div className="flex items-center gap-2">
+            <Switch id="tracer-string-arg" checked={stringArg} onCheckedChange={setStringArg} />
+            <Label htmlFor="tracer-string-arg">按 wasm-bindgen 约定传入字符串</Label>
+          </div>
+          <div className="flex items-center gap-2">
+            <Switch id="tracer-record-steps" checked={recordSteps} onCheckedChange={setRecordSteps} />
+            <Label htmlFor="tracer-record-steps">记录每条指令</Label>
+          </div>
+          <Button onClick={handleRun}><FaPlay /> 运行</Button>
+        </div>
+
+        {run && (
+          <>
+            <p className="text-sm font-mono">
+              {run.execution.trap
+                ? <span className="text-destructive">trap: {run.execution.trap}</span>
+                : `返回 ${run.execution.results.map(showValue).join(', ') || '（无）'}`}
+              {run.output !== null && ` · 字符串 ${JSON.stringify(run.output)}`}
+              {` · ${run.execution.steps} 条指令 · ${run.execution.trace.length} 个事件`}
+              {run.execution.truncated && '（超出记录上限）'}
+            </p>
+
+            {keyReads.length > 0 && (
+              <InspectTable
+                columns={[
+                  ['指令序号', (e) => <button type="button" className="underline" onClick={() => jumpTo(e.step)}>{e.step}</button>],
+                  ['地址', (e) => hex(e.address)],
+                  ['读出', (e) => `${e.value} ${JSON.stringify(String.fromCharCode(Number(e.value)))}`],
+                ]}
+                rows={keyReads}
+              />
+            )}
+
+            {current && (
+              <div className="space-y-2">
+                <Label htmlFor="tracer-cursor">第 {current.step} 条指令（共 {steps.length} 条）</Label>
+                <input
+                  id="tracer-cursor"
+                  type="range"
+                  className="w-full"
+                  min={0}
+                  max={steps.length - 1}
+                  value={cursor}
+                  onChange={(e) => setCursor(Number(e.target.value))}
+                />
+                <pre className="bg-muted p-3 rounded text-sm overflow-auto">
+                  {`func ${current.function} @ ${hex(current.offset)}  深度 ${current.depth}\n`}
+                  {`${keyReads.some((e) => e.step === current.step) ? '>>> ' : ''}${current.text}\n`}
+                  {`栈: [${current.stack.map(showValue).join(', ')}]\n`}
+                  {currentEvents.map((e) => `${e.kind} ${JSON.stringify({ ...e, kind: undefined, step: undefined })}\n`)}
+                </pre>
+              </div>
+            )}
+          </>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,256 @@
+import { useState, useEffect, useCallback } from 'react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { FaCubes, FaSearch, FaUpload } from 'react-icons/fa';
+import { toast } from 'sonner';
+import { motion } from 'motion/react';
+// 逆向工具单独打包（--features inspect），不增加其他页面加载的 wasm 体积
+import init, { inspect_wasm } from '@/wasm-inspect/rust_wasm';
+// 本站加密演示使用的模块，默认分析它
+import siteWasmUrl from '@/wasm/rust_wasm_bg.wasm?url';
+import CryptoScanCard from './CryptoScanCard';
+import DisassemblyCard from './DisassemblyCard';
+import InspectTable from './InspectTable';
+import ObfuscatorCard from './ObfuscatorCard';
+import PatcherCard from './PatcherCard';
+import SignaturesCard from './SignaturesCard';
+import StringsCard from './StringsCard';
+import TracerCard from './TracerCard';
+import { hex } from '../utils';
+
+function InfoCard({ title, children }) {
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>{title}</CardTitle>
+      </CardHeader>
+      <CardContent>{children}</CardContent>
+    </Card>
+  );
+}
+
+export default function WasmInspectDemo() {
+  const [ready, setReady] = useState(false);
+  const [wasmModule, setWasmModule] = useState(null);
+  const info = wasmModule?.info;
+
+  const inspect = useCallback((name, bytes, isSiteModule = false) => {
+    // 不是 wasm 的文件（如 native 可执行文件）只做密码学常量扫描
+    const isWasm = bytes[0] === 0 && bytes[1] === 0x61 && bytes[2] === 0x73 && bytes[3] === 0x6d;
+    if (!isWasm) {
+      setWasmModule({ name, bytes, isSiteModule, info: null });
+      return;
+    }
+    try {
+      setWasmModule({ name, bytes, isSiteModule, info: JSON.parse(inspect_wasm(bytes)) });
+    } catch (e) {
+      console.error('解析出错', e);
+      const reason = e?.code === 'INVALID_WASM'
+        ? `不是合法的 wasm 模块（偏移 ${e.details?.offset}）`
+        : e?.message ?? e;
+      toast.error(`解析出错：${reason}`);
+    }
+  }, []);
+
+  const inspectSiteWasm = useCallback(async () => {
+    const res = await fetch(siteWasmUrl);
+    inspect('rust_wasm_bg.wasm', new Uint8Array(await res.arrayBuffer()), true);
+  }, [inspect]);
+
+  useEffect(() => {
+    init().then(() => setReady(true));
+  }, []);
+
+  const handleFileChange = async (e) => {
+    const file = e.target.files?.[0];
+    if (!file) return;
+    inspect(file.name, new Uint8Array(await file.arrayBuffer()));
+  };
+
+  const functionName = (f) => f.name ?? f.exports[0] ?? f.import ?? '';
+
+  return (
+    <div className="min-h-screen bg-background text-foreground p-4 md:p-8">
+      <div className="max-w-6xl mx-auto space-y-6">
+        <div className="mb-8 text-center">
+          <h1 className="text-3xl font-bold mb-2">Rust WASM 模块结构</h1>
+          <p className="text-muted-foreground">
+            解析 .wasm 文件的各个 section ：类型、导入导出、函数、内存、全局变量、数据段和自定义 section
+          </p>
+        </div>
+
+        <motion.div
+          className="grid grid-cols-1 gap-6"
+          initial="hidden"
+          animate="visible"
+          variants={{ hidden: {}, visible: { transition: { staggerChildren: 0.1 } } }}
+        >
+          <InfoCard title={<span className="flex items-center gap-2"><FaCubes /> 选择模块</span>}>
+            <div className="flex flex-col md:flex-row md:items-end gap-4">
+              <div className="flex-1">
+                <Label htmlFor="wasm-file" className="mb-2 flex items-center gap-2">
+                  <FaUpload /> 上传 .wasm 文件（其他二进制文件只扫描密码学常量）
+                </Label>
+                <Input
+                  id="wasm-file"
+                  type="file"
+                  disabled={!ready}
+                  onChange={handleFileChange}
+                />
+              </div>
+              <Button disabled={!ready} onClick={inspectSiteWasm}>
+                <FaSearch /> 分析本站的 WASM 模块
+              </Button>
+            </div>
+            {info && (
+              <p className="mt-4 text-sm text-muted-foreground font-mono">
+                {wasmModule.name} · {info.size} 字节 · 版本 {info.version} · {info.functions.length} 个函数
+                {info.producers.map((field) => (
+                  <span key={field.name}>
+                    {' · '}{field.name}: {field.values.map((v) => `${v.name} ${v.version}`).join(', ')}
+                  </span>
+                ))}
+              </p>
+            )}
+          </InfoCard>
+
+          {wasmModule && <CryptoScanCard bytes={wasmModule.bytes} />}
+
+          {info && (
+            <>
+              <InfoCard title="Sections">
+                <InspectTable
+                  columns={[
+                    ['id', (s) => s.id],
+                    ['种类', (s) => (s.name ? `${s.kind} "${s.name}"` : s.kind)],
+                    ['偏移', (s) => hex(s.offset)],
+                    ['大小', (s) => s.size],
+                  ]}
+                  rows={info.sections}
+                />
+              </InfoCard>
+
+              <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
+                <InfoCard title={`导入（${info.imports.length}）`}>
+                  <InspectTable
+                    columns={[
+                      ['模块', (i) => i.module],
+                      ['名字', (i) => i.name],
+                      ['种类', (i) => `${i.kind} ${i.index}`],
+                    ]}
+                    rows={info.imports}
+                  />
+                </InfoCard>
+                <InfoCard title={`导出（${info.exports.length}）`}>
+                  <InspectTable
+                    columns={[
+                      ['名字', (e) => e.name],
+                      ['种类', (e) => `${e.kind} ${e.index}`],
+                    ]}
+                    rows={info.exports}
+                  />
+                </InfoCard>
+              </div>
+
+              <InfoCard title={`函数（${info.functions.length}）`}>
+                <InspectTable
+                  columns={[
+                    ['索引', (f) => f.index],
+                    ['名字', functionName],
+                    ['签名', (f) => f.signature],
+                    ['函数体', (f) => (f.body_offset === undefined ? '导入' : `${hex(f.body_offset)} (${f.body_size})`)],
+                  ]}
+                  rows={info.functions}
+                />
+              </InfoCard>
+
+              <DisassemblyCard
+                bytes={wasmModule.bytes}
+                info={info}
+                isSiteModule={wasmModule.isSiteModule}
+              />
+
+              <StringsCard bytes={wasmModule.bytes} />
+
+              <TracerCard bytes={wasmModule.bytes} info={info} />
+
+              <PatcherCard
+                bytes={wasmModule.bytes}
+                name={wasmModule.name}
+                info={info}
+                onInspect={inspect}
+              />
+
+              <SignaturesCard bytes={wasmModule.bytes} name={wasmModule.name} onInspect={inspect} />
+
+              <ObfuscatorCard
+                bytes={wasmModule.bytes}
+                name={wasmModule.name}
+                info={info}
+                onInspect={inspect}
+              />
+
+              <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
+                <InfoCard title="内存与表">
+                  <InspectTable
+                    columns={[
+                      ['', (m) => (m.element_type ? `table ${m.index}` : `memory ${m.index}`)],
+                      ['初始', (m) => m.initial],
+                      ['最大', (m) => m.maximum ?? '-'],
+                      ['说明', (m) => [m.element_type, m.shared && 'shared', m.imported && '导入'].filter(Boolean).join(' ')],
+                    ]}
+                    rows={[...info.memories, ...info.tables]}
+                  />
+                </InfoCard>
+                <InfoCard title={`全局变量（${info.globals.length}）`}>
+                  <InspectTable
+                    columns={[
+                      ['索引', (g) => g.index],
+                      ['类型', (g) => `${g.mutable ? 'mut ' : ''}${g.value_type}`],
+                      ['初始值', (g) => (g.imported ? '导入' : g.init)],
+                    ]}
+                    rows={info.globals}
+                  />
+                </InfoCard>
+              </div>
+
+              <InfoCard title={`数据段（${info.data.length}）`}>
+                <InspectTable
+                  columns={[
+                    ['索引', (d) => d.index],
+                    ['内存地址', (d) => (d.offset !== undefined ? hex(d.offset) : d.offset_expr ?? d.mode)],
+                    ['大小', (d) => d.size],
+                    ['文件偏移', (d) => hex(d.file_offset)],
+                    ['名字', (d) => info.names.data[d.index] ?? ''],
+                  ]}
+                  rows={info.data}
+                />
+              </InfoCard>
+
+              <InfoCard title="自定义 section">
+                <InspectTable
+                  columns={[
+                    ['名字', (c) => c.name],
+                    ['偏移', (c) => hex(c.offset)],
+                    ['大小', (c) => c.size],
+                  ]}
+                  rows={info.custom_sections}
+                />
+                {info.warnings.map((w) => (
+                  <p key={w} className="mt-2 text-sm text-destructive">{w}</p>
+                ))}
+              </InfoCard>
+            </>
+          )}
+        </motion.div>
+      </div>
+    </div>
+  );
+}
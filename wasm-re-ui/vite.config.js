@@ -4,6 +4,13 @@ import react from '@vitejs/plugin-react';
 import tailwindcss from '@tailwindcss/vite';
 import { getWebsiteBasePath } from './src/lib/routeUtils';
 
+// credentialless 不要求跨域资源带 CORP 响应头（跨域请求不带 cookie），页面引用的外部图片、字体等不会被拦截。
+// 不支持 credentialless 的浏览器（如 Safari）不会进入 crossOriginIsolated ，worker 回退到 simd / 标量构建
+const crossOriginIsolationHeaders = {
+  'Cross-Origin-Opener-Policy': 'same-origin',
+  'Cross-Origin-Embedder-Policy': 'credentialless',
+};
+
 // https://vite.dev/config/
 export default defineConfig(() => {
   const basePath = getWebsiteBasePath();
@@ -30,10 +37,21 @@ export default defineConfig(() => {
     resolve: {
       alias: {
         '@': path.resolve(__dirname, './src'),
+        // 「WASM 模块结构」页面把反汇编结果与 Rust 源码并排显示
+        '@rust-src': path.resolve(__dirname, '../rust-wasm/src'),
       },
     },
+    // 多线程 wasm 构建依赖 SharedArrayBuffer ，页面必须 crossOriginIsolated 。
+    // 部署环境不能设置这两个响应头时（如 GitHub Pages），worker 会回退到 simd / 标量构建
     server: {
       port: 5220,
+      headers: crossOriginIsolationHeaders,
+      fs: {
+        allow: ['.', '../rust-wasm/src'],
+      },
+    },
+    preview: {
+      headers: crossOriginIsolationHeaders,
     },
     worker: {
       format: 'es',